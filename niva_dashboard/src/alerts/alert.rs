@@ -30,9 +30,12 @@ impl Alert {
             Severity::Critical => alert_style.critical_color,
         };
 
+        // Centered in the bounds; the boxes are sized for the widest message
+        let text_width = context.calculate_text_width_with_font(
+            &self.message, 1.0, &alert_style.font_path, alert_style.font_size as u32)?;
         context.render_text_with_font(
             &self.message,
-            bounds.x,
+            bounds.x + (bounds.width - text_width) / 2.0,
             bounds.y,
             1.0,
             text_color,
@@ -50,6 +53,10 @@ impl Alert {
         self.severity
     }
 
+    pub fn has_remove_timeout(&self) -> bool {
+        self.remove_timeout.is_some()
+    }
+
     pub fn suppress(&mut self) {
         self.display_timeout = Some(std::time::Duration::ZERO);
         self.creation_time = std::time::Instant::now();     // Reset creation time for remove_timeout
@@ -1,9 +1,10 @@
 #![allow(dead_code)]
-use crate::hardware::sensor_manager::SensorManager;
+use crate::hardware::sensor_manager::SensorValuesSnapshot;
 use crate::alerts::watchdog::Watchdog;
 use crate::alerts::alert::Alert;
+use crate::alerts::alert_sound::AlertSound;
 use crate::graphics::ui_style::*;
-use crate::graphics::context::GraphicsContext;
+use crate::graphics::context::{opaque, GraphicsContext};
 
 // AlertManager is responsible for managing alerts and watchdogs.
 // Watchdogs are used to monitor hardware inputs and trigger alerts when certain conditions are met.
@@ -36,7 +37,7 @@ pub struct AlertManager {
     watchdogs: Vec<(u32, Watchdog)>,
     alerts: Vec<(u32, Alert)>,
     alert_style: AlertStyle,
-    sound_path: String,
+    sound: AlertSound,
 }
 
 impl AlertManager {
@@ -57,7 +58,7 @@ impl AlertManager {
                 corner_radius: ui_style.get_float(ALERT_CORNER_RADIUS, 8.0),
                 background_color: ui_style.get_color(ALERT_BACKGROUND_COLOR, (0.0, 0.0, 0.0)),
             },
-            sound_path: ui_style.get_string(ALERT_SOUND_PATH, ""),
+            sound: AlertSound::new(ui_style.get_string(ALERT_SOUND_PATH, "")),
         }
     }
 
@@ -82,12 +83,28 @@ impl AlertManager {
         self.watchdogs.push((id, watchdog));
     }
 
-    pub fn check_watchdogs(&mut self, sensor_manager: &SensorManager) {
+    pub fn check_watchdogs(&mut self, sensors: &SensorValuesSnapshot) {
         if !self.enabled {
             return;
         }
         for (watchdog_id, watchdog) in &mut self.watchdogs {
-            if watchdog.check(sensor_manager) {
+            let triggered = watchdog.check(sensors);
+            if !watchdog.condition_present() {
+                // Condition cleared: take the alert off the screen. It stays queued for its
+                // remove timeout so a flapping sensor doesn't flood the screen; alerts without
+                // one go right away so the next onset is shown again.
+                self.alerts.retain_mut(|(alert_id, alert)| {
+                    if alert_id != watchdog_id {
+                        return true;
+                    }
+                    if alert.is_active() {
+                        log::info!("Watchdog: condition on {:?} cleared", watchdog.hw_input());
+                        alert.suppress();
+                    }
+                    alert.has_remove_timeout()
+                });
+            }
+            if triggered {
                 let already_active = self.alerts.iter().any(|(alert_id, _)| alert_id == watchdog_id);
                 if already_active {
                     // Alert already active for this watchdog, skip adding a new one —
@@ -95,6 +112,7 @@ impl AlertManager {
                     continue;
                 }
                 log::info!("Watchdog: {:?} condition on {:?}", watchdog.severity(), watchdog.hw_input());
+                self.sound.alert_onset(watchdog.severity(), std::time::Instant::now());
                 self.alerts.push((*watchdog_id, Alert::new(
                     watchdog.message().clone(),
                     watchdog.severity(),
@@ -165,28 +183,31 @@ impl AlertManager {
         let total_alerts_height = (alert_height * active_alert_count as f32) + 
                                  (self.alert_style.margin * (active_alert_count - 1) as f32);
         
-        // Calculate starting Y coordinate to center alerts vertically on screen
-        let x_offset = (screen_width - max_text_width - self.alert_style.margin) / 2.0;
+        // One box per alert, all as wide as the widest message, stacked in the screen center
+        let box_width = max_text_width + 2.0 * (self.alert_style.margin + self.alert_style.border_width);
+        let x_offset = (screen_width - box_width) / 2.0;
         let start_y = (screen_height - total_alerts_height) / 2.0;
         
         let mut y_offset = start_y;
 
-        // Erase background
-        let _ = context.fill_rect(
-            x_offset - self.alert_style.margin,
-            start_y - self.alert_style.margin,
-            max_text_width + 2.0 * self.alert_style.margin,
-            total_alerts_height + 2.0 * self.alert_style.margin,
-            self.alert_style.background_color,
-        );
-
         // Render each alert with calculated positioning
         for alert in active_alerts.iter() {
+            let _ = context.fill_rounded_rect(
+                x_offset, y_offset, box_width, alert_height,
+                self.alert_style.background_color, self.alert_style.corner_radius,
+            );
+            if self.alert_style.border_width > 0.0 {
+                let _ = context.render_rectangle(
+                    x_offset, y_offset, box_width, alert_height, opaque(self.alert_style.border_color), false,
+                    self.alert_style.border_width, self.alert_style.corner_radius,
+                );
+            }
+
             let bounds = crate::indicators::indicator::IndicatorBounds {
-                x: x_offset + self.alert_style.margin,
-                y: y_offset + self.alert_style.margin,
-                width: screen_width - 2.0 * self.alert_style.margin,
-                height: alert_height,
+                x: x_offset,
+                y: y_offset + (alert_height - text_height) / 2.0,
+                width: box_width,
+                height: text_height,
             };
 
             if let Err(e) = alert.1.render(bounds, context, &self.alert_style) {
@@ -196,4 +217,77 @@ impl AlertManager {
             y_offset += alert_height + self.alert_style.margin;
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hardware::hw_providers::{HWInput, TestDigitalDataProvider};
+    use crate::hardware::sensor_manager::{SensorDigitalInputChain, SensorManager};
+    use crate::hardware::sensors::GenericDigitalSensor;
+    use crate::hardware::sensor_value::ValueConstraints;
+    use rppal::gpio::Level;
+
+    fn critical_lamp(input: HWInput, id: &str, active_level: Level) -> SensorDigitalInputChain {
+        SensorDigitalInputChain::new(
+            Box::new(TestDigitalDataProvider::new(input)),
+            vec![],
+            Box::new(GenericDigitalSensor::new(id.to_string(), id.to_string(), active_level,
+                                              ValueConstraints::digital_critical())),
+        )
+    }
+
+    #[test]
+    fn test_critical_sensor_enqueues_alert() {
+        // The test provider reads high for its first seconds: only the active-high lamp is critical
+        let mut sensors = SensorManager::new();
+        sensors.add_digital_sensor_chain(critical_lamp(HWInput::HwOilPressLow, "oil_press_low", Level::High));
+        sensors.add_digital_sensor_chain(critical_lamp(HWInput::HwCharge, "charge", Level::Low));
+        sensors.read_all_sensors().unwrap();
+        let sensors = sensors.values_snapshot();
+
+        let mut alerts = AlertManager::new(true, &UIStyle::new());
+        alerts.add_watchdog(Watchdog::new(HWInput::HwCharge, "CHARGE".to_string(), Severity::Critical, None, None, None));
+        alerts.add_watchdog(Watchdog::new(HWInput::HwOilPressLow, "OIL".to_string(), Severity::Critical, None, None, None));
+        alerts.check_watchdogs(&sensors);
+
+        assert_eq!(alerts.alerts.len(), 1);
+        let (_, alert) = &alerts.alerts[0];
+        assert_eq!(alert.message(), "OIL");
+        assert!(matches!(alert.severity(), Severity::Critical));
+        assert!(alert.is_active());
+
+        // Still critical on the next frame: no duplicate
+        alerts.check_watchdogs(&sensors);
+        assert_eq!(alerts.alerts.len(), 1);
+    }
+
+    #[test]
+    fn test_sound_plays_once_per_onset() {
+        let mut sensors = SensorManager::new();
+        sensors.add_digital_sensor_chain(critical_lamp(HWInput::HwOilPressLow, "oil_press_low", Level::High));
+        sensors.read_all_sensors().unwrap();
+        let sensors = sensors.values_snapshot();
+
+        let plays = std::rc::Rc::new(std::cell::Cell::new(0));
+        let mut alerts = AlertManager::new(true, &UIStyle::new());
+        alerts.sound = AlertSound::new("/usr/share/sounds/alert.wav".to_string()).with_player(Box::new({
+            let plays = plays.clone();
+            move |_| { plays.set(plays.get() + 1); Ok(()) }
+        }));
+        alerts.add_watchdog(Watchdog::new(HWInput::HwOilPressLow, "OIL".to_string(), Severity::Critical, None, None, None));
+        for _ in 0..5 {
+            alerts.check_watchdogs(&sensors);
+        }
+        assert_eq!(plays.get(), 1);
+
+        // No sound file configured: nothing to play
+        let plays = std::rc::Rc::new(std::cell::Cell::new(0));
+        let mut silent = AlertSound::new(String::new()).with_player(Box::new({
+            let plays = plays.clone();
+            move |_| { plays.set(plays.get() + 1); Ok(()) }
+        }));
+        silent.alert_onset(Severity::Critical, std::time::Instant::now());
+        assert_eq!(plays.get(), 0);
+    }
+}
@@ -0,0 +1,62 @@
+#![allow(dead_code)]
+use crate::alerts::alert_manager::Severity;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+// Alerts firing together (e.g. several lamps on at power-up) share one chime
+const MIN_REPLAY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Starts playing a sound file without waiting for it to finish
+pub type SoundPlayer = Box<dyn FnMut(&str) -> Result<(), String>>;
+
+/// Chime played once when a critical alert first fires. A no-op without a sound file,
+/// and stays silent for good once playback fails.
+pub struct AlertSound {
+    path: String,
+    last_played: Option<Instant>,
+    player: SoundPlayer,
+}
+
+impl AlertSound {
+    pub fn new(path: String) -> Self {
+        AlertSound { path, last_played: None, player: Box::new(play_with_system_player) }
+    }
+
+    /// Replace the playback function
+    pub fn with_player(mut self, player: SoundPlayer) -> Self {
+        self.player = player;
+        self
+    }
+
+    /// Called once per alert onset, not every frame the alert stays up
+    pub fn alert_onset(&mut self, severity: Severity, now: Instant) {
+        if self.path.is_empty() || !matches!(severity, Severity::Critical) {
+            return;
+        }
+        if self.last_played.is_some_and(|played| now.saturating_duration_since(played) < MIN_REPLAY_INTERVAL) {
+            return;
+        }
+        self.last_played = Some(now);
+        if let Err(e) = (self.player)(&self.path) {
+            log::warn!("Alert sound disabled: {}", e);
+            self.path.clear();
+        }
+    }
+}
+
+/// Play through ALSA's command line players, without waiting for the sound to finish:
+/// aplay for WAV, ogg123 for OGG
+fn play_with_system_player(path: &str) -> Result<(), String> {
+    let program = if path.to_ascii_lowercase().ends_with(".ogg") { "ogg123" } else { "aplay" };
+    let mut child = Command::new(program)
+        .arg("-q")
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run {} {}: {}", program, path, e))?;
+    // Reap the player when it exits
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
@@ -1,3 +1,4 @@
 pub mod alert_manager;
 pub mod watchdog;
-pub mod alert;
\ No newline at end of file
+pub mod alert;
+pub mod alert_sound;
\ No newline at end of file
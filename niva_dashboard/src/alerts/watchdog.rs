@@ -1,6 +1,6 @@
 use crate::alerts::alert_manager::Severity;
 use crate::hardware::hw_providers::HWInput;
-use crate::hardware::sensor_manager::SensorManager;
+use crate::hardware::sensor_manager::SensorValuesSnapshot;
 
 // Watchdog for a particular sensor input.
 // Monitors the sensor value and triggers an alert 
@@ -16,6 +16,7 @@ pub struct Watchdog {
                                                 // to prevent alert flooding.
     trigger_start_time: Option<std::time::Instant>,
     trigger_duration: Option<std::time::Duration>, // Duration the condition must persist to trigger an alert
+    condition_present: bool,    // Condition seen on the last check, whether or not it triggered yet
 }
 
 impl Watchdog {
@@ -23,12 +24,12 @@ impl Watchdog {
                alert_display_timeout: Option<std::time::Duration>, alert_remove_timeout: Option<std::time::Duration>,
                trigger_duration: Option<std::time::Duration>) -> Self {
         Self { hw_input, alert_message, severity, alert_display_timeout,
-               alert_remove_timeout, trigger_start_time: None, trigger_duration }
+               alert_remove_timeout, trigger_start_time: None, trigger_duration, condition_present: false }
     }
 
     // Return true when the watchdog detects a condition that should trigger an alert
-    pub fn check(&mut self, sensor_manager: &SensorManager) -> bool {
-        let sensor_value = sensor_manager.get_sensor_value(&self.hw_input);
+    pub fn check(&mut self, sensors: &SensorValuesSnapshot) -> bool {
+        let sensor_value = sensors.get_sensor_value(&self.hw_input);
         let trigger = if let Some(value) = sensor_value {
                 match self.severity {
                     Severity::Warning => value.is_warning(),
@@ -37,6 +38,7 @@ impl Watchdog {
             } else {
                 false
             };
+        self.condition_present = trigger;
         if trigger {
             if let Some(trigger_duration) = self.trigger_duration {
                 if let Some(start_time) = self.trigger_start_time {
@@ -57,6 +59,11 @@ impl Watchdog {
         false
     }
 
+    // False once the monitored condition has cleared, as of the last check
+    pub fn condition_present(&self) -> bool {
+        self.condition_present
+    }
+
     pub fn hw_input(&self) -> HWInput {
         self.hw_input
     }
@@ -0,0 +1,109 @@
+#![allow(dead_code)]
+use std::fmt;
+
+/// Error type for the graphics, sensor and style layers. Each variant carries the
+/// message that used to be returned as a plain String, so logs read the same.
+#[derive(Debug)]
+pub enum DashboardError {
+    /// KMS/DRM device, connector, mode and CRTC setup
+    Drm(String),
+    /// EGL display, config, context and surface setup
+    Egl(String),
+    /// GBM device and surface allocation
+    Gbm(String),
+    /// GL shaders, framebuffers and drawing
+    Gl(String),
+    /// FreeType font loading and glyph rendering
+    Font(String),
+    /// Image loading and framebuffer dumps
+    Image(String),
+    /// Sensor chains and their hardware providers
+    Sensor(String),
+    /// UI style values, palettes and themes
+    Style(String),
+    /// I/O failure, with what was being done when it happened (may be empty)
+    Io(String, std::io::Error),
+    /// Malformed JSON
+    Json(serde_json::Error),
+}
+
+impl DashboardError {
+    pub fn io(context: impl Into<String>, error: std::io::Error) -> Self {
+        DashboardError::Io(context.into(), error)
+    }
+}
+
+impl fmt::Display for DashboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DashboardError::Drm(msg)
+            | DashboardError::Egl(msg)
+            | DashboardError::Gbm(msg)
+            | DashboardError::Gl(msg)
+            | DashboardError::Font(msg)
+            | DashboardError::Image(msg)
+            | DashboardError::Sensor(msg)
+            | DashboardError::Style(msg) => write!(f, "{}", msg),
+            DashboardError::Io(context, e) if context.is_empty() => write!(f, "{}", e),
+            DashboardError::Io(context, e) => write!(f, "{}: {}", context, e),
+            DashboardError::Json(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for DashboardError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DashboardError::Io(_, e) => Some(e),
+            DashboardError::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for DashboardError {
+    fn from(error: std::io::Error) -> Self {
+        DashboardError::Io(String::new(), error)
+    }
+}
+
+impl From<serde_json::Error> for DashboardError {
+    fn from(error: serde_json::Error) -> Self {
+        DashboardError::Json(error)
+    }
+}
+
+// Pages, indicators and the rest of the app still report errors as strings
+impl From<DashboardError> for String {
+    fn from(error: DashboardError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_display_keeps_messages_and_sources() {
+        assert_eq!(DashboardError::Egl("Failed to choose EGL config".to_string()).to_string(),
+                   "Failed to choose EGL config");
+
+        let not_found = || std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+        let io = DashboardError::io("Failed to flush stdout", not_found());
+        assert_eq!(io.to_string(), "Failed to flush stdout: no such file");
+        assert!(io.source().is_some());
+        assert_eq!(DashboardError::from(not_found()).to_string(), "no such file");
+
+        let json: DashboardError = serde_json::from_str::<u32>("{").unwrap_err().into();
+        assert!(matches!(json, DashboardError::Json(_)));
+
+        // Functions still returning String can use `?` on the new error
+        fn legacy() -> Result<(), String> {
+            Err(DashboardError::Sensor("Analog sensor chain not found".to_string()))?;
+            Ok(())
+        }
+        assert_eq!(legacy(), Err("Analog sensor chain not found".to_string()));
+    }
+}
@@ -0,0 +1,128 @@
+#![allow(dead_code)]
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const BACKLIGHT_CLASS_DIR: &str = "/sys/class/backlight";
+
+/// Panel backlight exposed through the sysfs backlight class
+/// (e.g. /sys/class/backlight/10-0045/ on the official Raspberry Pi touchscreen)
+#[derive(Debug)]
+pub struct Backlight {
+    device_dir: PathBuf,
+    max_brightness: u32,
+}
+
+impl Backlight {
+    /// Find the first usable backlight device, if the panel has one
+    pub fn detect() -> Option<Self> {
+        Self::detect_in(Path::new(BACKLIGHT_CLASS_DIR))
+    }
+
+    fn detect_in(class_dir: &Path) -> Option<Self> {
+        let mut devices: Vec<PathBuf> = fs::read_dir(class_dir).ok()?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+        // read_dir order is unspecified; keep the pick stable across boots
+        devices.sort();
+
+        devices.into_iter().find_map(|dir| match Self::open(&dir) {
+            Ok(backlight) => Some(backlight),
+            Err(e) => {
+                log::warn!("Skipping backlight device {}: {}", dir.display(), e);
+                None
+            }
+        })
+    }
+
+    fn open(device_dir: &Path) -> Result<Self, String> {
+        let max_path = device_dir.join("max_brightness");
+        let max_brightness = fs::read_to_string(&max_path)
+            .map_err(|e| format!("Failed to read {}: {}", max_path.display(), e))?
+            .trim()
+            .parse::<u32>()
+            .map_err(|e| format!("Invalid {}: {}", max_path.display(), e))?;
+        if max_brightness == 0 {
+            return Err(format!("{} is zero", max_path.display()));
+        }
+        Ok(Backlight { device_dir: device_dir.to_path_buf(), max_brightness })
+    }
+
+    pub fn device_dir(&self) -> &Path {
+        &self.device_dir
+    }
+
+    pub fn max_brightness(&self) -> u32 {
+        self.max_brightness
+    }
+
+    /// Current backlight level as a 0.0..=1.0 fraction of max_brightness
+    pub fn level(&self) -> Result<f32, String> {
+        let path = self.device_dir.join("brightness");
+        let raw = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+            .trim()
+            .parse::<u32>()
+            .map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+        Ok((raw as f32 / self.max_brightness as f32).min(1.0))
+    }
+
+    /// Raw sysfs value for a 0.0..=1.0 level
+    fn raw_level(&self, level: f32) -> u32 {
+        (level.clamp(0.0, 1.0) * self.max_brightness as f32).round() as u32
+    }
+
+    /// Set the backlight to a 0.0..=1.0 fraction of max_brightness
+    pub fn set_level(&self, level: f32) -> Result<(), String> {
+        let path = self.device_dir.join("brightness");
+        fs::write(&path, self.raw_level(level).to_string())
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_class_dir(name: &str, devices: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("niva_backlight_{}_{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        for (device, max) in devices {
+            let device_dir = dir.join(device);
+            fs::create_dir_all(&device_dir).unwrap();
+            fs::write(device_dir.join("max_brightness"), max).unwrap();
+            fs::write(device_dir.join("brightness"), "0").unwrap();
+        }
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_detect_and_set_level() {
+        let dir = fake_class_dir("set", &[("rpi_backlight", "255\n")]);
+        let backlight = Backlight::detect_in(&dir).unwrap();
+        assert_eq!(backlight.max_brightness(), 255);
+
+        backlight.set_level(0.5).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("rpi_backlight/brightness")).unwrap(), "128");
+        assert!((backlight.level().unwrap() - 128.0 / 255.0).abs() < 1e-6);
+        backlight.set_level(2.0).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("rpi_backlight/brightness")).unwrap(), "255");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_detect_skips_unusable_devices() {
+        let dir = fake_class_dir("skip", &[("a_broken", "garbage"), ("b_zero", "0"), ("c_good", "31")]);
+        let backlight = Backlight::detect_in(&dir).unwrap();
+        assert_eq!(backlight.device_dir(), dir.join("c_good"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_detect_without_devices() {
+        let dir = fake_class_dir("none", &[]);
+        assert!(Backlight::detect_in(&dir).is_none());
+        assert!(Backlight::detect_in(&dir.join("missing")).is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -3,8 +3,14 @@
 use std::ffi::CString;
 use std::os::raw::{c_char, c_int, c_uint, c_void};
 use std::ptr;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 use freetype_sys as ft;
+use crate::graphics::backlight::Backlight;
+use crate::graphics::ui_style::{resolve_font_path, resolve_icon_path};
+use crate::error::DashboardError;
 
 // EGL types and constants
 type EGLDisplay = *mut c_void;
@@ -17,13 +23,14 @@ type EGLBoolean = c_uint;
 #[allow(dead_code)] const EGL_SUCCESS: EGLint = 0x3000;
 #[allow(dead_code)] const EGL_TRUE: EGLBoolean = 1;
 const EGL_FALSE: EGLBoolean = 0;
-#[allow(dead_code)] const EGL_DEFAULT_DISPLAY: *mut c_void = ptr::null_mut();
+const EGL_DEFAULT_DISPLAY: *mut c_void = ptr::null_mut();
 const EGL_NO_CONTEXT: EGLContext = ptr::null_mut();
 const EGL_NO_SURFACE: EGLSurface = ptr::null_mut();
 
 // EGL configuration attributes
 const EGL_SURFACE_TYPE: EGLint = 0x3033;
 const EGL_WINDOW_BIT: EGLint = 0x0004;
+const EGL_PBUFFER_BIT: EGLint = 0x0001;
 const EGL_RENDERABLE_TYPE: EGLint = 0x3040;
 const EGL_OPENGL_ES2_BIT: EGLint = 0x0004;
 const EGL_RED_SIZE: EGLint = 0x3024;
@@ -33,11 +40,16 @@ const EGL_ALPHA_SIZE: EGLint = 0x3021;
 const EGL_DEPTH_SIZE: EGLint = 0x3025;
 const EGL_NONE: EGLint = 0x3038;
 
+// EGL pbuffer attributes
+const EGL_WIDTH: EGLint = 0x3057;
+const EGL_HEIGHT: EGLint = 0x3056;
+
 // EGL context attributes
 const EGL_CONTEXT_CLIENT_VERSION: EGLint = 0x3098;
 
 // EGL platform constants
 const EGL_PLATFORM_GBM_MESA: EGLint = 0x31D7;
+const EGL_PLATFORM_SURFACELESS_MESA: EGLint = 0x31DD;
 
 // EGL/OpenGL ES external functions
 #[repr(C)]
@@ -74,6 +86,11 @@ extern "C" {
         win: *mut c_void,
         attrib_list: *const EGLint,
     ) -> EGLSurface;
+    fn eglCreatePbufferSurface(
+        dpy: EGLDisplay,
+        config: EGLConfig,
+        attrib_list: *const EGLint,
+    ) -> EGLSurface;
     fn eglMakeCurrent(
         dpy: EGLDisplay,
         draw: EGLSurface,
@@ -164,10 +181,15 @@ const DRM_MODE_CONNECTED: u32 = 1;
 
 // DRM page flip flags
 const DRM_MODE_PAGE_FLIP_EVENT: u32 = 0x01;
+const DRM_MODE_PAGE_FLIP_ASYNC: u32 = 0x02;
 
 // DRM event context version
 const DRM_EVENT_CONTEXT_VERSION: u32 = 2;
 
+// Brightness range; the minimum keeps the screen from going completely black
+pub const MIN_BRIGHTNESS: f32 = 0.1;
+pub const MAX_BRIGHTNESS: f32 = 1.0;
+
 /// DRM event context for handling vblank/page-flip events via drmHandleEvent()
 #[repr(C)]
 struct DrmEventContext {
@@ -244,10 +266,62 @@ struct DrmModeModeInfo {
     name: [i8; 32],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct DrmModeCrtc {
+    crtc_id: u32,
+    buffer_id: u32,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    mode_valid: c_int,
+    mode: DrmModeModeInfo,
+    gamma_size: c_int,
+}
+
+/// What the CRTC was showing before we took it over (normally the console), put back on exit
+#[derive(Clone, Copy)]
+struct SavedCrtc {
+    crtc_id: u32,
+    buffer_id: u32,
+    x: u32,
+    y: u32,
+    // None when the CRTC was off
+    mode: Option<DrmModeModeInfo>,
+}
+
+impl SavedCrtc {
+    fn from_crtc(crtc: &DrmModeCrtc) -> Self {
+        SavedCrtc {
+            crtc_id: crtc.crtc_id,
+            buffer_id: crtc.buffer_id,
+            x: crtc.x,
+            y: crtc.y,
+            mode: (crtc.mode_valid != 0 && crtc.buffer_id != 0).then_some(crtc.mode),
+        }
+    }
+
+    /// Put the saved configuration back through `set_crtc` (drmModeSetCrtc outside tests).
+    /// Returns false if the CRTC was off and there is nothing to restore.
+    fn restore(&self, set_crtc: impl FnOnce(&SavedCrtc, &DrmModeModeInfo) -> c_int) -> Result<bool, DashboardError> {
+        let Some(mode) = &self.mode else {
+            return Ok(false);
+        };
+        match set_crtc(self, mode) {
+            0 => Ok(true),
+            result => Err(DashboardError::Drm(format!("Failed to restore CRTC {}: error {}", self.crtc_id, result))),
+        }
+    }
+}
+
 /// Represents cached glyph data for efficient text rendering
 #[derive(Clone)]
 struct CachedGlyph {
-    texture_id: u32,
+    // Top-left of the glyph bitmap in the renderer's atlas, in pixels. UVs are derived when a
+    // quad is queued, from the atlas size at that point (see queue_text).
+    atlas_x: u32,
+    atlas_y: u32,
     width: f32,
     height: f32,
     bearing_x: f32,
@@ -255,17 +329,284 @@ struct CachedGlyph {
     advance: f32,
 }
 
+const GLYPH_ATLAS_WIDTH: u32 = 512;
+const GLYPH_ATLAS_INITIAL_HEIGHT: u32 = 128;
+const GLYPH_ATLAS_MAX_HEIGHT: u32 = 4096;
+// Empty texels right of and below each glyph so linear filtering never picks up a neighbour
+const GLYPH_ATLAS_PADDING: u32 = 1;
+
+/// Single-channel glyph atlas packed in shelves (rows of glyphs). Keeps a CPU copy of the
+/// pixels so the GL texture can be re-created when the atlas grows; ES has no glGetTexImage.
+struct GlyphAtlas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    shelf_x: u32,
+    shelf_y: u32,
+    shelf_height: u32,
+}
+
+impl GlyphAtlas {
+    fn new(width: u32, height: u32) -> Self {
+        GlyphAtlas {
+            width,
+            height,
+            pixels: vec![0; (width * height) as usize],
+            shelf_x: 0,
+            shelf_y: 0,
+            shelf_height: 0,
+        }
+    }
+
+    /// Reserve a `width` x `height` area; None if it does not fit at the current atlas height
+    fn allocate(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        if width == 0 || height == 0 {
+            return Some((0, 0));
+        }
+        let padded_width = width + GLYPH_ATLAS_PADDING;
+        let padded_height = height + GLYPH_ATLAS_PADDING;
+        if padded_width > self.width {
+            return None;
+        }
+        if self.shelf_x + padded_width > self.width {
+            self.shelf_y += self.shelf_height;
+            self.shelf_x = 0;
+            self.shelf_height = 0;
+        }
+        if self.shelf_y + padded_height > self.height {
+            return None;
+        }
+        let position = (self.shelf_x, self.shelf_y);
+        self.shelf_x += padded_width;
+        self.shelf_height = self.shelf_height.max(padded_height);
+        Some(position)
+    }
+
+    /// Double the height, keeping every glyph where it is
+    fn grow(&mut self) -> bool {
+        if self.height * 2 > GLYPH_ATLAS_MAX_HEIGHT {
+            return false;
+        }
+        self.height *= 2;
+        self.pixels.resize((self.width * self.height) as usize, 0);
+        true
+    }
+
+    /// Copy a glyph bitmap (rows `pitch` bytes apart) into the atlas, growing it if needed.
+    /// Returns the glyph's position.
+    fn insert(&mut self, width: u32, height: u32, bitmap: &[u8], pitch: usize) -> Result<(u32, u32), DashboardError> {
+        let (x, y) = loop {
+            if let Some(position) = self.allocate(width, height) {
+                break position;
+            }
+            if width + GLYPH_ATLAS_PADDING > self.width || !self.grow() {
+                return Err(DashboardError::Font(format!("Glyph {}x{} does not fit in the {}x{} glyph atlas",
+                                   width, height, self.width, self.height)));
+            }
+        };
+        for row in 0..height as usize {
+            let src = &bitmap[row * pitch..row * pitch + width as usize];
+            let dst_start = (y as usize + row) * self.width as usize + x as usize;
+            self.pixels[dst_start..dst_start + width as usize].copy_from_slice(src);
+        }
+        Ok((x, y))
+    }
+
+    /// Copy of the pixels in a rectangle
+    fn read(&self, x: u32, y: u32, width: u32, height: u32) -> Vec<u8> {
+        (y..y + height)
+            .flat_map(|row| {
+                let start = (row * self.width + x) as usize;
+                self.pixels[start..start + width as usize].iter().copied()
+            })
+            .collect()
+    }
+
+    /// A fresh atlas holding only the given (x, y, width, height) glyph rects, packed from
+    /// scratch. Updates each rect's position to its place in the new atlas.
+    fn repack(&self, rects: &mut [(u32, u32, u32, u32)]) -> Result<GlyphAtlas, DashboardError> {
+        let mut atlas = GlyphAtlas::new(self.width, GLYPH_ATLAS_INITIAL_HEIGHT);
+        for rect in rects.iter_mut() {
+            let (x, y, width, height) = *rect;
+            let bitmap = self.read(x, y, width, height);
+            let (new_x, new_y) = atlas.insert(width, height, &bitmap, width as usize)?;
+            rect.0 = new_x;
+            rect.1 = new_y;
+        }
+        Ok(atlas)
+    }
+
+    /// Texture coordinates (u0, v0, u1, v1) of a rectangle at the current atlas size
+    fn uv_rect(&self, x: u32, y: u32, width: f32, height: f32) -> (f32, f32, f32, f32) {
+        let (atlas_width, atlas_height) = (self.width as f32, self.height as f32);
+        (x as f32 / atlas_width, y as f32 / atlas_height,
+         (x as f32 + width) / atlas_width, (y as f32 + height) / atlas_height)
+    }
+}
+
+// Enough for Latin, Cyrillic, digits and symbols in several sizes; normal dashboards never evict
+const DEFAULT_GLYPH_CACHE_CAPACITY: usize = 1024;
+
+/// Map with a size cap that drops the least recently used entries
+struct LruCache<K, V> {
+    // Value and the clock tick of its last use
+    entries: HashMap<K, (V, u64)>,
+    capacity: usize,
+    clock: u64,
+}
+
+impl<K: Hash + Eq + Copy, V> LruCache<K, V> {
+    fn new(capacity: usize) -> Self {
+        LruCache { entries: HashMap::new(), capacity: capacity.max(1), clock: 0 }
+    }
+
+    /// Look up an entry, marking it as the most recently used
+    fn get(&mut self, key: &K) -> Option<&V> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.entries.get_mut(key).map(|(value, last_used)| {
+            *last_used = clock;
+            &*value
+        })
+    }
+
+    /// Insert an entry, returning whatever had to be evicted to stay within capacity
+    fn insert(&mut self, key: K, value: V) -> Vec<(K, V)> {
+        self.clock += 1;
+        self.entries.insert(key, (value, self.clock));
+        self.evict_over_capacity()
+    }
+
+    fn set_capacity(&mut self, capacity: usize) -> Vec<(K, V)> {
+        self.capacity = capacity.max(1);
+        self.evict_over_capacity()
+    }
+
+    fn evict_over_capacity(&mut self) -> Vec<(K, V)> {
+        let mut evicted = Vec::new();
+        while self.entries.len() > self.capacity {
+            let oldest = self.entries.iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| *key)
+                .unwrap();
+            let (value, _) = self.entries.remove(&oldest).unwrap();
+            evicted.push((oldest, value));
+        }
+        evicted
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the key is cached, without counting as a use
+    fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.entries.values_mut().map(|(value, _)| value)
+    }
+}
+
+/// Face to take `ch` from: the primary one, unless it lacks the character (glyph index 0,
+/// which would render as the .notdef box) and the fallback has it
+unsafe fn glyph_face(primary: ft::FT_Face, fallback: ft::FT_Face, ch: char) -> ft::FT_Face {
+    if !fallback.is_null()
+        && ft::FT_Get_Char_Index(primary, ch as ft::FT_ULong) == 0
+        && ft::FT_Get_Char_Index(fallback, ch as ft::FT_ULong) != 0 {
+        fallback
+    } else {
+        primary
+    }
+}
+
+/// Horizontal kerning adjustment in pixels between `left` and `right` at the face's current size.
+/// Zero when the font has no kerning table or the two characters come from different faces.
+unsafe fn kerning_delta(primary: ft::FT_Face, fallback: ft::FT_Face, left: char, right: char) -> f32 {
+    let face = glyph_face(primary, fallback, left);
+    if face != glyph_face(primary, fallback, right) || !ft::FT_HAS_KERNING(face) {
+        return 0.0;
+    }
+    let mut kerning = ft::FT_Vector { x: 0, y: 0 };
+    let error = ft::FT_Get_Kerning(
+        face,
+        ft::FT_Get_Char_Index(face, left as ft::FT_ULong),
+        ft::FT_Get_Char_Index(face, right as ft::FT_ULong),
+        ft::FT_KERNING_DEFAULT,
+        &mut kerning,
+    );
+    if error != 0 {
+        return 0.0;
+    }
+    kerning.x as f32 / 64.0
+}
+
+// Two triangles of (x, y, u, v) per glyph
+const TEXT_QUAD_FLOATS: usize = 24;
+
+/// Append the quad for one glyph whose top-left screen corner is (x, y). `uv` is the glyph's
+/// atlas rect as (u0, v0, u1, v1), v0 being the top row of the bitmap.
+fn push_glyph_quad(vertices: &mut Vec<f32>, x: f32, y: f32, width: f32, height: f32, uv: (f32, f32, f32, f32)) {
+    let (u0, v0, u1, v1) = uv;
+    vertices.extend_from_slice(&[
+        x,         y + height, u0, v1,  // Bottom-left on screen
+        x,         y,          u0, v0,  // Top-left
+        x + width, y,          u1, v0,  // Top-right
+        
+        x,         y + height, u0, v1,  // Bottom-left
+        x + width, y,          u1, v0,  // Top-right
+        x + width, y + height, u1, v1,  // Bottom-right
+    ]);
+}
+
+/// Position and color of each pass of a drop-shadowed string, in draw order:
+/// the shadow at the offset first, then the text itself on top
+fn shadow_passes(
+    x: f32,
+    y: f32,
+    color: (f32, f32, f32),
+    shadow_color: (f32, f32, f32),
+    offset: (f32, f32),
+) -> [(f32, f32, (f32, f32, f32)); 2] {
+    [(x + offset.0, y + offset.1, shadow_color), (x, y, color)]
+}
+
+/// Turn quads laid out horizontally at the origin by 90° and move them to (x, y).
+/// `extent` is the horizontal layout's height for clockwise and width for counter-clockwise,
+/// so the rotated block still starts at (x, y) instead of swinging into negative space.
+fn rotate_text_quads(vertices: &mut [f32], clockwise: bool, x: f32, y: f32, extent: f32) {
+    for vertex in vertices.chunks_exact_mut(4) {
+        let (dx, dy) = (vertex[0], vertex[1]);
+        // Screen y grows downward, so clockwise sends +x to +y and +y to -x
+        let (rx, ry) = if clockwise { (extent - dy, dx) } else { (dy, extent - dx) };
+        vertex[0] = x + rx;
+        vertex[1] = y + ry;
+    }
+}
+
 /// OpenGL text renderer using FreeType with glyph caching
 pub struct OpenGLTextRenderer {
     ft_library: ft::FT_Library,
     ft_face: ft::FT_Face,
+    // Optional second face for characters the primary font lacks; null when unset
+    fallback_face: ft::FT_Face,
     shader_program: u32,
     vao: u32,
     vbo: u32,
     font_size: u32,
-    glyph_cache: HashMap<char, CachedGlyph>,
-    projection_width: f32,
-    projection_height: f32,
+    glyph_cache: LruCache<char, CachedGlyph>,
+    // All cached glyphs live in one texture so a string renders with a single bind
+    atlas: GlyphAtlas,
+    atlas_texture: u32,
+    // Height the GL texture was last allocated with; differs from atlas.height after growth
+    atlas_texture_height: u32,
+    // Quads of the string being rendered, drawn with one call; kept to reuse its allocation
+    batch_vertices: Vec<f32>,
     projection_matrix: [f32; 16],
     // Cached uniform and attribute locations for performance
     projection_uniform: i32,
@@ -287,190 +628,1070 @@ pub enum InputEventType {
     KeyRelease(u32),
 }
 
-/// Text orientation options for rendering
-#[derive(Debug, Clone, Copy)]
-pub enum TextOrientation {
-    Horizontal,  // Normal left-to-right text
-    Vertical,    // Characters stacked vertically (top-to-bottom, not rotated)
+/// Clockwise rotation of the rendered image relative to the panel's native scanout,
+/// for panels mounted rotated in the dash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    #[default]
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
 }
 
-/// Converts a FreeType error code into a human-readable description.
-/// Covers the most common error codes defined in freetype/fterrdef.h.
-fn ft_error_description(code: freetype_sys::FT_Error) -> &'static str {
-    match code {
-        0x00 => "no error",
-        0x01 => "cannot open resource",
-        0x02 => "unknown file format",
-        0x03 => "broken file: invalid content",
-        0x04 => "invalid FreeType version",
-        0x05 => "module version is too low",
-        0x06 => "invalid argument",
-        0x07 => "unimplemented feature",
-        0x08 => "broken table",
-        0x09 => "broken offset within table",
-        0x0A => "array allocation size too large",
-        0x0B => "missing module",
-        0x0C => "missing property",
-        0x10 => "invalid glyph index",
-        0x11 => "invalid character code",
-        0x12 => "unsupported glyph image format",
-        0x13 => "cannot render this glyph format",
-        0x14 => "invalid outline",
-        0x15 => "invalid composite glyph",
-        0x16 => "too many hints",
-        0x17 => "invalid pixel size",
-        0x18 => "invalid SVG document",
-        0x20 => "invalid object handle",
-        0x21 => "invalid library handle",
-        0x22 => "invalid module handle",
-        0x23 => "invalid face handle",
-        0x24 => "invalid size handle",
-        0x25 => "invalid glyph slot handle",
-        0x26 => "invalid charmap handle",
-        0x27 => "invalid cache manager handle",
-        0x28 => "invalid stream handle",
-        0x30 => "too many modules",
-        0x31 => "too many extensions",
-        0x40 => "out of memory",
-        0x41 => "unlisted object",
-        0x51 => "cannot open stream",
-        0x52 => "invalid stream seek",
-        0x53 => "invalid stream skip",
-        0x54 => "invalid stream read",
-        0x55 => "invalid stream operation",
-        0x56 => "invalid frame operation",
-        0x57 => "nested frame access",
-        0x58 => "invalid frame read",
-        0x60 => "raster uninitialized",
-        0x61 => "raster corrupted",
-        0x62 => "raster overflow",
-        0x63 => "negative height while rastering",
-        0x70 => "too many registered caches",
-        0x80 => "invalid opcode",
-        0x81 => "too few arguments",
-        0x82 => "stack overflow",
-        0x83 => "code overflow",
-        0x84 => "bad argument",
-        0x85 => "division by zero",
-        0x86 => "invalid reference",
-        0x87 => "found debug opcode",
-        0x88 => "found ENDF opcode in execution stream",
-        0x89 => "nested DEFS",
-        0x8A => "invalid code range",
-        0x8B => "execution context too long",
-        0x8C => "too many function definitions",
-        0x8D => "too many instruction definitions",
-        0x8E => "SFNT font table missing",
-        0x8F => "horizontal header (hhea) table missing",
-        0x90 => "locations (loca) table missing",
-        0x91 => "name table missing",
-        0x92 => "character map (cmap) table missing",
-        0x93 => "horizontal metrics (hmtx) table missing",
-        0x94 => "PostScript (post) table missing",
-        0x95 => "OS/2 table missing",
-        _    => "unknown error",
+impl Rotation {
+    pub fn from_degrees(degrees: u32) -> Result<Self, String> {
+        match degrees {
+            0 => Ok(Rotation::Deg0),
+            90 => Ok(Rotation::Deg90),
+            180 => Ok(Rotation::Deg180),
+            270 => Ok(Rotation::Deg270),
+            _ => Err(format!("Unsupported rotation: {} (expected 0, 90, 180 or 270)", degrees)),
+        }
+    }
+
+    /// True when logical width and height are swapped relative to the display mode
+    pub fn is_transposed(self) -> bool {
+        matches!(self, Rotation::Deg90 | Rotation::Deg270)
     }
 }
 
-/// Graphics context using KMS/DRM backend with OpenGL ES
-pub struct GraphicsContext {
-    // DRM/KMS handles
-    drm_fd: c_int,
-    gbm_device: *mut c_void,
-    gbm_surface: *mut c_void,
+/// Horizontal and vertical flips of the rendered image, for panels seen through a mirror
+/// or reflected in a lens as a HUD. Applied to the logical image before the rotation, so
+/// horizontal stays the viewer's left-right whichever way the panel is mounted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Mirror {
+    pub horizontal: bool,
+    pub vertical: bool,
+}
+
+impl Mirror {
+    /// "none", "h", "v" or "hv"
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name {
+            "none" => Ok(Mirror { horizontal: false, vertical: false }),
+            "h" => Ok(Mirror { horizontal: true, vertical: false }),
+            "v" => Ok(Mirror { horizontal: false, vertical: true }),
+            "hv" | "vh" => Ok(Mirror { horizontal: true, vertical: true }),
+            _ => Err(format!("Unsupported mirror: {} (expected none, h, v or hv)", name)),
+        }
+    }
+}
+
+/// Orthographic projection from logical screen coordinates ((0,0) top-left, (width,height)
+/// bottom-right) to NDC, mirrored, then turned by the display rotation. Column-major, as GL expects.
+fn projection_matrix(width: f32, height: f32, rotation: Rotation, mirror: Mirror) -> [f32; 16] {
+    // Mirroring negates an axis' scale and moves its origin to the opposite edge
+    let (scale_x, offset_x) = if mirror.horizontal { (-2.0 / width, 1.0) } else { (2.0 / width, -1.0) };
+    // Negative Y to flip coordinates, unless mirrored back
+    let (scale_y, offset_y) = if mirror.vertical { (2.0 / height, -1.0) } else { (-2.0 / height, 1.0) };
+    let mut matrix = [
+        scale_x,  0.0,      0.0, 0.0,
+        0.0,      scale_y,  0.0, 0.0,
+        0.0,      0.0,      -1.0, 0.0,
+        offset_x, offset_y, 0.0, 1.0,
+    ];
+
+    // Rotate the NDC x/y rows clockwise: (x, y) -> (cos*x + sin*y, -sin*x + cos*y)
+    let (cos, sin) = match rotation {
+        Rotation::Deg0 => return matrix,
+        Rotation::Deg90 => (0.0, 1.0),
+        Rotation::Deg180 => (-1.0, 0.0),
+        Rotation::Deg270 => (0.0, -1.0),
+    };
+    for column in 0..4 {
+        let x = matrix[column * 4];
+        let y = matrix[column * 4 + 1];
+        matrix[column * 4] = cos * x + sin * y;
+        matrix[column * 4 + 1] = -sin * x + cos * y;
+    }
+    matrix
+}
+
+/// Image file format for save_framebuffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramebufferFormat {
+    Png,
+    Bmp,
+    Jpeg,
+}
+
+/// Writes RGBA pixels as read back by glReadPixels (bottom row first) to an image file,
+/// flipping them upright. With keep_alpha false the alpha channel is dropped.
+fn save_rgba_pixels(filename: &str, pixels: &[u8], width: u32, height: u32,
+                    format: FramebufferFormat, keep_alpha: bool) -> Result<(), DashboardError> {
+    let (image_format, supports_alpha) = match format {
+        FramebufferFormat::Png => (image::ImageFormat::Png, true),
+        FramebufferFormat::Bmp => (image::ImageFormat::Bmp, true),
+        FramebufferFormat::Jpeg => (image::ImageFormat::Jpeg, false),
+    };
+    if keep_alpha && !supports_alpha {
+        return Err(DashboardError::Image(format!("{:?} cannot store an alpha channel", format)));
+    }
+
+    // Flip image vertically (OpenGL has origin at bottom-left)
+    let row_size = width as usize * 4;
+    let mut flipped_pixels = Vec::with_capacity(pixels.len());
+    for row in pixels.chunks_exact(row_size).rev() {
+        flipped_pixels.extend_from_slice(row);
+    }
+
+    let (data, color_type) = if keep_alpha {
+        (flipped_pixels, image::ColorType::Rgba8)
+    } else {
+        let rgb_pixels = flipped_pixels.chunks_exact(4).flat_map(|chunk| [chunk[0], chunk[1], chunk[2]]).collect();
+        (rgb_pixels, image::ColorType::Rgb8)
+    };
+
+    image::save_buffer_with_format(filename, &data, width, height, color_type, image_format)
+        .map_err(|e| DashboardError::Image(format!("Failed to save framebuffer: {}", e)))
+}
+
+/// Segments used for full circles — smooth at gauge sizes on an 800x480 panel
+const CIRCLE_SEGMENTS: usize = 64;
+
+/// Triangle fan for a filled arc: the centre, then `segments + 1` points along the arc.
+/// Angles in degrees.
+fn arc_fan_vertices(center_x: f32, center_y: f32, radius: f32, start_angle: f32, end_angle: f32, segments: usize) -> Vec<f32> {
+    let mut vertices = Vec::with_capacity((segments + 2) * 2); // Center + arc points
+    vertices.push(center_x);
+    vertices.push(center_y);
+
+    let angle_step = (end_angle - start_angle) / segments as f32;
+    for i in 0..=segments {
+        let angle = (start_angle + i as f32 * angle_step).to_radians();
+        vertices.push(center_x + radius * angle.cos());
+        vertices.push(center_y + radius * angle.sin());
+    }
+    vertices
+}
+
+/// Triangle strip for a thick arc: `points` inner/outer pairs spread evenly from
+/// start to end inclusive. Angles in radians.
+fn arc_strip_vertices(center_x: f32, center_y: f32, inner_radius: f32, outer_radius: f32,
+                      start_angle: f32, end_angle: f32, points: usize) -> Vec<f32> {
+    let mut vertices = Vec::with_capacity(points * 4); // 2 points per step * 2 coords
+    let angle_step = (end_angle - start_angle) / (points - 1) as f32;
+    for i in 0..points {
+        let angle = start_angle + i as f32 * angle_step;
+        let (sin_a, cos_a) = angle.sin_cos();
+        vertices.push(center_x + inner_radius * cos_a);
+        vertices.push(center_y + inner_radius * sin_a);
+        vertices.push(center_x + outer_radius * cos_a);
+        vertices.push(center_y + outer_radius * sin_a);
+    }
+    vertices
+}
+
+/// Two triangles covering a line of the given thickness from (x1, y1) to (x2, y2):
+/// the segment offset by half the thickness to either side along its normal.
+/// None for a zero-length or zero-thickness line.
+fn line_quad_vertices(x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32) -> Option<[f32; 12]> {
+    let (dx, dy) = (x2 - x1, y2 - y1);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length <= f32::EPSILON || thickness <= 0.0 {
+        return None;
+    }
+    let (nx, ny) = (-dy / length * thickness / 2.0, dx / length * thickness / 2.0);
+    Some([
+        x1 + nx, y1 + ny,
+        x2 + nx, y2 + ny,
+        x1 - nx, y1 - ny,
+
+        x2 + nx, y2 + ny,
+        x2 - nx, y2 - ny,
+        x1 - nx, y1 - ny,
+    ])
+}
+
+/// Triangle fan for a convex polygon: its points in order, flattened.
+/// None for fewer than three points.
+fn polygon_fan_vertices(points: &[(f32, f32)]) -> Option<Vec<f32>> {
+    if points.len() < 3 {
+        return None;
+    }
+    Some(points.iter().flat_map(|&(x, y)| [x, y]).collect())
+}
+
+/// Compiles and links a vertex/fragment shader pair. Sources must be nul-terminated;
+/// `name` is only used in error messages.
+unsafe fn link_shader_program(name: &str, vertex_source: &[u8], fragment_source: &[u8]) -> Result<u32, DashboardError> {
+    // Create and compile vertex shader
+    let vertex_shader = gl::CreateShader(gl::VERTEX_SHADER);
+    if vertex_shader == 0 {
+        return Err(DashboardError::Gl(format!("Failed to create {} vertex shader", name)));
+    }
     
-    // EGL handles
-    egl_display: EGLDisplay,
-    egl_context: EGLContext,
-    egl_surface: EGLSurface,
-    egl_config: EGLConfig,
+    let vertex_src_ptr = vertex_source.as_ptr();
+    gl::ShaderSource(vertex_shader, 1, &vertex_src_ptr, std::ptr::null());
+    gl::CompileShader(vertex_shader);
     
-    // Display configuration
-    connector_id: u32,
-    crtc_id: u32,
-    mode: DrmModeModeInfo,
-    previous_crtc: *mut c_void,
+    let mut compile_status = 0i32;
+    gl::GetShaderiv(vertex_shader, gl::COMPILE_STATUS, &mut compile_status);
+    if compile_status == 0 {
+        gl::DeleteShader(vertex_shader);
+        return Err(DashboardError::Gl(format!("{} vertex shader compilation failed", name)));
+    }
     
-    // Framebuffer management
-    current_fb: u32,
-    previous_fb: u32,
-    flip_pending: bool,
+    // Create and compile fragment shader
+    let fragment_shader = gl::CreateShader(gl::FRAGMENT_SHADER);
+    if fragment_shader == 0 {
+        gl::DeleteShader(vertex_shader);
+        return Err(DashboardError::Gl(format!("Failed to create {} fragment shader", name)));
+    }
     
-    // Display properties
-    pub width: i32,
-    pub height: i32,
+    let fragment_src_ptr = fragment_source.as_ptr();
+    gl::ShaderSource(fragment_shader, 1, &fragment_src_ptr, std::ptr::null());
+    gl::CompileShader(fragment_shader);
     
-    // Text rendering - font management with HashMap
-    pub text_renderers: HashMap<String, OpenGLTextRenderer>,
+    let mut compile_status = 0i32;
+    gl::GetShaderiv(fragment_shader, gl::COMPILE_STATUS, &mut compile_status);
+    if compile_status == 0 {
+        gl::DeleteShader(vertex_shader);
+        gl::DeleteShader(fragment_shader);
+        return Err(DashboardError::Gl(format!("{} fragment shader compilation failed", name)));
+    }
     
-    // Brightness level (0.1 to 1.0), applied in every render function
-    // Minimum is 0.1 to avoid completely black screen (corresponds to 10% of max brightness value)
-    brightness: f32,
+    // Create and link shader program
+    let program = gl::CreateProgram();
+    if program == 0 {
+        gl::DeleteShader(vertex_shader);
+        gl::DeleteShader(fragment_shader);
+        return Err(DashboardError::Gl(format!("Failed to create {} shader program", name)));
+    }
     
-    // Cached shader programs for performance
-    rectangle_shader: Option<u32>,
-    // Persistent VBOs for per-frame primitive rendering — never deleted in the hot path.
-    // Shared by render_filled_rectangle, render_circle_segment, render_circle_arc_outline.
-    geometry_vbo: Option<u32>,
-    // Persistent VBO for the bloom fullscreen quad.
-    bloom_quad_vbo: Option<u32>,
+    gl::AttachShader(program, vertex_shader);
+    gl::AttachShader(program, fragment_shader);
+    gl::LinkProgram(program);
     
-    // Bloom post-processing effect
-    bloom_enabled: bool,
-    bloom_intensity: f32,
+    let mut link_status = 0i32;
+    gl::GetProgramiv(program, gl::LINK_STATUS, &mut link_status);
+    if link_status == 0 {
+        gl::DeleteShader(vertex_shader);
+        gl::DeleteShader(fragment_shader);
+        gl::DeleteProgram(program);
+        return Err(DashboardError::Gl(format!("{} shader program linking failed", name)));
+    }
+    
+    // Clean up individual shaders (they're now linked to the program)
+    gl::DeleteShader(vertex_shader);
+    gl::DeleteShader(fragment_shader);
+    
+    Ok(program)
+}
+
+/// Decodes an image file into tightly packed RGBA8 rows, top row first
+fn load_rgba_image(path: &str) -> Result<(u32, u32, Vec<u8>), DashboardError> {
+    let image = image::open(path)
+        .map_err(|e| DashboardError::Image(format!("Failed to load image {}: {}", path, e)))?
+        .to_rgba8();
+    Ok((image.width(), image.height(), image.into_raw()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GradientDirection {
+    Vertical,
+    Horizontal,
+}
+
+/// Floats per gradient vertex: x, y, r, g, b
+const GRADIENT_VERTEX_FLOATS: usize = 5;
+
+/// Two triangles covering the rectangle, colored `start` on the top (vertical) or left
+/// (horizontal) edge and `end` on the opposite one
+fn gradient_quad_vertices(x: f32, y: f32, width: f32, height: f32,
+                          start: (f32, f32, f32), end: (f32, f32, f32),
+                          direction: GradientDirection) -> [f32; 6 * GRADIENT_VERTEX_FLOATS] {
+    let (top_left, top_right, bottom_left, bottom_right) = match direction {
+        GradientDirection::Vertical => (start, start, end, end),
+        GradientDirection::Horizontal => (start, end, start, end),
+    };
+    let vertex = |px: f32, py: f32, c: (f32, f32, f32)| [px, py, c.0, c.1, c.2];
+    let corners = [
+        vertex(x,         y,          top_left),
+        vertex(x + width, y,          top_right),
+        vertex(x,         y + height, bottom_left),
+
+        vertex(x + width, y,          top_right),
+        vertex(x + width, y + height, bottom_right),
+        vertex(x,         y + height, bottom_left),
+    ];
+    let mut vertices = [0.0; 6 * GRADIENT_VERTEX_FLOATS];
+    for (chunk, corner) in vertices.chunks_exact_mut(GRADIENT_VERTEX_FLOATS).zip(corners) {
+        chunk.copy_from_slice(&corner);
+    }
+    vertices
+}
+
+/// Overlap of two (x, y, width, height) rectangles; zero-sized if they do not overlap
+fn intersect_rects(a: (f32, f32, f32, f32), b: (f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
+    let left = a.0.max(b.0);
+    let top = a.1.max(b.1);
+    let right = (a.0 + a.2).min(b.0 + b.2);
+    let bottom = (a.1 + a.3).min(b.1 + b.3);
+    (left, top, (right - left).max(0.0), (bottom - top).max(0.0))
+}
+
+/// glScissor box (window pixels, origin bottom-left) for a clip rectangle in logical
+/// screen coordinates (origin top-left, possibly rotated). Rounds outwards so edges drawn
+/// exactly on the clip boundary are kept.
+fn scissor_box(clip: (f32, f32, f32, f32), logical_width: f32, logical_height: f32,
+               display_width: i32, display_height: i32, rotation: Rotation, mirror: Mirror) -> (i32, i32, i32, i32) {
+    let matrix = projection_matrix(logical_width, logical_height, rotation, mirror);
+    let to_window = |x: f32, y: f32| {
+        let ndc_x = matrix[0] * x + matrix[4] * y + matrix[12];
+        let ndc_y = matrix[1] * x + matrix[5] * y + matrix[13];
+        ((ndc_x + 1.0) / 2.0 * display_width as f32, (ndc_y + 1.0) / 2.0 * display_height as f32)
+    };
+    let (x1, y1) = to_window(clip.0, clip.1);
+    let (x2, y2) = to_window(clip.0 + clip.2, clip.1 + clip.3);
+    // The matrix round trip leaves values like 429.9999; snap those before widening to whole pixels
+    let snap = |v: f32| if (v - v.round()).abs() < 1e-3 { v.round() } else { v };
+    let left = snap(x1.min(x2)).floor().max(0.0) as i32;
+    let bottom = snap(y1.min(y2)).floor().max(0.0) as i32;
+    let right = (snap(x1.max(x2)).ceil() as i32).min(display_width);
+    let top = (snap(y1.max(y2)).ceil() as i32).min(display_height);
+    (left, bottom, (right - left).max(0), (top - bottom).max(0))
+}
+
+/// Offscreen color buffer: a framebuffer with a single RGBA texture attached
+#[derive(Debug, Clone, Copy)]
+struct RenderTarget {
+    framebuffer: u32,
+    texture: u32,
+    width: i32,
+    height: i32,
+}
+
+impl RenderTarget {
+    unsafe fn create(width: i32, height: i32) -> Result<Self, DashboardError> {
+        let mut framebuffer = 0;
+        gl::GenFramebuffers(1, &mut framebuffer);
+        gl::BindFramebuffer(gl::FRAMEBUFFER, framebuffer);
+        
+        let mut texture = 0;
+        gl::GenTextures(1, &mut texture);
+        gl::BindTexture(gl::TEXTURE_2D, texture);
+        gl::TexImage2D(
+            gl::TEXTURE_2D, 0, gl::RGBA as i32,
+            width, height, 0,
+            gl::RGBA, gl::UNSIGNED_BYTE,
+            ptr::null()
+        );
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+        gl::FramebufferTexture2D(gl::FRAMEBUFFER, gl::COLOR_ATTACHMENT0, gl::TEXTURE_2D, texture, 0);
+        
+        let status = gl::CheckFramebufferStatus(gl::FRAMEBUFFER);
+        gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        let target = RenderTarget { framebuffer, texture, width, height };
+        if status != gl::FRAMEBUFFER_COMPLETE {
+            target.delete();
+            return Err(DashboardError::Gl(format!("Framebuffer {}x{} incomplete (status 0x{:x})", width, height, status)));
+        }
+        Ok(target)
+    }
+    
+    unsafe fn delete(&self) {
+        gl::DeleteFramebuffers(1, &self.framebuffer);
+        gl::DeleteTextures(1, &self.texture);
+    }
+}
+
+// Bloom blur radius in taps; one tap is two display pixels (one texel at the default half resolution)
+const DEFAULT_BLOOM_RADIUS: usize = 3;
+// Each extra tap costs two texture fetches per blur pass per pixel
+const MAX_BLOOM_RADIUS: usize = 8;
+// Square root of the total weight of the former single-pass 7x7 kernel, so a uniformly
+// bright area glows as strongly after the two 1D passes as it used to
+const BLOOM_KERNEL_SUM: f32 = 3.846;
+
+/// Size of the bloom blur targets for a display size and downsample factor, rounded up
+fn bloom_target_size(display_width: i32, display_height: i32, downsample: u32) -> (i32, i32) {
+    let factor = downsample as i32;
+    ((display_width + factor - 1) / factor, (display_height + factor - 1) / factor)
+}
+
+/// One-sided weights of the separable bloom blur: the center tap first, then each offset
+/// which is sampled on both sides. The total stays the same for every radius, so a wider
+/// radius spreads the glow rather than brightening it.
+fn bloom_blur_weights(radius: usize) -> Vec<f32> {
+    let sigma = radius as f32 / 2.0;
+    let mut weights: Vec<f32> = (0..=radius)
+        .map(|i| {
+            let offset = i as f32;
+            (-offset * offset / (2.0 * sigma * sigma)).exp()
+        })
+        .collect();
+    let total = weights[0] + 2.0 * weights[1..].iter().sum::<f32>();
+    for weight in weights.iter_mut() {
+        *weight *= BLOOM_KERNEL_SUM / total;
+    }
+    weights
+}
+
+// Shared by the fullscreen post-processing passes drawn with render_fullscreen_quad
+const POST_PROCESS_VERTEX_SHADER: &[u8] = b"
+    #version 300 es
+    precision mediump float;
+    
+    layout(location = 0) in vec2 position;
+    layout(location = 1) in vec2 texCoord;
+    
+    out vec2 vTexCoord;
+    
+    void main() {
+        gl_Position = vec4(position, 0.0, 1.0);
+        vTexCoord = texCoord;
+    }
+\0";
+
+/// Fragment shader for one 1D bloom blur pass; GLSL ES needs the tap count at compile time
+fn bloom_blur_shader_source(radius: usize) -> String {
+    format!("
+        #version 300 es
+        precision mediump float;
+        
+        in vec2 vTexCoord;
+        out vec4 fragColor;
+        
+        uniform sampler2D uTexture;
+        // One tap along the blur direction
+        uniform vec2 uTexelStep;
+        uniform float uWeights[{taps}];
+        
+        void main() {{
+            vec3 sum = texture(uTexture, vTexCoord).rgb * uWeights[0];
+            for(int i = 1; i < {taps}; i++) {{
+                vec2 offset = uTexelStep * float(i);
+                sum += texture(uTexture, vTexCoord + offset).rgb * uWeights[i];
+                sum += texture(uTexture, vTexCoord - offset).rgb * uWeights[i];
+            }}
+            fragColor = vec4(sum, 1.0);
+        }}
+    \0", taps = radius + 1)
+}
+
+// Outgoing and incoming page during an animated page switch
+const PAGE_LAYER_COUNT: usize = 2;
+
+/// Blends the two page layers. Offsets are in texture coordinates; outside the incoming
+/// page's shifted rectangle the outgoing page shows through.
+const PAGE_TRANSITION_FRAGMENT_SHADER: &[u8] = b"
+    #version 300 es
+    precision mediump float;
+    
+    in vec2 vTexCoord;
+    out vec4 fragColor;
+    
+    uniform sampler2D uFrom;
+    uniform sampler2D uTo;
+    uniform vec2 uFromOffset;
+    uniform vec2 uToOffset;
+    uniform float uToWeight;
+    
+    void main() {
+        vec2 fromCoord = vTexCoord - uFromOffset;
+        vec2 toCoord = vTexCoord - uToOffset;
+        bool insideTo = all(greaterThanEqual(toCoord, vec2(0.0))) && all(lessThanEqual(toCoord, vec2(1.0)));
+        vec3 fromColor = texture(uFrom, fromCoord).rgb;
+        vec3 toColor = texture(uTo, toCoord).rgb;
+        fragColor = vec4(mix(fromColor, toColor, insideTo ? uToWeight : 0.0), 1.0);
+    }
+\0";
+
+/// Display texture-space direction of the logical +x axis, which the rotation turns clockwise
+/// and a horizontal mirror reverses
+fn logical_x_direction(rotation: Rotation, mirror: Mirror) -> (f32, f32) {
+    let (x, y) = match rotation {
+        Rotation::Deg0 => (1.0, 0.0),
+        Rotation::Deg90 => (0.0, -1.0),
+        Rotation::Deg180 => (-1.0, 0.0),
+        Rotation::Deg270 => (0.0, 1.0),
+    };
+    if mirror.horizontal { (-x, -y) } else { (x, y) }
+}
+
+/// Darkening toward the screen edges, applied after bloom. Off by default.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct VignetteSettings {
+    enabled: bool,
+    // How dark the corners get: 0.0 = no effect, 1.0 = black
+    strength: f32,
+    // Distance from the center where darkening starts; 1.0 is the middle of an edge,
+    // the corners are at ~1.41
+    radius: f32,
+}
+
+impl Default for VignetteSettings {
+    fn default() -> Self {
+        VignetteSettings { enabled: false, strength: 0.35, radius: 0.75 }
+    }
+}
+
+impl VignetteSettings {
+    fn set_strength(&mut self, strength: f32) {
+        self.strength = strength.clamp(0.0, 1.0);
+    }
+
+    fn set_radius(&mut self, radius: f32) {
+        self.radius = radius.clamp(0.0, 1.4);
+    }
+}
+
+/// Handle to a texture loaded with GraphicsContext::load_texture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextureId(u32);
+
+#[derive(Debug, Clone, Copy)]
+struct LoadedTexture {
+    id: TextureId,
+    width: u32,
+    height: u32,
+}
+
+/// Text orientation options for rendering
+#[derive(Debug, Clone, Copy)]
+pub enum TextOrientation {
+    Horizontal,  // Normal left-to-right text
+    Vertical,    // Characters stacked vertically (top-to-bottom, not rotated)
+    RotatedCW,   // Whole string turned 90° clockwise, reading top-to-bottom
+    RotatedCCW,  // Whole string turned 90° counter-clockwise, reading bottom-to-top
+}
+
+/// Horizontal placement of text relative to an anchor x
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextAlign {
+    Left,    // Text starts at the anchor
+    Center,  // Text is centered on the anchor
+    Right,   // Text ends at the anchor
+}
+
+impl TextAlign {
+    /// Left edge for text of `width` placed at `anchor_x`
+    pub fn start_x(self, anchor_x: f32, width: f32) -> f32 {
+        match self {
+            TextAlign::Left => anchor_x,
+            TextAlign::Center => anchor_x - width / 2.0,
+            TextAlign::Right => anchor_x - width,
+        }
+    }
+}
+
+/// Fully opaque RGBA for an RGB color, for render_rectangle
+pub fn opaque(color: (f32, f32, f32)) -> (f32, f32, f32, f32) {
+    (color.0, color.1, color.2, 1.0)
+}
+
+/// Font, size, scale and color for the render_text_* helpers that also take layout arguments
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle<'a> {
+    pub font_path: &'a str,
+    pub font_size: u32,
+    pub scale: f32,
+    pub color: (f32, f32, f32),
+}
+
+impl<'a> TextStyle<'a> {
+    pub fn new(font_path: &'a str, font_size: u32, color: (f32, f32, f32)) -> Self {
+        Self { font_path, font_size, scale: 1.0, color }
+    }
+
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+/// Lines of a horizontal text block; "\r\n" line endings are accepted too
+fn text_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.split('\n').map(|line| line.strip_suffix('\r').unwrap_or(line))
+}
+
+/// Break text into lines no wider than `max_width` as reported by `measure`. Breaks at spaces
+/// and existing newlines; a word wider than the limit on its own is split between characters.
+fn wrap_text<F, E>(text: &str, max_width: f32, mut measure: F) -> Result<Vec<String>, E>
+where
+    F: FnMut(&str) -> Result<f32, E>,
+{
+    let mut lines = Vec::new();
+    for paragraph in text_lines(text) {
+        let mut line = String::new();
+        for word in paragraph.split(' ').filter(|word| !word.is_empty()) {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if measure(&candidate)? <= max_width {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            if measure(word)? <= max_width {
+                line = word.to_string();
+                continue;
+            }
+            // Hard break; every line keeps at least one character so this always progresses
+            for ch in word.chars() {
+                let mut candidate = line.clone();
+                candidate.push(ch);
+                if !line.is_empty() && measure(&candidate)? > max_width {
+                    lines.push(std::mem::take(&mut line));
+                }
+                line.push(ch);
+            }
+        }
+        lines.push(line);
+    }
+    Ok(lines)
+}
+
+const ELLIPSIS: char = '…';
+
+/// Shorten `text` to fit `max_width` as reported by `measure`, dropping trailing characters
+/// and ending it with an ellipsis. Returns the text to draw and whether it was cut; when not
+/// even the ellipsis fits, that is all that's left.
+fn truncate_text<F, E>(text: &str, max_width: f32, mut measure: F) -> Result<(String, bool), E>
+where
+    F: FnMut(&str) -> Result<f32, E>,
+{
+    if measure(text)? <= max_width {
+        return Ok((text.to_string(), false));
+    }
+    let mut kept: Vec<char> = text.chars().collect();
+    while !kept.is_empty() {
+        kept.pop();
+        // No dangling space before the ellipsis
+        let candidate: String = kept.iter().collect::<String>().trim_end().chars().chain([ELLIPSIS]).collect();
+        if measure(&candidate)? <= max_width {
+            return Ok((candidate, true));
+        }
+    }
+    Ok((ELLIPSIS.to_string(), true))
+}
+
+/// Height of a block of lines `line_height` apart: every line but the last takes a full
+/// line height, the last only as much as its glyphs
+fn stacked_lines_height(line_count: usize, last_line_height: f32, line_height: f32) -> f32 {
+    line_count.saturating_sub(1) as f32 * line_height + last_line_height
+}
+
+/// Converts a FreeType error code into a human-readable description.
+/// Covers the most common error codes defined in freetype/fterrdef.h.
+fn ft_error_description(code: freetype_sys::FT_Error) -> &'static str {
+    match code {
+        0x00 => "no error",
+        0x01 => "cannot open resource",
+        0x02 => "unknown file format",
+        0x03 => "broken file: invalid content",
+        0x04 => "invalid FreeType version",
+        0x05 => "module version is too low",
+        0x06 => "invalid argument",
+        0x07 => "unimplemented feature",
+        0x08 => "broken table",
+        0x09 => "broken offset within table",
+        0x0A => "array allocation size too large",
+        0x0B => "missing module",
+        0x0C => "missing property",
+        0x10 => "invalid glyph index",
+        0x11 => "invalid character code",
+        0x12 => "unsupported glyph image format",
+        0x13 => "cannot render this glyph format",
+        0x14 => "invalid outline",
+        0x15 => "invalid composite glyph",
+        0x16 => "too many hints",
+        0x17 => "invalid pixel size",
+        0x18 => "invalid SVG document",
+        0x20 => "invalid object handle",
+        0x21 => "invalid library handle",
+        0x22 => "invalid module handle",
+        0x23 => "invalid face handle",
+        0x24 => "invalid size handle",
+        0x25 => "invalid glyph slot handle",
+        0x26 => "invalid charmap handle",
+        0x27 => "invalid cache manager handle",
+        0x28 => "invalid stream handle",
+        0x30 => "too many modules",
+        0x31 => "too many extensions",
+        0x40 => "out of memory",
+        0x41 => "unlisted object",
+        0x51 => "cannot open stream",
+        0x52 => "invalid stream seek",
+        0x53 => "invalid stream skip",
+        0x54 => "invalid stream read",
+        0x55 => "invalid stream operation",
+        0x56 => "invalid frame operation",
+        0x57 => "nested frame access",
+        0x58 => "invalid frame read",
+        0x60 => "raster uninitialized",
+        0x61 => "raster corrupted",
+        0x62 => "raster overflow",
+        0x63 => "negative height while rastering",
+        0x70 => "too many registered caches",
+        0x80 => "invalid opcode",
+        0x81 => "too few arguments",
+        0x82 => "stack overflow",
+        0x83 => "code overflow",
+        0x84 => "bad argument",
+        0x85 => "division by zero",
+        0x86 => "invalid reference",
+        0x87 => "found debug opcode",
+        0x88 => "found ENDF opcode in execution stream",
+        0x89 => "nested DEFS",
+        0x8A => "invalid code range",
+        0x8B => "execution context too long",
+        0x8C => "too many function definitions",
+        0x8D => "too many instruction definitions",
+        0x8E => "SFNT font table missing",
+        0x8F => "horizontal header (hhea) table missing",
+        0x90 => "locations (loca) table missing",
+        0x91 => "name table missing",
+        0x92 => "character map (cmap) table missing",
+        0x93 => "horizontal metrics (hmtx) table missing",
+        0x94 => "PostScript (post) table missing",
+        0x95 => "OS/2 table missing",
+        _    => "unknown error",
+    }
+}
+
+/// Display mode as (width, height, refresh Hz)
+pub type DisplayMode = (u16, u16, u32);
+
+/// Graphics context using KMS/DRM backend with OpenGL ES
+pub struct GraphicsContext {
+    // DRM/KMS handles
+    drm_fd: c_int,
+    gbm_device: *mut c_void,
+    gbm_surface: *mut c_void,
+    
+    // EGL handles
+    egl_display: EGLDisplay,
+    egl_context: EGLContext,
+    egl_surface: EGLSurface,
+    egl_config: EGLConfig,
+    
+    // Display configuration
+    // (connector index, mode index) as reported by list_modes(); None picks the first
+    // connected connector's preferred mode
+    mode_selection: Option<(usize, usize)>,
+    connector_id: u32,
+    crtc_id: u32,
+    mode: DrmModeModeInfo,
+    previous_crtc: Option<SavedCrtc>,
+    
+    // Framebuffer management
+    scanout_buffers: FramebufferSlots<ScanoutBuffer>,
+    framebuffer_cache: FramebufferCache,
+    // Set when a flip is queued, cleared by page_flip_handler. Boxed so the address handed
+    // to the kernel as the event's user data survives moves of the context.
+    flip_pending: Box<AtomicBool>,
+    // Consecutive frames dropped waiting on flip_pending
+    flip_timeouts: u32,
+    
+    // Display properties
+    // Logical size seen by rendering code — the display mode size, swapped for 90/270 rotation
+    pub width: i32,
+    pub height: i32,
+    // Display mode size, for everything sized in scanout pixels (GBM, FBs, viewport, readback)
+    display_width: i32,
+    display_height: i32,
+    rotation: Rotation,
+    mirror: Mirror,
+    
+    // Text rendering - font management with HashMap
+    pub text_renderers: HashMap<String, OpenGLTextRenderer>,
+    
+    // Brightness level (0.1 to 1.0), applied in every render function
+    // Minimum is 0.1 to avoid completely black screen (corresponds to 10% of max brightness value)
+    brightness: f32,
+    // Panel backlight, when present; it then carries the brightness level and colors are left unscaled
+    backlight: Option<Backlight>,
+    
+    // Cached shader programs for performance
+    rectangle_shader: Option<u32>,
+    gradient_shader: Option<u32>,
+    texture_shader: Option<u32>,
+    
+    // Textures loaded from image files, keyed by path like the font renderers
+    textures: HashMap<String, LoadedTexture>,
+    // Paths that failed to load, so a missing image isn't re-read and re-logged every frame
+    failed_textures: HashSet<String>,
+    // render_text_truncated results, see TruncatedTextKey
+    truncated_text_cache: HashMap<TruncatedTextKey, (String, bool)>,
+    
+    // Active clip rectangles in logical coordinates (x, y, width, height); each entry is
+    // already intersected with the ones below it
+    clip_stack: Vec<(f32, f32, f32, f32)>,
+    // Persistent VBOs for per-frame primitive rendering — never deleted in the hot path.
+    // Shared by every primitive drawn through render_solid_vertices.
+    geometry_vbo: Option<u32>,
+    // Persistent VBO for the bloom fullscreen quad.
+    bloom_quad_vbo: Option<u32>,
+    
+    // Bloom post-processing effect
+    bloom_enabled: bool,
+    bloom_intensity: f32,
     bloom_threshold: f32,
     bloom_framebuffer: Option<u32>,
     bloom_texture: Option<u32>,
+    // Downsampled ping-pong targets for the bright pass and the two blur passes
+    bloom_blur_targets: Vec<RenderTarget>,
+    // Display size divided by this gives the blur target size (1, 2 or 4)
+    bloom_downsample: u32,
+    // Blur taps on each side of the center, compiled into bloom_blur_shader
+    bloom_radius: usize,
+    
+    // Vignette post-processing effect
+    vignette: VignetteSettings,
+    vignette_shader: Option<u32>,
+    bloom_bright_shader: Option<u32>,
+    bloom_blur_shader: Option<u32>,
+    // Composites the scene texture plus the blurred glow
     bloom_shader: Option<u32>,
     
+    // Offscreen page copies for animated page switches, created on first use
+    page_layers: Vec<RenderTarget>,
+    page_transition_shader: Option<u32>,
+    // Framebuffer that was bound when the first page layer began, restored by the composite
+    page_layer_return_framebuffer: Option<u32>,
+    
     // State
     initialized: bool,
     display_configured: bool,
+    // Rendering into an EGL pbuffer with no DRM device behind it (see new_headless)
+    headless: bool,
+    // Page flips wait for vblank (see set_vsync)
+    vsync: bool,
+    // Background the screen, bloom scene and page layers are cleared to
+    clear_color: (f32, f32, f32),
+}
+
+/// Opens the primary DRM device, falling back to the vc4 driver on the Raspberry Pi
+fn open_drm_device() -> Result<c_int, DashboardError> {
+    unsafe {
+        let card_name = CString::new("card0").unwrap();
+        let drm_fd = drmOpen(card_name.as_ptr(), ptr::null());
+        if drm_fd >= 0 {
+            return Ok(drm_fd);
+        }
+
+        let vc4_name = CString::new("vc4").unwrap();
+        let drm_fd = drmOpen(vc4_name.as_ptr(), ptr::null());
+        if drm_fd < 0 {
+            return Err(DashboardError::Drm("Failed to open DRM device. Make sure you have access to /dev/dri/card* devices.".to_string()));
+        }
+        Ok(drm_fd)
+    }
+}
+
+/// Create/destroy counters, for checking that buffer churn stays bounded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FramebufferStats {
+    pub created: u64,
+    pub destroyed: u64,
+    /// Currently allocated
+    pub live: u64,
+}
+
+/// DRM framebuffers keyed by GBM buffer object handle. GBM recycles a small set of
+/// buffers, so each gets its framebuffer created once instead of every frame.
+#[derive(Debug, Default)]
+struct FramebufferCache {
+    fbs: HashMap<u32, u32>,
+    created: u64,
+    destroyed: u64,
+}
+
+impl FramebufferCache {
+    fn get_or_create<E>(&mut self, handle: u32, create: impl FnOnce() -> Result<u32, E>) -> Result<u32, E> {
+        if let Some(&fb) = self.fbs.get(&handle) {
+            return Ok(fb);
+        }
+        let fb = create()?;
+        self.fbs.insert(handle, fb);
+        self.created += 1;
+        Ok(fb)
+    }
+
+    /// Forgets all cached framebuffers, returning them for removal
+    fn clear(&mut self) -> Vec<u32> {
+        let fbs: Vec<u32> = self.fbs.drain().map(|(_, fb)| fb).collect();
+        self.destroyed += fbs.len() as u64;
+        fbs
+    }
+
+    fn stats(&self) -> FramebufferStats {
+        FramebufferStats {
+            created: self.created,
+            destroyed: self.destroyed,
+            live: self.fbs.len() as u64,
+        }
+    }
+}
+
+/// A GBM buffer object that stays locked while on screen or queued to be, and the
+/// (cached) DRM framebuffer wrapping it
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ScanoutBuffer {
+    fb: u32,
+    bo: *mut c_void,
+}
+
+/// Tracks which scanout buffers are in use. Every created buffer goes through one of the
+/// transitions below, each of which returns the buffers that are now safe to release, so
+/// no path can strand one.
+#[derive(Debug)]
+struct FramebufferSlots<T> {
+    // On screen, or about to be once the pending flip completes
+    current: Option<T>,
+    // Was on screen before the pending flip; must live until that flip completes
+    previous: Option<T>,
 }
 
-/// No-op page flip handler — we only need to drain the DRM event, not inspect it.
-unsafe extern "C" fn page_flip_handler_stub(
+impl<T> Default for FramebufferSlots<T> {
+    fn default() -> Self {
+        FramebufferSlots { current: None, previous: None }
+    }
+}
+
+impl<T> FramebufferSlots<T> {
+    /// A page flip to `buffer` was queued. `current` stays on screen until it completes.
+    /// Anything still in `previous` means a completion was missed; it is off screen by now.
+    fn flip_queued(&mut self, buffer: T) -> Vec<T> {
+        let released = self.previous.take();
+        self.previous = self.current.replace(buffer);
+        released.into_iter().collect()
+    }
+
+    /// The pending flip completed, so the buffer it replaced is off screen
+    fn flip_completed(&mut self) -> Vec<T> {
+        self.previous.take().into_iter().collect()
+    }
+
+    /// `buffer` is on screen via an immediate modeset; everything older is off screen.
+    fn displayed(&mut self, buffer: T) -> Vec<T> {
+        [self.previous.take(), self.current.replace(buffer)].into_iter().flatten().collect()
+    }
+
+    /// `buffer` was created but could not be shown
+    fn discard(&mut self, buffer: T) -> Vec<T> {
+        vec![buffer]
+    }
+
+    /// All tracked buffers, for teardown
+    fn take_all(&mut self) -> Vec<T> {
+        [self.previous.take(), self.current.take()].into_iter().flatten().collect()
+    }
+}
+
+/// Longest wait for a queued page flip to complete — three vblanks at 60Hz
+const FLIP_WAIT_TIMEOUT: Duration = Duration::from_millis(50);
+/// Timed-out waits in a row after which the flip event is given up on and the next
+/// frame goes up with a modeset instead
+const MAX_FLIP_TIMEOUTS: u32 = 5;
+
+/// Lines kept by render_text_truncated's cache - a few screens of text
+const MAX_TRUNCATED_TEXT_CACHE: usize = 256;
+
+/// render_text_truncated cache key: text, font path, font size, scale bits, max width bits
+type TruncatedTextKey = (String, String, u32, u32, u32);
+
+/// Page flip completion handler. `user_data` is the context's flip_pending flag, passed
+/// to drmModePageFlip.
+unsafe extern "C" fn page_flip_handler(
     _fd: c_int,
     _sequence: u32,
     _tv_sec: u32,
     _tv_usec: u32,
-    _user_data: *mut c_void,
-) {}
+    user_data: *mut c_void,
+) {
+    if !user_data.is_null() {
+        (*(user_data as *const AtomicBool)).store(false, Ordering::Release);
+    }
+}
+
+impl GraphicsContext {
+    /// Create a new graphics context with KMS/DRM backend
+    pub fn new(title: &str, width: i32, height: i32) -> Result<Self, DashboardError> {
+        Self::create(title, width, height, None)
+    }
+
+    /// Create a context on a specific connector and mode, indexed as in list_modes().
+    /// Lets a panel be driven at its exact timing instead of whatever mode the connector
+    /// lists first (an HDMI monitor's preferred mode rarely matches the 800x480 panel).
+    pub fn new_with_mode(title: &str, connector_index: usize, mode_index: usize) -> Result<Self, DashboardError> {
+        Self::create(title, 0, 0, Some((connector_index, mode_index)))
+    }
+
+    /// Available modes as (width, height, refresh Hz) for every DRM connector, in
+    /// connector order. Disconnected connectors report no modes.
+    pub fn list_modes() -> Result<Vec<Vec<DisplayMode>>, DashboardError> {
+        let drm_fd = open_drm_device()?;
+        let mut connectors = Vec::new();
+        unsafe {
+            let resources = drmModeGetResources(drm_fd);
+            if resources.is_null() {
+                drmClose(drm_fd);
+                return Err(DashboardError::Drm("Failed to get DRM resources".to_string()));
+            }
+            let res = &*(resources as *const DrmModeRes);
+            for i in 0..res.count_connectors {
+                let connector_id = *res.connectors.offset(i as isize);
+                let connector = drmModeGetConnector(drm_fd, connector_id);
+                let mut modes = Vec::new();
+                if !connector.is_null() {
+                    let conn = &*(connector as *const DrmModeConnector);
+                    if conn.connection == DRM_MODE_CONNECTED {
+                        for m in 0..conn.count_modes {
+                            let mode = &*conn.modes.offset(m as isize);
+                            modes.push((mode.hdisplay, mode.vdisplay, mode.vrefresh));
+                        }
+                    }
+                    drmModeFreeConnector(connector);
+                }
+                connectors.push(modes);
+            }
+            drmModeFreeResources(resources);
+            drmClose(drm_fd);
+        }
+        Ok(connectors)
+    }
+
+    /// Offscreen context rendering into an EGL pbuffer: no DRM device, CRTC setup or page
+    /// flipping, so it runs in CI and on a desktop. Drawing, text and save_framebuffer work as
+    /// on the panel; swap_buffers does nothing. Needs an EGL with the Mesa surfaceless
+    /// platform - on Debian/Raspberry Pi OS, libegl-mesa0 and libgles2 (llvmpipe renders
+    /// without a GPU) - and no display server. The dashboard itself always uses the KMS path.
+    pub fn new_headless(width: i32, height: i32) -> Result<Self, DashboardError> {
+        let mut context = Self::unconfigured(width, height, None);
+        context.headless = true;
+        gl::load_with(|name| {
+            let c_str = std::ffi::CString::new(name).unwrap();
+            context.get_proc_address(c_str.as_ptr()) as *const _
+        });
+
+        log::info!("Initializing headless graphics context ({}x{})", width, height);
+        context.init_egl()?;
+        unsafe {
+            glViewport(0, 0, context.display_width, context.display_height);
+            glClearColor(0.0, 0.0, 0.0, 1.0);
+        }
+        if let Err(e) = context.init_bloom() {
+            log::warn!("Warning: Failed to initialize bloom effect: {}", e);
+            context.bloom_enabled = false;
+        }
+
+        context.initialized = true;
+        Ok(context)
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.headless
+    }
 
-impl GraphicsContext {
-    /// Create a new graphics context with KMS/DRM backend
-    pub fn new(title: &str, width: i32, height: i32) -> Result<Self, String> {
-        let mut context = GraphicsContext {
-            drm_fd: -1,
-            gbm_device: ptr::null_mut(),
-            gbm_surface: ptr::null_mut(),
-            egl_display: ptr::null_mut(),
-            egl_context: EGL_NO_CONTEXT,
-            egl_surface: EGL_NO_SURFACE,
-            egl_config: ptr::null_mut(),
-            connector_id: 0,
-            crtc_id: 0,
-            mode: unsafe { std::mem::zeroed() },
-            previous_crtc: ptr::null_mut(),
-            current_fb: 0,
-            previous_fb: 0,
-            flip_pending: false,
-            width,
-            height,
-            text_renderers: HashMap::new(),
-            brightness: 1.0,
-            rectangle_shader: None,
-            geometry_vbo: None,
-            bloom_quad_vbo: None,
-            bloom_enabled: true,
-            bloom_intensity: 0.5,  // Increased for more visible glow
-            bloom_threshold: 0.3,  // Lowered to catch more bright pixels
-            bloom_framebuffer: None,
-            bloom_texture: None,
-            bloom_shader: None,
-            initialized: false,
-            display_configured: false,
-        };
+    fn create(title: &str, width: i32, height: i32, mode_selection: Option<(usize, usize)>) -> Result<Self, DashboardError> {
+        let mut context = Self::unconfigured(width, height, mode_selection);
 
         // Load OpenGL function pointers
         gl::load_with(|name| {
@@ -479,7 +1700,7 @@ impl GraphicsContext {
         });
         
         log::info!("Initializing KMS/DRM graphics context: {} ({}x{})", title, width, height);
-        log::info!("Setting up direct display output...");
+        log::debug!("Setting up direct display output...");
         
         // Initialize DRM
         context.init_drm()?;
@@ -497,7 +1718,7 @@ impl GraphicsContext {
         
         // Set up OpenGL viewport
         unsafe {
-            glViewport(0, 0, context.width, context.height);
+            glViewport(0, 0, context.display_width, context.display_height);
             glClearColor(0.0, 0.0, 0.0, 1.0);
         }
         
@@ -507,50 +1728,100 @@ impl GraphicsContext {
             context.bloom_enabled = false;
         }
         
+        context.detect_backlight();
+        
         context.initialized = true;
         log::info!("Graphics context initialized successfully: {}x{}", context.width, context.height);
-        log::info!("✓ Display setup complete - output should be visible on screen");
+        log::debug!("✓ Display setup complete - output should be visible on screen");
         log::info!("  Resolution: {}x{}@{}Hz", context.width, context.height, context.mode.vrefresh);
-        log::info!("  CRTC: {}, Connector: {}", context.crtc_id, context.connector_id);
+        log::debug!("  CRTC: {}, Connector: {}", context.crtc_id, context.connector_id);
         
         Ok(context)
     }
+
+    /// Context with every handle unset, before any DRM/EGL setup
+    fn unconfigured(width: i32, height: i32, mode_selection: Option<(usize, usize)>) -> Self {
+        GraphicsContext {
+            drm_fd: -1,
+            gbm_device: ptr::null_mut(),
+            gbm_surface: ptr::null_mut(),
+            egl_display: ptr::null_mut(),
+            egl_context: EGL_NO_CONTEXT,
+            egl_surface: EGL_NO_SURFACE,
+            egl_config: ptr::null_mut(),
+            mode_selection,
+            connector_id: 0,
+            crtc_id: 0,
+            mode: unsafe { std::mem::zeroed() },
+            previous_crtc: None,
+            scanout_buffers: FramebufferSlots::default(),
+            framebuffer_cache: FramebufferCache::default(),
+            flip_pending: Box::new(AtomicBool::new(false)),
+            flip_timeouts: 0,
+            width,
+            height,
+            display_width: width,
+            display_height: height,
+            rotation: Rotation::Deg0,
+            mirror: Mirror::default(),
+            text_renderers: HashMap::new(),
+            brightness: 1.0,
+            backlight: None,
+            rectangle_shader: None,
+            gradient_shader: None,
+            texture_shader: None,
+            textures: HashMap::new(),
+            failed_textures: HashSet::new(),
+            truncated_text_cache: HashMap::new(),
+            clip_stack: Vec::new(),
+            geometry_vbo: None,
+            bloom_quad_vbo: None,
+            bloom_enabled: true,
+            bloom_intensity: 0.5,  // Increased for more visible glow
+            bloom_threshold: 0.3,  // Lowered to catch more bright pixels
+            bloom_framebuffer: None,
+            bloom_texture: None,
+            bloom_blur_targets: Vec::new(),
+            bloom_downsample: 2,
+            bloom_radius: DEFAULT_BLOOM_RADIUS,
+            vignette: VignetteSettings::default(),
+            vignette_shader: None,
+            bloom_bright_shader: None,
+            bloom_blur_shader: None,
+            bloom_shader: None,
+            page_layers: Vec::new(),
+            page_transition_shader: None,
+            page_layer_return_framebuffer: None,
+            initialized: false,
+            display_configured: false,
+            headless: false,
+            vsync: true,
+            clear_color: (0.0, 0.0, 0.0),
+        }
+    }
     
     /// Create a context specifically for dashboard applications (800x480)
-    pub fn new_dashboard(title: &str) -> Result<Self, String> {
+    pub fn new_dashboard(title: &str) -> Result<Self, DashboardError> {
         Self::new(title, 800, 480)
     }
     
     /// Initialize DRM (Direct Rendering Manager)
-    fn init_drm(&mut self) -> Result<(), String> {
+    fn init_drm(&mut self) -> Result<(), DashboardError> {
+        self.drm_fd = open_drm_device()?;
         unsafe {
-            // Try to open the primary DRM device
-            let card_name = CString::new("card0").unwrap();
-            self.drm_fd = drmOpen(card_name.as_ptr(), ptr::null());
-            
-            if self.drm_fd < 0 {
-                // Fallback to vc4 driver for Raspberry Pi
-                let vc4_name = CString::new("vc4").unwrap();
-                self.drm_fd = drmOpen(vc4_name.as_ptr(), ptr::null());
-                
-                if self.drm_fd < 0 {
-                    return Err("Failed to open DRM device. Make sure you have access to /dev/dri/card* devices.".to_string());
-                }
-            }
-            
-            log::info!("DRM device opened successfully (fd: {})", self.drm_fd);
+            log::debug!("DRM device opened successfully (fd: {})", self.drm_fd);
             
             // Get DRM resources to check display configuration
             let resources = drmModeGetResources(self.drm_fd);
             if !resources.is_null() {
                 let res = &*(resources as *const DrmModeRes);
-                log::info!("DRM Resources found:");
-                log::info!("  CRTCs: {}", res.count_crtcs);
-                log::info!("  Connectors: {}", res.count_connectors);
-                log::info!("  Encoders: {}", res.count_encoders);
+                log::debug!("DRM Resources found:");
+                log::debug!("  CRTCs: {}", res.count_crtcs);
+                log::debug!("  Connectors: {}", res.count_connectors);
+                log::debug!("  Encoders: {}", res.count_encoders);
                 
                 if res.count_connectors > 0 {
-                    log::info!("  Display appears to be available");
+                    log::debug!("  Display appears to be available");
                 } else {
                     log::warn!("  Warning: No display connectors found");
                 }
@@ -565,20 +1836,31 @@ impl GraphicsContext {
     }
     
     /// Find and configure display mode
-    fn setup_display(&mut self) -> Result<(), String> {
+    fn setup_display(&mut self) -> Result<(), DashboardError> {
         unsafe {
             let resources = drmModeGetResources(self.drm_fd);
             if resources.is_null() {
-                return Err("Failed to get DRM resources".to_string());
+                return Err(DashboardError::Drm("Failed to get DRM resources".to_string()));
             }
             
             let res = &*(resources as *const DrmModeRes);
-            log::info!("Setting up display mode...");
-            log::info!("Available CRTCs: {}, Connectors: {}", res.count_crtcs, res.count_connectors);
+            log::debug!("Setting up display mode...");
+            log::debug!("Available CRTCs: {}, Connectors: {}", res.count_crtcs, res.count_connectors);
+            
+            if let Some((connector_index, _)) = self.mode_selection {
+                if connector_index >= res.count_connectors as usize {
+                    drmModeFreeResources(resources);
+                    return Err(DashboardError::Drm(format!("Connector index {} out of range ({} connectors)",
+                                       connector_index, res.count_connectors)));
+                }
+            }
             
             // Find a connected display
             let mut found_display = false;
             for i in 0..res.count_connectors {
+                if self.mode_selection.is_some_and(|(connector_index, _)| connector_index != i as usize) {
+                    continue;
+                }
                 let connector_id = *res.connectors.offset(i as isize);
                 let connector = drmModeGetConnector(self.drm_fd, connector_id);
                 
@@ -586,10 +1868,18 @@ impl GraphicsContext {
                     let conn = &*(connector as *const DrmModeConnector);
                     
                     if conn.connection == DRM_MODE_CONNECTED && conn.count_modes > 0 {
-                        log::info!("Found connected display on connector {}", connector_id);
+                        log::debug!("Found connected display on connector {}", connector_id);
                         
-                        // Use the first mode (usually the preferred mode)
-                        let mode = &*conn.modes;
+                        // Use the requested mode, else the first one (usually the preferred mode)
+                        let mode_index = self.mode_selection.map_or(0, |(_, mode_index)| mode_index);
+                        if mode_index >= conn.count_modes as usize {
+                            let count_modes = conn.count_modes;
+                            drmModeFreeConnector(connector);
+                            drmModeFreeResources(resources);
+                            return Err(DashboardError::Drm(format!("Mode index {} out of range ({} modes on connector {})",
+                                               mode_index, count_modes, connector_id)));
+                        }
+                        let mode = &*conn.modes.add(mode_index);
                         self.mode = *mode;
                         self.connector_id = connector_id;
                         
@@ -609,15 +1899,21 @@ impl GraphicsContext {
                         }
                         
                         // Save current CRTC configuration for restoration
-                        self.previous_crtc = drmModeGetCrtc(self.drm_fd, self.crtc_id);
+                        let crtc = drmModeGetCrtc(self.drm_fd, self.crtc_id);
+                        if !crtc.is_null() {
+                            self.previous_crtc = Some(SavedCrtc::from_crtc(&*(crtc as *const DrmModeCrtc)));
+                            drmModeFreeCrtc(crtc);
+                        }
                         
                         log::info!("Display mode: {}x{}@{}Hz", 
                                 mode.hdisplay, mode.vdisplay, mode.vrefresh);
-                        log::info!("Using CRTC: {}, Connector: {}", self.crtc_id, self.connector_id);
+                        log::debug!("Using CRTC: {}, Connector: {}", self.crtc_id, self.connector_id);
                         
                         // Update dimensions to match display mode
-                        self.width = mode.hdisplay as i32;
-                        self.height = mode.vdisplay as i32;
+                        self.display_width = mode.hdisplay as i32;
+                        self.display_height = mode.vdisplay as i32;
+                        self.width = self.display_width;
+                        self.height = self.display_height;
                         
                         found_display = true;
                         drmModeFreeConnector(connector);
@@ -631,7 +1927,10 @@ impl GraphicsContext {
             drmModeFreeResources(resources);
             
             if !found_display {
-                return Err("No connected display found".to_string());
+                if let Some((connector_index, _)) = self.mode_selection {
+                    return Err(DashboardError::Drm(format!("No connected display on connector index {}", connector_index)));
+                }
+                return Err(DashboardError::Drm("No connected display found".to_string()));
             }
         }
         
@@ -639,43 +1938,47 @@ impl GraphicsContext {
     }
     
     /// Initialize GBM (Generic Buffer Management)
-    fn init_gbm(&mut self) -> Result<(), String> {
+    fn init_gbm(&mut self) -> Result<(), DashboardError> {
         unsafe {
             // Create GBM device
             self.gbm_device = gbm_create_device(self.drm_fd);
             if self.gbm_device.is_null() {
-                return Err("Failed to create GBM device".to_string());
+                return Err(DashboardError::Gbm("Failed to create GBM device".to_string()));
             }
             
             // Create GBM surface
             self.gbm_surface = gbm_surface_create(
                 self.gbm_device,
-                self.width as u32,
-                self.height as u32,
+                self.display_width as u32,
+                self.display_height as u32,
                 GBM_FORMAT_XRGB8888,
                 GBM_BO_USE_SCANOUT | GBM_BO_USE_RENDERING,
             );
             
             if self.gbm_surface.is_null() {
-                return Err("Failed to create GBM surface".to_string());
+                return Err(DashboardError::Gbm("Failed to create GBM surface".to_string()));
             }
             
-            log::info!("GBM device and surface created successfully");
+            log::debug!("GBM device and surface created successfully");
         }
         
         Ok(())
     }
     
     /// Initialize EGL (Embedded-System Graphics Library)
-    fn init_egl(&mut self) -> Result<(), String> {
+    fn init_egl(&mut self) -> Result<(), DashboardError> {
         unsafe {
             // Try to get platform display first (preferred method)
-            self.egl_display = eglGetPlatformDisplay(EGL_PLATFORM_GBM_MESA, self.gbm_device, ptr::null());
+            self.egl_display = if self.headless {
+                eglGetPlatformDisplay(EGL_PLATFORM_SURFACELESS_MESA, EGL_DEFAULT_DISPLAY, ptr::null())
+            } else {
+                eglGetPlatformDisplay(EGL_PLATFORM_GBM_MESA, self.gbm_device, ptr::null())
+            };
             if self.egl_display.is_null() {
                 // Fallback to traditional method
                 self.egl_display = eglGetDisplay(self.gbm_device);
                 if self.egl_display.is_null() {
-                    return Err("Failed to get EGL display".to_string());
+                    return Err(DashboardError::Egl("Failed to get EGL display".to_string()));
                 }
             }
             
@@ -683,14 +1986,14 @@ impl GraphicsContext {
             let mut major = 0;
             let mut minor = 0;
             if eglInitialize(self.egl_display, &mut major, &mut minor) == EGL_FALSE {
-                return Err(format!("Failed to initialize EGL: error {}", eglGetError()));
+                return Err(DashboardError::Egl(format!("Failed to initialize EGL: error {}", eglGetError())));
             }
             
-            log::info!("EGL initialized: version {}.{}", major, minor);
+            log::debug!("EGL initialized: version {}.{}", major, minor);
             
             // Choose EGL configuration
             let config_attribs = [
-                EGL_SURFACE_TYPE, EGL_WINDOW_BIT,
+                EGL_SURFACE_TYPE, if self.headless { EGL_PBUFFER_BIT } else { EGL_WINDOW_BIT },
                 EGL_RENDERABLE_TYPE, EGL_OPENGL_ES2_BIT,
                 EGL_RED_SIZE, 8,
                 EGL_GREEN_SIZE, 8,
@@ -710,7 +2013,7 @@ impl GraphicsContext {
                 1,
                 &mut num_configs,
             ) == EGL_FALSE || num_configs == 0 {
-                return Err("Failed to choose EGL config".to_string());
+                return Err(DashboardError::Egl("Failed to choose EGL config".to_string()));
             }
             
             self.egl_config = config;
@@ -729,19 +2032,28 @@ impl GraphicsContext {
             );
             
             if self.egl_context == EGL_NO_CONTEXT {
-                return Err("Failed to create EGL context".to_string());
+                return Err(DashboardError::Egl("Failed to create EGL context".to_string()));
             }
             
             // Create EGL surface
-            self.egl_surface = eglCreateWindowSurface(
-                self.egl_display,
-                self.egl_config,
-                self.gbm_surface,
-                ptr::null(),
-            );
+            self.egl_surface = if self.headless {
+                let pbuffer_attribs = [
+                    EGL_WIDTH, self.display_width,
+                    EGL_HEIGHT, self.display_height,
+                    EGL_NONE,
+                ];
+                eglCreatePbufferSurface(self.egl_display, self.egl_config, pbuffer_attribs.as_ptr())
+            } else {
+                eglCreateWindowSurface(
+                    self.egl_display,
+                    self.egl_config,
+                    self.gbm_surface,
+                    ptr::null(),
+                )
+            };
             
             if self.egl_surface == EGL_NO_SURFACE {
-                return Err("Failed to create EGL surface".to_string());
+                return Err(DashboardError::Egl("Failed to create EGL surface".to_string()));
             }
             
             // Make context current
@@ -751,54 +2063,36 @@ impl GraphicsContext {
                 self.egl_surface,
                 self.egl_context,
             ) == EGL_FALSE {
-                return Err("Failed to make EGL context current".to_string());
+                return Err(DashboardError::Egl("Failed to make EGL context current".to_string()));
             }
             
-            // Enable vsync to prevent tearing
-            eglSwapInterval(self.egl_display, 1);
+            // Vsync on by default to prevent tearing
+            eglSwapInterval(self.egl_display, self.vsync as EGLint);
             
-            log::info!("EGL context created and made current");
+            log::debug!("EGL context created and made current");
         }
         
         Ok(())
     }
     
     /// Configure the display to show our framebuffer
-    fn configure_display(&mut self) -> Result<(), String> {
+    fn configure_display(&mut self) -> Result<(), DashboardError> {
         unsafe {
-            log::info!("Configuring display output...");
+            log::debug!("Configuring display output...");
             
             // Get the initial front buffer to set up the display
             let bo = gbm_surface_lock_front_buffer(self.gbm_surface);
             if bo.is_null() {
-                return Err("Failed to lock front buffer for display setup".to_string());
-            }
-            
-            // Get buffer properties
-            let handle = gbm_bo_get_handle(bo).u32;
-            let stride = gbm_bo_get_stride(bo);
-            log::info!("Buffer handle: {}, stride: {}", handle, stride);
-            
-            // Create DRM framebuffer
-            let mut fb_id = 0;
-            let result = drmModeAddFB(
-                self.drm_fd,
-                self.width as u32,
-                self.height as u32,
-                24, // depth
-                32, // bpp
-                stride,
-                handle,
-                &mut fb_id,
-            );
-            
-            if result != 0 {
-                gbm_surface_release_buffer(self.gbm_surface, bo);
-                return Err(format!("Failed to create framebuffer: error {}", result));
+                return Err(DashboardError::Gbm("Failed to lock front buffer for display setup".to_string()));
             }
             
-            log::info!("Created framebuffer: {}", fb_id);
-            self.current_fb = fb_id;
+            let fb_id = match self.framebuffer_for_bo(bo) {
+                Ok(fb_id) => fb_id,
+                Err(result) => {
+                    gbm_surface_release_buffer(self.gbm_surface, bo);
+                    return Err(DashboardError::Drm(format!("Failed to create framebuffer: error {}", result)));
+                }
+            };
             
             // Set the CRTC to display our framebuffer
             let mut connector_id = self.connector_id;
@@ -814,23 +2108,34 @@ impl GraphicsContext {
                 &mut mode,
             );
             
+            let buffer = ScanoutBuffer { fb: fb_id, bo };
             if result != 0 {
-                drmModeRmFB(self.drm_fd, fb_id);
-                gbm_surface_release_buffer(self.gbm_surface, bo);
-                return Err(format!("Failed to set CRTC: error {}", result));
+                let released = self.scanout_buffers.discard(buffer);
+                self.release_scanout_buffers(&released);
+                return Err(DashboardError::Drm(format!("Failed to set CRTC: error {}", result)));
             }
             
+            // The buffer stays locked while on screen; it goes back to GBM once replaced
+            let released = self.scanout_buffers.displayed(buffer);
+            self.release_scanout_buffers(&released);
             log::info!("✓ Display CRTC configured - framebuffer {} is now showing", fb_id);
-            
-            // Release the buffer back to GBM
-            gbm_surface_release_buffer(self.gbm_surface, bo);
         }
         
         Ok(())
     }
     
-    /// Swap the front and back buffers and update display
+    /// Swap the front and back buffers and update display.
+    ///
+    /// Frame loop contract: render, then call this once per frame. It queues the frame for
+    /// the next vblank and returns without waiting for it; the following call first waits
+    /// (up to FLIP_WAIT_TIMEOUT) for that flip to complete before queuing its own. A loop
+    /// that renders faster than the refresh rate is therefore paced to it, with one frame
+    /// in flight. A frame is dropped, not shown late, if the previous flip times out.
     pub fn swap_buffers(&mut self) {
+        if self.headless {
+            // Nothing to present; the pbuffer keeps the frame for save_framebuffer
+            return;
+        }
         unsafe {
             if self.initialized {
                 // Swap the EGL buffers first to render content
@@ -868,27 +2173,28 @@ impl GraphicsContext {
             // If a page flip is already queued, wait for its vblank event before proceeding.
             // Without this, drmModePageFlip returns -EBUSY and we fall back to drmModeSetCrtc,
             // which causes erratic frame timing (60 → 30 FPS cliff effect).
-            if self.flip_pending {
-                let mut evctx = DrmEventContext {
-                    version: DRM_EVENT_CONTEXT_VERSION,
-                    vblank_handler: None,
-                    page_flip_handler: Some(page_flip_handler_stub),
-                };
-                // Use select() to wait up to 50ms for the event (one full vblank at 60Hz = 16.7ms)
-                let mut fds: libc::fd_set = std::mem::zeroed();
-                libc::FD_SET(self.drm_fd, &mut fds);
-                let mut timeout = libc::timeval { tv_sec: 0, tv_usec: 50_000 };
-                let ready = libc::select(
-                    self.drm_fd + 1,
-                    &mut fds,
-                    ptr::null_mut(),
-                    ptr::null_mut(),
-                    &mut timeout,
-                );
-                if ready > 0 {
-                    drmHandleEvent(self.drm_fd, &mut evctx);
+            let mut force_modeset = false;
+            if self.wait_for_flip(FLIP_WAIT_TIMEOUT) {
+                self.flip_timeouts = 0;
+            } else {
+                self.flip_timeouts += 1;
+                if self.flip_timeouts < MAX_FLIP_TIMEOUTS {
+                    // Flip still outstanding: the previous buffer may still be scanned out, so
+                    // nothing can be released yet. Drop this frame rather than queue another.
+                    log::warn!("Timed out waiting for page flip, dropping frame");
+                    let bo = gbm_surface_lock_front_buffer(self.gbm_surface);
+                    if !bo.is_null() {
+                        gbm_surface_release_buffer(self.gbm_surface, bo);
+                    }
+                    return;
                 }
-                self.flip_pending = false;
+                // The event is lost (e.g. the CRTC was reset under us) and would freeze the
+                // display for good. Stop waiting on it and show this frame with a modeset,
+                // which takes every older buffer off screen.
+                log::warn!("No page flip completion after {} waits, falling back to modeset", self.flip_timeouts);
+                self.flip_pending.store(false, Ordering::Release);
+                self.flip_timeouts = 0;
+                force_modeset = true;
             }
 
             // Get the current front buffer from GBM
@@ -897,75 +2203,158 @@ impl GraphicsContext {
                 return; // Skip this frame if buffer isn't ready
             }
             
-            // Get buffer properties
-            let handle = gbm_bo_get_handle(bo).u32;
-            let stride = gbm_bo_get_stride(bo);
+            // Reuse this buffer's framebuffer, creating it the first time the buffer comes round
+            let new_fb_id = match self.framebuffer_for_bo(bo) {
+                Ok(fb_id) => fb_id,
+                Err(_) => {
+                    gbm_surface_release_buffer(self.gbm_surface, bo);
+                    return;
+                }
+            };
+            let buffer = ScanoutBuffer { fb: new_fb_id, bo };
             
-            // Create a new framebuffer for this buffer
-            let mut new_fb_id = 0;
-            let result = drmModeAddFB(
-                self.drm_fd,
-                self.width as u32,
-                self.height as u32,
-                24, // depth
-                32, // bpp
-                stride,
-                handle,
-                &mut new_fb_id,
-            );
+            // Queue a vsync-aligned page flip, or an immediate one with vsync off
+            let mut flip_result = -1;
+            if !force_modeset {
+                self.flip_pending.store(true, Ordering::Release);
+                let user_data = &*self.flip_pending as *const AtomicBool as *mut c_void;
+                if !self.vsync {
+                    flip_result = drmModePageFlip(self.drm_fd, self.crtc_id, new_fb_id,
+                                                  DRM_MODE_PAGE_FLIP_EVENT | DRM_MODE_PAGE_FLIP_ASYNC, user_data);
+                }
+                if flip_result != 0 {
+                    flip_result = drmModePageFlip(self.drm_fd, self.crtc_id, new_fb_id, DRM_MODE_PAGE_FLIP_EVENT, user_data);
+                }
+            }
             
-            if result == 0 {
-                // Queue a vsync-aligned page flip
-                let flip_result = drmModePageFlip(
+            if flip_result == 0 {
+                // Flip queued — rotate buffers; the one on screen is released once it completes
+                let released = self.scanout_buffers.flip_queued(buffer);
+                self.release_scanout_buffers(&released);
+            } else {
+                self.flip_pending.store(false, Ordering::Release);
+                
+                // Page flip failed — fallback to immediate modeset
+                log::debug!("Page flip failed ({}), falling back to modeset", flip_result);
+                let mut connector_id = self.connector_id;
+                let mut mode = self.mode;
+                let crtc_result = drmModeSetCrtc(
                     self.drm_fd,
                     self.crtc_id,
                     new_fb_id,
-                    DRM_MODE_PAGE_FLIP_EVENT,
-                    ptr::null_mut(),
+                    0, // x
+                    0, // y
+                    &mut connector_id,
+                    1, // connector count
+                    &mut mode,
                 );
                 
-                if flip_result == 0 {
-                    // Flip queued — mark pending and rotate framebuffers
-                    self.flip_pending = true;
-                    if self.previous_fb != 0 {
-                        drmModeRmFB(self.drm_fd, self.previous_fb);
-                    }
-                    self.previous_fb = self.current_fb;
-                    self.current_fb = new_fb_id;
+                let released = if crtc_result == 0 {
+                    self.scanout_buffers.displayed(buffer)
                 } else {
-                    // Page flip failed — fallback to immediate modeset
-                    let mut connector_id = self.connector_id;
-                    let mut mode = self.mode;
-                    let crtc_result = drmModeSetCrtc(
-                        self.drm_fd,
-                        self.crtc_id,
-                        new_fb_id,
-                        0, // x
-                        0, // y
-                        &mut connector_id,
-                        1, // connector count
-                        &mut mode,
-                    );
-                    
-                    if crtc_result == 0 {
-                        if self.current_fb != 0 {
-                            drmModeRmFB(self.drm_fd, self.current_fb);
-                        }
-                        self.current_fb = new_fb_id;
-                    } else {
-                        drmModeRmFB(self.drm_fd, new_fb_id);
-                    }
+                    self.scanout_buffers.discard(buffer)
+                };
+                self.release_scanout_buffers(&released);
+            }
+        }
+    }
+    
+    /// Waits for the queued page flip, if any, to complete. Returns false on timeout.
+    fn wait_for_flip(&mut self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut evctx = DrmEventContext {
+            version: DRM_EVENT_CONTEXT_VERSION,
+            vblank_handler: None,
+            page_flip_handler: Some(page_flip_handler),
+        };
+        
+        while self.flip_pending.load(Ordering::Acquire) {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return false;
+            }
+            unsafe {
+                let mut fds: libc::fd_set = std::mem::zeroed();
+                libc::FD_SET(self.drm_fd, &mut fds);
+                let mut select_timeout = libc::timeval {
+                    tv_sec: 0,
+                    tv_usec: remaining.as_micros() as libc::suseconds_t,
+                };
+                let ready = libc::select(
+                    self.drm_fd + 1,
+                    &mut fds,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                    &mut select_timeout,
+                );
+                if ready < 0 && std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted {
+                    continue;
                 }
+                if ready <= 0 {
+                    return false;
+                }
+                // Dispatches to page_flip_handler, which clears flip_pending
+                drmHandleEvent(self.drm_fd, &mut evctx);
             }
-            
-            // Release the buffer back to GBM
-            gbm_surface_release_buffer(self.gbm_surface, bo);
         }
+        
+        let released = self.scanout_buffers.flip_completed();
+        unsafe { self.release_scanout_buffers(&released) };
+        true
+    }
+    
+    /// Hands buffer objects back to GBM. Their framebuffers stay cached for the next time
+    /// GBM hands out the same buffer.
+    unsafe fn release_scanout_buffers(&self, buffers: &[ScanoutBuffer]) {
+        for buffer in buffers {
+            gbm_surface_release_buffer(self.gbm_surface, buffer.bo);
+        }
+    }
+    
+    /// DRM framebuffer for a GBM buffer object, created on first use. Returns the
+    /// drmModeAddFB error code on failure.
+    unsafe fn framebuffer_for_bo(&mut self, bo: *mut c_void) -> Result<u32, c_int> {
+        let handle = gbm_bo_get_handle(bo).u32;
+        let stride = gbm_bo_get_stride(bo);
+        let (drm_fd, width, height) = (self.drm_fd, self.display_width as u32, self.display_height as u32);
+        self.framebuffer_cache.get_or_create(handle, || {
+            let mut fb_id = 0;
+            let result = drmModeAddFB(
+                drm_fd,
+                width,
+                height,
+                24, // depth
+                32, // bpp
+                stride,
+                handle,
+                &mut fb_id,
+            );
+            if result != 0 {
+                return Err(result);
+            }
+            log::debug!("Created framebuffer {} for buffer handle {} (stride {})", fb_id, handle, stride);
+            Ok(fb_id)
+        })
+    }
+    
+    /// Removes every cached framebuffer. Only for teardown — one of them is on screen.
+    unsafe fn clear_framebuffer_cache(&mut self) {
+        for fb in self.framebuffer_cache.clear() {
+            drmModeRmFB(self.drm_fd, fb);
+        }
+    }
+    
+    /// Framebuffer create/destroy counters. `live` is bounded by the number of buffers
+    /// GBM cycles through (2-4) and should stop changing after the first few frames.
+    pub fn framebuffer_stats(&self) -> FramebufferStats {
+        self.framebuffer_cache.stats()
     }
     
-    /// Clear the screen with black color
+    /// Clear the screen with the clear color (set_clear_color)
     pub fn clear(&self) {
+        let (r, g, b) = self.clear_color;
         unsafe {
+            glClearColor(r, g, b, 1.0);
             glClear(GL_COLOR_BUFFER_BIT);
         }
     }
@@ -977,10 +2366,11 @@ impl GraphicsContext {
         Vec::new()
     }
     
-    /// Check if a quit event was received
+    /// Check if a quit event or SIGTERM/SIGINT was received
     pub fn should_quit(&self) -> bool {
         let events = self.poll_events();
-        events.iter().any(|event| matches!(event.event_type, InputEventType::Quit))
+        crate::util::shutdown::shutdown_requested()
+            || events.iter().any(|event| matches!(event.event_type, InputEventType::Quit))
     }
 
     /// Get OpenGL function pointer (needed for gl::load_with)
@@ -997,57 +2387,35 @@ impl GraphicsContext {
     }
     
     /// Save the current framebuffer to an image file (for testing)
-    pub fn save_framebuffer(&self, filename: &str) -> Result<(), String> {
+    pub fn save_framebuffer(&self, filename: &str, format: FramebufferFormat, keep_alpha: bool) -> Result<(), DashboardError> {
+        let pixels = self.read_pixels();
+        save_rgba_pixels(filename, &pixels, self.display_width as u32, self.display_height as u32, format, keep_alpha)?;
+        log::info!("Framebuffer saved to: {}", filename);
+        Ok(())
+    }
+    
+    /// RGBA contents of the current framebuffer at display size, bottom row first
+    pub fn read_pixels(&self) -> Vec<u8> {
+        let mut pixels = vec![0u8; (self.display_width * self.display_height * 4) as usize];
         unsafe {
-            let mut pixels = vec![0u8; (self.width * self.height * 4) as usize];
             gl::ReadPixels(
                 0, 0, 
-                self.width, self.height,
+                self.display_width, self.display_height,
                 gl::RGBA, gl::UNSIGNED_BYTE,
                 pixels.as_mut_ptr() as *mut std::ffi::c_void
             );
-            
-            // Flip image vertically (OpenGL has origin at bottom-left)
-            let mut flipped_pixels = vec![0u8; pixels.len()];
-            let row_size = (self.width * 4) as usize;
-            for y in 0..self.height as usize {
-                let src_row = &pixels[y * row_size..(y + 1) * row_size];
-                let dst_y = (self.height as usize - 1 - y) * row_size;
-                flipped_pixels[dst_y..dst_y + row_size].copy_from_slice(src_row);
-            }
-            
-            // Convert RGBA to RGB (remove alpha channel)
-            let mut rgb_pixels = Vec::with_capacity((self.width * self.height * 3) as usize);
-            for chunk in flipped_pixels.chunks(4) {
-                rgb_pixels.push(chunk[0]); // R
-                rgb_pixels.push(chunk[1]); // G
-                rgb_pixels.push(chunk[2]); // B
-            }
-            
-            match image::save_buffer(
-                filename,
-                &rgb_pixels,
-                self.width as u32,
-                self.height as u32,
-                image::ColorType::Rgb8,
-            ) {
-                Ok(()) => {
-                    log::info!("Framebuffer saved to: {}", filename);
-                    Ok(())
-                }
-                Err(e) => Err(format!("Failed to save framebuffer: {}", e)),
-            }
         }
+        pixels
     }
-    
+
     /// Hide the mouse cursor for dashboard applications
-    pub fn hide_cursor(&self) -> Result<(), String> {
+    pub fn hide_cursor(&self) -> Result<(), DashboardError> {
         use std::fs::File;
         use std::io::Write;
         
         // Method 1: Hide cursor via console escape sequence
         print!("\x1b[?25l"); // ANSI escape sequence to hide cursor
-        std::io::stdout().flush().map_err(|e| format!("Failed to flush stdout: {}", e))?;
+        std::io::stdout().flush().map_err(|e| DashboardError::io("Failed to flush stdout", e))?;
         
         // Method 2: Try to hide cursor via /dev/tty
         if let Ok(mut tty) = File::options().write(true).open("/dev/tty") {
@@ -1064,13 +2432,13 @@ impl GraphicsContext {
     }
     
     /// Show the mouse cursor (restore visibility)
-    pub fn show_cursor(&self) -> Result<(), String> {
+    pub fn show_cursor(&self) -> Result<(), DashboardError> {
         use std::fs::File;
         use std::io::Write;
         
         // Method 1: Show cursor via console escape sequence
         print!("\x1b[?25h"); // ANSI escape sequence to show cursor
-        std::io::stdout().flush().map_err(|e| format!("Failed to flush stdout: {}", e))?;
+        std::io::stdout().flush().map_err(|e| DashboardError::io("Failed to flush stdout", e))?;
         
         // Method 2: Try to show cursor via /dev/tty
         if let Ok(mut tty) = File::options().write(true).open("/dev/tty") {
@@ -1092,12 +2460,104 @@ impl GraphicsContext {
 
     /// Clamp brightness value to valid range (10% to 100%)
     fn clamp_brightness(&mut self, brightness: f32) -> f32 {
-        brightness.clamp(0.1, 1.0)
+        brightness.clamp(MIN_BRIGHTNESS, MAX_BRIGHTNESS)
+    }
+
+    /// Rotate all rendering to match how the panel is mounted. Updates the logical
+    /// width/height, so set it before anything lays itself out from them.
+    pub fn set_rotation(&mut self, rotation: Rotation) {
+        self.rotation = rotation;
+        if rotation.is_transposed() {
+            self.width = self.display_height;
+            self.height = self.display_width;
+        } else {
+            self.width = self.display_width;
+            self.height = self.display_height;
+        }
     }
 
-    /// Set display brightness (0.0 to 1.0)
+    pub fn get_rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// Mirror all rendering, text included, on top of the rotation
+    pub fn set_mirror(&mut self, mirror: Mirror) {
+        self.mirror = mirror;
+    }
+
+    pub fn get_mirror(&self) -> Mirror {
+        self.mirror
+    }
+
+    /// Turn vsync on (the default) or off, e.g. to measure uncapped throughput.
+    /// On the KMS path the swap interval has no effect; what counts is the page flip,
+    /// which is queued as an async flip while vsync is off (may tear). Drivers that
+    /// can't flip asynchronously fall back to vsynced flips.
+    pub fn set_vsync(&mut self, enabled: bool) -> Result<(), DashboardError> {
+        if self.initialized && unsafe { eglSwapInterval(self.egl_display, enabled as EGLint) } == EGL_FALSE {
+            return Err(DashboardError::Egl(format!("Failed to set swap interval: error 0x{:X}",
+                                                   unsafe { eglGetError() })));
+        }
+        self.vsync = enabled;
+        log::debug!("Vsync {}", if enabled { "on" } else { "off" });
+        Ok(())
+    }
+
+    pub fn is_vsync_enabled(&self) -> bool {
+        self.vsync
+    }
+
+    /// Set display brightness (0.0 to 1.0), through the panel backlight when there is one
     pub fn set_brightness(&mut self, brightness: f32) {
-        self.brightness = self.clamp_brightness(brightness);
+        self.set_hardware_brightness(brightness);
+    }
+
+    /// Drive the sysfs backlight to `level` (clamped like the software brightness).
+    /// Returns false when there is no usable backlight and software scaling was used instead.
+    pub fn set_hardware_brightness(&mut self, level: f32) -> bool {
+        self.brightness = self.clamp_brightness(level);
+        let Some(backlight) = &self.backlight else {
+            return false;
+        };
+        match backlight.set_level(self.brightness) {
+            Ok(()) => true,
+            Err(e) => {
+                // Typically a permissions problem; stop retrying every step and dim in software
+                log::warn!("Backlight control failed, falling back to software brightness: {}", e);
+                self.backlight = None;
+                false
+            }
+        }
+    }
+
+    /// Look for a sysfs backlight and, if found, let it carry the brightness level from now on
+    pub fn detect_backlight(&mut self) {
+        self.backlight = Backlight::detect();
+        match &self.backlight {
+            Some(backlight) => {
+                log::info!("Using backlight {} (max_brightness {})",
+                           backlight.device_dir().display(), backlight.max_brightness());
+                match backlight.level() {
+                    Ok(level) => self.brightness = self.clamp_brightness(level),
+                    Err(e) => log::warn!("Could not read backlight level: {}", e),
+                }
+            }
+            None => log::info!("No backlight device found, using software brightness"),
+        }
+    }
+
+    /// Stop driving the panel backlight: leave it at full and dim in software from now on
+    pub fn release_backlight(&mut self) {
+        if let Some(backlight) = self.backlight.take() {
+            if let Err(e) = backlight.set_level(MAX_BRIGHTNESS) {
+                log::warn!("Could not restore backlight level: {}", e);
+            }
+            log::info!("Backlight released, using software brightness");
+        }
+    }
+
+    pub fn has_hardware_backlight(&self) -> bool {
+        self.backlight.is_some()
     }
 
     /// Get current brightness level
@@ -1107,20 +2567,26 @@ impl GraphicsContext {
 
     /// Increase brightness by a step
     pub fn increase_brightness(&mut self, step: f32) {
-        self.brightness = self.clamp_brightness(self.brightness + step);
+        self.set_hardware_brightness(self.brightness + step);
     }
 
     /// Decrease brightness by a step
     pub fn decrease_brightness(&mut self, step: f32) {
-        self.brightness = self.clamp_brightness(self.brightness - step);
+        self.set_hardware_brightness(self.brightness - step);
     }
 
-    /// Clear the screen with black
+    /// Clear the screen with the clear color (set_clear_color)
     pub fn clear_screen(&mut self) {
-        unsafe {
-            gl::ClearColor(0.0, 0.0, 0.0, 1.0);
-            gl::Clear(gl::COLOR_BUFFER_BIT);
-        }
+        self.clear();
+    }
+
+    /// Background color for clear()/clear_screen(), black by default
+    pub fn set_clear_color(&mut self, r: f32, g: f32, b: f32) {
+        self.clear_color = (r, g, b);
+    }
+
+    pub fn get_clear_color(&self) -> (f32, f32, f32) {
+        self.clear_color
     }
 
     // =============================================================================
@@ -1134,7 +2600,8 @@ impl GraphicsContext {
     /// * `y` - Y coordinate of top-left corner  
     /// * `width` - Rectangle width
     /// * `height` - Rectangle height
-    /// * `color` - RGB color as (r, g, b) tuple, values 0.0-1.0
+    /// * `color` - RGBA color as (r, g, b, a) tuple, values 0.0-1.0; alpha blends over what is
+    ///   already drawn (see `opaque` for plain RGB colors)
     /// * `filled` - If true, fills the rectangle; if false, draws outline only
     /// * `thickness` - Line thickness for outline (ignored if filled=true)
     /// * `corner_radius` - Corner radius for rounded rectangles (0.0 for sharp corners)
@@ -1144,12 +2611,13 @@ impl GraphicsContext {
         y: f32, 
         width: f32, 
         height: f32, 
-        color: (f32, f32, f32),
+        color: (f32, f32, f32, f32),
         filled: bool,
         thickness: f32,
         corner_radius: f32,
-    ) -> Result<(), String> {
-        let color = self.apply_brightness(color);
+    ) -> Result<(), DashboardError> {
+        let (r, g, b) = self.apply_brightness((color.0, color.1, color.2));
+        let color = (r, g, b, color.3.clamp(0.0, 1.0));
         unsafe {
             if corner_radius > 0.0 {
                 // Render rounded rectangle
@@ -1176,21 +2644,8 @@ impl GraphicsContext {
         y: f32, 
         width: f32, 
         height: f32, 
-        color: (f32, f32, f32)
-    ) -> Result<(), String> {
-        // Create simple rectangle shader program if needed
-        let shader_program = self.get_or_create_rectangle_shader()?;
-        gl::UseProgram(shader_program);
-        
-        // Set up projection matrix for 2D rendering
-        let projection_matrix = self.create_2d_projection_matrix();
-        let projection_uniform = gl::GetUniformLocation(shader_program, b"projection\0".as_ptr());
-        gl::UniformMatrix4fv(projection_uniform, 1, gl::FALSE, projection_matrix.as_ptr());
-        
-        // Set color uniform
-        let color_uniform = gl::GetUniformLocation(shader_program, b"color\0".as_ptr());
-        gl::Uniform3f(color_uniform, color.0, color.1, color.2);
-        
+        color: (f32, f32, f32, f32)
+    ) -> Result<(), DashboardError> {
         // Define rectangle vertices (2 triangles)
         let vertices: [f32; 12] = [
             x,         y,          // Top-left
@@ -1201,28 +2656,7 @@ impl GraphicsContext {
             x + width, y + height, // Bottom-right
             x,         y + height, // Bottom-left
         ];
-        
-        // Bind persistent VBO — no per-call gen/delete
-        let vbo = self.get_or_create_geometry_vbo();
-        gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
-        
-        // Upload vertex data
-        gl::BufferData(
-            gl::ARRAY_BUFFER,
-            (vertices.len() * std::mem::size_of::<f32>()) as isize,
-            vertices.as_ptr() as *const std::ffi::c_void,
-            gl::DYNAMIC_DRAW,
-        );
-        
-        // Set up vertex attributes
-        let position_attr = gl::GetAttribLocation(shader_program, b"position\0".as_ptr()) as u32;
-        gl::VertexAttribPointer(position_attr, 2, gl::FLOAT, gl::FALSE, 0, std::ptr::null());
-        gl::EnableVertexAttribArray(position_attr);
-        
-        // Render
-        gl::DrawArrays(gl::TRIANGLES, 0, 6);
-        
-        Ok(())
+        self.render_solid_vertices(&vertices, gl::TRIANGLES, color)
     }
     
     /// Render rectangle outline with specified thickness
@@ -1232,9 +2666,9 @@ impl GraphicsContext {
         y: f32, 
         width: f32, 
         height: f32, 
-        color: (f32, f32, f32),
+        color: (f32, f32, f32, f32),
         thickness: f32
-    ) -> Result<(), String> {
+    ) -> Result<(), DashboardError> {
         // Draw 4 filled rectangles for the outline
         let half_thickness = thickness / 2.0;
         
@@ -1260,9 +2694,9 @@ impl GraphicsContext {
         y: f32, 
         width: f32, 
         height: f32, 
-        color: (f32, f32, f32),
+        color: (f32, f32, f32, f32),
         corner_radius: f32
-    ) -> Result<(), String> {
+    ) -> Result<(), DashboardError> {
         let radius = corner_radius.min(width / 2.0).min(height / 2.0);
         
         // Draw main rectangle (without corners)
@@ -1286,10 +2720,10 @@ impl GraphicsContext {
         y: f32, 
         width: f32, 
         height: f32, 
-        color: (f32, f32, f32),
+        color: (f32, f32, f32, f32),
         thickness: f32,
         corner_radius: f32
-    ) -> Result<(), String> {
+    ) -> Result<(), DashboardError> {
         let radius = corner_radius.min(width / 2.0).min(height / 2.0);
         let half_thickness = thickness / 2.0;
         
@@ -1304,163 +2738,155 @@ impl GraphicsContext {
         self.render_filled_rectangle(x + width - half_thickness, y + radius, thickness, height - 2.0 * radius, color)?;
         
         // Draw rounded corner outlines using circle arcs
-        self.render_circle_arc_outline(x + radius, y + radius, radius, thickness, color, 180.0_f32.to_radians(), 270.0_f32.to_radians(), 16)?; // Top-left
-        self.render_circle_arc_outline(x + width - radius, y + radius, radius, thickness, color, 270.0_f32.to_radians(), 360.0_f32.to_radians(), 16)?; // Top-right
-        self.render_circle_arc_outline(x + width - radius, y + height - radius, radius, thickness, color, 0.0_f32.to_radians(), 90.0_f32.to_radians(), 16)?; // Bottom-right
-        self.render_circle_arc_outline(x + radius, y + height - radius, radius, thickness, color, 90.0_f32.to_radians(), 180.0_f32.to_radians(), 16)?; // Bottom-left
+        self.render_arc_strip(x + radius, y + radius, radius, thickness, color, 180.0_f32.to_radians(), 270.0_f32.to_radians(), 16)?; // Top-left
+        self.render_arc_strip(x + width - radius, y + radius, radius, thickness, color, 270.0_f32.to_radians(), 360.0_f32.to_radians(), 16)?; // Top-right
+        self.render_arc_strip(x + width - radius, y + height - radius, radius, thickness, color, 0.0_f32.to_radians(), 90.0_f32.to_radians(), 16)?; // Bottom-right
+        self.render_arc_strip(x + radius, y + height - radius, radius, thickness, color, 90.0_f32.to_radians(), 180.0_f32.to_radians(), 16)?; // Bottom-left
 
         Ok(())
     }
     
-    /// Render a filled circle segment (for rounded corners)
-    unsafe fn render_circle_segment(
+    /// Render a filled circle segment (for rounded corners)
+    unsafe fn render_circle_segment(
+        &mut self,
+        center_x: f32, 
+        center_y: f32, 
+        radius: f32, 
+        color: (f32, f32, f32, f32),
+        start_angle: f32, 
+        end_angle: f32
+    ) -> Result<(), DashboardError> {
+        let segments = 16; // Number of triangular segments for smooth curve
+        let vertices = arc_fan_vertices(center_x, center_y, radius, start_angle, end_angle, segments);
+        self.render_solid_vertices(&vertices, gl::TRIANGLE_FAN, color)
+    }
+    
+    /// Render a circle arc outline (for rounded corner borders)
+    pub fn render_circle_arc_outline(
+        &mut self,
+        center_x: f32, 
+        center_y: f32, 
+        radius: f32, 
+        thickness: f32,
+        color: (f32, f32, f32),
+        start_angle: f32, 
+        end_angle: f32,
+        segments: usize,
+    ) -> Result<(), DashboardError> {
+        let (r, g, b) = self.apply_brightness(color);
+        unsafe {
+            self.render_arc_strip(center_x, center_y, radius, thickness, (r, g, b, 1.0), start_angle, end_angle, segments)
+        }
+    }
+    
+    /// Thick arc as a triangle strip between the inner and outer radius. `color` must
+    /// already have brightness applied.
+    unsafe fn render_arc_strip(
         &mut self,
         center_x: f32, 
         center_y: f32, 
         radius: f32, 
-        color: (f32, f32, f32),
+        thickness: f32,
+        color: (f32, f32, f32, f32),
         start_angle: f32, 
-        end_angle: f32
-    ) -> Result<(), String> {
+        end_angle: f32,
+        segments: usize,
+    ) -> Result<(), DashboardError> {
+        let outer_radius = radius + thickness / 2.0;
+        let inner_radius = radius - thickness / 2.0;
+        let vertices = arc_strip_vertices(center_x, center_y, inner_radius, outer_radius, start_angle, end_angle, segments);
+        self.render_solid_vertices(&vertices, gl::TRIANGLE_STRIP, color)
+    }
+    
+    /// Render a filled circle
+    pub fn fill_circle(&mut self, center_x: f32, center_y: f32, radius: f32, color: (f32, f32, f32)) -> Result<(), DashboardError> {
+        let (r, g, b) = self.apply_brightness(color);
+        let vertices = arc_fan_vertices(center_x, center_y, radius, 0.0, 360.0, CIRCLE_SEGMENTS);
+        unsafe { self.render_solid_vertices(&vertices, gl::TRIANGLE_FAN, (r, g, b, 1.0)) }
+    }
+    
+    /// Render a circle outline; `thickness` is centred on `radius`
+    pub fn stroke_ring(&mut self, center_x: f32, center_y: f32, radius: f32, thickness: f32, color: (f32, f32, f32)) -> Result<(), DashboardError> {
+        let (r, g, b) = self.apply_brightness(color);
+        let inner_radius = (radius - thickness / 2.0).max(0.0);
+        let outer_radius = radius + thickness / 2.0;
+        // One extra point so the strip closes back on its start
+        let vertices = arc_strip_vertices(center_x, center_y, inner_radius, outer_radius,
+                                          0.0, std::f32::consts::TAU, CIRCLE_SEGMENTS + 1);
+        unsafe { self.render_solid_vertices(&vertices, gl::TRIANGLE_STRIP, (r, g, b, 1.0)) }
+    }
+    
+    /// Render a straight line of the given thickness between two points, in the same
+    /// screen coordinates as fill_rect. Ends are square and do not extend past the endpoints.
+    pub fn draw_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: (f32, f32, f32)) -> Result<(), DashboardError> {
+        let Some(vertices) = line_quad_vertices(x1, y1, x2, y2, thickness) else {
+            return Ok(()); // Zero length or thickness: nothing to draw
+        };
+        let (r, g, b) = self.apply_brightness(color);
+        unsafe { self.render_solid_vertices(&vertices, gl::TRIANGLES, (r, g, b, 1.0)) }
+    }
+    
+    /// Render a filled polygon from its outline points, in the same screen coordinates as
+    /// fill_rect. Drawn as a triangle fan from the first point, so the polygon must be
+    /// convex (as needle shapes are); concave outlines get filled past their edges.
+    pub fn fill_polygon(&mut self, points: &[(f32, f32)], color: (f32, f32, f32)) -> Result<(), DashboardError> {
+        let Some(vertices) = polygon_fan_vertices(points) else {
+            return Ok(()); // Fewer than three points: nothing to fill
+        };
+        let (r, g, b) = self.apply_brightness(color);
+        unsafe { self.render_solid_vertices(&vertices, gl::TRIANGLE_FAN, (r, g, b, 1.0)) }
+    }
+    
+    /// Draws 2D vertices in screen coordinates with the cached rectangle shader and the
+    /// shared geometry VBO. `color` is RGBA with brightness already applied.
+    unsafe fn render_solid_vertices(&mut self, vertices: &[f32], mode: gl::types::GLenum, color: (f32, f32, f32, f32)) -> Result<(), DashboardError> {
         let shader_program = self.get_or_create_rectangle_shader()?;
         gl::UseProgram(shader_program);
         
-        // Set up projection matrix
         let projection_matrix = self.create_2d_projection_matrix();
-        let projection_uniform = gl::GetUniformLocation(shader_program, b"projection\0".as_ptr());
+        let projection_uniform = gl::GetUniformLocation(shader_program, c"projection".as_ptr().cast());
         gl::UniformMatrix4fv(projection_uniform, 1, gl::FALSE, projection_matrix.as_ptr());
         
-        // Set color uniform
-        let color_uniform = gl::GetUniformLocation(shader_program, b"color\0".as_ptr());
-        gl::Uniform3f(color_uniform, color.0, color.1, color.2);
-        
-        // Generate vertices for circle segment
-        let segments = 16; // Number of triangular segments for smooth curve
-        let mut vertices = Vec::with_capacity((segments + 2) * 2); // Center + arc points
-        
-        // Add center point
-        vertices.push(center_x);
-        vertices.push(center_y);
+        let color_uniform = gl::GetUniformLocation(shader_program, c"color".as_ptr().cast());
+        gl::Uniform4f(color_uniform, color.0, color.1, color.2, color.3);
         
-        // Add arc points
-        let angle_step = (end_angle - start_angle) / segments as f32;
-        for i in 0..=segments {
-            let angle = (start_angle + i as f32 * angle_step).to_radians();
-            vertices.push(center_x + radius * angle.cos());
-            vertices.push(center_y + radius * angle.sin());
+        if color.3 < 1.0 {
+            gl::Enable(gl::BLEND);
+            gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
         }
         
-        // Bind persistent VBO — no per-call gen/delete
         let vbo = self.get_or_create_geometry_vbo();
-        
         gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
-        
-        // Upload vertex data
         gl::BufferData(
             gl::ARRAY_BUFFER,
-            (vertices.len() * std::mem::size_of::<f32>()) as isize,
+            std::mem::size_of_val(vertices) as isize,
             vertices.as_ptr() as *const std::ffi::c_void,
             gl::DYNAMIC_DRAW,
         );
         
-        // Set up vertex attributes
-        let position_attr = gl::GetAttribLocation(shader_program, b"position\0".as_ptr()) as u32;
+        let position_attr = gl::GetAttribLocation(shader_program, c"position".as_ptr().cast()) as u32;
         gl::VertexAttribPointer(position_attr, 2, gl::FLOAT, gl::FALSE, 0, std::ptr::null());
         gl::EnableVertexAttribArray(position_attr);
         
-        // Render as triangle fan
-        gl::DrawArrays(gl::TRIANGLE_FAN, 0, vertices.len() as i32 / 2);
-        
-        Ok(())
-    }
-    
-    /// Render a circle arc outline (for rounded corner borders)
-    pub fn render_circle_arc_outline(
-        &mut self,
-        center_x: f32, 
-        center_y: f32, 
-        radius: f32, 
-        thickness: f32,
-        color: (f32, f32, f32),
-        start_angle: f32, 
-        end_angle: f32,
-        segments: usize,
-    ) -> Result<(), String> {
-        let color = self.apply_brightness(color);
-        unsafe {
-            // For thick arcs, we render the difference between outer and inner arcs
-            let outer_radius = radius + thickness / 2.0;
-            let inner_radius = radius - thickness / 2.0;
-            
-            let shader_program = self.get_or_create_rectangle_shader()?;
-            gl::UseProgram(shader_program);
-            
-            // Set up projection matrix
-            let projection_matrix = self.create_2d_projection_matrix();
-            let projection_uniform = gl::GetUniformLocation(shader_program, b"projection\0".as_ptr());
-            gl::UniformMatrix4fv(projection_uniform, 1, gl::FALSE, projection_matrix.as_ptr());
-            
-            // Set color uniform
-            let color_uniform = gl::GetUniformLocation(shader_program, b"color\0".as_ptr());
-            gl::Uniform3f(color_uniform, color.0, color.1, color.2);
-            
-            // Generate vertices for arc ring (triangle strip)
-            let mut vertices = Vec::with_capacity(segments * 4 * 2); // 2 points per segment * 2 coords
-            
-            let angle_step = (end_angle - start_angle) / (segments - 1) as f32;
-            for i in 0..segments {
-                let angle = start_angle + i as f32 * angle_step; // Angles already in radians
-                let cos_a = angle.cos();
-                let sin_a = angle.sin();
-                
-                // Inner point
-                vertices.push(center_x + inner_radius * cos_a);
-                vertices.push(center_y + inner_radius * sin_a);
-                
-                // Outer point
-                vertices.push(center_x + outer_radius * cos_a);
-                vertices.push(center_y + outer_radius * sin_a);
-            }
-            
-            // Bind persistent VBO — no per-call gen/delete
-            let vbo = self.get_or_create_geometry_vbo();
-            
-            gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
-            
-            // Upload vertex data
-            gl::BufferData(
-                gl::ARRAY_BUFFER,
-                (vertices.len() * std::mem::size_of::<f32>()) as isize,
-                vertices.as_ptr() as *const std::ffi::c_void,
-                gl::DYNAMIC_DRAW,
-            );
-            
-            // Set up vertex attributes
-            let position_attr = gl::GetAttribLocation(shader_program, b"position\0".as_ptr()) as u32;
-            gl::VertexAttribPointer(position_attr, 2, gl::FLOAT, gl::FALSE, 0, std::ptr::null());
-            gl::EnableVertexAttribArray(position_attr);
-            
-            // Render as triangle strip
-            gl::DrawArrays(gl::TRIANGLE_STRIP, 0, vertices.len() as i32 / 2);
-        }
+        gl::DrawArrays(mode, 0, vertices.len() as i32 / 2);
         
         Ok(())
     }
     
     /// Get or create the rectangle shader program (cached)
-    unsafe fn get_or_create_rectangle_shader(&mut self) -> Result<u32, String> {
+    unsafe fn get_or_create_rectangle_shader(&mut self) -> Result<u32, DashboardError> {
         if let Some(shader) = self.rectangle_shader {
             Ok(shader)
         } else {
             let shader = self.create_rectangle_shader_program()?;
             self.rectangle_shader = Some(shader);
-            log::info!("Rectangle shader program cached for reuse");
+            log::debug!("Rectangle shader program cached for reuse");
             Ok(shader)
         }
     }
 
     /// Return the persistent VBO used by all primitive geometry functions, allocating on first call.
-    /// Shared across render_filled_rectangle, render_circle_segment, and render_circle_arc_outline —
+    /// Shared by every primitive drawn through render_solid_vertices —
     /// these render sequentially so a single buffer is sufficient.
     unsafe fn get_or_create_geometry_vbo(&mut self) -> u32 {
         if self.geometry_vbo.is_none() {
@@ -1482,7 +2908,7 @@ impl GraphicsContext {
     }
     
     /// Create shader program for rectangle rendering
-    unsafe fn create_rectangle_shader_program(&self) -> Result<u32, String> {
+    unsafe fn create_rectangle_shader_program(&self) -> Result<u32, DashboardError> {
         let vertex_shader_source = b"
 attribute vec2 position;
 uniform mat4 projection;
@@ -1494,87 +2920,30 @@ void main() {
         
         let fragment_shader_source = b"
 precision mediump float;
-uniform vec3 color;
+uniform vec4 color;
 
 void main() {
-    gl_FragColor = vec4(color, 1.0);
+    gl_FragColor = color;
 }
 \0";
         
-        // Create and compile vertex shader
-        let vertex_shader = gl::CreateShader(gl::VERTEX_SHADER);
-        if vertex_shader == 0 {
-            return Err("Failed to create rectangle vertex shader".to_string());
-        }
-        
-        let vertex_src_ptr = vertex_shader_source.as_ptr();
-        gl::ShaderSource(vertex_shader, 1, &vertex_src_ptr, std::ptr::null());
-        gl::CompileShader(vertex_shader);
-        
-        let mut compile_status = 0i32;
-        gl::GetShaderiv(vertex_shader, gl::COMPILE_STATUS, &mut compile_status);
-        if compile_status == 0 {
-            gl::DeleteShader(vertex_shader);
-            return Err("Rectangle vertex shader compilation failed".to_string());
-        }
-        
-        // Create and compile fragment shader
-        let fragment_shader = gl::CreateShader(gl::FRAGMENT_SHADER);
-        if fragment_shader == 0 {
-            gl::DeleteShader(vertex_shader);
-            return Err("Failed to create rectangle fragment shader".to_string());
-        }
-        
-        let fragment_src_ptr = fragment_shader_source.as_ptr();
-        gl::ShaderSource(fragment_shader, 1, &fragment_src_ptr, std::ptr::null());
-        gl::CompileShader(fragment_shader);
-        
-        let mut compile_status = 0i32;
-        gl::GetShaderiv(fragment_shader, gl::COMPILE_STATUS, &mut compile_status);
-        if compile_status == 0 {
-            gl::DeleteShader(vertex_shader);
-            gl::DeleteShader(fragment_shader);
-            return Err("Rectangle fragment shader compilation failed".to_string());
-        }
-        
-        // Create and link shader program
-        let program = gl::CreateProgram();
-        if program == 0 {
-            gl::DeleteShader(vertex_shader);
-            gl::DeleteShader(fragment_shader);
-            return Err("Failed to create rectangle shader program".to_string());
-        }
-        
-        gl::AttachShader(program, vertex_shader);
-        gl::AttachShader(program, fragment_shader);
-        gl::LinkProgram(program);
-        
-        let mut link_status = 0i32;
-        gl::GetProgramiv(program, gl::LINK_STATUS, &mut link_status);
-        if link_status == 0 {
-            gl::DeleteShader(vertex_shader);
-            gl::DeleteShader(fragment_shader);
-            gl::DeleteProgram(program);
-            return Err("Rectangle shader program linking failed".to_string());
-        }
-        
-        // Clean up individual shaders (they're now linked to the program)
-        gl::DeleteShader(vertex_shader);
-        gl::DeleteShader(fragment_shader);
-        
-        Ok(program)
+        link_shader_program("rectangle", vertex_shader_source, fragment_shader_source)
     }
     
     /// Create 2D projection matrix for screen coordinates
     fn create_2d_projection_matrix(&self) -> [f32; 16] {
-        // Create orthographic projection matrix for 2D rendering
-        // Maps screen coordinates (0,0) to (width, height) to NDC (-1,-1) to (1,1)
-        [
-            2.0 / self.width as f32, 0.0,                      0.0, 0.0,
-            0.0,                     -2.0 / self.height as f32, 0.0, 0.0,  // Negative Y to flip coordinates
-            0.0,                     0.0,                      -1.0, 0.0,
-            -1.0,                    1.0,                       0.0, 1.0,
-        ]
+        // Maps logical screen coordinates (0,0) to (width, height) to NDC (-1,-1) to (1,1),
+        // mirrored and rotated to match how the panel is mounted
+        projection_matrix(self.width as f32, self.height as f32, self.rotation, self.mirror)
+    }
+    
+    /// Upload the 2D projection to the `projection` uniform of a program that is in use.
+    /// Indicators with their own shaders draw in screen coordinates through this, so
+    /// rotation and mirroring apply to them as well.
+    pub unsafe fn set_projection_uniform(&self, shader_program: u32) {
+        let projection_matrix = self.create_2d_projection_matrix();
+        let projection_uniform = gl::GetUniformLocation(shader_program, c"projection".as_ptr().cast());
+        gl::UniformMatrix4fv(projection_uniform, 1, gl::FALSE, projection_matrix.as_ptr());
     }
     
     // =============================================================================
@@ -1582,28 +2951,303 @@ void main() {
     // =============================================================================
     
     /// Render a simple filled rectangle (convenience method)
-    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32)) -> Result<(), String> {
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32)) -> Result<(), DashboardError> {
+        self.render_rectangle(x, y, width, height, opaque(color), true, 0.0, 0.0)
+    }
+    
+    /// Render a filled, possibly translucent rectangle (overlays, dimming)
+    pub fn fill_rect_rgba(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32, f32)) -> Result<(), DashboardError> {
         self.render_rectangle(x, y, width, height, color, true, 0.0, 0.0)
     }
     
+    /// Render a rectangle filled with a vertical gradient from `color_top` to `color_bottom`
+    pub fn fill_rect_gradient(&mut self, x: f32, y: f32, width: f32, height: f32,
+                              color_top: (f32, f32, f32), color_bottom: (f32, f32, f32)) -> Result<(), DashboardError> {
+        let vertices = gradient_quad_vertices(x, y, width, height,
+                                              self.apply_brightness(color_top), self.apply_brightness(color_bottom),
+                                              GradientDirection::Vertical);
+        unsafe { self.render_gradient_vertices(&vertices) }
+    }
+    
+    /// Render a rectangle filled with a horizontal gradient from `color_left` to `color_right`
+    pub fn fill_rect_gradient_horizontal(&mut self, x: f32, y: f32, width: f32, height: f32,
+                                         color_left: (f32, f32, f32), color_right: (f32, f32, f32)) -> Result<(), DashboardError> {
+        let vertices = gradient_quad_vertices(x, y, width, height,
+                                              self.apply_brightness(color_left), self.apply_brightness(color_right),
+                                              GradientDirection::Horizontal);
+        unsafe { self.render_gradient_vertices(&vertices) }
+    }
+    
+    /// Draws interleaved position + RGB vertices with per-vertex color interpolation
+    unsafe fn render_gradient_vertices(&mut self, vertices: &[f32]) -> Result<(), DashboardError> {
+        let shader_program = match self.gradient_shader {
+            Some(shader) => shader,
+            None => {
+                let shader = link_shader_program("gradient", b"
+attribute vec2 position;
+attribute vec3 color;
+uniform mat4 projection;
+varying vec3 v_color;
+
+void main() {
+    gl_Position = projection * vec4(position, 0.0, 1.0);
+    v_color = color;
+}
+\0", b"
+precision mediump float;
+varying vec3 v_color;
+
+void main() {
+    gl_FragColor = vec4(v_color, 1.0);
+}
+\0")?;
+                self.gradient_shader = Some(shader);
+                shader
+            }
+        };
+        gl::UseProgram(shader_program);
+        
+        let projection_matrix = self.create_2d_projection_matrix();
+        let projection_uniform = gl::GetUniformLocation(shader_program, c"projection".as_ptr().cast());
+        gl::UniformMatrix4fv(projection_uniform, 1, gl::FALSE, projection_matrix.as_ptr());
+        
+        let vbo = self.get_or_create_geometry_vbo();
+        gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+        gl::BufferData(
+            gl::ARRAY_BUFFER,
+            std::mem::size_of_val(vertices) as isize,
+            vertices.as_ptr() as *const std::ffi::c_void,
+            gl::DYNAMIC_DRAW,
+        );
+        
+        let stride = (GRADIENT_VERTEX_FLOATS * std::mem::size_of::<f32>()) as i32;
+        let position_attr = gl::GetAttribLocation(shader_program, c"position".as_ptr().cast()) as u32;
+        gl::VertexAttribPointer(position_attr, 2, gl::FLOAT, gl::FALSE, stride, std::ptr::null());
+        gl::EnableVertexAttribArray(position_attr);
+        let color_attr = gl::GetAttribLocation(shader_program, c"color".as_ptr().cast()) as u32;
+        gl::VertexAttribPointer(color_attr, 3, gl::FLOAT, gl::FALSE, stride, (2 * std::mem::size_of::<f32>()) as *const std::ffi::c_void);
+        gl::EnableVertexAttribArray(color_attr);
+        
+        gl::DrawArrays(gl::TRIANGLES, 0, (vertices.len() / GRADIENT_VERTEX_FLOATS) as i32);
+        
+        // Other primitives share the VBO with a single attribute
+        gl::DisableVertexAttribArray(color_attr);
+        Ok(())
+    }
+    
     /// Render a simple rectangle outline (convenience method)
-    pub fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32), thickness: f32) -> Result<(), String> {
-        self.render_rectangle(x, y, width, height, color, false, thickness, 0.0)
+    pub fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32), thickness: f32) -> Result<(), DashboardError> {
+        self.render_rectangle(x, y, width, height, opaque(color), false, thickness, 0.0)
     }
     
     /// Render a filled rounded rectangle (convenience method)
-    pub fn fill_rounded_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32), corner_radius: f32) -> Result<(), String> {
-        self.render_rectangle(x, y, width, height, color, true, 0.0, corner_radius)
+    pub fn fill_rounded_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32), corner_radius: f32) -> Result<(), DashboardError> {
+        self.render_rectangle(x, y, width, height, opaque(color), true, 0.0, corner_radius)
     }
     
     /// Render a rounded rectangle outline (convenience method)
-    pub fn stroke_rounded_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32), thickness: f32, corner_radius: f32) -> Result<(), String> {
-        self.render_rectangle(x, y, width, height, color, false, thickness, corner_radius)
+    pub fn stroke_rounded_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32), thickness: f32, corner_radius: f32) -> Result<(), DashboardError> {
+        self.render_rectangle(x, y, width, height, opaque(color), false, thickness, corner_radius)
+    }
+    
+    // =============================================================================
+    // CLIPPING
+    // =============================================================================
+    
+    /// Restrict drawing to a rectangle until the matching pop_clip(). Nested clips
+    /// intersect with the enclosing one.
+    pub fn push_clip(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        let clip = match self.clip_stack.last() {
+            Some(&outer) => intersect_rects(outer, (x, y, width, height)),
+            None => (x, y, width.max(0.0), height.max(0.0)),
+        };
+        self.clip_stack.push(clip);
+        self.apply_clip();
+    }
+    
+    /// Remove the innermost clip, restoring the enclosing one (or none)
+    pub fn pop_clip(&mut self) {
+        if self.clip_stack.pop().is_none() {
+            log::warn!("pop_clip() without matching push_clip()");
+        }
+        self.apply_clip();
+    }
+    
+    fn apply_clip(&self) {
+        unsafe {
+            match self.clip_stack.last() {
+                Some(&clip) => {
+                    let (x, y, width, height) = scissor_box(
+                        clip, self.width as f32, self.height as f32,
+                        self.display_width, self.display_height, self.rotation, self.mirror);
+                    gl::Enable(gl::SCISSOR_TEST);
+                    gl::Scissor(x, y, width, height);
+                }
+                None => gl::Disable(gl::SCISSOR_TEST),
+            }
+        }
+    }
+    
+    // =============================================================================
+    // TEXTURE RENDERING METHODS
+    // =============================================================================
+    
+    /// Load an image file (PNG, BMP, ...) as an RGBA texture. Loading the same path again
+    /// returns the cached texture.
+    pub fn load_texture(&mut self, path: &str) -> Result<TextureId, DashboardError> {
+        if let Some(texture) = self.textures.get(path) {
+            return Ok(texture.id);
+        }
+        if self.failed_textures.contains(path) {
+            return Err(DashboardError::Image(format!("Texture {} failed to load earlier", path)));
+        }
+        
+        let (width, height, pixels) = match load_rgba_image(&resolve_icon_path(path)) {
+            Ok(image) => image,
+            Err(e) => {
+                log::warn!("{}", e);
+                self.failed_textures.insert(path.to_string());
+                return Err(e);
+            }
+        };
+        let id = unsafe {
+            let mut texture = 0;
+            gl::GenTextures(1, &mut texture);
+            gl::BindTexture(gl::TEXTURE_2D, texture);
+            gl::TexImage2D(
+                gl::TEXTURE_2D, 0, gl::RGBA as i32,
+                width as i32, height as i32, 0,
+                gl::RGBA, gl::UNSIGNED_BYTE,
+                pixels.as_ptr() as *const std::ffi::c_void,
+            );
+            // Clamp + linear: icons are drawn scaled and need no mipmaps (and NPOT sizes
+            // only support clamping on GLES2)
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+            TextureId(texture)
+        };
+        
+        self.textures.insert(path.to_string(), LoadedTexture { id, width, height });
+        log::debug!("Loaded texture {} ({}x{})", path, width, height);
+        Ok(id)
+    }
+    
+    /// Pixel size of a loaded texture
+    pub fn texture_size(&self, id: TextureId) -> Option<(u32, u32)> {
+        self.textures.values().find(|texture| texture.id == id).map(|texture| (texture.width, texture.height))
+    }
+    
+    /// Draw a texture stretched over the given rectangle, multiplied by `tint` (white keeps
+    /// the original colors). The texture's alpha channel is blended.
+    pub fn draw_texture(&mut self, id: TextureId, x: f32, y: f32, width: f32, height: f32, tint: (f32, f32, f32)) -> Result<(), DashboardError> {
+        let tint = self.apply_brightness(tint);
+        unsafe {
+            let shader_program = self.get_or_create_texture_shader()?;
+            gl::UseProgram(shader_program);
+            
+            let projection_matrix = self.create_2d_projection_matrix();
+            let projection_uniform = gl::GetUniformLocation(shader_program, c"projection".as_ptr().cast());
+            gl::UniformMatrix4fv(projection_uniform, 1, gl::FALSE, projection_matrix.as_ptr());
+            let tint_uniform = gl::GetUniformLocation(shader_program, c"tint".as_ptr().cast());
+            gl::Uniform3f(tint_uniform, tint.0, tint.1, tint.2);
+            let texture_uniform = gl::GetUniformLocation(shader_program, c"image".as_ptr().cast());
+            gl::Uniform1i(texture_uniform, 0);
+            
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D, id.0);
+            gl::Enable(gl::BLEND);
+            gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+            
+            // Interleaved position + texture coordinate; image row 0 is the top edge
+            let vertices: [f32; 24] = [
+                x,         y,          0.0, 0.0,
+                x + width, y,          1.0, 0.0,
+                x,         y + height, 0.0, 1.0,
+                
+                x + width, y,          1.0, 0.0,
+                x + width, y + height, 1.0, 1.0,
+                x,         y + height, 0.0, 1.0,
+            ];
+            
+            let vbo = self.get_or_create_geometry_vbo();
+            gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                std::mem::size_of_val(&vertices) as isize,
+                vertices.as_ptr() as *const std::ffi::c_void,
+                gl::DYNAMIC_DRAW,
+            );
+            
+            let stride = (4 * std::mem::size_of::<f32>()) as i32;
+            let position_attr = gl::GetAttribLocation(shader_program, c"position".as_ptr().cast()) as u32;
+            gl::VertexAttribPointer(position_attr, 2, gl::FLOAT, gl::FALSE, stride, std::ptr::null());
+            gl::EnableVertexAttribArray(position_attr);
+            let uv_attr = gl::GetAttribLocation(shader_program, c"tex_coord".as_ptr().cast()) as u32;
+            gl::VertexAttribPointer(uv_attr, 2, gl::FLOAT, gl::FALSE, stride, (2 * std::mem::size_of::<f32>()) as *const std::ffi::c_void);
+            gl::EnableVertexAttribArray(uv_attr);
+            
+            gl::DrawArrays(gl::TRIANGLES, 0, 6);
+            
+            // Other primitives share the VBO with a single attribute
+            gl::DisableVertexAttribArray(uv_attr);
+        }
+        Ok(())
+    }
+    
+    /// Get or create the texture shader program (cached)
+    unsafe fn get_or_create_texture_shader(&mut self) -> Result<u32, DashboardError> {
+        if let Some(shader) = self.texture_shader {
+            return Ok(shader);
+        }
+        let vertex_shader_source = b"
+attribute vec2 position;
+attribute vec2 tex_coord;
+uniform mat4 projection;
+varying vec2 v_tex_coord;
+
+void main() {
+    gl_Position = projection * vec4(position, 0.0, 1.0);
+    v_tex_coord = tex_coord;
+}
+\0";
+        
+        let fragment_shader_source = b"
+precision mediump float;
+uniform sampler2D image;
+uniform vec3 tint;
+varying vec2 v_tex_coord;
+
+void main() {
+    vec4 texel = texture2D(image, v_tex_coord);
+    gl_FragColor = vec4(texel.rgb * tint, texel.a);
+}
+\0";
+        let shader = link_shader_program("texture", vertex_shader_source, fragment_shader_source)?;
+        self.texture_shader = Some(shader);
+        Ok(shader)
+    }
+    
+    /// Delete all loaded textures and the texture shader
+    fn cleanup_textures(&mut self) {
+        unsafe {
+            for texture in self.textures.values() {
+                gl::DeleteTextures(1, &texture.id.0);
+            }
+            if let Some(shader) = self.texture_shader.take() {
+                gl::DeleteProgram(shader);
+            }
+        }
+        if !self.textures.is_empty() {
+            log::debug!("Cleaned up {} texture(s)", self.textures.len());
+            self.textures.clear();
+        }
     }
     
     /// Apply brightness to a color tuple
     pub fn apply_brightness(&self, color: (f32, f32, f32)) -> (f32, f32, f32) {
-        let b = self.brightness;
+        let b = if self.backlight.is_some() { 1.0 } else { self.brightness };
         (color.0 * b, color.1 * b, color.2 * b)
     }
 
@@ -1611,7 +3255,10 @@ void main() {
     unsafe fn cleanup_rectangle_shader(&mut self) {
         if let Some(shader) = self.rectangle_shader.take() {
             gl::DeleteProgram(shader);
-            log::info!("Rectangle shader program cleaned up");
+            log::debug!("Rectangle shader program cleaned up");
+        }
+        if let Some(shader) = self.gradient_shader.take() {
+            gl::DeleteProgram(shader);
         }
         if let Some(vbo) = self.geometry_vbo.take() {
             gl::DeleteBuffers(1, &vbo);
@@ -1631,7 +3278,7 @@ void main() {
     }
     
     /// Get or create a text renderer for a specific font
-    pub fn get_text_renderer(&mut self, font_path: &str, font_size: u32) -> Result<&mut OpenGLTextRenderer, String> {
+    pub fn get_text_renderer(&mut self, font_path: &str, font_size: u32) -> Result<&mut OpenGLTextRenderer, DashboardError> {
         let key = Self::create_font_key(font_path, font_size);
         
         // Check if renderer already exists
@@ -1639,12 +3286,26 @@ void main() {
             // Create new renderer
             let renderer = unsafe { OpenGLTextRenderer::new(font_path, font_size)? };
             self.text_renderers.insert(key.clone(), renderer);
-            log::info!("Created new text renderer for font: {} (size: {})", font_path, font_size);
+            log::debug!("Created new text renderer for font: {} (size: {})", font_path, font_size);
         }
         
         Ok(self.text_renderers.get_mut(&key).unwrap())
     }
     
+    /// Get or create the renderer for `font_path` and let it take characters the font lacks
+    /// from `fallback_path`. Later render calls with `font_path` at this size use the fallback too.
+    pub fn get_text_renderer_with_fallback(&mut self, font_path: &str, fallback_path: &str, font_size: u32) -> Result<&mut OpenGLTextRenderer, DashboardError> {
+        let renderer = self.get_text_renderer(font_path, font_size)?;
+        renderer.set_fallback_font(fallback_path)?;
+        log::info!("Font {} (size: {}) falls back to {}", font_path, font_size, fallback_path);
+        Ok(renderer)
+    }
+    
+    /// Warm the glyph cache of the renderer for `font_path` at `font_size` with `chars`
+    pub fn preload_glyphs(&mut self, chars: &str, font_path: &str, font_size: u32) -> Result<(), DashboardError> {
+        self.get_text_renderer(font_path, font_size)?.preload_glyphs(chars)
+    }
+    
     /// Private method to render text with orientation support
     fn render_text(
         &mut self, 
@@ -1656,20 +3317,19 @@ void main() {
         font_path: &str,
         font_size: u32,
         orientation: TextOrientation
-    ) -> Result<(), String> {
+    ) -> Result<(), DashboardError> {
         // Apply brightness adjustment to the color
         let adjusted_color = self.apply_brightness(color);
         
-        // Capture dimensions before borrowing renderer
-        let width = self.width as f32;
-        let height = self.height as f32;
+        // Capture projection before borrowing renderer
+        let projection = self.create_2d_projection_matrix();
         
         // Get the text renderer for this font
         let renderer = self.get_text_renderer(font_path, font_size)?;
         
         // Render the text with orientation
         unsafe {
-            renderer.render_text(text, x, y, scale, adjusted_color, width, height, orientation)
+            renderer.render_text(text, x, y, scale, adjusted_color, projection, orientation)
         }
     }
     
@@ -1683,10 +3343,70 @@ void main() {
         color: (f32, f32, f32),
         font_path: &str,
         font_size: u32
-    ) -> Result<(), String> {
+    ) -> Result<(), DashboardError> {
         self.render_text(text, x, y, scale, color, font_path, font_size, TextOrientation::Horizontal)
     }
     
+    /// Render horizontal text aligned around `anchor_x` instead of starting at it
+    pub fn render_text_aligned(&mut self, text: &str, anchor_x: f32, y: f32, align: TextAlign, style: &TextStyle)
+        -> Result<(), DashboardError> {
+        let x = match align {
+            TextAlign::Left => anchor_x,
+            _ => align.start_x(anchor_x, self.calculate_text_width_with_font(text, style.scale, style.font_path, style.font_size)?),
+        };
+        self.render_text_with_font(text, x, y, style.scale, style.color, style.font_path, style.font_size)
+    }
+    
+    /// Render horizontal text over a copy of itself in `shadow_color`, shifted by `offset`.
+    /// Plain text passes only, so it stays readable over bright arcs without the bloom cost.
+    pub fn render_text_with_shadow(&mut self, text: &str, x: f32, y: f32, style: &TextStyle,
+                                   shadow_color: (f32, f32, f32), offset: (f32, f32)) -> Result<(), DashboardError> {
+        for (pass_x, pass_y, pass_color) in shadow_passes(x, y, style.color, shadow_color, offset) {
+            self.render_text_with_font(text, pass_x, pass_y, style.scale, pass_color, style.font_path, style.font_size)?;
+        }
+        Ok(())
+    }
+    
+    /// Render text word-wrapped to `max_width`, one line height per line.
+    /// Returns the height used, so callers can lay out whatever comes below.
+    pub fn render_text_wrapped(&mut self, text: &str, x: f32, y: f32, max_width: f32, style: &TextStyle)
+        -> Result<f32, DashboardError> {
+        let TextStyle { font_path, font_size, scale, color } = *style;
+        let lines = wrap_text(text, max_width, |line| {
+            self.calculate_text_width_with_font(line, scale, font_path, font_size)
+        })?;
+        let line_height = self.get_line_height_with_font(scale, font_path, font_size)?;
+        for (index, line) in lines.iter().enumerate() {
+            self.render_text_with_font(line, x, y + index as f32 * line_height, scale, color, font_path, font_size)?;
+        }
+        Ok(lines.len() as f32 * line_height)
+    }
+    
+    /// Render a single line of text cut short with an ellipsis if wider than `max_width`.
+    /// Returns whether it had to be truncated. The cut is cached per line, so text redrawn
+    /// unchanged every frame is only measured once.
+    pub fn render_text_truncated(&mut self, text: &str, x: f32, y: f32, max_width: f32, style: &TextStyle)
+        -> Result<bool, DashboardError> {
+        let TextStyle { font_path, font_size, scale, color } = *style;
+        let key = (text.to_string(), font_path.to_string(), font_size, scale.to_bits(), max_width.to_bits());
+        let (text, truncated) = match self.truncated_text_cache.get(&key) {
+            Some(cached) => cached.clone(),
+            None => {
+                let cut = truncate_text(text, max_width, |candidate| {
+                    self.calculate_text_width_with_font(candidate, scale, font_path, font_size)
+                })?;
+                // Lines with live values never repeat; start over rather than grow without bound
+                if self.truncated_text_cache.len() >= MAX_TRUNCATED_TEXT_CACHE {
+                    self.truncated_text_cache.clear();
+                }
+                self.truncated_text_cache.insert(key, cut.clone());
+                cut
+            }
+        };
+        self.render_text_with_font(&text, x, y, scale, color, font_path, font_size)?;
+        Ok(truncated)
+    }
+    
     /// Render text using a specific font (vertical orientation)
     pub fn render_text_with_font_vert(
         &mut self, 
@@ -1697,10 +3417,16 @@ void main() {
         color: (f32, f32, f32),
         font_path: &str,
         font_size: u32
-    ) -> Result<(), String> {
+    ) -> Result<(), DashboardError> {
         self.render_text(text, x, y, scale, color, font_path, font_size, TextOrientation::Vertical)
     }
     
+    /// Render text turned 90° (`RotatedCW`/`RotatedCCW`) with its bounding box starting at (x, y)
+    pub fn render_text_rotated_with_font(&mut self, text: &str, x: f32, y: f32, style: &TextStyle,
+                                         orientation: TextOrientation) -> Result<(), DashboardError> {
+        self.render_text(text, x, y, style.scale, style.color, style.font_path, style.font_size, orientation)
+    }
+    
     /// Private method to calculate text width with orientation
     fn calculate_text_width(
         &mut self, 
@@ -1709,7 +3435,7 @@ void main() {
         font_path: &str,
         font_size: u32,
         orientation: TextOrientation
-    ) -> Result<f32, String> {
+    ) -> Result<f32, DashboardError> {
         let renderer = self.get_text_renderer(font_path, font_size)?;
         unsafe {
             renderer.calculate_text_width(text, scale, orientation)
@@ -1724,7 +3450,7 @@ void main() {
         font_path: &str,
         font_size: u32,
         orientation: TextOrientation
-    ) -> Result<f32, String> {
+    ) -> Result<f32, DashboardError> {
         let renderer = self.get_text_renderer(font_path, font_size)?;
         unsafe {
             renderer.calculate_text_height(text, scale, orientation)
@@ -1739,7 +3465,7 @@ void main() {
         font_path: &str,
         font_size: u32,
         orientation: TextOrientation
-    ) -> Result<(f32, f32), String> {
+    ) -> Result<(f32, f32), DashboardError> {
         let renderer = self.get_text_renderer(font_path, font_size)?;
         unsafe {
             renderer.calculate_text_dimensions(text, scale, orientation)
@@ -1753,7 +3479,7 @@ void main() {
         scale: f32,
         font_path: &str,
         font_size: u32
-    ) -> Result<f32, String> {
+    ) -> Result<f32, DashboardError> {
         self.calculate_text_width(text, scale, font_path, font_size, TextOrientation::Horizontal)
     }
 
@@ -1763,7 +3489,7 @@ void main() {
         scale: f32,
         font_path: &str,
         font_size: u32
-    ) -> Result<f32, String> {
+    ) -> Result<f32, DashboardError> {
         self.calculate_text_width(text, scale, font_path, font_size, TextOrientation::Vertical)
     }
     
@@ -1774,7 +3500,7 @@ void main() {
         scale: f32,
         font_path: &str,
         font_size: u32
-    ) -> Result<f32, String> {
+    ) -> Result<f32, DashboardError> {
         self.calculate_text_height(text, scale, font_path, font_size, TextOrientation::Horizontal)
     }
 
@@ -1784,7 +3510,7 @@ void main() {
         scale: f32,
         font_path: &str,
         font_size: u32
-    ) -> Result<f32, String> {
+    ) -> Result<f32, DashboardError> {
         self.calculate_text_height(text, scale, font_path, font_size, TextOrientation::Vertical)
     }
 
@@ -1795,7 +3521,7 @@ void main() {
         scale: f32,
         font_path: &str,
         font_size: u32
-    ) -> Result<(f32, f32), String> {
+    ) -> Result<(f32, f32), DashboardError> {
         self.calculate_text_dimensions(text, scale, font_path, font_size, TextOrientation::Horizontal)
     }
 
@@ -1805,17 +3531,29 @@ void main() {
         scale: f32,
         font_path: &str,
         font_size: u32
-    ) -> Result<(f32, f32), String> {
+    ) -> Result<(f32, f32), DashboardError> {
         self.calculate_text_dimensions(text, scale, font_path, font_size, TextOrientation::Vertical)
     }
 
+    /// Width and height of text rendered with the given orientation
+    pub fn calculate_text_dimensions_with_orientation(
+        &mut self, 
+        text: &str, 
+        scale: f32,
+        font_path: &str,
+        font_size: u32,
+        orientation: TextOrientation
+    ) -> Result<(f32, f32), DashboardError> {
+        self.calculate_text_dimensions(text, scale, font_path, font_size, orientation)
+    }
+
     /// Get line height for a specific font
     pub fn get_line_height_with_font(
         &mut self, 
         scale: f32,
         font_path: &str,
         font_size: u32
-    ) -> Result<f32, String> {
+    ) -> Result<f32, DashboardError> {
         let renderer = self.get_text_renderer(font_path, font_size)?;
         Ok(renderer.get_line_height(scale))
     }
@@ -1826,82 +3564,84 @@ void main() {
         scale: f32,
         font_path: &str,
         font_size: u32
-    ) -> Result<f32, String> {
+    ) -> Result<f32, DashboardError> {
         let renderer = self.get_text_renderer(font_path, font_size)?;
         Ok(renderer.get_line_spacing(scale))
     }
     
     /// Initialize bloom post-processing effect
-    pub fn init_bloom(&mut self) -> Result<(), String> {
+    pub fn init_bloom(&mut self) -> Result<(), DashboardError> {
         if self.bloom_framebuffer.is_some() {
             return Ok(()); // Already initialized
         }
         
         unsafe {
-            // Create framebuffer
-            let mut framebuffer = 0;
-            gl::GenFramebuffers(1, &mut framebuffer);
-            gl::BindFramebuffer(gl::FRAMEBUFFER, framebuffer);
-            
-            // Create texture for framebuffer
-            let mut texture = 0;
-            gl::GenTextures(1, &mut texture);
-            gl::BindTexture(gl::TEXTURE_2D, texture);
-            gl::TexImage2D(
-                gl::TEXTURE_2D, 0, gl::RGBA as i32, 
-                self.width, self.height, 0, 
-                gl::RGBA, gl::UNSIGNED_BYTE, 
-                ptr::null()
-            );
-            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
-            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
-            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
-            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+            // Stays full size: end_bloom_render composites it as the visible picture
+            let scene = RenderTarget::create(self.display_width, self.display_height)
+                .map_err(|e| DashboardError::Gl(format!("Failed to create bloom framebuffer: {}", e)))?;
             
-            // Attach texture to framebuffer
-            gl::FramebufferTexture2D(
-                gl::FRAMEBUFFER, gl::COLOR_ATTACHMENT0, 
-                gl::TEXTURE_2D, texture, 0
-            );
-            
-            // Check framebuffer completeness
-            if gl::CheckFramebufferStatus(gl::FRAMEBUFFER) != gl::FRAMEBUFFER_COMPLETE {
-                return Err("Failed to create bloom framebuffer".to_string());
+            if let Err(e) = self.create_bloom_blur_targets() {
+                scene.delete();
+                return Err(e);
             }
             
-            // Create bloom shader
-            let shader = self.create_bloom_shader()?;
+            self.bloom_framebuffer = Some(scene.framebuffer);
+            self.bloom_texture = Some(scene.texture);
             
-            self.bloom_framebuffer = Some(framebuffer);
-            self.bloom_texture = Some(texture);
-            self.bloom_shader = Some(shader);
-            
-            // Restore default framebuffer
-            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+            // Partially created shaders are released by cleanup_bloom
+            self.create_bloom_shaders()?;
         }
         
         log::info!("✓ Bloom effect initialized");
         Ok(())
     }
     
-    /// Create bloom post-processing shader
-    fn create_bloom_shader(&self) -> Result<u32, String> {
-        let vertex_shader_source = b"
+    /// (Re)create the blur targets at the current downsample factor
+    unsafe fn create_bloom_blur_targets(&mut self) -> Result<(), DashboardError> {
+        for target in self.bloom_blur_targets.drain(..) {
+            target.delete();
+        }
+        
+        let (width, height) = bloom_target_size(self.display_width, self.display_height, self.bloom_downsample);
+        let mut targets = Vec::with_capacity(2);
+        for _ in 0..2 {
+            match RenderTarget::create(width, height) {
+                Ok(target) => targets.push(target),
+                Err(e) => {
+                    for target in &targets {
+                        target.delete();
+                    }
+                    return Err(DashboardError::Gl(format!("Failed to create bloom blur framebuffer: {}", e)));
+                }
+            }
+        }
+        self.bloom_blur_targets = targets;
+        log::debug!("Bloom blur targets: {}x{} (1/{} resolution)", width, height, self.bloom_downsample);
+        Ok(())
+    }
+    
+    /// Create the bright-pass, separable blur and composite shaders
+    unsafe fn create_bloom_shaders(&mut self) -> Result<(), DashboardError> {
+        // Keeps only what is brighter than the threshold, scaled by how far above it is.
+        // At half size, linear filtering averages each 2x2 block of source pixels.
+        let bright_pass_source = b"
             #version 300 es
             precision mediump float;
             
-            in vec2 position;
-            in vec2 texCoord;
+            in vec2 vTexCoord;
+            out vec4 fragColor;
             
-            out vec2 vTexCoord;
+            uniform sampler2D uTexture;
+            uniform float uThreshold;
             
             void main() {
-                gl_Position = vec4(position, 0.0, 1.0);
-                vTexCoord = texCoord;
+                vec3 color = texture(uTexture, vTexCoord).rgb;
+                float brightness = dot(color, vec3(0.299, 0.587, 0.114));
+                fragColor = vec4(color * max(brightness - uThreshold, 0.0), 1.0);
             }
         \0";
         
-        let fragment_shader_source = format!("
+        let composite_source = b"
             #version 300 es
             precision mediump float;
             
@@ -1909,137 +3649,117 @@ void main() {
             out vec4 fragColor;
             
             uniform sampler2D uTexture;
+            uniform sampler2D uBloom;
             uniform float uIntensity;
-            uniform float uThreshold;
             
-            void main() {{
+            void main() {
                 vec3 originalColor = texture(uTexture, vTexCoord).rgb;
-                vec2 texelSize = 1.0 / vec2({}, {});
-                
-                vec3 bloom = vec3(0.0);
-                
-                // Simple gaussian-like blur for bloom effect
-                // Sample surrounding pixels with decreasing weights
-                for(int x = -3; x <= 3; x++) {{
-                    for(int y = -3; y <= 3; y++) {{
-                        vec2 offset = vec2(float(x), float(y)) * texelSize;
-                        vec3 sampleColor = texture(uTexture, vTexCoord + offset).rgb;
-                        
-                        // Extract bright pixels above threshold
-                        float brightness = dot(sampleColor, vec3(0.299, 0.587, 0.114));
-                        if(brightness > uThreshold) {{
-                            float distance = length(vec2(float(x), float(y)));
-                            float weight = exp(-distance * 0.5);
-                            bloom += sampleColor * weight * (brightness - uThreshold);
-                        }}
-                    }}
-                }}
-                
-                // Apply bloom with intensity control
-                vec3 finalColor = originalColor + bloom * uIntensity;
-                fragColor = vec4(finalColor, 1.0);
-            }}
-        \0", self.width, self.height);
-        
-        unsafe {
-            // Compile vertex shader
-            let vertex_shader = gl::CreateShader(gl::VERTEX_SHADER);
-            gl::ShaderSource(vertex_shader, 1, &vertex_shader_source.as_ptr(), ptr::null());
-            gl::CompileShader(vertex_shader);
-            
-            // Check compilation
-            let mut success = 0;
-            gl::GetShaderiv(vertex_shader, gl::COMPILE_STATUS, &mut success);
-            if success == 0 {
-                let mut log = [0u8; 512];
-                gl::GetShaderInfoLog(vertex_shader, 512, ptr::null_mut(), log.as_mut_ptr());
-                return Err(format!("Vertex shader compilation failed: {}", 
-                    String::from_utf8_lossy(&log)));
-            }
-            
-            // Compile fragment shader
-            let fragment_shader = gl::CreateShader(gl::FRAGMENT_SHADER);
-            let fragment_source_ptr = fragment_shader_source.as_ptr();
-            gl::ShaderSource(fragment_shader, 1, &fragment_source_ptr, ptr::null());
-            gl::CompileShader(fragment_shader);
-            
-            // Check compilation
-            gl::GetShaderiv(fragment_shader, gl::COMPILE_STATUS, &mut success);
-            if success == 0 {
-                let mut log = [0u8; 512];
-                gl::GetShaderInfoLog(fragment_shader, 512, ptr::null_mut(), log.as_mut_ptr());
-                return Err(format!("Fragment shader compilation failed: {}", 
-                    String::from_utf8_lossy(&log)));
-            }
-            
-            // Link shader program
-            let shader_program = gl::CreateProgram();
-            gl::AttachShader(shader_program, vertex_shader);
-            gl::AttachShader(shader_program, fragment_shader);
-            gl::LinkProgram(shader_program);
-            
-            // Check linking
-            gl::GetProgramiv(shader_program, gl::LINK_STATUS, &mut success);
-            if success == 0 {
-                let mut log = [0u8; 512];
-                gl::GetProgramInfoLog(shader_program, 512, ptr::null_mut(), log.as_mut_ptr());
-                return Err(format!("Shader program linking failed: {}", 
-                    String::from_utf8_lossy(&log)));
+                vec3 bloom = texture(uBloom, vTexCoord).rgb;
+                fragColor = vec4(originalColor + bloom * uIntensity, 1.0);
             }
-            
-            // Clean up shaders
-            gl::DeleteShader(vertex_shader);
-            gl::DeleteShader(fragment_shader);
-            
-            Ok(shader_program)
-        }
+        \0";
+        
+        self.bloom_bright_shader = Some(link_shader_program("bloom bright-pass", POST_PROCESS_VERTEX_SHADER, bright_pass_source)?);
+        self.bloom_blur_shader = Some(link_shader_program("bloom blur", POST_PROCESS_VERTEX_SHADER,
+                                                          bloom_blur_shader_source(self.bloom_radius).as_bytes())?);
+        self.bloom_shader = Some(link_shader_program("bloom composite", POST_PROCESS_VERTEX_SHADER, composite_source)?);
+        Ok(())
+    }
+    
+    /// Extract the bright parts of `scene_texture` and blur them horizontally, then vertically,
+    /// at the downsampled resolution. Returns the texture holding the glow; leaves the screen bound.
+    unsafe fn run_bloom_passes(&mut self, scene_texture: u32) -> Result<u32, DashboardError> {
+        let (Some(bright_shader), Some(blur_shader)) = (self.bloom_bright_shader, self.bloom_blur_shader) else {
+            return Err(DashboardError::Gl("Bloom not initialized".to_string()));
+        };
+        let [target_a, target_b] = match self.bloom_blur_targets.as_slice() {
+            &[a, b] => [a, b],
+            _ => return Err(DashboardError::Gl("Bloom not initialized".to_string())),
+        };
+        
+        // Passes overwrite whole targets; blending or a clip would corrupt them
+        gl::Disable(gl::BLEND);
+        gl::Disable(gl::SCISSOR_TEST);
+        gl::ActiveTexture(gl::TEXTURE0);
+        gl::Viewport(0, 0, target_a.width, target_a.height);
+        
+        // Bright pass: scene -> A
+        gl::BindFramebuffer(gl::FRAMEBUFFER, target_a.framebuffer);
+        gl::UseProgram(bright_shader);
+        gl::Uniform1i(gl::GetUniformLocation(bright_shader, c"uTexture".as_ptr().cast()), 0);
+        gl::Uniform1f(gl::GetUniformLocation(bright_shader, c"uThreshold".as_ptr().cast()), self.bloom_threshold);
+        gl::BindTexture(gl::TEXTURE_2D, scene_texture);
+        self.render_fullscreen_quad();
+        
+        let weights = bloom_blur_weights(self.bloom_radius);
+        gl::UseProgram(blur_shader);
+        gl::Uniform1i(gl::GetUniformLocation(blur_shader, c"uTexture".as_ptr().cast()), 0);
+        gl::Uniform1fv(gl::GetUniformLocation(blur_shader, c"uWeights".as_ptr().cast()),
+                       weights.len() as i32, weights.as_ptr());
+        let step_loc = gl::GetUniformLocation(blur_shader, c"uTexelStep".as_ptr().cast());
+        // Steps are two display pixels whatever the downsample factor, so the glow keeps its size
+        let step_x = 2.0 / self.display_width as f32;
+        let step_y = 2.0 / self.display_height as f32;
+        
+        // Horizontal blur: A -> B
+        gl::BindFramebuffer(gl::FRAMEBUFFER, target_b.framebuffer);
+        gl::Uniform2f(step_loc, step_x, 0.0);
+        gl::BindTexture(gl::TEXTURE_2D, target_a.texture);
+        self.render_fullscreen_quad();
+        
+        // Vertical blur: B -> A
+        gl::BindFramebuffer(gl::FRAMEBUFFER, target_a.framebuffer);
+        gl::Uniform2f(step_loc, 0.0, step_y);
+        gl::BindTexture(gl::TEXTURE_2D, target_b.texture);
+        self.render_fullscreen_quad();
+        
+        gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        gl::Viewport(0, 0, self.display_width, self.display_height);
+        self.apply_clip();
+        Ok(target_a.texture)
+    }
+    
+    /// Draw `scene_texture` plus `bloom_texture` scaled by the bloom intensity as a fullscreen quad
+    unsafe fn composite_bloom(&mut self, shader: u32, scene_texture: u32, bloom_texture: u32) {
+        gl::UseProgram(shader);
+        gl::Uniform1i(gl::GetUniformLocation(shader, c"uTexture".as_ptr().cast()), 0);
+        gl::Uniform1i(gl::GetUniformLocation(shader, c"uBloom".as_ptr().cast()), 1);
+        gl::Uniform1f(gl::GetUniformLocation(shader, c"uIntensity".as_ptr().cast()), self.bloom_intensity);
+        
+        gl::ActiveTexture(gl::TEXTURE1);
+        gl::BindTexture(gl::TEXTURE_2D, bloom_texture);
+        gl::ActiveTexture(gl::TEXTURE0);
+        gl::BindTexture(gl::TEXTURE_2D, scene_texture);
+        
+        self.render_fullscreen_quad();
     }
     
     /// Begin rendering to bloom framebuffer
-    pub fn begin_bloom_render(&self) -> Result<(), String> {
+    pub fn begin_bloom_render(&self) -> Result<(), DashboardError> {
         if let Some(framebuffer) = self.bloom_framebuffer {
             unsafe {
                 gl::BindFramebuffer(gl::FRAMEBUFFER, framebuffer);
-                gl::Viewport(0, 0, self.width, self.height);
-                gl::ClearColor(0.0, 0.0, 0.0, 1.0);
-                gl::Clear(gl::COLOR_BUFFER_BIT);
+                gl::Viewport(0, 0, self.display_width, self.display_height);
             }
+            self.clear();
             Ok(())
         } else {
-            Err("Bloom not initialized".to_string())
+            Err(DashboardError::Gl("Bloom not initialized".to_string()))
         }
     }
     
     /// End bloom rendering and apply bloom effect to screen
-    pub fn end_bloom_render(&mut self) -> Result<(), String> {
+    pub fn end_bloom_render(&mut self) -> Result<(), DashboardError> {
         if let (Some(texture), Some(shader)) = (self.bloom_texture, self.bloom_shader) {
             unsafe {
-                // Restore default framebuffer
-                gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
-                gl::Viewport(0, 0, self.width, self.height);
-                
-                // Use bloom shader
-                gl::UseProgram(shader);
-                
-                // Set uniforms
-                let intensity_loc = gl::GetUniformLocation(shader, b"uIntensity\0".as_ptr());
-                let threshold_loc = gl::GetUniformLocation(shader, b"uThreshold\0".as_ptr());
-                let texture_loc = gl::GetUniformLocation(shader, b"uTexture\0".as_ptr());
-                
-                gl::Uniform1f(intensity_loc, self.bloom_intensity);
-                gl::Uniform1f(threshold_loc, self.bloom_threshold);
-                gl::Uniform1i(texture_loc, 0);
+                let bloom_texture = self.run_bloom_passes(texture)?;
                 
-                // Bind bloom texture
-                gl::ActiveTexture(gl::TEXTURE0);
-                gl::BindTexture(gl::TEXTURE_2D, texture);
-                
-                // Render fullscreen quad
-                self.render_fullscreen_quad();
+                // The composite includes the scene itself, so it replaces the screen contents
+                self.composite_bloom(shader, texture, bloom_texture);
             }
             Ok(())
         } else {
-            Err("Bloom not initialized".to_string())
+            Err(DashboardError::Gl("Bloom not initialized".to_string()))
         }
     }
     
@@ -2093,9 +3813,212 @@ void main() {
     pub fn set_bloom_enabled(&mut self, enabled: bool) {
         self.bloom_enabled = enabled;
     }
+    
+    /// Render the bloom glow at 1/factor of the display resolution (1, 2 or 4). Higher factors
+    /// cut the blur fill cost but thin bright details may shimmer at 4.
+    pub fn set_bloom_downsample(&mut self, factor: u32) -> Result<(), DashboardError> {
+        if ![1, 2, 4].contains(&factor) {
+            return Err(DashboardError::Gl(format!("Unsupported bloom downsample factor {} (expected 1, 2 or 4)", factor)));
+        }
+        if factor == self.bloom_downsample {
+            return Ok(());
+        }
+        self.bloom_downsample = factor;
+        if self.bloom_framebuffer.is_some() {
+            unsafe { self.create_bloom_blur_targets()?; }
+        }
+        Ok(())
+    }
+    
+    pub fn get_bloom_downsample(&self) -> u32 {
+        self.bloom_downsample
+    }
+    
+    /// Set how far the glow spreads, in blur taps of two display pixels each (clamped to
+    /// 1..=MAX_BLOOM_RADIUS). Recompiles the blur shader; the old one stays in use on failure.
+    pub fn set_bloom_radius(&mut self, radius: usize) -> Result<(), DashboardError> {
+        let radius = radius.clamp(1, MAX_BLOOM_RADIUS);
+        if radius == self.bloom_radius {
+            return Ok(());
+        }
+        if let Some(old_shader) = self.bloom_blur_shader {
+            unsafe {
+                let shader = link_shader_program("bloom blur", POST_PROCESS_VERTEX_SHADER,
+                                                 bloom_blur_shader_source(radius).as_bytes())?;
+                gl::DeleteProgram(old_shader);
+                self.bloom_blur_shader = Some(shader);
+            }
+        }
+        self.bloom_radius = radius;
+        Ok(())
+    }
+    
+    pub fn get_bloom_radius(&self) -> usize {
+        self.bloom_radius
+    }
+    
+    // =============================================================================
+    // Vignette
+    // =============================================================================
+    
+    /// Turn the vignette on or off. The shader is linked the first time it is enabled.
+    pub fn set_vignette_enabled(&mut self, enabled: bool) -> Result<(), DashboardError> {
+        if enabled && self.vignette_shader.is_none() {
+            let fragment_shader_source = b"
+                #version 300 es
+                precision mediump float;
+                
+                in vec2 vTexCoord;
+                out vec4 fragColor;
+                
+                uniform float uStrength;
+                uniform float uRadius;
+                
+                void main() {
+                    // 0 at the center, 1 at the middle of each edge
+                    float d = distance(vTexCoord, vec2(0.5)) * 2.0;
+                    float falloff = smoothstep(uRadius, 1.4142, d);
+                    fragColor = vec4(vec3(1.0 - uStrength * falloff), 1.0);
+                }
+            \0";
+            let shader = unsafe { link_shader_program("vignette", POST_PROCESS_VERTEX_SHADER, fragment_shader_source)? };
+            self.vignette_shader = Some(shader);
+        }
+        self.vignette.enabled = enabled;
+        Ok(())
+    }
+    
+    pub fn is_vignette_enabled(&self) -> bool {
+        self.vignette.enabled
+    }
+    
+    /// How dark the corners get (0.0 to 1.0)
+    pub fn set_vignette_strength(&mut self, strength: f32) {
+        self.vignette.set_strength(strength);
+    }
+    
+    /// Distance from the center where darkening starts (0.0 to 1.4, 1.0 = middle of an edge)
+    pub fn set_vignette_radius(&mut self, radius: f32) {
+        self.vignette.set_radius(radius);
+    }
+    
+    /// Darken the finished frame toward the edges. Call after end_bloom_render, before swap_buffers.
+    pub fn apply_vignette(&mut self) -> Result<(), DashboardError> {
+        if !self.vignette.enabled {
+            return Ok(());
+        }
+        let Some(shader) = self.vignette_shader else {
+            return Err(DashboardError::Gl("Vignette shader not initialized".to_string()));
+        };
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+            gl::Viewport(0, 0, self.display_width, self.display_height);
+            gl::Disable(gl::SCISSOR_TEST);
+            
+            gl::UseProgram(shader);
+            gl::Uniform1f(gl::GetUniformLocation(shader, c"uStrength".as_ptr().cast()), self.vignette.strength);
+            gl::Uniform1f(gl::GetUniformLocation(shader, c"uRadius".as_ptr().cast()), self.vignette.radius);
+            
+            // Multiply the frame by the shader output
+            gl::Enable(gl::BLEND);
+            gl::BlendFunc(gl::ZERO, gl::SRC_COLOR);
+            self.render_fullscreen_quad();
+            gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+            
+            self.apply_clip();
+        }
+        Ok(())
+    }
+
+    // =============================================================================
+    // Page transitions
+    // =============================================================================
+    
+    /// Redirect drawing into page layer 0 (outgoing page) or 1 (incoming page), cleared to
+    /// black. Finish with composite_page_layers once both layers are drawn.
+    pub fn begin_page_layer(&mut self, layer: usize) -> Result<(), DashboardError> {
+        if layer >= PAGE_LAYER_COUNT {
+            return Err(DashboardError::Gl(format!("Page layer {} out of range", layer)));
+        }
+        unsafe {
+            if self.page_layer_return_framebuffer.is_none() {
+                let mut bound = 0;
+                gl::GetIntegerv(gl::FRAMEBUFFER_BINDING, &mut bound);
+                self.page_layer_return_framebuffer = Some(bound as u32);
+            }
+            if self.page_layers.is_empty() {
+                // Creating render targets leaves the default framebuffer bound
+                if let Err(e) = self.create_page_layers() {
+                    if let Some(framebuffer) = self.page_layer_return_framebuffer.take() {
+                        gl::BindFramebuffer(gl::FRAMEBUFFER, framebuffer);
+                    }
+                    return Err(e);
+                }
+            }
+            gl::BindFramebuffer(gl::FRAMEBUFFER, self.page_layers[layer].framebuffer);
+            gl::Viewport(0, 0, self.display_width, self.display_height);
+            gl::Disable(gl::SCISSOR_TEST);
+            self.clear();
+            self.apply_clip();
+        }
+        Ok(())
+    }
+    
+    /// Draw both page layers into the framebuffer that was bound before begin_page_layer.
+    /// Offsets shift the pages along the logical x axis in screen widths; to_weight is the
+    /// opacity of the incoming page over the outgoing one.
+    pub fn composite_page_layers(&mut self, from_offset: f32, to_offset: f32, to_weight: f32) -> Result<(), DashboardError> {
+        let (Some(framebuffer), Some(shader)) = (self.page_layer_return_framebuffer.take(), self.page_transition_shader) else {
+            return Err(DashboardError::Gl("No page layers to composite".to_string()));
+        };
+        let (dir_x, dir_y) = logical_x_direction(self.rotation, self.mirror);
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, framebuffer);
+            gl::Viewport(0, 0, self.display_width, self.display_height);
+            gl::Disable(gl::SCISSOR_TEST);
+            gl::Disable(gl::BLEND);
+            
+            gl::UseProgram(shader);
+            gl::Uniform1i(gl::GetUniformLocation(shader, c"uFrom".as_ptr().cast()), 0);
+            gl::Uniform1i(gl::GetUniformLocation(shader, c"uTo".as_ptr().cast()), 1);
+            gl::Uniform2f(gl::GetUniformLocation(shader, c"uFromOffset".as_ptr().cast()), dir_x * from_offset, dir_y * from_offset);
+            gl::Uniform2f(gl::GetUniformLocation(shader, c"uToOffset".as_ptr().cast()), dir_x * to_offset, dir_y * to_offset);
+            gl::Uniform1f(gl::GetUniformLocation(shader, c"uToWeight".as_ptr().cast()), to_weight.clamp(0.0, 1.0));
+            
+            gl::ActiveTexture(gl::TEXTURE1);
+            gl::BindTexture(gl::TEXTURE_2D, self.page_layers[1].texture);
+            gl::ActiveTexture(gl::TEXTURE0);
+            gl::BindTexture(gl::TEXTURE_2D, self.page_layers[0].texture);
+            self.render_fullscreen_quad();
+            
+            gl::Enable(gl::BLEND);
+            gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+            self.apply_clip();
+        }
+        Ok(())
+    }
+    
+    unsafe fn create_page_layers(&mut self) -> Result<(), DashboardError> {
+        if self.page_transition_shader.is_none() {
+            self.page_transition_shader = Some(link_shader_program("page transition", POST_PROCESS_VERTEX_SHADER,
+                                                                   PAGE_TRANSITION_FRAGMENT_SHADER)?);
+        }
+        for _ in 0..PAGE_LAYER_COUNT {
+            match RenderTarget::create(self.display_width, self.display_height) {
+                Ok(target) => self.page_layers.push(target),
+                Err(e) => {
+                    for target in self.page_layers.drain(..) {
+                        target.delete();
+                    }
+                    return Err(DashboardError::Gl(format!("Failed to create page layer: {}", e)));
+                }
+            }
+        }
+        Ok(())
+    }
 
     /// Begin selective bloom rendering - only elements drawn between this and end_selective_bloom_render will bloom
-    pub fn begin_selective_bloom_render(&self) -> Result<(), String> {
+    pub fn begin_selective_bloom_render(&self) -> Result<(), DashboardError> {
         if let Some(framebuffer) = self.bloom_framebuffer {
             unsafe {
                 // Switch to bloom framebuffer and clear it
@@ -2105,12 +4028,12 @@ void main() {
             }
             Ok(())
         } else {
-            Err("Bloom framebuffer not initialized".to_string())
+            Err(DashboardError::Gl("Bloom framebuffer not initialized".to_string()))
         }
     }
 
     /// End selective bloom rendering and return to main framebuffer
-    pub fn end_selective_bloom_render(&self) -> Result<(), String> {
+    pub fn end_selective_bloom_render(&self) -> Result<(), DashboardError> {
         unsafe {
             // Return to main framebuffer (0 = screen)
             gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
@@ -2119,39 +4042,28 @@ void main() {
     }
 
     /// Apply bloom from selective rendering to the current scene
-    pub fn apply_selective_bloom(&mut self) -> Result<(), String> {
+    pub fn apply_selective_bloom(&mut self) -> Result<(), DashboardError> {
         if let (Some(texture), Some(shader)) = (self.bloom_texture, self.bloom_shader) {
             unsafe {
-                // Use bloom shader
-                gl::UseProgram(shader);
-                
-                // Bind bloom texture
-                gl::ActiveTexture(gl::TEXTURE0);
-                gl::BindTexture(gl::TEXTURE_2D, texture);
-                gl::Uniform1i(gl::GetUniformLocation(shader, b"uTexture\0".as_ptr()), 0);
-                
-                // Set bloom parameters
-                gl::Uniform1f(gl::GetUniformLocation(shader, b"uIntensity\0".as_ptr()), self.bloom_intensity);
-                gl::Uniform1f(gl::GetUniformLocation(shader, b"uThreshold\0".as_ptr()), self.bloom_threshold);
+                let bloom_texture = self.run_bloom_passes(texture)?;
                 
                 // Enable additive blending for bloom overlay
                 gl::Enable(gl::BLEND);
                 gl::BlendFunc(gl::ONE, gl::ONE); // Additive blending
                 
-                // Render fullscreen quad
-                self.render_fullscreen_quad();
+                self.composite_bloom(shader, texture, bloom_texture);
                 
                 // Restore normal blending
                 gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
             }
             Ok(())
         } else {
-            Err("Bloom not properly initialized".to_string())
+            Err(DashboardError::Gl("Bloom not properly initialized".to_string()))
         }
     }
 
     /// Draw text with bloom effect
-    pub fn draw_text_with_bloom(&mut self, text: &str, x: f32, y: f32, color: (f32, f32, f32), font_path: &str, font_size: u32) -> Result<(), String> {
+    pub fn draw_text_with_bloom(&mut self, text: &str, x: f32, y: f32, color: (f32, f32, f32), font_path: &str, font_size: u32) -> Result<(), DashboardError> {
         // First, draw normally to main framebuffer
         self.render_text_with_font(text, x, y, 1.0, color, font_path, font_size)?;
         
@@ -2169,7 +4081,7 @@ void main() {
     }
 
     /// Draw rectangle with bloom effect
-    pub fn draw_rect_with_bloom(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32)) -> Result<(), String> {
+    pub fn draw_rect_with_bloom(&mut self, x: f32, y: f32, width: f32, height: f32, color: (f32, f32, f32)) -> Result<(), DashboardError> {
         // First, draw normally to main framebuffer
         self.fill_rect(x, y, width, height, color)?;
         
@@ -2187,7 +4099,7 @@ void main() {
     }
 
     /// Begin custom bloom element group - for complex elements
-    pub fn begin_bloom_element(&self) -> Result<(), String> {
+    pub fn begin_bloom_element(&self) -> Result<(), DashboardError> {
         if self.bloom_enabled {
             self.begin_selective_bloom_render()
         } else {
@@ -2196,7 +4108,7 @@ void main() {
     }
 
     /// End custom bloom element group
-    pub fn end_bloom_element(&mut self) -> Result<(), String> {
+    pub fn end_bloom_element(&mut self) -> Result<(), DashboardError> {
         if self.bloom_enabled {
             self.end_selective_bloom_render()?;
             self.apply_selective_bloom()
@@ -2212,7 +4124,7 @@ void main() {
     /// Cleanup text renderer before destroying OpenGL context
     fn cleanup_text_renderer(&mut self) {
         if !self.text_renderers.is_empty() {
-            log::info!("Cleaning up {} text renderer(s)...", self.text_renderers.len());
+            log::debug!("Cleaning up {} text renderer(s)...", self.text_renderers.len());
             self.text_renderers.clear(); // This will trigger Drop for all OpenGLTextRenderer instances
         }
     }
@@ -2226,11 +4138,16 @@ void main() {
             if let Some(texture) = self.bloom_texture.take() {
                 gl::DeleteTextures(1, &texture);
             }
-            if let Some(shader) = self.bloom_shader.take() {
+            for target in self.bloom_blur_targets.drain(..).chain(self.page_layers.drain(..)) {
+                target.delete();
+            }
+            for shader in [self.bloom_bright_shader.take(), self.bloom_blur_shader.take(), self.bloom_shader.take(),
+                           self.vignette_shader.take(), self.page_transition_shader.take()]
+                .into_iter().flatten() {
                 gl::DeleteProgram(shader);
             }
         }
-        log::info!("Cleaned up bloom effect resources");
+        log::info!("Cleaned up bloom, vignette and page transition resources");
     }
 }
 
@@ -2242,13 +4159,35 @@ impl Drop for GraphicsContext {
                 self.cleanup_text_renderer();
                 self.cleanup_rectangle_shader();
                 self.cleanup_bloom();
+                self.cleanup_textures();
+                
+                // Let an outstanding flip land before pulling its buffers out from under it
+                if !self.wait_for_flip(FLIP_WAIT_TIMEOUT) {
+                    log::warn!("Page flip still pending at shutdown");
+                }
                 
-                // Restore previous CRTC configuration
-                if !self.previous_crtc.is_null() {
-                    // This would restore the original display state
-                    // For now, we'll just free the saved CRTC
-                    drmModeFreeCrtc(self.previous_crtc);
+                // Hand the display back before our framebuffers go away
+                if let Some(saved) = self.previous_crtc.take() {
+                    let (drm_fd, mut connector_id) = (self.drm_fd, self.connector_id);
+                    let restored = saved.restore(|saved, mode| {
+                        let mut mode = *mode;
+                        drmModeSetCrtc(drm_fd, saved.crtc_id, saved.buffer_id, saved.x, saved.y,
+                                       &mut connector_id, 1, &mut mode)
+                    });
+                    match restored {
+                        Ok(true) => log::info!("Restored previous display configuration"),
+                        Ok(false) => log::debug!("CRTC was off before startup, nothing to restore"),
+                        Err(e) => log::warn!("{}", e),
+                    }
                 }
+                if !self.headless {
+                    if let Err(e) = self.show_cursor() {
+                        log::warn!("Failed to show cursor: {}", e);
+                    }
+                }
+                let released = self.scanout_buffers.take_all();
+                self.release_scanout_buffers(&released);
+                self.clear_framebuffer_cache();
                 
                 // Clean up EGL
                 if self.egl_display != ptr::null_mut() {
@@ -2282,29 +4221,33 @@ impl Drop for GraphicsContext {
 
 
 impl OpenGLTextRenderer {
-    unsafe fn new(font_path: &str, font_size: u32) -> Result<Self, String> {
+    unsafe fn new(font_path: &str, font_size: u32) -> Result<Self, DashboardError> {
+        let font_path = resolve_font_path(font_path);
+        let font_path = font_path.as_str();
+        
         // Initialize FreeType
         let mut ft_library: ft::FT_Library = std::ptr::null_mut();
         if ft::FT_Init_FreeType(&mut ft_library) != 0 {
-            return Err("Failed to initialize FreeType library".to_string());
+            return Err(DashboardError::Font("Failed to initialize FreeType library".to_string()));
         }
         
         // Load font face
         let mut ft_face: ft::FT_Face = std::ptr::null_mut();
-        let font_path_cstr = std::ffi::CString::new(font_path).map_err(|_| "Invalid font path")?;
+        let font_path_cstr = std::ffi::CString::new(font_path)
+            .map_err(|_| DashboardError::Font("Invalid font path".to_string()))?;
         
         let ft_error = ft::FT_New_Face(ft_library, font_path_cstr.as_ptr(), 0, &mut ft_face);
         if ft_error != 0 {
             ft::FT_Done_FreeType(ft_library);
-            return Err(format!("Failed to load font '{}': FreeType error code 0x{:02X} ({})",
-                font_path, ft_error, ft_error_description(ft_error)));
+            return Err(DashboardError::Font(format!("Failed to load font '{}': FreeType error code 0x{:02X} ({})",
+                font_path, ft_error, ft_error_description(ft_error))));
         }
         
         // Set font size
         if ft::FT_Set_Pixel_Sizes(ft_face, 0, font_size) != 0 {
             ft::FT_Done_Face(ft_face);
             ft::FT_Done_FreeType(ft_library);
-            return Err("Failed to set font size".to_string());
+            return Err(DashboardError::Font("Failed to set font size".to_string()));
         }
         
         // Create text rendering shader
@@ -2322,19 +4265,32 @@ impl OpenGLTextRenderer {
         gl::GenBuffers(1, &mut vao);
         gl::GenBuffers(1, &mut vbo);
         
-        log::info!("OpenGL text renderer initialized with FreeType + glyph caching");
-        log::info!("Font: {}, Size: {}px", font_path, font_size);
+        let atlas = GlyphAtlas::new(GLYPH_ATLAS_WIDTH, GLYPH_ATLAS_INITIAL_HEIGHT);
+        let mut atlas_texture = 0u32;
+        gl::GenTextures(1, &mut atlas_texture);
+        gl::BindTexture(gl::TEXTURE_2D, atlas_texture);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+        Self::upload_atlas(&atlas);
+        
+        log::debug!("OpenGL text renderer initialized with FreeType + glyph caching");
+        log::debug!("Font: {}, Size: {}px", font_path, font_size);
         
         Ok(OpenGLTextRenderer {
             ft_library,
             ft_face,
+            fallback_face: std::ptr::null_mut(),
             shader_program,
             vao,
             vbo,
             font_size,
-            glyph_cache: HashMap::new(),
-            projection_width: 0.0,
-            projection_height: 0.0,
+            glyph_cache: LruCache::new(DEFAULT_GLYPH_CACHE_CAPACITY),
+            atlas_texture_height: atlas.height,
+            atlas,
+            atlas_texture,
+            batch_vertices: Vec::new(),
             projection_matrix: [0.0; 16],
             projection_uniform,
             color_uniform,
@@ -2343,7 +4299,7 @@ impl OpenGLTextRenderer {
         })
     }
     
-    unsafe fn create_text_shader_program() -> Result<u32, String> {
+    unsafe fn create_text_shader_program() -> Result<u32, DashboardError> {
         let vertex_shader_source = b"
 attribute vec4 vertex; // <vec2 pos, vec2 tex>
 varying vec2 tex_coords;
@@ -2370,7 +4326,7 @@ void main() {
         // Create and compile vertex shader
         let vertex_shader = gl::CreateShader(gl::VERTEX_SHADER);
         if vertex_shader == 0 {
-            return Err("Failed to create text vertex shader".to_string());
+            return Err(DashboardError::Gl("Failed to create text vertex shader".to_string()));
         }
         
         let vertex_src_ptr = vertex_shader_source.as_ptr();
@@ -2380,13 +4336,13 @@ void main() {
         let mut compile_status = 0i32;
         gl::GetShaderiv(vertex_shader, gl::COMPILE_STATUS, &mut compile_status);
         if compile_status == 0 {
-            return Err("Text vertex shader compilation failed".to_string());
+            return Err(DashboardError::Gl("Text vertex shader compilation failed".to_string()));
         }
         
         // Create and compile fragment shader
         let fragment_shader = gl::CreateShader(gl::FRAGMENT_SHADER);
         if fragment_shader == 0 {
-            return Err("Failed to create text fragment shader".to_string());
+            return Err(DashboardError::Gl("Failed to create text fragment shader".to_string()));
         }
         
         let fragment_src_ptr = fragment_shader_source.as_ptr();
@@ -2396,13 +4352,13 @@ void main() {
         let mut compile_status = 0i32;
         gl::GetShaderiv(fragment_shader, gl::COMPILE_STATUS, &mut compile_status);
         if compile_status == 0 {
-            return Err("Text fragment shader compilation failed".to_string());
+            return Err(DashboardError::Gl("Text fragment shader compilation failed".to_string()));
         }
         
         // Create and link shader program
         let program = gl::CreateProgram();
         if program == 0 {
-            return Err("Failed to create text shader program".to_string());
+            return Err(DashboardError::Gl("Failed to create text shader program".to_string()));
         }
         
         gl::AttachShader(program, vertex_shader);
@@ -2412,29 +4368,20 @@ void main() {
         let mut link_status = 0i32;
         gl::GetProgramiv(program, gl::LINK_STATUS, &mut link_status);
         if link_status == 0 {
-            return Err("Text shader program linking failed".to_string());
+            return Err(DashboardError::Gl("Text shader program linking failed".to_string()));
         }
         
-        log::info!("Text rendering shader program created successfully!");
+        log::debug!("Text rendering shader program created successfully!");
         Ok(program)
     }
     
-    unsafe fn render_text(&mut self, text: &str, x: f32, y: f32, scale: f32, color: (f32, f32, f32), width: f32, height: f32, orientation: TextOrientation) -> Result<(), String> {
+    unsafe fn render_text(&mut self, text: &str, x: f32, y: f32, scale: f32, color: (f32, f32, f32), projection: [f32; 16], orientation: TextOrientation) -> Result<(), DashboardError> {
         // Use cached program state
         gl::UseProgram(self.shader_program);
         
-        // Only update projection matrix if dimensions changed
-        if self.projection_width != width || self.projection_height != height {
-            self.projection_width = width;
-            self.projection_height = height;
-            
-            // Calculate projection matrix once
-            self.projection_matrix = [
-                2.0/width, 0.0,         0.0, 0.0,
-                0.0,       -2.0/height, 0.0, 0.0,  // Negative Y scaling to flip coordinate system
-                0.0,       0.0,         -1.0, 0.0,
-                -1.0,      1.0,         0.0, 1.0,  // Y translation adjusted for flipped coordinates
-            ];
+        // Only upload the projection matrix if it changed (screen size or rotation)
+        if self.projection_matrix != projection {
+            self.projection_matrix = projection;
             
             // Upload to GPU using cached uniform location
             gl::UniformMatrix4fv(self.projection_uniform, 1, 0, self.projection_matrix.as_ptr());
@@ -2446,18 +4393,56 @@ void main() {
         // Set up texture uniform using cached location
         gl::Uniform1i(self.texture_uniform, 0);
         
-        // Set up vertex attributes using cached location
-        gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
-        gl::EnableVertexAttribArray(self.vertex_attr as u32);
-        gl::VertexAttribPointer(self.vertex_attr as u32, 4, gl::FLOAT, 0, 0, std::ptr::null());
+        // Every glyph comes from the atlas; uploads of newly cached glyphs keep it bound
+        gl::ActiveTexture(gl::TEXTURE0);
+        gl::BindTexture(gl::TEXTURE_2D, self.atlas_texture);
+        
+        // Collect every glyph quad first, then upload and draw them with a single call
+        let mut vertices = std::mem::take(&mut self.batch_vertices);
+        vertices.clear();
+        let layout = self.queue_text(text, x, y, scale, orientation, &mut vertices);
+        
+        if layout.is_ok() && !vertices.is_empty() {
+            // Set up vertex attributes using cached location
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                (vertices.len() * std::mem::size_of::<f32>()) as isize,
+                vertices.as_ptr() as *const std::ffi::c_void,
+                gl::DYNAMIC_DRAW,
+            );
+            gl::EnableVertexAttribArray(self.vertex_attr as u32);
+            gl::VertexAttribPointer(self.vertex_attr as u32, 4, gl::FLOAT, 0, 0, std::ptr::null());
+            gl::DrawArrays(gl::TRIANGLES, 0, (vertices.len() / 4) as i32);
+        }
+        
+        self.batch_vertices = vertices;
+        layout
+    }
+    
+    /// Lay out `text` into glyph quads appended to `vertices`, caching glyphs as needed
+    unsafe fn queue_text(&mut self, text: &str, x: f32, y: f32, scale: f32, orientation: TextOrientation,
+                         vertices: &mut Vec<f32>) -> Result<(), DashboardError> {
+        // Cache the whole string before building any quad: caching a glyph can grow or compact
+        // the atlas, which would leave the UVs of quads already queued pointing at the wrong place
+        self.preload_glyphs(text)?;
         
-        // Render each character using cached glyphs with orientation-based positioning
+        // Position each character using cached glyphs with orientation-based positioning
         match orientation {
             TextOrientation::Horizontal => {
-                // Traditional horizontal text - advance cursor in X direction
-                let mut cursor_x = x;
-                for ch in text.chars() {
-                    cursor_x += self.render_cached_character(ch, cursor_x, y, scale)?;
+                // Traditional horizontal text - advance cursor in X direction, each line one line height lower
+                let line_height = self.get_line_height(scale);
+                for (index, line) in text_lines(text).enumerate() {
+                    let line_y = y + index as f32 * line_height;
+                    let mut cursor_x = x;
+                    let mut previous = None;
+                    for ch in line.chars() {
+                        if let Some(left) = previous {
+                            cursor_x += kerning_delta(self.ft_face, self.fallback_face, left, ch) * scale;
+                        }
+                        cursor_x += self.queue_character(ch, cursor_x, line_y, scale, vertices)?;
+                        previous = Some(ch);
+                    }
                 }
             },
             TextOrientation::Vertical => {
@@ -2467,132 +4452,228 @@ void main() {
                     // For vertical text, we need to calculate the character's advance in Y direction
                     let glyph = self.get_or_cache_glyph(ch)?;
                     
-                    // Render character at current position
-                    self.render_cached_character(ch, x, cursor_y, scale)?;
+                    // Place character at current position
+                    self.queue_character(ch, x, cursor_y, scale, vertices)?;
                     
                     // Advance cursor downward by the character height plus small spacing
                     let char_height = glyph.height * scale;
                     cursor_y += char_height + scale * 2.0; // Add some spacing between characters
                 }
+            },
+            TextOrientation::RotatedCW | TextOrientation::RotatedCCW => {
+                let clockwise = matches!(orientation, TextOrientation::RotatedCW);
+                let extent = if clockwise {
+                    self.calculate_text_height(text, scale, TextOrientation::Horizontal)?
+                } else {
+                    self.calculate_text_width(text, scale, TextOrientation::Horizontal)?
+                };
+                let start = vertices.len();
+                self.queue_text(text, 0.0, 0.0, scale, TextOrientation::Horizontal, vertices)?;
+                rotate_text_quads(&mut vertices[start..], clockwise, x, y, extent);
             }
         }
         
         Ok(())
     }
     
-    unsafe fn get_or_cache_glyph(&mut self, ch: char) -> Result<CachedGlyph, String> {
+    unsafe fn get_or_cache_glyph(&mut self, ch: char) -> Result<CachedGlyph, DashboardError> {
         // Check if glyph is already cached
         if let Some(cached_glyph) = self.glyph_cache.get(&ch) {
             return Ok(cached_glyph.clone());
         }
         
-        // Load character glyph
-        if ft::FT_Load_Char(self.ft_face, ch as u64, ft::FT_LOAD_RENDER as i32) != 0 {
-            return Err(format!("Failed to load character: {}", ch));
+        // Load character glyph, from the fallback font if only that one has it
+        let face = glyph_face(self.ft_face, self.fallback_face, ch);
+        if ft::FT_Load_Char(face, ch as u64, ft::FT_LOAD_RENDER as i32) != 0 {
+            return Err(DashboardError::Font(format!("Failed to load character: {}", ch)));
         }
         
         // Get glyph slot
-        let glyph = (*self.ft_face).glyph;
-        
-        // Create a dedicated texture for this glyph
-        let mut texture_id = 0u32;
-        gl::GenTextures(1, &mut texture_id);
-        gl::BindTexture(gl::TEXTURE_2D, texture_id);
-        
-        // Set pixel alignment to 1 byte to handle FreeType's bitmap format
-        gl::PixelStorei(gl::UNPACK_ALIGNMENT, 1);
-        
-        gl::TexImage2D(
-            gl::TEXTURE_2D,
-            0,
-            gl::RED as i32,
-            (*glyph).bitmap.width as i32,
-            (*glyph).bitmap.rows as i32,
-            0,
-            gl::RED,
-            gl::UNSIGNED_BYTE,
-            (*glyph).bitmap.buffer as *const std::ffi::c_void,
-        );
-        
-        // Reset pixel alignment to default
-        gl::PixelStorei(gl::UNPACK_ALIGNMENT, 4);
+        let glyph = (*face).glyph;
+        let bitmap = &(*glyph).bitmap;
+        let (width, height) = (bitmap.width as u32, bitmap.rows as u32);
+        let pitch = bitmap.pitch.unsigned_abs() as usize;
+        let pixels: &[u8] = if width == 0 || height == 0 || bitmap.buffer.is_null() {
+            &[]
+        } else {
+            std::slice::from_raw_parts(bitmap.buffer, pitch * (height as usize - 1) + width as usize)
+        };
         
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
-        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+        // Pack into the atlas. Evicted glyphs leave holes, so when it is full compact it before
+        // giving up. After growth or compaction the whole texture is re-created.
+        let mut repacked = false;
+        let (atlas_x, atlas_y) = match self.atlas.insert(width, height, pixels, pitch) {
+            Ok(position) => position,
+            Err(e) => {
+                self.compact_atlas().map_err(|_| e)?;
+                repacked = true;
+                self.atlas.insert(width, height, pixels, pitch)?
+            }
+        };
+        gl::BindTexture(gl::TEXTURE_2D, self.atlas_texture);
+        if repacked || self.atlas.height != self.atlas_texture_height {
+            log::debug!("Glyph atlas for {}px font now {}x{}", self.font_size, self.atlas.width, self.atlas.height);
+            Self::upload_atlas(&self.atlas);
+            self.atlas_texture_height = self.atlas.height;
+        } else if !pixels.is_empty() {
+            gl::PixelStorei(gl::UNPACK_ALIGNMENT, 1);
+            let packed: Vec<u8> = (0..height as usize)
+                .flat_map(|row| pixels[row * pitch..row * pitch + width as usize].iter().copied())
+                .collect();
+            gl::TexSubImage2D(
+                gl::TEXTURE_2D, 0,
+                atlas_x as i32, atlas_y as i32, width as i32, height as i32,
+                gl::RED, gl::UNSIGNED_BYTE,
+                packed.as_ptr() as *const std::ffi::c_void,
+            );
+            gl::PixelStorei(gl::UNPACK_ALIGNMENT, 4);
+        }
         
         // Cache the glyph data
         let cached_glyph = CachedGlyph {
-            texture_id,
-            width: (*glyph).bitmap.width as f32,
-            height: (*glyph).bitmap.rows as f32,
+            atlas_x,
+            atlas_y,
+            width: width as f32,
+            height: height as f32,
             bearing_x: (*glyph).bitmap_left as f32,
             bearing_y: (*glyph).bitmap_top as f32,
             advance: ((*glyph).advance.x >> 6) as f32,
         };
         
-        self.glyph_cache.insert(ch, cached_glyph.clone());
+        let evicted = self.glyph_cache.insert(ch, cached_glyph.clone());
+        if !evicted.is_empty() {
+            log::debug!("Glyph cache for {}px font full, evicted {} glyph(s)", self.font_size, evicted.len());
+        }
         Ok(cached_glyph)
     }
     
-    unsafe fn render_cached_character(&mut self, ch: char, x: f32, y: f32, scale: f32) -> Result<f32, String> {
+    /// Render and upload every character of `chars` that isn't cached yet, so text using
+    /// them later doesn't stall on FreeType and texture uploads in the middle of a frame
+    pub fn preload_glyphs(&mut self, chars: &str) -> Result<(), DashboardError> {
+        for ch in chars.chars().filter(|ch| !ch.is_control()) {
+            if !self.glyph_cache.contains(&ch) {
+                unsafe { self.get_or_cache_glyph(ch)?; }
+            }
+        }
+        Ok(())
+    }
+    
+    pub fn is_glyph_cached(&self, ch: char) -> bool {
+        self.glyph_cache.contains(&ch)
+    }
+    
+    /// Re-pack the atlas with only the glyphs still cached, reclaiming evicted glyphs' space
+    fn compact_atlas(&mut self) -> Result<(), DashboardError> {
+        let mut glyphs: Vec<&mut CachedGlyph> = self.glyph_cache.values_mut().collect();
+        let mut rects: Vec<(u32, u32, u32, u32)> = glyphs.iter()
+            .map(|glyph| (glyph.atlas_x, glyph.atlas_y, glyph.width as u32, glyph.height as u32))
+            .collect();
+        self.atlas = self.atlas.repack(&mut rects)?;
+        for (glyph, rect) in glyphs.iter_mut().zip(rects) {
+            glyph.atlas_x = rect.0;
+            glyph.atlas_y = rect.1;
+        }
+        Ok(())
+    }
+    
+    /// Use the font at `font_path` for characters this renderer's font does not have, replacing
+    /// any previous fallback. Glyphs are re-rendered since missing ones were cached as .notdef.
+    pub fn set_fallback_font(&mut self, font_path: &str) -> Result<(), DashboardError> {
+        let font_path = resolve_font_path(font_path);
+        let font_path = font_path.as_str();
+        unsafe {
+            let path = std::ffi::CString::new(font_path)
+                .map_err(|_| DashboardError::Font("Invalid font path".to_string()))?;
+            let mut face: ft::FT_Face = std::ptr::null_mut();
+            let ft_error = ft::FT_New_Face(self.ft_library, path.as_ptr(), 0, &mut face);
+            if ft_error != 0 {
+                return Err(DashboardError::Font(format!("Failed to load fallback font '{}': FreeType error code 0x{:02X} ({})",
+                    font_path, ft_error, ft_error_description(ft_error))));
+            }
+            if ft::FT_Set_Pixel_Sizes(face, 0, self.font_size) != 0 {
+                ft::FT_Done_Face(face);
+                return Err(DashboardError::Font(format!("Failed to set fallback font size {}", self.font_size)));
+            }
+            
+            if !self.fallback_face.is_null() {
+                ft::FT_Done_Face(self.fallback_face);
+            }
+            self.fallback_face = face;
+        }
+        
+        self.glyph_cache.clear();
+        self.atlas = GlyphAtlas::new(GLYPH_ATLAS_WIDTH, GLYPH_ATLAS_INITIAL_HEIGHT);
+        // Forces a full texture upload with the next cached glyph
+        self.atlas_texture_height = 0;
+        Ok(())
+    }
+    
+    /// Limit how many glyphs stay cached; the least recently used ones are dropped beyond that
+    pub fn set_glyph_cache_capacity(&mut self, capacity: usize) {
+        let evicted = self.glyph_cache.set_capacity(capacity);
+        if !evicted.is_empty() {
+            log::debug!("Glyph cache for {}px font reduced to {}, evicted {} glyph(s)",
+                       self.font_size, capacity, evicted.len());
+        }
+    }
+    
+    /// (Re)allocate the atlas texture at the atlas size and upload all of its pixels.
+    /// Expects the atlas texture to be bound.
+    unsafe fn upload_atlas(atlas: &GlyphAtlas) {
+        gl::PixelStorei(gl::UNPACK_ALIGNMENT, 1);
+        gl::TexImage2D(
+            gl::TEXTURE_2D, 0, gl::RED as i32,
+            atlas.width as i32, atlas.height as i32, 0,
+            gl::RED, gl::UNSIGNED_BYTE,
+            atlas.pixels.as_ptr() as *const std::ffi::c_void,
+        );
+        gl::PixelStorei(gl::UNPACK_ALIGNMENT, 4);
+    }
+    
+    /// Append the quad for one character at the cursor and return the cursor advance
+    unsafe fn queue_character(&mut self, ch: char, x: f32, y: f32, scale: f32, vertices: &mut Vec<f32>) -> Result<f32, DashboardError> {
         // Get cached glyph (or create if not cached)
         let glyph = self.get_or_cache_glyph(ch)?;
         
-        // Bind the glyph's texture
-        gl::ActiveTexture(gl::TEXTURE0);
-        gl::BindTexture(gl::TEXTURE_2D, glyph.texture_id);
-        
-        // Calculate quad vertices
-        let w = glyph.width * scale;
-        let h = glyph.height * scale;
-        let xrel = x + glyph.bearing_x * scale;
-        
-        // Get font ascender to convert from top-of-line to baseline coordinates
-        let face_ref = &*self.ft_face;
-        let ascender = face_ref.size.as_ref().unwrap().metrics.ascender as f32 / 64.0 * scale;
-        
-        // Calculate y position: y is top of line, so add ascender to get baseline, then subtract bearing_y
-        let yrel = y + ascender - glyph.bearing_y * scale;
-        
-        // Create quad vertices (x, y, tex_x, tex_y)
-        let vertices: [f32; 24] = [
-            xrel,     yrel + h, 0.0, 1.0,  // Top-left corner, tex coords (0,1) - flipped V
-            xrel,     yrel,     0.0, 0.0,  // Bottom-left corner, tex coords (0,0) - flipped V
-            xrel + w, yrel,     1.0, 0.0,  // Bottom-right corner, tex coords (1,0) - flipped V
+        // Spaces and other blank glyphs only advance the cursor
+        if glyph.width > 0.0 && glyph.height > 0.0 {
+            let xrel = x + glyph.bearing_x * scale;
             
-            xrel,     yrel + h, 0.0, 1.0,  // Top-left corner, tex coords (0,1) - flipped V
-            xrel + w, yrel,     1.0, 0.0,  // Bottom-right corner, tex coords (1,0) - flipped V
-            xrel + w, yrel + h, 1.0, 1.0,  // Top-right corner, tex coords (1,1) - flipped V
-        ];
-        
-        // Upload vertex data
-        gl::BufferData(
-            gl::ARRAY_BUFFER,
-            (vertices.len() * std::mem::size_of::<f32>()) as isize,
-            vertices.as_ptr() as *const std::ffi::c_void,
-            gl::STATIC_DRAW,
-        );
-        
-        // Render quad
-        gl::DrawArrays(gl::TRIANGLES, 0, 6);
+            // Get font ascender to convert from top-of-line to baseline coordinates
+            let face_ref = &*self.ft_face;
+            let ascender = face_ref.size.as_ref().unwrap().metrics.ascender as f32 / 64.0 * scale;
+            
+            // Calculate y position: y is top of line, so add ascender to get baseline, then subtract bearing_y
+            let yrel = y + ascender - glyph.bearing_y * scale;
+            
+            let uv = self.atlas.uv_rect(glyph.atlas_x, glyph.atlas_y, glyph.width, glyph.height);
+            push_glyph_quad(vertices, xrel, yrel, glyph.width * scale, glyph.height * scale, uv);
+        }
         
         // Return advance for next character
         Ok(glyph.advance * scale)
     }
     
     /// Calculate the total width of a text string with the current font and scale
-    unsafe fn calculate_text_width(&mut self, text: &str, scale: f32, orientation: TextOrientation) -> Result<f32, String> {
+    unsafe fn calculate_text_width(&mut self, text: &str, scale: f32, orientation: TextOrientation) -> Result<f32, DashboardError> {
         match orientation {
             TextOrientation::Horizontal => {
-                // For horizontal text, width is the sum of character advances
-                let mut total_width = 0.0;
-                for ch in text.chars() {
-                    let glyph = self.get_or_cache_glyph(ch)?;
-                    total_width += glyph.advance * scale;
+                // For horizontal text, width is the sum of character advances of the widest line
+                let mut max_width: f32 = 0.0;
+                for line in text_lines(text) {
+                    let mut line_width = 0.0;
+                    let mut previous = None;
+                    for ch in line.chars() {
+                        let glyph = self.get_or_cache_glyph(ch)?;
+                        line_width += glyph.advance * scale;
+                        if let Some(left) = previous {
+                            line_width += kerning_delta(self.ft_face, self.fallback_face, left, ch) * scale;
+                        }
+                        previous = Some(ch);
+                    }
+                    max_width = max_width.max(line_width);
                 }
-                Ok(total_width)
+                Ok(max_width)
             },
             TextOrientation::Vertical => {
                 // For vertical text, width is the maximum character width
@@ -2605,19 +4686,26 @@ void main() {
                     }
                 }
                 Ok(max_width)
+            },
+            // Rotated text is as wide as the horizontal layout is tall
+            TextOrientation::RotatedCW | TextOrientation::RotatedCCW => {
+                self.calculate_text_height(text, scale, TextOrientation::Horizontal)
             }
         }
     }
     
     /// Calculate the maximum height of a text string with the current font and scale
-    unsafe fn calculate_text_height(&mut self, text: &str, scale: f32, orientation: TextOrientation) -> Result<f32, String> {
+    unsafe fn calculate_text_height(&mut self, text: &str, scale: f32, orientation: TextOrientation) -> Result<f32, DashboardError> {
         match orientation {
             TextOrientation::Horizontal => {
-                // For horizontal text, height is the maximum character height
+                // For horizontal text, height is the maximum character height of the last line,
+                // plus a line height for each line above it
+                let line_count = text_lines(text).count();
+                let last_line = text_lines(text).last().unwrap_or("");
                 let mut max_height = 0.0;
                 let mut max_descent = 0.0;
                 
-                for ch in text.chars() {
+                for ch in last_line.chars() {
                     let glyph = self.get_or_cache_glyph(ch)?;
                     let char_height = glyph.bearing_y * scale;
                     let char_descent = (glyph.height - glyph.bearing_y) * scale;
@@ -2630,7 +4718,7 @@ void main() {
                     }
                 }
                 
-                Ok(max_height + max_descent)
+                Ok(stacked_lines_height(line_count, max_height + max_descent, self.get_line_height(scale)))
             },
             TextOrientation::Vertical => {
                 // For vertical text, height is the sum of character heights plus spacing
@@ -2640,12 +4728,15 @@ void main() {
                     total_height += glyph.height * scale + scale * 2.0; // Add spacing
                 }
                 Ok(total_height)
+            },
+            TextOrientation::RotatedCW | TextOrientation::RotatedCCW => {
+                self.calculate_text_width(text, scale, TextOrientation::Horizontal)
             }
         }
     }
     
     /// Calculate both width and height of a text string (convenience function)
-    unsafe fn calculate_text_dimensions(&mut self, text: &str, scale: f32, orientation: TextOrientation) -> Result<(f32, f32), String> {
+    unsafe fn calculate_text_dimensions(&mut self, text: &str, scale: f32, orientation: TextOrientation) -> Result<(f32, f32), DashboardError> {
         let width = self.calculate_text_width(text, scale, orientation)?;
         let height = self.calculate_text_height(text, scale, orientation)?;
         Ok((width, height))
@@ -2669,6 +4760,9 @@ void main() {
 impl Drop for OpenGLTextRenderer {
     fn drop(&mut self) {
         unsafe {
+            if !self.fallback_face.is_null() {
+                ft::FT_Done_Face(self.fallback_face);
+            }
             if !self.ft_face.is_null() {
                 ft::FT_Done_Face(self.ft_face);
             }
@@ -2676,11 +4770,947 @@ impl Drop for OpenGLTextRenderer {
                 ft::FT_Done_FreeType(self.ft_library);
             }
             
-            // Clean up cached glyph textures
-            for cached_glyph in self.glyph_cache.values() {
-                gl::DeleteTextures(1, &cached_glyph.texture_id);
-            }
+            // Clean up the glyph atlas texture
+            gl::DeleteTextures(1, &self.atlas_texture);
             // Note: VAO/VBO cleanup would need proper OpenGL context
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Applies a column-major projection to a 2D point, returning NDC x/y
+    fn project(matrix: &[f32; 16], x: f32, y: f32) -> (f32, f32) {
+        (
+            matrix[0] * x + matrix[4] * y + matrix[12],
+            matrix[1] * x + matrix[5] * y + matrix[13],
+        )
+    }
+
+    fn assert_maps_to(matrix: &[f32; 16], point: (f32, f32), expected: (f32, f32)) {
+        let (x, y) = project(matrix, point.0, point.1);
+        assert!((x - expected.0).abs() < 1e-6 && (y - expected.1).abs() < 1e-6,
+                "{:?} mapped to ({}, {}), expected {:?}", point, x, y, expected);
+    }
+
+    #[test]
+    fn test_projection_matrix_no_rotation() {
+        let matrix = projection_matrix(800.0, 480.0, Rotation::Deg0, Mirror::default());
+        assert_maps_to(&matrix, (0.0, 0.0), (-1.0, 1.0));      // Top-left
+        assert_maps_to(&matrix, (800.0, 480.0), (1.0, -1.0));  // Bottom-right
+    }
+
+    #[test]
+    fn test_projection_matrix_rotation_180() {
+        let matrix = projection_matrix(800.0, 480.0, Rotation::Deg180, Mirror::default());
+        assert_maps_to(&matrix, (0.0, 0.0), (1.0, -1.0));
+        assert_maps_to(&matrix, (800.0, 0.0), (-1.0, -1.0));
+        assert_maps_to(&matrix, (0.0, 480.0), (1.0, 1.0));
+        assert_maps_to(&matrix, (800.0, 480.0), (-1.0, 1.0));
+        assert_maps_to(&matrix, (400.0, 240.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_projection_matrix_rotation_90() {
+        // Logical 480x800 portrait on an 800x480 mode: logical top-left lands top-right
+        let matrix = projection_matrix(480.0, 800.0, Rotation::Deg90, Mirror::default());
+        assert_maps_to(&matrix, (0.0, 0.0), (1.0, 1.0));
+        assert_maps_to(&matrix, (480.0, 0.0), (1.0, -1.0));
+        assert_maps_to(&matrix, (0.0, 800.0), (-1.0, 1.0));
+
+        let matrix = projection_matrix(480.0, 800.0, Rotation::Deg270, Mirror::default());
+        assert_maps_to(&matrix, (0.0, 0.0), (-1.0, -1.0));
+        assert_maps_to(&matrix, (480.0, 0.0), (-1.0, 1.0));
+    }
+
+    #[test]
+    fn test_projection_matrix_mirror() {
+        let horizontal = Mirror { horizontal: true, vertical: false };
+        let vertical = Mirror { horizontal: false, vertical: true };
+
+        // Horizontal flip puts x = 0 on the right edge, leaving y alone
+        let matrix = projection_matrix(800.0, 480.0, Rotation::Deg0, horizontal);
+        assert_maps_to(&matrix, (0.0, 0.0), (1.0, 1.0));
+        assert_maps_to(&matrix, (800.0, 480.0), (-1.0, -1.0));
+        assert_maps_to(&matrix, (400.0, 240.0), (0.0, 0.0));
+
+        let matrix = projection_matrix(800.0, 480.0, Rotation::Deg0, vertical);
+        assert_maps_to(&matrix, (0.0, 0.0), (-1.0, -1.0));
+
+        // Both flips are the same picture as a 180° turn
+        let both = projection_matrix(800.0, 480.0, Rotation::Deg0, Mirror { horizontal: true, vertical: true });
+        assert_eq!(both, projection_matrix(800.0, 480.0, Rotation::Deg180, Mirror::default()));
+
+        // Mirroring happens before the rotation: a mirrored logical top-left lands where the
+        // unmirrored top-right does, whichever way the panel is turned
+        for rotation in [Rotation::Deg90, Rotation::Deg180, Rotation::Deg270] {
+            let (width, height) = if rotation.is_transposed() { (480.0, 800.0) } else { (800.0, 480.0) };
+            let plain = projection_matrix(width, height, rotation, Mirror::default());
+            let mirrored = projection_matrix(width, height, rotation, horizontal);
+            assert_maps_to(&mirrored, (0.0, 0.0), project(&plain, width, 0.0));
+            assert_maps_to(&mirrored, (0.0, height), project(&plain, width, height));
+            let mirrored = projection_matrix(width, height, rotation, vertical);
+            assert_maps_to(&mirrored, (0.0, 0.0), project(&plain, 0.0, height));
+        }
+
+        // Page transitions slide the other way across the panel
+        assert_eq!(logical_x_direction(Rotation::Deg90, horizontal), (0.0, 1.0));
+        assert_eq!(Mirror::from_name("hv"), Ok(Mirror { horizontal: true, vertical: true }));
+        assert!(Mirror::from_name("x").is_err());
+    }
+
+    fn test_image_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("niva_framebuffer_{}_{}", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_save_rgba_pixels_round_trip() {
+        // 2x2, bottom row first as glReadPixels returns it: bottom semi-transparent red, top opaque blue
+        let pixels = [
+            255, 0, 0, 128,  255, 0, 0, 128,
+            0, 0, 255, 255,  0, 0, 255, 255,
+        ];
+
+        let path = test_image_path("alpha.png");
+        save_rgba_pixels(&path, &pixels, 2, 2, FramebufferFormat::Png, true).unwrap();
+        let image = image::open(&path).unwrap().to_rgba8();
+        assert_eq!(image.get_pixel(0, 0).0, [0, 0, 255, 255]);   // Flipped: top row is blue
+        assert_eq!(image.get_pixel(1, 1).0, [255, 0, 0, 128]);   // Alpha preserved
+        std::fs::remove_file(&path).ok();
+
+        let path = test_image_path("opaque.bmp");
+        save_rgba_pixels(&path, &pixels, 2, 2, FramebufferFormat::Bmp, false).unwrap();
+        let image = image::open(&path).unwrap();
+        assert!(!image.color().has_alpha());
+        assert_eq!(image.to_rgb8().get_pixel(0, 1).0, [255, 0, 0]);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_save_rgba_pixels_jpeg() {
+        let pixels = [200u8, 100, 50, 255].repeat(16 * 16);
+        let path = test_image_path("solid.jpg");
+        save_rgba_pixels(&path, &pixels, 16, 16, FramebufferFormat::Jpeg, false).unwrap();
+        // Lossy, but a solid color survives closely
+        let pixel = image::open(&path).unwrap().to_rgb8().get_pixel(8, 8).0;
+        for (channel, expected) in pixel.iter().zip([200u8, 100, 50]) {
+            assert!((*channel as i32 - expected as i32).abs() <= 3, "Got {:?}", pixel);
+        }
+        std::fs::remove_file(&path).ok();
+
+        assert!(save_rgba_pixels(&path, &pixels, 16, 16, FramebufferFormat::Jpeg, true).is_err());
+    }
+
+    #[test]
+    fn test_framebuffer_slots_flips_stay_bounded() {
+        let mut slots = FramebufferSlots::default();
+        assert!(slots.displayed(1).is_empty());
+
+        for fb in 2..1000 {
+            assert!(slots.flip_queued(fb).is_empty());  // Old one still on screen until the flip lands
+            assert_eq!((slots.current, slots.previous), (Some(fb), Some(fb - 1)));
+            assert_eq!(slots.flip_completed(), vec![fb - 1]);
+            assert_eq!((slots.current, slots.previous), (Some(fb), None));
+        }
+
+        assert_eq!(slots.take_all(), vec![999]);
+        assert_eq!((slots.current, slots.previous), (None, None));
+    }
+
+    #[test]
+    fn test_framebuffer_slots_missed_completion() {
+        let mut slots = FramebufferSlots::default();
+        for fb in 1..=2 {
+            slots.flip_queued(fb);
+        }
+        // No completion seen for the flip to 2; by the next flip 1 is long off screen
+        assert_eq!(slots.flip_queued(3), vec![1]);
+        assert_eq!((slots.current, slots.previous), (Some(3), Some(2)));
+    }
+
+    #[test]
+    fn test_framebuffer_slots_modeset_fallback() {
+        let mut slots = FramebufferSlots::default();
+        for fb in 1..=2 {
+            slots.flip_queued(fb);
+        }
+
+        // Flip failed, modeset succeeded: both older FBs are off screen
+        let mut released = slots.displayed(3);
+        released.sort();
+        assert_eq!(released, vec![1, 2]);
+
+        // Flip and modeset both failed: the new FB is removed, the shown one kept
+        assert_eq!(slots.discard(4), vec![4]);
+        assert_eq!((slots.current, slots.previous), (Some(3), None));
+        assert_eq!(slots.take_all(), vec![3]);
+    }
+
+    #[test]
+    fn test_framebuffer_cache_reuses_per_buffer() {
+        let mut cache = FramebufferCache::default();
+        let mut next_fb = 100;
+        // GBM cycling three buffer objects for many frames
+        for frame in 0..1000u32 {
+            let handle = frame % 3 + 1;
+            let fb = cache.get_or_create(handle, || -> Result<u32, i32> {
+                next_fb += 1;
+                Ok(next_fb)
+            }).unwrap();
+            assert_eq!(fb, 100 + handle);
+        }
+        assert_eq!(cache.stats(), FramebufferStats { created: 3, destroyed: 0, live: 3 });
+
+        let mut removed = cache.clear();
+        removed.sort();
+        assert_eq!(removed, vec![101, 102, 103]);
+        assert_eq!(cache.stats(), FramebufferStats { created: 3, destroyed: 3, live: 0 });
+    }
+
+    #[test]
+    fn test_framebuffer_cache_failed_create_not_cached() {
+        let mut cache = FramebufferCache::default();
+        assert_eq!(cache.get_or_create(1, || Err(-22)), Err(-22));
+        assert_eq!(cache.stats().live, 0);
+        assert_eq!(cache.get_or_create(1, || Ok::<u32, i32>(7)), Ok(7));
+        assert_eq!(cache.stats().created, 1);
+    }
+
+    #[test]
+    fn test_saved_crtc_restore() {
+        // The console's CRTC as drmModeGetCrtc reports it
+        let mut crtc: DrmModeCrtc = unsafe { std::mem::zeroed() };
+        crtc.crtc_id = 87;
+        crtc.buffer_id = 12;
+        crtc.mode_valid = 1;
+        crtc.mode.hdisplay = 800;
+        crtc.mode.vdisplay = 480;
+        let saved = SavedCrtc::from_crtc(&crtc);
+
+        let mut calls = Vec::new();
+        let restored = saved.restore(|saved, mode| {
+            calls.push((saved.crtc_id, saved.buffer_id, mode.hdisplay, mode.vdisplay));
+            0
+        });
+        assert!(matches!(restored, Ok(true)));
+        assert_eq!(calls, vec![(87, 12, 800, 480)]);
+
+        // A failed modeset is reported, not ignored
+        let failed = saved.restore(|_, _| -16);
+        assert_eq!(failed.unwrap_err().to_string(), "Failed to restore CRTC 87: error -16");
+
+        // The CRTC was off (no console): leave it alone
+        crtc.buffer_id = 0;
+        let off = SavedCrtc::from_crtc(&crtc);
+        assert!(matches!(off.restore(|_, _| panic!("CRTC was off")), Ok(false)));
+    }
+
+    // Point-in-triangle test over the quad's two triangles
+    fn quad_contains(vertices: &[f32; 12], px: f32, py: f32) -> bool {
+        vertices.chunks_exact(6).any(|t| {
+            let sign = |ax: f32, ay: f32, bx: f32, by: f32| (px - bx) * (ay - by) - (ax - bx) * (py - by);
+            let d1 = sign(t[0], t[1], t[2], t[3]);
+            let d2 = sign(t[2], t[3], t[4], t[5]);
+            let d3 = sign(t[4], t[5], t[0], t[1]);
+            let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+            let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+            !(has_neg && has_pos)
+        })
+    }
+
+    #[test]
+    fn test_line_quad_diagonal() {
+        let vertices = line_quad_vertices(100.0, 100.0, 300.0, 300.0, 4.0).unwrap();
+        // Midpoint and points just inside the half-thickness are covered
+        assert!(quad_contains(&vertices, 200.0, 200.0));
+        assert!(quad_contains(&vertices, 201.0, 199.0));  // 1.41 px off the centre line
+        // 2.83 px off the centre line is outside a 4 px line
+        assert!(!quad_contains(&vertices, 202.0, 198.0));
+        // Square ends stop at the endpoints
+        assert!(!quad_contains(&vertices, 99.0, 99.0));
+        assert!(!quad_contains(&vertices, 301.0, 301.0));
+    }
+
+    #[test]
+    fn test_line_quad_horizontal_matches_rect() {
+        // A horizontal line covers the same area as fill_rect(10, 19, 90, 2)
+        let vertices = line_quad_vertices(10.0, 20.0, 100.0, 20.0, 2.0).unwrap();
+        let mut xs: Vec<f32> = vertices.iter().step_by(2).copied().collect();
+        let mut ys: Vec<f32> = vertices.iter().skip(1).step_by(2).copied().collect();
+        xs.sort_by(f32::total_cmp);
+        ys.sort_by(f32::total_cmp);
+        assert_eq!((xs[0], xs[5]), (10.0, 100.0));
+        assert_eq!((ys[0], ys[5]), (19.0, 21.0));
+
+        assert!(line_quad_vertices(5.0, 5.0, 5.0, 5.0, 2.0).is_none());
+        assert!(line_quad_vertices(0.0, 0.0, 10.0, 0.0, 0.0).is_none());
+    }
+
+    #[test]
+    fn test_polygon_fan_triangle_ndc() {
+        // Needle-like triangle: tip at the top centre, base along the bottom edge
+        let vertices = polygon_fan_vertices(&[(400.0, 0.0), (800.0, 480.0), (0.0, 480.0)]).unwrap();
+        assert_eq!(vertices.len(), 6);
+        let matrix = projection_matrix(800.0, 480.0, Rotation::Deg0, Mirror::default());
+        let expected = [(0.0, 1.0), (1.0, -1.0), (-1.0, -1.0)];
+        for (point, expected) in vertices.chunks_exact(2).zip(expected) {
+            assert_maps_to(&matrix, (point[0], point[1]), expected);
+        }
+
+        assert!(polygon_fan_vertices(&[(0.0, 0.0), (10.0, 10.0)]).is_none());
+    }
+
+    #[test]
+    fn test_circle_fan_vertex_count() {
+        let vertices = arc_fan_vertices(50.0, 50.0, 10.0, 0.0, 360.0, CIRCLE_SEGMENTS);
+        assert_eq!(vertices.len() / 2, CIRCLE_SEGMENTS + 2);  // Centre + closed rim
+        assert_eq!((vertices[0], vertices[1]), (50.0, 50.0));
+        // Rim starts and ends at the same point, all at the radius
+        let rim: Vec<&[f32]> = vertices[2..].chunks_exact(2).collect();
+        assert!((rim[0][0] - rim[CIRCLE_SEGMENTS][0]).abs() < 1e-4 && (rim[0][1] - rim[CIRCLE_SEGMENTS][1]).abs() < 1e-4);
+        for point in rim {
+            let r = ((point[0] - 50.0).powi(2) + (point[1] - 50.0).powi(2)).sqrt();
+            assert!((r - 10.0).abs() < 1e-4);
+        }
+
+        assert_eq!(arc_fan_vertices(0.0, 0.0, 5.0, 0.0, 90.0, 16).len() / 2, 18);
+    }
+
+    #[test]
+    fn test_ring_strip_vertex_count() {
+        let vertices = arc_strip_vertices(0.0, 0.0, 8.0, 12.0, 0.0, std::f32::consts::TAU, CIRCLE_SEGMENTS + 1);
+        assert_eq!(vertices.len() / 2, (CIRCLE_SEGMENTS + 1) * 2);
+        for (i, point) in vertices.chunks_exact(2).enumerate() {
+            let r = (point[0].powi(2) + point[1].powi(2)).sqrt();
+            let expected = if i % 2 == 0 { 8.0 } else { 12.0 };
+            assert!((r - expected).abs() < 1e-4, "Vertex {} at radius {}", i, r);
+        }
+        // Closed: last pair coincides with the first
+        let n = vertices.len();
+        assert!((vertices[0] - vertices[n - 4]).abs() < 1e-4 && (vertices[1] - vertices[n - 3]).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_load_rgba_image_round_trip() {
+        // 3x2 with distinct pixels, including a translucent one
+        let mut generated = image::RgbaImage::new(3, 2);
+        for (x, y, pixel) in generated.enumerate_pixels_mut() {
+            *pixel = image::Rgba([x as u8 * 100, y as u8 * 200, 50, if x == 2 { 128 } else { 255 }]);
+        }
+        let path = test_image_path("texture.png");
+        generated.save(&path).unwrap();
+
+        let (width, height, pixels) = load_rgba_image(&path).unwrap();
+        assert_eq!((width, height), (3, 2));
+        assert_eq!(pixels, generated.into_raw());
+        assert_eq!(&pixels[0..4], &[0, 0, 50, 255]);      // Top-left first
+        assert_eq!(&pixels[20..24], &[200, 200, 50, 128]); // Bottom-right last
+        std::fs::remove_file(&path).ok();
+
+        assert!(load_rgba_image(&test_image_path("missing.png")).is_err());
+    }
+
+    // Color of the gradient vertex at (x, y)
+    fn gradient_corner_color(vertices: &[f32], x: f32, y: f32) -> (f32, f32, f32) {
+        let vertex = vertices.chunks_exact(GRADIENT_VERTEX_FLOATS)
+            .find(|v| v[0] == x && v[1] == y)
+            .expect("corner not found");
+        (vertex[2], vertex[3], vertex[4])
+    }
+
+    #[test]
+    fn test_gradient_corner_colors() {
+        let top = (1.0, 0.0, 0.0);
+        let bottom = (0.0, 0.0, 1.0);
+
+        let vertical = gradient_quad_vertices(10.0, 20.0, 100.0, 50.0, top, bottom, GradientDirection::Vertical);
+        assert_eq!(gradient_corner_color(&vertical, 10.0, 20.0), top);
+        assert_eq!(gradient_corner_color(&vertical, 110.0, 20.0), top);
+        assert_eq!(gradient_corner_color(&vertical, 10.0, 70.0), bottom);
+        assert_eq!(gradient_corner_color(&vertical, 110.0, 70.0), bottom);
+
+        let horizontal = gradient_quad_vertices(10.0, 20.0, 100.0, 50.0, top, bottom, GradientDirection::Horizontal);
+        assert_eq!(gradient_corner_color(&horizontal, 10.0, 20.0), top);
+        assert_eq!(gradient_corner_color(&horizontal, 10.0, 70.0), top);
+        assert_eq!(gradient_corner_color(&horizontal, 110.0, 20.0), bottom);
+        assert_eq!(gradient_corner_color(&horizontal, 110.0, 70.0), bottom);
+
+        // Shared corners of the two triangles agree
+        for v in vertical.chunks_exact(GRADIENT_VERTEX_FLOATS) {
+            assert_eq!((v[2], v[3], v[4]), gradient_corner_color(&vertical, v[0], v[1]));
+        }
+    }
+
+    #[test]
+    fn test_intersect_rects_nested() {
+        let outer = (100.0, 50.0, 200.0, 100.0);
+        assert_eq!(intersect_rects(outer, (150.0, 0.0, 500.0, 80.0)), (150.0, 50.0, 150.0, 30.0));
+        assert_eq!(intersect_rects(outer, (120.0, 60.0, 10.0, 10.0)), (120.0, 60.0, 10.0, 10.0));
+        // Disjoint clips leave nothing visible
+        let (_, _, width, height) = intersect_rects(outer, (400.0, 300.0, 10.0, 10.0));
+        assert_eq!((width, height), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_scissor_box_flips_y() {
+        // Top-left 100x50 of an 800x480 screen is the top of the window, i.e. y = 430..480
+        assert_eq!(scissor_box((0.0, 0.0, 100.0, 50.0), 800.0, 480.0, 800, 480, Rotation::Deg0, Mirror::default()), (0, 430, 100, 50));
+        assert_eq!(scissor_box((10.0, 400.0, 20.0, 80.0), 800.0, 480.0, 800, 480, Rotation::Deg0, Mirror::default()), (10, 0, 20, 80));
+        // Clamped to the window
+        assert_eq!(scissor_box((-50.0, -50.0, 100.0, 100.0), 800.0, 480.0, 800, 480, Rotation::Deg0, Mirror::default()), (0, 430, 50, 50));
+    }
+
+    #[test]
+    fn test_scissor_box_rotated() {
+        // 180°: logical top-left corner is the window's bottom-right
+        assert_eq!(scissor_box((0.0, 0.0, 100.0, 50.0), 800.0, 480.0, 800, 480, Rotation::Deg180, Mirror::default()), (700, 0, 100, 50));
+        // 90°: logical 480x800 portrait; its top-left strip lands along the window's right edge
+        assert_eq!(scissor_box((0.0, 0.0, 480.0, 100.0), 480.0, 800.0, 800, 480, Rotation::Deg90, Mirror::default()), (700, 0, 100, 480));
+    }
+
+    #[test]
+    fn test_text_batch_draw_calls() {
+        // A string is queued into one vertex buffer that render_text draws with a single call
+        let mut context = match GraphicsContext::new_headless(16, 16) {
+            Ok(context) => context,
+            Err(e) => {
+                eprintln!("Skipping headless text batch test: {}", e);
+                return;
+            }
+        };
+        let font = format!("{}/../fonts/DejaVuSansMono.ttf", env!("CARGO_MANIFEST_DIR"));
+        let renderer = context.get_text_renderer(&font, 24).unwrap();
+        let mut vertices = Vec::new();
+        unsafe { renderer.queue_text("88.8 km/h", 0.0, 0.0, 1.0, TextOrientation::Horizontal, &mut vertices).unwrap() };
+
+        // Every glyph but the space has a quad in the same buffer
+        assert_eq!(vertices.len(), 8 * TEXT_QUAD_FLOATS);
+        // Monospaced: the last quad ('h', 9th cell) starts eight advances right of the first
+        let advance = renderer.glyph_cache.get(&'8').unwrap().advance;
+        let left = |quad: usize| quad_bounds(&vertices[quad * TEXT_QUAD_FLOATS..(quad + 1) * TEXT_QUAD_FLOATS]).0;
+        let bearing = |ch: char, renderer: &mut OpenGLTextRenderer| renderer.glyph_cache.get(&ch).unwrap().bearing_x;
+        let expected = 8.0 * advance + bearing('h', renderer) - bearing('8', renderer);
+        assert!((left(7) - left(0) - expected).abs() < 0.01, "{} vs {}", left(7) - left(0), expected);
+    }
+
+    #[test]
+    fn test_text_uvs_survive_atlas_growth() {
+        let mut context = match GraphicsContext::new_headless(16, 16) {
+            Ok(context) => context,
+            Err(e) => {
+                eprintln!("Skipping headless atlas growth test: {}", e);
+                return;
+            }
+        };
+        let font = format!("{}/../fonts/DejaVuSansMono.ttf", env!("CARGO_MANIFEST_DIR"));
+        // Glyphs this large fill the initial atlas within the string, so it grows part way through
+        let renderer = context.get_text_renderer(&font, 96).unwrap();
+        let mut vertices = Vec::new();
+        unsafe { renderer.queue_text("ABCDEFGHIJKLMNOPQRST", 0.0, 0.0, 1.0, TextOrientation::Horizontal, &mut vertices).unwrap() };
+        assert!(renderer.atlas.height > GLYPH_ATLAS_INITIAL_HEIGHT);
+
+        // The first glyph's quad uses UVs at the final atlas size; vertex 0 is its bottom-left
+        let glyph = renderer.glyph_cache.get(&'A').unwrap().clone();
+        let (u0, _, _, v1) = renderer.atlas.uv_rect(glyph.atlas_x, glyph.atlas_y, glyph.width, glyph.height);
+        assert_eq!(&vertices[2..4], &[u0, v1]);
+    }
+
+    #[test]
+    fn test_shadow_passes_order_and_offsets() {
+        let passes = shadow_passes(100.0, 40.0, (1.0, 0.65, 0.0), (0.0, 0.0, 0.0), (3.0, 2.0));
+        assert_eq!(passes.len(), 2);
+        // Shadow goes down first, shifted by the offset
+        assert_eq!(passes[0], (103.0, 42.0, (0.0, 0.0, 0.0)));
+        // Main text lands exactly where the caller asked, on top
+        assert_eq!(passes[1], (100.0, 40.0, (1.0, 0.65, 0.0)));
+
+        let negative = shadow_passes(10.0, 10.0, (1.0, 1.0, 1.0), (0.2, 0.2, 0.2), (-1.5, -1.5));
+        assert_eq!((negative[0].0, negative[0].1), (8.5, 8.5));
+    }
+
+    fn quad_bounds(vertices: &[f32]) -> (f32, f32, f32, f32) {
+        vertices.chunks_exact(4).fold(
+            (f32::MAX, f32::MAX, f32::MIN, f32::MIN),
+            |(x0, y0, x1, y1), v| (x0.min(v[0]), y0.min(v[1]), x1.max(v[0]), y1.max(v[1])),
+        )
+    }
+
+    #[test]
+    fn test_rotated_text_bounds_swap_axes() {
+        // "RPM" laid out horizontally: three 8x12 glyphs on a 10px advance
+        let mut horizontal = Vec::new();
+        for index in 0..3 {
+            push_glyph_quad(&mut horizontal, index as f32 * 10.0, 0.0, 8.0, 12.0, (0.0, 0.0, 0.5, 0.5));
+        }
+        assert_eq!(quad_bounds(&horizontal), (0.0, 0.0, 28.0, 12.0));
+
+        let mut cw = horizontal.clone();
+        rotate_text_quads(&mut cw, true, 100.0, 50.0, 12.0);
+        assert_eq!(quad_bounds(&cw), (100.0, 50.0, 112.0, 78.0));
+        // Clockwise reads top-to-bottom: the first glyph ends up at the top
+        assert_eq!(quad_bounds(&cw[..TEXT_QUAD_FLOATS]), (100.0, 50.0, 112.0, 58.0));
+
+        let mut ccw = horizontal.clone();
+        rotate_text_quads(&mut ccw, false, 100.0, 50.0, 28.0);
+        assert_eq!(quad_bounds(&ccw), (100.0, 50.0, 112.0, 78.0));
+        // Counter-clockwise reads bottom-to-top: the first glyph ends up at the bottom
+        assert_eq!(quad_bounds(&ccw[..TEXT_QUAD_FLOATS]), (100.0, 70.0, 112.0, 78.0));
+
+        // Texture coordinates travel with their vertices untouched
+        for (rotated, original) in cw.chunks_exact(4).zip(horizontal.chunks_exact(4)) {
+            assert_eq!(&rotated[2..], &original[2..]);
+        }
+    }
+
+    fn repo_font(name: &str) -> std::ffi::CString {
+        std::ffi::CString::new(format!("{}/../fonts/{}", env!("CARGO_MANIFEST_DIR"), name)).unwrap()
+    }
+
+    #[test]
+    fn test_glyph_face_uses_fallback_for_missing_chars() {
+        unsafe {
+            let mut library: ft::FT_Library = std::ptr::null_mut();
+            assert_eq!(ft::FT_Init_FreeType(&mut library), 0);
+            let mut primary: ft::FT_Face = std::ptr::null_mut();
+            let mut fallback: ft::FT_Face = std::ptr::null_mut();
+            // 7-segment font: digits only; DejaVu Sans Mono has Latin and Cyrillic
+            assert_eq!(ft::FT_New_Face(library, repo_font("DSEG7Classic-Regular.ttf").as_ptr(), 0, &mut primary), 0);
+            assert_eq!(ft::FT_New_Face(library, repo_font("DejaVuSansMono.ttf").as_ptr(), 0, &mut fallback), 0);
+
+            let faces: Vec<ft::FT_Face> = "88 Ж".chars().map(|ch| glyph_face(primary, fallback, ch)).collect();
+            assert_eq!(faces, vec![primary, primary, primary, fallback]);
+            // Without a fallback, or when neither font has it, the primary face's .notdef is used
+            assert_eq!(glyph_face(primary, std::ptr::null_mut(), 'Ж'), primary);
+            assert_eq!(glyph_face(primary, fallback, '\u{1F697}'), primary);
+
+            ft::FT_Done_Face(primary);
+            ft::FT_Done_Face(fallback);
+            ft::FT_Done_FreeType(library);
+        }
+    }
+
+    #[test]
+    fn test_kerning_tightens_av_pair() {
+        unsafe {
+            let mut library: ft::FT_Library = std::ptr::null_mut();
+            assert_eq!(ft::FT_Init_FreeType(&mut library), 0);
+            // DejaVu Sans Mono is monospaced and ships no kern table, so use the proportional
+            // OpenGost face the dashboard renders most of its text with
+            let mut face: ft::FT_Face = std::ptr::null_mut();
+            let mut mono: ft::FT_Face = std::ptr::null_mut();
+            assert_eq!(ft::FT_New_Face(library, repo_font("OpenGostTypeB.ttf").as_ptr(), 0, &mut face), 0);
+            assert_eq!(ft::FT_New_Face(library, repo_font("DejaVuSansMono.ttf").as_ptr(), 0, &mut mono), 0);
+            ft::FT_Set_Pixel_Sizes(face, 0, 48);
+            ft::FT_Set_Pixel_Sizes(mono, 0, 48);
+
+            let advance = |ch: char| {
+                assert_eq!(ft::FT_Load_Char(face, ch as ft::FT_ULong, ft::FT_LOAD_DEFAULT), 0);
+                (*(*face).glyph).advance.x as f32 / 64.0
+            };
+            let naive_width = advance('A') + advance('V');
+            let kerned_width = naive_width + kerning_delta(face, std::ptr::null_mut(), 'A', 'V');
+            assert!(kerned_width < naive_width, "{} vs {}", kerned_width, naive_width);
+
+            // Faces without a kern table are left alone
+            assert!(!ft::FT_HAS_KERNING(mono));
+            assert_eq!(kerning_delta(mono, std::ptr::null_mut(), 'A', 'V'), 0.0);
+
+            ft::FT_Done_Face(face);
+            ft::FT_Done_Face(mono);
+            ft::FT_Done_FreeType(library);
+        }
+    }
+
+    #[test]
+    fn test_lru_cache_evicts_oldest() {
+        let mut cache = LruCache::new(3);
+        for ch in ['A', 'B', 'C'] {
+            assert!(cache.insert(ch, ch as u32).is_empty());
+        }
+        // Touch A so B becomes the least recently used
+        assert_eq!(cache.get(&'A'), Some(&65));
+        assert_eq!(cache.insert('D', 68), vec![('B', 66)]);
+        assert_eq!(cache.len(), 3);
+        assert!(cache.get(&'B').is_none());
+
+        assert_eq!(cache.set_capacity(1), vec![('C', 67), ('A', 65)]);
+        assert_eq!(cache.get(&'D'), Some(&68));
+    }
+
+    #[test]
+    fn test_glyph_atlas_repack_keeps_pixels() {
+        let mut atlas = GlyphAtlas::new(16, 16);
+        atlas.insert(4, 2, &[1; 8], 4).unwrap();
+        atlas.insert(3, 3, &[2; 9], 3).unwrap();
+        // Only the second glyph is still cached
+        let mut rects = [(5, 0, 3, 3)];
+        let mut repacked = atlas.repack(&mut rects).unwrap();
+        assert_eq!(rects[0], (0, 0, 3, 3));
+        assert_eq!(repacked.read(0, 0, 3, 3), vec![2; 9]);
+        assert_eq!(repacked.allocate(1, 1), Some((4, 0)));
+    }
+
+    #[test]
+    fn test_glyph_atlas_packs_shelves() {
+        let mut atlas = GlyphAtlas::new(32, 32);
+        assert_eq!(atlas.allocate(10, 8), Some((0, 0)));
+        assert_eq!(atlas.allocate(10, 12), Some((11, 0)));
+        // 22 + 11 > 32: next shelf starts below the tallest glyph of this one, plus padding
+        assert_eq!(atlas.allocate(10, 5), Some((0, 13)));
+        // Empty glyphs (spaces) take no room
+        assert_eq!(atlas.allocate(0, 0), Some((0, 0)));
+        assert_eq!(atlas.allocate(11, 5), Some((11, 13)));
+    }
+
+    #[test]
+    fn test_glyph_atlas_grows_for_new_glyph() {
+        let mut atlas = GlyphAtlas::new(16, 16);
+        let first = [7u8; 15 * 15];
+        assert_eq!(atlas.insert(15, 15, &first, 15).unwrap(), (0, 0));
+        let (_, _, _, v1_before) = atlas.uv_rect(0, 0, 15.0, 15.0);
+
+        // Rows 3 bytes apart, 2 used, as FreeType may pad bitmap rows
+        let second = [1, 2, 0, 3, 4, 0];
+        assert_eq!(atlas.insert(2, 2, &second, 3).unwrap(), (0, 16));
+        assert_eq!(atlas.height, 32);
+        assert_eq!(atlas.pixels.len(), 16 * 32);
+
+        // The first glyph kept its pixels; its UVs shrink with the taller atlas
+        assert_eq!(atlas.pixels[14 * 16 + 14], 7);
+        assert_eq!(&atlas.pixels[16 * 16..16 * 16 + 2], &[1, 2]);
+        assert_eq!(&atlas.pixels[17 * 16..17 * 16 + 2], &[3, 4]);
+        let (_, _, _, v1_after) = atlas.uv_rect(0, 0, 15.0, 15.0);
+        assert_eq!(v1_after, v1_before / 2.0);
+
+        assert!(atlas.insert(16, 1, &[0; 16], 16).is_err());
+    }
+
+    #[test]
+    fn test_text_align_start_x() {
+        assert_eq!(TextAlign::Left.start_x(400.0, 120.0), 400.0);
+        assert_eq!(TextAlign::Center.start_x(400.0, 120.0), 340.0);
+        assert_eq!(TextAlign::Right.start_x(400.0, 120.0), 280.0);
+    }
+
+    // Monospace stand-in for the font: 10px per character
+    fn measure_monospace(text: &str) -> Result<f32, String> {
+        Ok(text.chars().count() as f32 * 10.0)
+    }
+
+    #[test]
+    fn test_wrap_text_at_spaces() {
+        let lines = wrap_text("Coolant temperature above limit, reduce engine load", 200.0, measure_monospace).unwrap();
+        assert_eq!(lines, vec!["Coolant temperature", "above limit, reduce", "engine load"]);
+        assert!(lines.iter().all(|line| measure_monospace(line).unwrap() <= 200.0));
+    }
+
+    #[test]
+    fn test_truncate_text_ends_with_ellipsis() {
+        let (text, truncated) = truncate_text("ТЕМПЕРАТУРА ДВИГАТЕЛЯ", 120.0, measure_monospace).unwrap();
+        assert!(truncated);
+        assert_eq!(text, "ТЕМПЕРАТУРА…");
+        assert!(text.ends_with(ELLIPSIS));
+        assert!(measure_monospace(&text).unwrap() <= 120.0);
+
+        // The trailing space of a cut word boundary is dropped
+        assert_eq!(truncate_text("ДАВЛ МАСЛА", 60.0, measure_monospace).unwrap(), ("ДАВЛ…".to_string(), true));
+        assert_eq!(truncate_text("ЗАРЯД", 50.0, measure_monospace).unwrap(), ("ЗАРЯД".to_string(), false));
+        assert_eq!(truncate_text("ЗАРЯД", 5.0, measure_monospace).unwrap(), ("…".to_string(), true));
+    }
+
+    #[test]
+    fn test_wrap_text_hard_breaks_long_words() {
+        let lines = wrap_text("ID 0x18FEEE00ABCDEF", 60.0, measure_monospace).unwrap();
+        assert_eq!(lines, vec!["ID", "0x18FE", "EE00AB", "CDEF"]);
+        // Existing newlines still break, and a limit narrower than one character still progresses
+        assert_eq!(wrap_text("a b\nc", 100.0, measure_monospace).unwrap(), vec!["a b", "c"]);
+        assert_eq!(wrap_text("abc", 5.0, measure_monospace).unwrap(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_text_lines() {
+        assert_eq!(text_lines("RPM").collect::<Vec<_>>(), vec!["RPM"]);
+        assert_eq!(text_lines("OIL\r\nLOW\n").collect::<Vec<_>>(), vec!["OIL", "LOW", ""]);
+    }
+
+    #[test]
+    fn test_two_line_height_is_about_two_line_heights() {
+        let Some(mut context) = headless_or_skip(16, 16, "headless two-line height test") else {
+            return;
+        };
+        let font = format!("{}/../fonts/DejaVuSansMono.ttf", env!("CARGO_MANIFEST_DIR"));
+        let line_height = context.get_line_height_with_font(1.0, &font, 16).unwrap();
+        let one_line = context.calculate_text_height_with_font("A", 1.0, &font, 16).unwrap();
+        let two_lines = context.calculate_text_height_with_font("A\nB", 1.0, &font, 16).unwrap();
+
+        // The second line adds one line advance to the first line's glyph height
+        assert!(one_line > 0.0 && one_line < line_height);
+        assert!(two_lines > 1.5 * line_height && two_lines <= 2.0 * line_height,
+                "two lines {} with line height {}", two_lines, line_height);
+        assert!((two_lines - one_line - line_height).abs() <= 1.0);
+    }
+
+    #[test]
+    fn test_vignette_defaults_off_and_clamps() {
+        let mut vignette = VignetteSettings::default();
+        assert!(!vignette.enabled);
+        vignette.set_strength(3.0);
+        vignette.set_radius(-1.0);
+        assert_eq!((vignette.strength, vignette.radius), (1.0, 0.0));
+    }
+
+    #[test]
+    fn test_bloom_target_size() {
+        assert_eq!(bloom_target_size(800, 480, 1), (800, 480));
+        assert_eq!(bloom_target_size(800, 480, 2), (400, 240));
+        assert_eq!(bloom_target_size(800, 480, 4), (200, 120));
+        // Odd sizes round up so the edge pixels still have a texel
+        assert_eq!(bloom_target_size(1366, 765, 4), (342, 192));
+    }
+
+    #[test]
+    fn test_bloom_blur_weights() {
+        let weights = bloom_blur_weights(DEFAULT_BLOOM_RADIUS);
+        // Two 1D passes must carry the same total weight as the old 7x7 kernel
+        let total = weights[0] + 2.0 * weights[1..].iter().sum::<f32>();
+        assert!((total * total - 14.794).abs() < 0.01);
+        assert!(weights.windows(2).all(|pair| pair[0] > pair[1]));
+
+        // Wider kernels spread the same total weight
+        let wide = bloom_blur_weights(MAX_BLOOM_RADIUS);
+        assert_eq!(wide.len(), MAX_BLOOM_RADIUS + 1);
+        let wide_total = wide[0] + 2.0 * wide[1..].iter().sum::<f32>();
+        assert!((wide_total - total).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_bloom_blur_shader_source_matches_radius() {
+        // The weights uniform array and loop bound must match the uploaded weight count
+        let source = bloom_blur_shader_source(5);
+        assert!(source.contains("uniform float uWeights[6];"));
+        assert!(source.contains("i < 6;"));
+        assert!(source.ends_with('\0'));
+    }
+
+    #[test]
+    fn test_rotation_from_degrees() {
+        assert_eq!(Rotation::from_degrees(270).unwrap(), Rotation::Deg270);
+        assert!(Rotation::from_degrees(45).is_err());
+        assert!(Rotation::Deg90.is_transposed());
+        assert!(!Rotation::Deg180.is_transposed());
+    }
+
+    /// Headless context for a pixel test, or None (test skipped) where there is no EGL with
+    /// the surfaceless platform. Set NIVA_REQUIRE_EGL on machines that have it, such as CI,
+    /// to turn the skip into a failure.
+    fn headless_or_skip(width: i32, height: i32, test: &str) -> Option<GraphicsContext> {
+        match GraphicsContext::new_headless(width, height) {
+            Ok(context) => Some(context),
+            Err(e) if std::env::var("NIVA_REQUIRE_EGL").is_ok() => panic!("{}: no headless context: {}", test, e),
+            Err(e) => {
+                eprintln!("Skipping {}: {}", test, e);
+                None
+            }
+        }
+    }
+
+    /// RGB at screen position (x, y), y down, from read_pixels() output (bottom row first)
+    fn screen_pixel(pixels: &[u8], width: usize, height: usize, x: usize, y: usize) -> [u8; 3] {
+        let i = ((height - 1 - y) * width + x) * 4;
+        [pixels[i], pixels[i + 1], pixels[i + 2]]
+    }
+
+    #[test]
+    fn test_headless_context_renders_offscreen() {
+        let Some(mut context) = headless_or_skip(64, 32, "headless rendering test") else {
+            return;
+        };
+        assert!(context.is_headless());
+        assert_eq!((context.width, context.height), (64, 32));
+
+        context.clear();
+        context.fill_rect(0.0, 0.0, 32.0, 32.0, (1.0, 0.0, 0.0)).unwrap();
+        context.swap_buffers();
+        let pixels = context.read_pixels();
+        assert_eq!(pixels.len(), 64 * 32 * 4);
+        // Left half red, right half still the black clear color, on every row
+        let pixel = |x: usize, y: usize| &pixels[(y * 64 + x) * 4..(y * 64 + x) * 4 + 3];
+        for y in [0, 16, 31] {
+            assert_eq!(pixel(5, y), [255, 0, 0]);
+            assert_eq!(pixel(60, y), [0, 0, 0]);
+        }
+    }
+
+    #[test]
+    fn test_headless_alpha_blend() {
+        let Some(mut context) = headless_or_skip(16, 16, "headless alpha blend test") else {
+            return;
+        };
+        context.clear();
+        context.fill_rect(0.0, 0.0, 16.0, 16.0, (1.0, 0.0, 0.0)).unwrap();
+        context.fill_rect_rgba(0.0, 0.0, 8.0, 16.0, (0.0, 0.0, 1.0, 0.5)).unwrap();
+        context.swap_buffers();
+        let pixels = context.read_pixels();
+
+        // Half-transparent blue over red comes out an even mix; the uncovered half stays red
+        let [r, g, b] = screen_pixel(&pixels, 16, 16, 4, 8);
+        assert!(r.abs_diff(128) <= 2 && g == 0 && b.abs_diff(128) <= 2, "blended pixel {:?}", (r, g, b));
+        assert_eq!(screen_pixel(&pixels, 16, 16, 12, 8), [255, 0, 0]);
+    }
+
+    #[test]
+    fn test_headless_line_covers_midpoint() {
+        let Some(mut context) = headless_or_skip(64, 32, "headless line test") else {
+            return;
+        };
+        context.clear();
+        context.draw_line(0.0, 0.0, 64.0, 32.0, 4.0, (0.0, 1.0, 0.0)).unwrap();
+        context.swap_buffers();
+        let pixels = context.read_pixels();
+
+        // The diagonal runs through the middle of the screen and misses the other corners
+        assert_eq!(screen_pixel(&pixels, 64, 32, 32, 16), [0, 255, 0]);
+        assert_eq!(screen_pixel(&pixels, 64, 32, 8, 4), [0, 255, 0]);
+        assert_eq!(screen_pixel(&pixels, 64, 32, 60, 2), [0, 0, 0]);
+        assert_eq!(screen_pixel(&pixels, 64, 32, 2, 30), [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_headless_clip_rect() {
+        let Some(mut context) = headless_or_skip(64, 32, "headless clip test") else {
+            return;
+        };
+        context.clear();
+        context.push_clip(8.0, 4.0, 16.0, 8.0);
+        context.fill_rect(0.0, 0.0, 64.0, 32.0, (1.0, 1.0, 1.0)).unwrap();
+        context.pop_clip();
+        context.swap_buffers();
+        let pixels = context.read_pixels();
+
+        // Only the clip rectangle (x 8..24, y 4..12 from the top) is filled
+        assert_eq!(screen_pixel(&pixels, 64, 32, 12, 8), [255, 255, 255]);
+        assert_eq!(screen_pixel(&pixels, 64, 32, 23, 11), [255, 255, 255]);
+        assert_eq!(screen_pixel(&pixels, 64, 32, 30, 8), [0, 0, 0]);
+        assert_eq!(screen_pixel(&pixels, 64, 32, 12, 2), [0, 0, 0]);
+        assert_eq!(screen_pixel(&pixels, 64, 32, 12, 24), [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_headless_bloom_radius_relinks_blur_shader() {
+        let Some(mut context) = headless_or_skip(16, 16, "headless bloom radius test") else {
+            return;
+        };
+        let original = context.bloom_blur_shader.expect("bloom is set up with the context");
+
+        context.set_bloom_radius(5).unwrap();
+        let relinked = context.bloom_blur_shader.unwrap();
+        assert_ne!(relinked, original);
+        unsafe {
+            assert_eq!(gl::IsProgram(original), gl::FALSE);
+            assert_eq!(gl::IsProgram(relinked), gl::TRUE);
+            // The new program carries the six weights a radius of 5 needs
+            assert!(gl::GetUniformLocation(relinked, c"uWeights[5]".as_ptr().cast()) >= 0);
+        }
+
+        // Same radius again (and a clamped one equal to it) keeps the program
+        context.set_bloom_radius(5).unwrap();
+        assert_eq!(context.bloom_blur_shader, Some(relinked));
+        context.set_bloom_radius(MAX_BLOOM_RADIUS + 10).unwrap();
+        context.set_bloom_radius(MAX_BLOOM_RADIUS).unwrap();
+        assert_eq!(context.get_bloom_radius(), MAX_BLOOM_RADIUS);
+    }
+
+    #[test]
+    fn test_headless_vignette_links_once_and_darkens_corners() {
+        let Some(mut context) = headless_or_skip(32, 32, "headless vignette test") else {
+            return;
+        };
+        context.set_vignette_enabled(true).unwrap();
+        let shader = context.vignette_shader.expect("shader linked on first enable");
+        context.set_vignette_enabled(false).unwrap();
+        context.set_vignette_enabled(true).unwrap();
+        assert_eq!(context.vignette_shader, Some(shader));
+
+        context.set_vignette_strength(1.0);
+        context.set_vignette_radius(0.5);
+        context.clear();
+        context.fill_rect(0.0, 0.0, 32.0, 32.0, (1.0, 1.0, 1.0)).unwrap();
+        context.apply_vignette().unwrap();
+        context.swap_buffers();
+        let pixels = context.read_pixels();
+
+        // Center untouched, corners pulled well towards black
+        assert_eq!(screen_pixel(&pixels, 32, 32, 16, 16), [255, 255, 255]);
+        assert!(screen_pixel(&pixels, 32, 32, 0, 0)[0] < 64);
+        assert!(screen_pixel(&pixels, 32, 32, 31, 31)[0] < 64);
+    }
+
+    #[test]
+    fn test_headless_truncated_text_is_cached_per_line() {
+        let Some(mut context) = headless_or_skip(64, 16, "headless truncated text test") else {
+            return;
+        };
+        let font = format!("{}/../fonts/DejaVuSansMono.ttf", env!("CARGO_MANIFEST_DIR"));
+        let style = TextStyle::new(&font, 12, (1.0, 1.0, 1.0));
+        let line = "Coolant temperature 92.5";
+
+        for _ in 0..3 {
+            assert!(context.render_text_truncated(line, 0.0, 0.0, 40.0, &style).unwrap());
+        }
+        assert!(!context.render_text_truncated("OK", 0.0, 0.0, 40.0, &style).unwrap());
+        assert_eq!(context.truncated_text_cache.len(), 2);
+        let key = (line.to_string(), font.clone(), 12, 1.0f32.to_bits(), 40.0f32.to_bits());
+        let (cut, _) = &context.truncated_text_cache[&key];
+        assert!(cut.ends_with(ELLIPSIS) && cut.len() < line.len());
+    }
+
+    #[test]
+    fn test_clear_color_defaults_to_black() {
+        let mut context = GraphicsContext::unconfigured(800, 480, None);
+        assert_eq!(context.get_clear_color(), (0.0, 0.0, 0.0));
+        context.set_clear_color(0.1, 0.2, 0.3);
+        assert_eq!(context.get_clear_color(), (0.1, 0.2, 0.3));
+    }
+
+    #[test]
+    fn test_headless_preload_glyphs() {
+        let mut context = match GraphicsContext::new_headless(16, 16) {
+            Ok(context) => context,
+            Err(e) => {
+                eprintln!("Skipping headless glyph preload test: {}", e);
+                return;
+            }
+        };
+        let font = format!("{}/../fonts/DejaVuSansMono.ttf", env!("CARGO_MANIFEST_DIR"));
+        let chars = "0123456789 кгс/см² ДАВЛ МАСЛА";
+        context.preload_glyphs(chars, &font, 24).unwrap();
+
+        let renderer = context.get_text_renderer(&font, 24).unwrap();
+        for ch in chars.chars() {
+            assert!(renderer.is_glyph_cached(ch), "'{}' not cached", ch);
+        }
+        assert!(!renderer.is_glyph_cached('Ж'));
+    }
+
+    #[test]
+    fn test_headless_vsync_toggle() {
+        let mut context = match GraphicsContext::new_headless(16, 16) {
+            Ok(context) => context,
+            Err(e) => {
+                eprintln!("Skipping headless vsync test: {}", e);
+                return;
+            }
+        };
+        assert!(context.is_vsync_enabled());
+        context.set_vsync(false).unwrap();
+        assert!(!context.is_vsync_enabled());
+        context.swap_buffers();
+        context.set_vsync(true).unwrap();
+        assert!(context.is_vsync_enabled());
+    }
+}
@@ -2,3 +2,4 @@ pub mod opengl_test;
 pub mod context;
 pub mod ui_style;
 pub mod text_box;
+pub mod backlight;
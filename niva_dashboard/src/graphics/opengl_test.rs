@@ -1,9 +1,13 @@
 #![allow(dead_code)]
 use crate::graphics::context::GraphicsContext;
+use crate::util::frame_limiter::FrameLimiter;
 use gl::types::*;
 use freetype_sys as ft;
 use std::collections::HashMap;
 
+// Frame rate the animated tests run at
+const DEMO_FPS: f32 = 60.0;
+
 #[derive(Clone)]
 struct CachedGlyph {
     texture_id: u32,
@@ -49,6 +53,7 @@ pub fn run_basic_geometry_test(context: &mut GraphicsContext) -> Result<(), Stri
         let mut frame_count = 0;
         let total_frames = 300; // 5 seconds at 60fps
         
+        let mut frame_limiter = FrameLimiter::new(DEMO_FPS);
         while frame_count < total_frames {
             if context.should_quit() {
                 break;
@@ -68,7 +73,7 @@ pub fn run_basic_geometry_test(context: &mut GraphicsContext) -> Result<(), Stri
             context.swap_buffers();
             frame_count += 1;
             
-            std::thread::sleep(std::time::Duration::from_millis(16)); // ~60fps
+            frame_limiter.wait();
         }
         
         // Cleanup
@@ -814,6 +819,7 @@ pub fn run_opengl_text_rendering_test(context: &mut GraphicsContext) -> Result<(
         let mut frame_count = 0;
         let total_frames = 300; // 5 seconds at 60fps
         
+        let mut frame_limiter = FrameLimiter::new(DEMO_FPS);
         while frame_count < total_frames {
             if context.should_quit() {
                 break;
@@ -835,7 +841,7 @@ pub fn run_opengl_text_rendering_test(context: &mut GraphicsContext) -> Result<(
             text_renderer.render_text(&animated_text, 50.0, 350.0, pulse_scale, (1.0, 0.5, 1.0), context.width as f32, context.height as f32)?;
             
             // Add FPS counter
-            let fps_text = format!("FPS: {:.1}", 1.0 / 0.016);
+            let fps_text = format!("FPS: {:.1}", frame_limiter.fps());
             text_renderer.render_text(&fps_text, 600.0, 50.0, 0.8, (0.8, 0.8, 0.8), context.width as f32, context.height as f32)?;
             
             // Clean up OpenGL state
@@ -851,7 +857,7 @@ pub fn run_opengl_text_rendering_test(context: &mut GraphicsContext) -> Result<(
                 log::info!("Frame {} - OpenGL text rendering with FreeType", frame_count);
             }
             
-            std::thread::sleep(std::time::Duration::from_millis(16)); // ~60fps
+            frame_limiter.wait();
         }
         
         log::info!("OpenGL text rendering test completed successfully!");
@@ -983,12 +989,17 @@ pub fn run_dashboard_performance_test(context: &mut GraphicsContext) -> Result<(
     unsafe {
         log::info!("Starting dashboard performance test...");
         
+        // Uncapped, to measure maximum throughput
+        if let Err(e) = context.set_vsync(false) {
+            log::warn!("Could not disable vsync: {}", e);
+        }
+        let mut frame_limiter = FrameLimiter::unlimited();
         loop {
             frame_count += 1;
             let elapsed = start_time.elapsed().as_secs_f32();
             
             // Exit after 30 seconds or on any input
-            if elapsed > 30.0 {
+            if elapsed > 30.0 || context.should_quit() {
                 break;
             }
             
@@ -1032,8 +1043,7 @@ pub fn run_dashboard_performance_test(context: &mut GraphicsContext) -> Result<(
                 log::info!("Frame {} - FPS: {:.1} - Glyph cache size: {} - {} gauges", frame_count, fps, cache_size, gauges.len());
             }
             
-            // 60fps timing
-            std::thread::sleep(std::time::Duration::from_millis(16));
+            frame_limiter.wait();
         }
         
         let final_fps = frame_count as f32 / start_time.elapsed().as_secs_f32();
@@ -1292,6 +1302,7 @@ pub fn run_rotating_needle_gauge_test(context: &mut GraphicsContext) -> Result<(
         log::info!("Starting rotating needle gauge animation...");
         context.swap_buffers();
         
+        let mut frame_limiter = FrameLimiter::new(DEMO_FPS);
         loop {
             let elapsed = start_time.elapsed().as_secs_f32();
             
@@ -1335,11 +1346,11 @@ pub fn run_rotating_needle_gauge_test(context: &mut GraphicsContext) -> Result<(
             }
             
             // Exit after 10 seconds
-            if elapsed > 10.0 {
+            if elapsed > 10.0 || context.should_quit() {
                 break;
             }
             
-            std::thread::sleep(std::time::Duration::from_millis(16)); // ~60 FPS
+            frame_limiter.wait();
         }
     }
     
@@ -1786,6 +1797,7 @@ pub fn run_indicator_zero_position_test(context: &mut GraphicsContext) -> Result
     log::info!("Rendering indicators at zero position...");
     
     // Render loop for 5 seconds
+    let mut frame_limiter = FrameLimiter::new(DEMO_FPS);
     loop {
         let elapsed = start_time.elapsed();
         
@@ -1813,11 +1825,11 @@ pub fn run_indicator_zero_position_test(context: &mut GraphicsContext) -> Result
         context.swap_buffers();
         
         // Exit after 5 seconds
-        if elapsed.as_secs() >= 5 {
+        if elapsed.as_secs() >= 5 || context.should_quit() {
             break;
         }
         
-        std::thread::sleep(std::time::Duration::from_millis(16)); // ~60 FPS
+        frame_limiter.wait();
     }
     
     log::info!("Zero position indicator test completed!");
@@ -1972,7 +1984,8 @@ pub fn run_indicator_middle_position_test(context: &mut GraphicsContext) -> Resu
     let start_time = std::time::Instant::now();
     let display_duration = std::time::Duration::from_secs(5);
     
-    while start_time.elapsed() < display_duration {
+    let mut frame_limiter = FrameLimiter::new(DEMO_FPS);
+    while start_time.elapsed() < display_duration && !context.should_quit() {
         unsafe {
             gl::ClearColor(0.05, 0.05, 0.1, 1.0);
             gl::Clear(gl::COLOR_BUFFER_BIT);
@@ -1987,8 +2000,7 @@ pub fn run_indicator_middle_position_test(context: &mut GraphicsContext) -> Resu
         // Swap buffers
         context.swap_buffers();
         
-        // Small delay to prevent excessive CPU usage
-        std::thread::sleep(std::time::Duration::from_millis(16)); // ~60 FPS
+        frame_limiter.wait();
     }
     
     log::info!("Middle position indicator test completed!");
@@ -2143,7 +2155,8 @@ pub fn run_indicator_max_position_test(context: &mut GraphicsContext) -> Result<
     let start_time = std::time::Instant::now();
     let display_duration = std::time::Duration::from_secs(5);
     
-    while start_time.elapsed() < display_duration {
+    let mut frame_limiter = FrameLimiter::new(DEMO_FPS);
+    while start_time.elapsed() < display_duration && !context.should_quit() {
         unsafe {
             gl::ClearColor(0.05, 0.05, 0.1, 1.0);
             gl::Clear(gl::COLOR_BUFFER_BIT);
@@ -2158,8 +2171,7 @@ pub fn run_indicator_max_position_test(context: &mut GraphicsContext) -> Result<
         // Swap buffers
         context.swap_buffers();
         
-        // Small delay to prevent excessive CPU usage
-        std::thread::sleep(std::time::Duration::from_millis(16)); // ~60 FPS
+        frame_limiter.wait();
     }
     
     log::info!("Maximum position indicator test completed!");
@@ -2227,7 +2239,8 @@ pub fn run_fuel_level_grid_test(context: &mut GraphicsContext) -> Result<(), Str
     // Per-second accumulator for render timing
     let mut render_us_bucket: Vec<u64> = Vec::with_capacity(120);
 
-    loop {
+    // Runs until SIGTERM/SIGINT
+    while !context.should_quit() {
         let elapsed = test_start.elapsed().as_secs_f32();
 
         // Slow sine sweep: 0→100→0% over 10 seconds, exercises the full needle arc
@@ -2279,4 +2292,5 @@ pub fn run_fuel_level_grid_test(context: &mut GraphicsContext) -> Result<(), Str
             last_report = Instant::now();
         }
     }
+    Ok(())
 }
@@ -19,8 +19,11 @@
 //! }
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use serde::{Deserialize, Serialize};
+use crate::error::DashboardError;
 
 // =============================================================================
 // STYLE ELEMENT NAME CONSTANTS
@@ -40,7 +43,34 @@ pub const DIGITAL_DISPLAY_MONO_FONT_PATH: &str = "/home/user/Work/Niva_Dashboard
 // Terminal-style monospace font, for scrolling text boxes (log/ADC diagnostic output)
 pub const TERMINAL_FONT_PATH: &str = "/home/user/Work/Niva_Dashboard_Rpi/Niva_dashboard_rpi/fonts/DejaVuSansMono.ttf";
 
+// Tell-tale icon images
+pub const ICON_TURN_SIGNAL_PATH: &str = "/home/user/Work/Niva_Dashboard_Rpi/Niva_dashboard_rpi/icons/turn_signal.png";
+pub const ICON_HIGH_BEAM_PATH: &str = "/home/user/Work/Niva_Dashboard_Rpi/Niva_dashboard_rpi/icons/high_beam.png";
+pub const ICON_PARK_BRAKE_PATH: &str = "/home/user/Work/Niva_Dashboard_Rpi/Niva_dashboard_rpi/icons/park_brake.png";
+pub const ICON_CHECK_ENGINE_PATH: &str = "/home/user/Work/Niva_Dashboard_Rpi/Niva_dashboard_rpi/icons/check_engine.png";
+
+// Directory searched first for font files, so the hardcoded paths work on any machine
+pub const FONT_DIR_ENV_VAR: &str = "NIVA_FONT_DIR";
+// Same for icon images
+pub const ICON_DIR_ENV_VAR: &str = "NIVA_ICON_DIR";
+
+// Style file read at startup and watched for live edits
+pub const UI_STYLE_FILE_PATH: &str = "/etc/niva_dashboard/ui_style.json";
+
+// Built-in theme groups, selected with UIStyle::set_active_theme
+pub const THEME_DAY: &str = "day";
+pub const THEME_NIGHT: &str = "night";
+
+// Group of named colors that other values reference as "@name"
+pub const PALETTE_GROUP: &str = "palette";
+
 // Global Style Elements
+// Not set by defaults: brightness is runtime state, a style file only overrides it explicitly
+pub const GLOBAL_BRIGHTNESS: &str = "global_brightness";
+// Saved by the settings page; also runtime state, so absent from the defaults
+pub const GLOBAL_THEME: &str = "global_theme";                  // THEME_DAY or THEME_NIGHT
+pub const GLOBAL_BACKLIGHT_ENABLED: &str = "global_backlight_enabled";  // false = dim in software
+pub const GLOBAL_BLOOM_ENABLED: &str = "global_bloom_enabled";
 pub const GLOBAL_CONTRAST: &str = "global_contrast";
 pub const GLOBAL_BACKGROUND_COLOR: &str = "global_background_color";
 pub const GLOBAL_FONT_PATH: &str = "global_font_path";
@@ -54,6 +84,10 @@ pub const PAGE_BUTTON_LABEL_COLOR: &str = "page_button_label_color";
 pub const PAGE_STATUS_FONT: &str = "page_status_font";
 pub const PAGE_STATUS_FONT_SIZE: &str = "page_status_font_size";
 pub const PAGE_STATUS_COLOR: &str = "page_status_color";
+// Seconds without input or motion before the screen dims (0 = never), and the dimmed brightness
+pub const PAGE_IDLE_DIM_TIMEOUT: &str = "page_idle_dim_timeout";
+pub const PAGE_IDLE_DIM_BRIGHTNESS: &str = "page_idle_dim_brightness";
+pub const PAGE_LONG_PRESS_TIME: &str = "page_long_press_time";       // Seconds a button is held for a long press
 
 // Gauge Style Elements
 pub const GAUGE_BACKGROUND_COLOR: &str = "gauge_background_color";
@@ -147,6 +181,10 @@ pub const BAR_MARK_LABELS_COLOR: &str = "bar_mark_labels_color";
 pub const BAR_SEGMENT_COUNT: &str = "bar_segment_count";
 pub const BAR_SEGMENT_GAP: &str = "bar_segment_gap";
 
+// Sparkline (history trace) Style Elements
+pub const SPARKLINE_COLOR: &str = "sparkline_color";
+pub const SPARKLINE_THICKNESS: &str = "sparkline_thickness";
+
 // Text Style Elements
 pub const TEXT_PRIMARY_COLOR: &str = "text_primary_color";
 pub const TEXT_SECONDARY_COLOR: &str = "text_secondary_color";
@@ -188,6 +226,12 @@ pub const DIGITAL_DISPLAY_BORDER_ENABLED: &str = "digital_display_border_enabled
 pub const DIGITAL_DISPLAY_BORDER_COLOR: &str = "digital_display_border_color";
 pub const DIGITAL_DISPLAY_BORDER_WIDTH: &str = "digital_display_border_width";
 pub const DIGITAL_DISPLAY_BORDER_RADIUS: &str = "digital_display_border_radius";
+pub const DIGITAL_DISPLAY_SHADOW_COLOR: &str = "digital_display_shadow_color";
+pub const DIGITAL_DISPLAY_SHADOW_OFFSET: &str = "digital_display_shadow_offset";
+// Number format overrides; left unset so each display keeps its own width and decimals
+pub const DIGITAL_DISPLAY_DECIMALS: &str = "digital_display_decimals";
+pub const DIGITAL_DISPLAY_WIDTH: &str = "digital_display_width";
+pub const DIGITAL_DISPLAY_LEADING_ZEROS: &str = "digital_display_leading_zeros";
 
 // Extended Digital Display Fonts (additional variants)
 pub const DIGITAL_DISPLAY_FONT_ITALIC: &str = "digital_display_font_italic";
@@ -204,6 +248,12 @@ pub const INDICATOR_GLOW_ENABLED: &str = "indicator_glow_enabled";
 pub const INDICATOR_GLOW_RADIUS: &str = "indicator_glow_radius";
 pub const INDICATOR_SIZE: &str = "indicator_size";
 
+// Icon (tell-tale) image paths, one key per indicator
+pub const ICON_TURN_SIGNAL: &str = "icon_turn_signal";
+pub const ICON_HIGH_BEAM: &str = "icon_high_beam";
+pub const ICON_PARK_BRAKE: &str = "icon_park_brake";
+pub const ICON_CHECK_ENGINE: &str = "icon_check_engine";
+
 // Animation Settings
 pub const ANIMATION_NEEDLE_SPEED: &str = "animation_needle_speed";
 pub const ANIMATION_BAR_SPEED: &str = "animation_bar_speed";
@@ -236,63 +286,96 @@ pub enum UIStyleValue {
 
 impl UIStyleValue {
     /// Convert to color tuple (r, g, b) with values 0.0-1.0
-    pub fn as_color(&self) -> Result<(f32, f32, f32), String> {
+    pub fn as_color(&self) -> Result<(f32, f32, f32), DashboardError> {
         match self {
-            UIStyleValue::Color(color_str) => parse_color(color_str),
-            _ => Err("Value is not a color".to_string()),
+            UIStyleValue::Color(color_str) => parse_color(color_str).map_err(DashboardError::Style),
+            _ => Err(DashboardError::Style("Value is not a color".to_string())),
         }
     }
     
     /// Convert to color tuple with alpha (r, g, b, a) with values 0.0-1.0
-    pub fn as_color_rgba(&self) -> Result<(f32, f32, f32, f32), String> {
-        let (r, g, b) = self.as_color()?;
-        Ok((r, g, b, 1.0))
+    pub fn as_color_rgba(&self) -> Result<(f32, f32, f32, f32), DashboardError> {
+        match self {
+            UIStyleValue::Color(color_str) => parse_color_rgba(color_str).map_err(DashboardError::Style),
+            _ => Err(DashboardError::Style("Value is not a color".to_string())),
+        }
     }
     
-    pub fn as_float(&self) -> Result<f32, String> {
+    pub fn as_float(&self) -> Result<f32, DashboardError> {
         match self {
             UIStyleValue::Float(f) => Ok(*f),
             UIStyleValue::Integer(i) => Ok(*i as f32),
-            _ => Err("Value is not a float".to_string()),
+            _ => Err(DashboardError::Style("Value is not a float".to_string())),
         }
     }
     
-    pub fn as_integer(&self) -> Result<u32, String> {
+    pub fn as_integer(&self) -> Result<u32, DashboardError> {
         match self {
             UIStyleValue::Integer(i) => Ok(*i),
             UIStyleValue::Float(f) => Ok(*f as u32),
-            _ => Err("Value is not an integer".to_string()),
+            _ => Err(DashboardError::Style("Value is not an integer".to_string())),
         }
     }
     
-    pub fn as_bool(&self) -> Result<bool, String> {
+    pub fn as_bool(&self) -> Result<bool, DashboardError> {
         match self {
             UIStyleValue::Boolean(b) => Ok(*b),
-            _ => Err("Value is not a boolean".to_string()),
+            _ => Err(DashboardError::Style("Value is not a boolean".to_string())),
         }
     }
     
-    pub fn as_string(&self) -> Result<&str, String> {
+    pub fn as_string(&self) -> Result<&str, DashboardError> {
         match self {
             UIStyleValue::String(s) => Ok(s),
-            _ => Err("Value is not a string".to_string()),
+            _ => Err(DashboardError::Style("Value is not a string".to_string())),
         }
     }
+    
+    /// Palette color name if this value is an "@name" reference
+    fn palette_reference(&self) -> Option<&str> {
+        match self {
+            UIStyleValue::String(s) | UIStyleValue::Color(s) => s.strip_prefix('@'),
+            _ => None,
+        }
+    }
+    
+    fn type_name(&self) -> &'static str {
+        match self {
+            UIStyleValue::Color(_) => "Color",
+            UIStyleValue::Float(_) => "Float",
+            UIStyleValue::Integer(_) => "Integer",
+            UIStyleValue::Boolean(_) => "Boolean",
+            UIStyleValue::String(_) => "String",
+        }
+    }
+    
+    /// Whether a value of this variant is accepted where `expected`'s variant is declared.
+    /// Floats and integers convert into each other, matching as_float/as_integer.
+    fn matches_type_of(&self, expected: &UIStyleValue) -> bool {
+        matches!(
+            (self, expected),
+            (UIStyleValue::Float(_) | UIStyleValue::Integer(_), UIStyleValue::Float(_) | UIStyleValue::Integer(_))
+        ) || std::mem::discriminant(self) == std::mem::discriminant(expected)
+    }
 }
 
 // =============================================================================
 // UI STYLE MAIN STRUCT
 // =============================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UIStyle {
     values: HashMap<String, HashMap<String, UIStyleValue>>,
+    // Group consulted before "default" when no explicit group is requested
+    #[serde(skip)]
+    active_theme: Option<String>,
 }
 
 impl UIStyle {
     pub fn new() -> Self {
         let mut style = UIStyle {
             values: HashMap::new(),
+            active_theme: None,
         };
         style.load_defaults();
         style
@@ -300,10 +383,10 @@ impl UIStyle {
     
     /// Load style from JSON string
     /// Supports both old flat format and new grouped format
-    pub fn from_json(json_str: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn from_json(json_str: &str) -> Result<Self, DashboardError> {
         // Try to parse as new grouped format first
         if let Ok(grouped_values) = serde_json::from_str::<HashMap<String, HashMap<String, UIStyleValue>>>(json_str) {
-            let mut style = UIStyle { values: grouped_values };
+            let mut style = UIStyle { values: grouped_values, active_theme: None };
             // Ensure we have a default group
             if !style.values.contains_key("default") {
                 style.values.insert("default".to_string(), HashMap::new());
@@ -331,13 +414,87 @@ impl UIStyle {
     }
     
     /// Load style from JSON file
-    pub fn from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn from_file(path: &str) -> Result<Self, DashboardError> {
         let json_str = std::fs::read_to_string(path)?;
         Self::from_json(&json_str)
     }
     
+    /// Check loaded values against the declared style keys.
+    /// Returns one warning per unknown key, value of the wrong type, or unparsable color.
+    pub fn validate(&self) -> Vec<String> {
+        let declared = UIStyle::new();
+        let mut warnings = Vec::new();
+        for (group, values) in &self.values {
+            for (key, value) in values {
+                let value = match self.resolve_palette(value) {
+                    Ok(resolved) => resolved,
+                    Err(e) => {
+                        warnings.push(format!("Style key '{}' in group '{}': {}", key, group, e));
+                        continue;
+                    }
+                };
+                // Palette names are free-form, they only have to be colors
+                if group == PALETTE_GROUP {
+                    if let Err(e) = value.as_color() {
+                        warnings.push(format!("Palette color '{}': {}", key, e));
+                    }
+                    continue;
+                }
+                // Brightness has no default (it's runtime state) but is a valid override
+                let expected = match key.as_str() {
+                    GLOBAL_BRIGHTNESS => Some(&UIStyleValue::Float(1.0)),
+                    _ => declared.get(key),
+                };
+                match expected {
+                    None => warnings.push(format!("Unknown style key '{}' in group '{}'", key, group)),
+                    Some(expected) if !value.matches_type_of(expected) => warnings.push(format!(
+                        "Style key '{}' in group '{}' should be {}, found {}",
+                        key, group, expected.type_name(), value.type_name()
+                    )),
+                    Some(_) => {
+                        if let UIStyleValue::Color(color) = value {
+                            if let Err(e) = parse_color(color) {
+                                warnings.push(format!("Style key '{}' in group '{}': {}", key, group, e));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        warnings.sort();
+        warnings
+    }
+    
+    /// Follow "@name" references through the palette group to a concrete value.
+    /// Errors on dangling references and reference cycles.
+    fn resolve_palette<'a>(&'a self, value: &'a UIStyleValue) -> Result<&'a UIStyleValue, DashboardError> {
+        let mut current = value;
+        let mut visited: Vec<&str> = Vec::new();
+        while let Some(name) = current.palette_reference() {
+            if visited.contains(&name) {
+                return Err(DashboardError::Style(format!("Palette reference cycle through @{}", name)));
+            }
+            visited.push(name);
+            current = self.values.get(PALETTE_GROUP)
+                .and_then(|palette| palette.get(name))
+                .ok_or_else(|| DashboardError::Style(format!("Unknown palette color @{}", name)))?;
+        }
+        Ok(current)
+    }
+    
+    /// Start watching a style file; the returned watcher yields a freshly loaded style
+    /// whenever the file's modification time changes
+    pub fn watch_file(path: &str) -> StyleWatcher {
+        StyleWatcher::new(path)
+    }
+    
+    /// Write the complete built-in style as a starting point for a custom style file
+    pub fn dump_defaults_to_file(path: &str) -> Result<(), DashboardError> {
+        UIStyle::new().to_file(path)
+    }
+    
     /// Save style to JSON file
-    pub fn to_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn to_file(&self, path: &str) -> Result<(), DashboardError> {
         let json_str = self.to_json()?;
         std::fs::write(path, json_str)?;
         Ok(())
@@ -350,12 +507,10 @@ impl UIStyle {
     
     /// Get a style value with optional group parameter
     pub fn get_with_group(&self, key: &str, group: Option<&str>) -> Option<&UIStyleValue> {
-        // Try specific group first if provided
-        if let Some(group_name) = group {
-            if let Some(group_values) = self.values.get(group_name) {
-                if let Some(value) = group_values.get(key) {
-                    return Some(value);
-                }
+        // Try specific group first if provided, then the active theme
+        for group_name in [group, self.active_theme.as_deref()].into_iter().flatten() {
+            if let Some(value) = self.values.get(group_name).and_then(|values| values.get(key)) {
+                return Some(value);
             }
         }
         
@@ -363,6 +518,62 @@ impl UIStyle {
         self.values.get("default")?.get(key)
     }
     
+    /// Make `theme` the group consulted before "default"; "default" itself turns themes off
+    pub fn set_active_theme(&mut self, theme: &str) -> Result<(), DashboardError> {
+        if theme == "default" {
+            self.active_theme = None;
+            return Ok(());
+        }
+        if !self.values.contains_key(theme) {
+            return Err(DashboardError::Style(format!("Unknown style theme: {}", theme)));
+        }
+        self.active_theme = Some(theme.to_string());
+        Ok(())
+    }
+    
+    pub fn active_theme(&self) -> Option<&str> {
+        self.active_theme.as_deref()
+    }
+    
+    /// Value of `key` as seen with `theme` active, ignoring the currently active theme
+    fn get_in_theme(&self, key: &str, theme: &str) -> Option<&UIStyleValue> {
+        self.values.get(theme).and_then(|values| values.get(key))
+            .or_else(|| self.values.get("default")?.get(key))
+    }
+    
+    /// Transient style part way between two themes, for animating a theme switch.
+    /// Colors are interpolated linearly; other values switch from `from` to `to` at t = 0.5.
+    /// The result has no active theme: the blended values live in its "default" group.
+    pub fn blend_themes(&self, from: &str, to: &str, t: f32) -> UIStyle {
+        let t = t.clamp(0.0, 1.0);
+        let mut blended = UIStyle { values: self.values.clone(), active_theme: None };
+        let keys: HashSet<&String> = ["default", from, to].into_iter()
+            .filter_map(|group| self.values.get(group))
+            .flat_map(|values| values.keys())
+            .collect();
+
+        for key in keys {
+            let (Some(start), Some(end)) = (self.get_in_theme(key, from), self.get_in_theme(key, to)) else {
+                continue;
+            };
+            let color = |value| match self.resolve_palette(value) {
+                Ok(UIStyleValue::Color(color)) => parse_color_rgba(color).ok(),
+                _ => None,
+            };
+            let value = match (color(start), color(end)) {
+                (Some(a), Some(b)) => UIStyleValue::Color(format_color_rgba((
+                    a.0 + (b.0 - a.0) * t,
+                    a.1 + (b.1 - a.1) * t,
+                    a.2 + (b.2 - a.2) * t,
+                    a.3 + (b.3 - a.3) * t,
+                ))),
+                _ => if t < 0.5 { start.clone() } else { end.clone() },
+            };
+            blended.set(key, value);
+        }
+        blended
+    }
+    
     /// Set a style value in specific group (defaults to "default" group)
     pub fn set(&mut self, key: &str, value: UIStyleValue) {
         self.set_with_group(key, value, None);
@@ -388,10 +599,10 @@ impl UIStyle {
     /// Get color value with optional group parameter and brightness applied
     pub fn get_color_with_group(&self, key: &str, default: (f32, f32, f32), group: Option<&str>) -> (f32, f32, f32) {
         match self.get_with_group(key, group) {
-            Some(value) => match value.as_color() {
-                Ok((r, g, b)) => (r, g, b),
-                Err(_) => {
-                    log::warn!("Warning: Style key '{}' exists but cannot be converted to color, using default: ({}, {}, {})", key, default.0, default.1, default.2);
+            Some(value) => match self.resolve_palette(value).and_then(|v| v.as_color()) {
+                Ok(rgb) => self.apply_contrast(rgb, group),
+                Err(e) => {
+                    log::warn!("Warning: Style key '{}' exists but cannot be converted to color ({}), using default: ({}, {}, {})", key, e, default.0, default.1, default.2);
                     default
                 }
             },
@@ -402,6 +613,19 @@ impl UIStyle {
         }
     }
     
+    /// Push each channel away from (contrast > 1) or towards (contrast < 1) mid-grey by GLOBAL_CONTRAST.
+    /// Brightness is applied afterwards by the graphics context when drawing.
+    fn apply_contrast(&self, color: (f32, f32, f32), group: Option<&str>) -> (f32, f32, f32) {
+        let contrast = self.get_with_group(GLOBAL_CONTRAST, group)
+            .and_then(|value| value.as_float().ok())
+            .unwrap_or(1.0);
+        if contrast == 1.0 {
+            return color;
+        }
+        let stretch = |channel: f32| ((channel - 0.5) * contrast + 0.5).clamp(0.0, 1.0);
+        (stretch(color.0), stretch(color.1), stretch(color.2))
+    }
+    
     /// Get color value with alpha and brightness applied
     pub fn get_color_rgba(&self, key: &str, default: (f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
         self.get_color_rgba_with_group(key, default, None)
@@ -409,8 +633,22 @@ impl UIStyle {
     
     /// Get color value with alpha, optional group parameter, and brightness applied
     pub fn get_color_rgba_with_group(&self, key: &str, default: (f32, f32, f32, f32), group: Option<&str>) -> (f32, f32, f32, f32) {
-        let (r, g, b) = self.get_color_with_group(key, (default.0, default.1, default.2), group);
-        (r, g, b, default.3)
+        match self.get_with_group(key, group) {
+            Some(value) => match self.resolve_palette(value).and_then(|v| v.as_color_rgba()) {
+                Ok((r, g, b, a)) => {
+                    let (r, g, b) = self.apply_contrast((r, g, b), group);
+                    (r, g, b, a)
+                }
+                Err(e) => {
+                    log::warn!("Warning: Style key '{}' exists but cannot be converted to color ({}), using default: ({}, {}, {}, {})", key, e, default.0, default.1, default.2, default.3);
+                    default
+                }
+            },
+            None => {
+                log::warn!("Warning: Style key '{}' not found, using default color: ({}, {}, {}, {})", key, default.0, default.1, default.2, default.3);
+                default
+            }
+        }
     }
     
     /// Get float value with fallback
@@ -520,6 +758,9 @@ impl UIStyle {
         //self.set(PAGE_STATUS_FONT, UIStyleValue::String("/home/user/Work/Niva_Dashboard_Rpi/Niva_dashboard_rpi/fonts/OpenGostTypeB.ttf".to_string()));
         self.set(PAGE_STATUS_FONT, UIStyleValue::String(TERMINAL_FONT_PATH.to_string()));
         self.set(PAGE_STATUS_FONT_SIZE, UIStyleValue::Integer(14));
+        self.set(PAGE_IDLE_DIM_TIMEOUT, UIStyleValue::Float(300.0));
+        self.set(PAGE_IDLE_DIM_BRIGHTNESS, UIStyleValue::Float(0.2));
+        self.set(PAGE_LONG_PRESS_TIME, UIStyleValue::Float(0.8));
         self.set(PAGE_STATUS_COLOR, UIStyleValue::Color("#FFFFFF".to_string()));
 
         // Gauge defaults
@@ -614,6 +855,9 @@ impl UIStyle {
         self.set(BAR_SEGMENT_COUNT, UIStyleValue::Integer(10));
         self.set(BAR_SEGMENT_GAP, UIStyleValue::Float(2.0));
 
+        self.set(SPARKLINE_COLOR, UIStyleValue::Color("#FF7D00".to_string()));
+        self.set(SPARKLINE_THICKNESS, UIStyleValue::Float(2.0));
+
         // Text defaults
         self.set(TEXT_PRIMARY_COLOR, UIStyleValue::Color("#FF7D00".to_string()));
         self.set(TEXT_SECONDARY_COLOR, UIStyleValue::Color("#b77700".to_string()));
@@ -651,6 +895,11 @@ impl UIStyle {
         self.set(INDICATOR_GLOW_ENABLED, UIStyleValue::Boolean(false));
         self.set(INDICATOR_GLOW_RADIUS, UIStyleValue::Float(5.0));
         self.set(INDICATOR_SIZE, UIStyleValue::Float(24.0));
+
+        self.set(ICON_TURN_SIGNAL, UIStyleValue::String(ICON_TURN_SIGNAL_PATH.to_string()));
+        self.set(ICON_HIGH_BEAM, UIStyleValue::String(ICON_HIGH_BEAM_PATH.to_string()));
+        self.set(ICON_PARK_BRAKE, UIStyleValue::String(ICON_PARK_BRAKE_PATH.to_string()));
+        self.set(ICON_CHECK_ENGINE, UIStyleValue::String(ICON_CHECK_ENGINE_PATH.to_string()));
         
         // Digital display defaults (amber theme like classic LCD displays)
         self.set(DIGITAL_DISPLAY_FONT, UIStyleValue::String(DIGITAL_DISPLAY_FONT_ITALIC_PATH.to_string()));
@@ -665,6 +914,8 @@ impl UIStyle {
         self.set(DIGITAL_DISPLAY_BORDER_COLOR, UIStyleValue::Color("#FFA500".to_string()));
         self.set(DIGITAL_DISPLAY_BORDER_WIDTH, UIStyleValue::Float(4.0));
         self.set(DIGITAL_DISPLAY_BORDER_RADIUS, UIStyleValue::Float(10.0));
+        self.set(DIGITAL_DISPLAY_SHADOW_COLOR, UIStyleValue::Color("#000000".to_string()));
+        self.set(DIGITAL_DISPLAY_SHADOW_OFFSET, UIStyleValue::Float(3.0));
 
         // Extended digital display font defaults
         self.set(DIGITAL_DISPLAY_FONT_ITALIC, UIStyleValue::String(DIGITAL_DISPLAY_FONT_ITALIC_PATH.to_string()));
@@ -687,6 +938,39 @@ impl UIStyle {
         self.set(ALERT_MARGIN, UIStyleValue::Float(8.0));
         self.set(ALERT_CORNER_RADIUS, UIStyleValue::Float(8.0));
         self.set(ALERT_SOUND_PATH, UIStyleValue::String("".to_string())); // No sound by default
+
+        self.load_theme_defaults();
+    }
+    
+    /// Built-in day/night themes; groups already provided (e.g. by a style file) are left alone
+    fn load_theme_defaults(&mut self) {
+        // Day is the default palette as-is
+        self.values.entry(THEME_DAY.to_string()).or_default();
+
+        // Night: dim, red-shifted palette that doesn't ruin night vision.
+        // Warning and critical colors are kept so alerts still stand out.
+        if !self.values.contains_key(THEME_NIGHT) {
+            self.set_with_group(TEXT_PRIMARY_COLOR, UIStyleValue::Color("#B03000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(TEXT_SECONDARY_COLOR, UIStyleValue::Color("#801800".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(PAGE_BUTTON_LABEL_COLOR, UIStyleValue::Color("#A02000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(PAGE_STATUS_COLOR, UIStyleValue::Color("#802000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(GAUGE_BORDER_COLOR, UIStyleValue::Color("#802000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(GAUGE_NEEDLE_COLOR, UIStyleValue::Color("#C00000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(GAUGE_MAJOR_MARK_COLOR, UIStyleValue::Color("#A02000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(GAUGE_MINOR_MARK_COLOR, UIStyleValue::Color("#802000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(GAUGE_LABEL_COLOR, UIStyleValue::Color("#A02000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(GAUGE_TITLE_COLOR, UIStyleValue::Color("#A02000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(GAUGE_UNIT_COLOR, UIStyleValue::Color("#802000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(BAR_BORDER_COLOR, UIStyleValue::Color("#902000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(BAR_NORMAL_COLOR, UIStyleValue::Color("#B03000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(BAR_MARKS_COLOR, UIStyleValue::Color("#902000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(BAR_MARK_LABELS_COLOR, UIStyleValue::Color("#902000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(DIGITAL_DISPLAY_ACTIVE_COLOR, UIStyleValue::Color("#B03000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(DIGITAL_DISPLAY_INACTIVE_COLOR, UIStyleValue::Color("#401000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(DIGITAL_DISPLAY_BORDER_COLOR, UIStyleValue::Color("#902000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(TERMINAL_BORDER_COLOR, UIStyleValue::Color("#802000".to_string()), Some(THEME_NIGHT));
+            self.set_with_group(TERMINAL_TEXT_COLOR, UIStyleValue::Color("#A02000".to_string()), Some(THEME_NIGHT));
+        }
     }
 }
 
@@ -696,56 +980,138 @@ impl Default for UIStyle {
     }
 }
 
+/// Polls a style file's mtime so styles can be tuned on a running dashboard
+#[derive(Debug)]
+pub struct StyleWatcher {
+    path: PathBuf,
+    last_modified: Option<SystemTime>,
+}
+
+impl StyleWatcher {
+    fn new(path: &str) -> Self {
+        let path = PathBuf::from(path);
+        let last_modified = Self::modified(&path);
+        StyleWatcher { path, last_modified }
+    }
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reload the style if the file changed since the last poll.
+    /// A file that fails to parse is logged and skipped until it changes again,
+    /// so the caller keeps its previous valid style.
+    pub fn poll(&mut self) -> Option<UIStyle> {
+        let modified = Self::modified(&self.path);
+        if modified.is_none() || modified == self.last_modified {
+            return None;
+        }
+        self.last_modified = modified;
+
+        match UIStyle::from_file(&self.path.to_string_lossy()) {
+            Ok(style) => {
+                log::info!("Reloaded UI style from {}", self.path.display());
+                for warning in style.validate() {
+                    log::warn!("{}: {}", self.path.display(), warning);
+                }
+                Some(style)
+            }
+            Err(e) => {
+                log::error!("Failed to reload UI style from {}, keeping previous style: {}", self.path.display(), e);
+                None
+            }
+        }
+    }
+}
+
 // =============================================================================
 // HELPER FUNCTIONS
 // =============================================================================
 
-/// Parse color string to RGB values (0.0-1.0)
+/// Parse color string to RGB values (0.0-1.0), ignoring any alpha
 fn parse_color(color_str: &str) -> Result<(f32, f32, f32), String> {
-    if color_str.starts_with('#') {
-        // Hex color: #RRGGBB or #RGB
-        let hex = &color_str[1..];
-        match hex.len() {
-            3 => {
-                // #RGB -> #RRGGBB
-                let r = u8::from_str_radix(&hex[0..1].repeat(2), 16)
-                    .map_err(|_| format!("Invalid hex color: {}", color_str))?;
-                let g = u8::from_str_radix(&hex[1..2].repeat(2), 16)
-                    .map_err(|_| format!("Invalid hex color: {}", color_str))?;
-                let b = u8::from_str_radix(&hex[2..3].repeat(2), 16)
-                    .map_err(|_| format!("Invalid hex color: {}", color_str))?;
-                Ok((r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0))
-            },
-            6 => {
-                // #RRGGBB
-                let r = u8::from_str_radix(&hex[0..2], 16)
-                    .map_err(|_| format!("Invalid hex color: {}", color_str))?;
-                let g = u8::from_str_radix(&hex[2..4], 16)
-                    .map_err(|_| format!("Invalid hex color: {}", color_str))?;
-                let b = u8::from_str_radix(&hex[4..6], 16)
-                    .map_err(|_| format!("Invalid hex color: {}", color_str))?;
-                Ok((r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0))
-            },
-            _ => Err(format!("Invalid hex color format: {}", color_str)),
+    let (r, g, b, _) = parse_color_rgba(color_str)?;
+    Ok((r, g, b))
+}
+
+/// Parse color string to RGBA values (0.0-1.0).
+/// Accepts #RGB, #RGBA, #RRGGBB, #RRGGBBAA and color names; forms without alpha are opaque.
+fn parse_color_rgba(color_str: &str) -> Result<(f32, f32, f32, f32), String> {
+    if let Some(hex) = color_str.strip_prefix('#') {
+        // Short forms repeat each digit: #F80 == #FF8800
+        let digits_per_channel = match hex.len() {
+            3 | 4 => 1,
+            6 | 8 => 2,
+            _ => return Err(format!("Invalid hex color format: {}", color_str)),
+        };
+        let mut channels = [1.0f32; 4];
+        for (index, channel) in channels.iter_mut().take(hex.len() / digits_per_channel).enumerate() {
+            let digits = hex.get(index * digits_per_channel..(index + 1) * digits_per_channel)
+                .ok_or_else(|| format!("Invalid hex color: {}", color_str))?;
+            let value = u8::from_str_radix(&digits.repeat(3 - digits_per_channel), 16)
+                .map_err(|_| format!("Invalid hex color: {}", color_str))?;
+            *channel = value as f32 / 255.0;
         }
+        Ok((channels[0], channels[1], channels[2], channels[3]))
     } else {
         // Named color
-        match color_str.to_lowercase().as_str() {
-            "black" => Ok((0.0, 0.0, 0.0)),
-            "white" => Ok((1.0, 1.0, 1.0)),
-            "red" => Ok((1.0, 0.0, 0.0)),
-            "green" => Ok((0.0, 1.0, 0.0)),
-            "blue" => Ok((0.0, 0.0, 1.0)),
-            "yellow" => Ok((1.0, 1.0, 0.0)),
-            "cyan" => Ok((0.0, 1.0, 1.0)),
-            "magenta" => Ok((1.0, 0.0, 1.0)),
-            "gray" | "grey" => Ok((0.5, 0.5, 0.5)),
-            "orange" => Ok((1.0, 0.5, 0.0)),
-            _ => Err(format!("Unknown color name: {}", color_str)),
-        }
+        let (r, g, b) = match color_str.to_lowercase().as_str() {
+            "black" => (0.0, 0.0, 0.0),
+            "white" => (1.0, 1.0, 1.0),
+            "red" => (1.0, 0.0, 0.0),
+            "green" => (0.0, 1.0, 0.0),
+            "blue" => (0.0, 0.0, 1.0),
+            "yellow" => (1.0, 1.0, 0.0),
+            "cyan" => (0.0, 1.0, 1.0),
+            "magenta" => (1.0, 0.0, 1.0),
+            "gray" | "grey" => (0.5, 0.5, 0.5),
+            "orange" => (1.0, 0.5, 0.0),
+            _ => return Err(format!("Unknown color name: {}", color_str)),
+        };
+        Ok((r, g, b, 1.0))
     }
 }
 
+/// Format RGBA values (0.0-1.0) as a #RRGGBBAA color string
+fn format_color_rgba(color: (f32, f32, f32, f32)) -> String {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02X}{:02X}{:02X}{:02X}", channel(color.0), channel(color.1), channel(color.2), channel(color.3))
+}
+
+/// Locate a font file on this machine. The file name of `font_path` is looked up in
+/// $NIVA_FONT_DIR, then in a relative fonts/ directory (../fonts/ too, for `cargo run` from
+/// the crate directory of a checkout), and only then at `font_path` itself.
+pub fn resolve_font_path(font_path: &str) -> String {
+    resolve_font_path_in(font_path, std::env::var(FONT_DIR_ENV_VAR).ok().as_deref())
+}
+
+fn resolve_font_path_in(font_path: &str, font_dir: Option<&str>) -> String {
+    resolve_asset_path_in(font_path, font_dir, ["fonts", "../fonts"])
+}
+
+/// Locate an icon image the way fonts are located: $NIVA_ICON_DIR, then icons/ and
+/// ../icons/, and only then `icon_path` itself.
+pub fn resolve_icon_path(icon_path: &str) -> String {
+    resolve_asset_path_in(icon_path, std::env::var(ICON_DIR_ENV_VAR).ok().as_deref(), ["icons", "../icons"])
+}
+
+fn resolve_asset_path_in(path: &str, override_dir: Option<&str>, relative_dirs: [&str; 2]) -> String {
+    let Some(file_name) = Path::new(path).file_name() else {
+        return path.to_string();
+    };
+    override_dir.into_iter()
+        .chain(relative_dirs)
+        .map(|dir| Path::new(dir).join(file_name))
+        .chain(std::iter::once(PathBuf::from(path)))
+        .find(|candidate| candidate.is_file())
+        .map(|found| found.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
 /// Calculate the average of two RGB colors
 /// Returns a color that is the blend of color1 and color2 with equal weight (0.5 each)
 pub fn average_colors(color1: (f32, f32, f32), color2: (f32, f32, f32)) -> (f32, f32, f32) {
@@ -792,6 +1158,31 @@ mod tests {
         assert!(parse_color("invalid").is_err());
     }
     
+    #[test]
+    fn test_color_parsing_rgba() {
+        let close = |a: (f32, f32, f32, f32), b: (f32, f32, f32, f32)| {
+            (a.0 - b.0).abs() < 1e-6 && (a.1 - b.1).abs() < 1e-6 && (a.2 - b.2).abs() < 1e-6 && (a.3 - b.3).abs() < 1e-6
+        };
+        assert!(close(parse_color_rgba("#FF000080").unwrap(), (1.0, 0.0, 0.0, 128.0 / 255.0)));
+        assert!(close(parse_color_rgba("#F008").unwrap(), (1.0, 0.0, 0.0, 136.0 / 255.0)));
+        // Forms without alpha stay opaque
+        assert_eq!(parse_color_rgba("#00FF00"), Ok((0.0, 1.0, 0.0, 1.0)));
+        assert_eq!(parse_color_rgba("#0F0"), Ok((0.0, 1.0, 0.0, 1.0)));
+        assert_eq!(parse_color_rgba("blue"), Ok((0.0, 0.0, 1.0, 1.0)));
+        // RGB accessors still work on colors carrying alpha
+        assert_eq!(parse_color("#FF000080"), Ok((1.0, 0.0, 0.0)));
+
+        for invalid in ["#", "#F", "#FF", "#FF000", "#FF00000", "#FF0000800", "#GG0000", "#FF00ÿ"] {
+            assert!(parse_color_rgba(invalid).is_err(), "{} should be rejected", invalid);
+        }
+
+        let value = UIStyleValue::Color("#00000040".to_string());
+        assert!(close(value.as_color_rgba().unwrap(), (0.0, 0.0, 0.0, 64.0 / 255.0)));
+        let mut style = UIStyle::new();
+        style.set(ALERT_BACKGROUND_COLOR, value);
+        assert!(close(style.get_color_rgba(ALERT_BACKGROUND_COLOR, (1.0, 1.0, 1.0, 1.0)), (0.0, 0.0, 0.0, 64.0 / 255.0)));
+    }
+    
     #[test]
     fn test_style_value_conversion() {
         let color_val = UIStyleValue::Color("#FF0000".to_string());
@@ -819,6 +1210,211 @@ mod tests {
         assert_eq!(loaded_style.get_bool(GAUGE_LABEL_ENABLED, false), true);
     }
 
+    #[test]
+    fn test_validate_flags_unknown_keys_and_type_mismatches() {
+        assert!(UIStyle::new().validate().is_empty());
+
+        let style = UIStyle::from_json(r##"{
+            "gauge_border_colour": {"Color": "#FFFFFF"},
+            "gauge_border_color": {"Float": 1.0},
+            "gauge_background_color": {"Color": "#FF000"},
+            "gauge_border_width": {"Integer": 3},
+            "global_brightness": {"Float": 0.5}
+        }"##).unwrap();
+        assert_eq!(style.validate(), vec![
+            "Style key 'gauge_background_color' in group 'default': Invalid hex color format: #FF000".to_string(),
+            "Style key 'gauge_border_color' in group 'default' should be Color, found Float".to_string(),
+            "Unknown style key 'gauge_border_colour' in group 'default'".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_palette_references() {
+        let mut style = UIStyle::from_json(r##"{
+            "gauge_border_color": {"String": "@brand_red"},
+            "gauge_label_color": {"Color": "@accent"},
+            "text_primary_color": {"String": "@missing"},
+            "text_secondary_color": {"String": "@loop_a"}
+        }"##).unwrap();
+        style.set_with_group("brand_red", UIStyleValue::Color("#CC0000".to_string()), Some(PALETTE_GROUP));
+        style.set_with_group("accent", UIStyleValue::String("@brand_red".to_string()), Some(PALETTE_GROUP));
+        style.set_with_group("loop_a", UIStyleValue::String("@loop_b".to_string()), Some(PALETTE_GROUP));
+        style.set_with_group("loop_b", UIStyleValue::String("@loop_a".to_string()), Some(PALETTE_GROUP));
+
+        let brand_red = parse_color("#CC0000").unwrap();
+        assert_eq!(style.get_color(GAUGE_BORDER_COLOR, (0.0, 0.0, 0.0)), brand_red);
+        // References may chain through other palette entries
+        assert_eq!(style.get_color(GAUGE_LABEL_COLOR, (0.0, 0.0, 0.0)), brand_red);
+        assert_eq!(style.get_color_rgba(GAUGE_BORDER_COLOR, (0.0, 0.0, 0.0, 0.0)), (brand_red.0, brand_red.1, brand_red.2, 1.0));
+        // Dangling references and cycles fall back to the caller's default
+        assert_eq!(style.get_color(TEXT_PRIMARY_COLOR, (0.1, 0.2, 0.3)), (0.1, 0.2, 0.3));
+        assert_eq!(style.get_color(TEXT_SECONDARY_COLOR, (0.1, 0.2, 0.3)), (0.1, 0.2, 0.3));
+
+        let warnings = style.validate();
+        assert!(warnings.contains(&"Style key 'text_primary_color' in group 'default': Unknown palette color @missing".to_string()));
+        assert!(warnings.iter().any(|w| w.starts_with("Style key 'text_secondary_color'") && w.contains("cycle")));
+        assert!(!warnings.iter().any(|w| w.contains("gauge_border_color") || w.contains("gauge_label_color")));
+    }
+
+    #[test]
+    fn test_global_contrast() {
+        let mut style = UIStyle::new();
+        style.set(TEXT_PRIMARY_COLOR, UIStyleValue::Color("#B3B3B3".to_string()));
+        style.set(TEXT_SECONDARY_COLOR, UIStyleValue::Color("#4D4D4D".to_string()));
+        let separation = |style: &UIStyle| {
+            style.get_color(TEXT_PRIMARY_COLOR, (0.0, 0.0, 0.0)).0 - style.get_color(TEXT_SECONDARY_COLOR, (0.0, 0.0, 0.0)).0
+        };
+
+        // Default contrast of 1.0 leaves colors untouched
+        assert_eq!(style.get_color(TEXT_PRIMARY_COLOR, (0.0, 0.0, 0.0)), parse_color("#B3B3B3").unwrap());
+        let base = separation(&style);
+
+        style.set(GLOBAL_CONTRAST, UIStyleValue::Float(1.5));
+        assert!(separation(&style) > base);
+        // Mid-grey is the pivot, and channels clamp at the ends
+        style.set(TEXT_ACCENT_COLOR, UIStyleValue::Color("#FF0080".to_string()));
+        let (r, g, b) = style.get_color(TEXT_ACCENT_COLOR, (0.0, 0.0, 0.0));
+        assert_eq!((r, g), (1.0, 0.0));
+        assert!((b - 0.5).abs() < 0.01);
+        assert_eq!(style.get_color_rgba(TEXT_ACCENT_COLOR, (0.0, 0.0, 0.0, 0.0)).0, 1.0);
+
+        style.set(GLOBAL_CONTRAST, UIStyleValue::Float(0.5));
+        assert!(separation(&style) < base);
+    }
+
+    #[test]
+    fn test_blend_themes() {
+        let mut style = UIStyle::new();
+        style.set_with_group(TEXT_PRIMARY_COLOR, UIStyleValue::Color("#000000".to_string()), Some("dark"));
+        style.set_with_group(TEXT_PRIMARY_COLOR, UIStyleValue::Color("#FF804000".to_string()), Some("light"));
+        style.set_with_group(GAUGE_BORDER_WIDTH, UIStyleValue::Float(1.0), Some("dark"));
+        style.set_with_group(GAUGE_BORDER_WIDTH, UIStyleValue::Float(3.0), Some("light"));
+
+        let mid = style.blend_themes("dark", "light", 0.5);
+        let (r, g, b, a) = mid.get_color_rgba(TEXT_PRIMARY_COLOR, (0.0, 0.0, 0.0, 0.0));
+        let step = 1.0 / 255.0;
+        assert!((r - 0.5).abs() <= step && (g - 0.25).abs() <= step && (b - 0.125).abs() <= step && (a - 0.5).abs() <= step);
+        assert_eq!(mid.active_theme(), None);
+
+        // Colors only one theme overrides blend towards the default
+        style.set_with_group(GAUGE_NEEDLE_COLOR, UIStyleValue::Color("#00FF00".to_string()), Some("light"));
+        let (r, g, _) = style.blend_themes("dark", "light", 0.5).get_color(GAUGE_NEEDLE_COLOR, (0.0, 0.0, 0.0));
+        assert!((r - 0.5).abs() <= step && (g - 0.5).abs() <= step);
+
+        // Non-color values snap halfway through
+        assert_eq!(style.blend_themes("dark", "light", 0.49).get_float(GAUGE_BORDER_WIDTH, 0.0), 1.0);
+        assert_eq!(style.blend_themes("dark", "light", 0.5).get_float(GAUGE_BORDER_WIDTH, 0.0), 3.0);
+
+        // The ends reproduce each theme
+        assert_eq!(style.blend_themes("dark", "light", 0.0).get_color(TEXT_PRIMARY_COLOR, (1.0, 1.0, 1.0)), (0.0, 0.0, 0.0));
+        assert_eq!(style.blend_themes("dark", "light", 1.0).get_color(TEXT_PRIMARY_COLOR, (0.0, 0.0, 0.0)), parse_color("#FF8040").unwrap());
+    }
+
+    #[test]
+    fn test_theme_switching() {
+        let mut style = UIStyle::new();
+        assert_eq!(style.active_theme(), None);
+        let day_text = style.get_color(TEXT_PRIMARY_COLOR, (0.0, 0.0, 0.0));
+
+        style.set_active_theme(THEME_NIGHT).unwrap();
+        let night_text = style.get_color(TEXT_PRIMARY_COLOR, (0.0, 0.0, 0.0));
+        assert_ne!(night_text, day_text);
+        assert_eq!(night_text, parse_color("#B03000").unwrap());
+        // Keys the theme doesn't override fall back to the default group
+        assert_eq!(style.get_color(ALERT_CRITICAL_COLOR, (0.0, 0.0, 0.0)), (1.0, 0.0, 0.0));
+        assert_eq!(style.get_float(GAUGE_BORDER_WIDTH, 0.0), 2.0);
+        // An explicit group still wins over the theme
+        style.set_with_group(TEXT_PRIMARY_COLOR, UIStyleValue::Color("#0000FF".to_string()), Some("alerts"));
+        assert_eq!(style.get_color_with_group(TEXT_PRIMARY_COLOR, (0.0, 0.0, 0.0), Some("alerts")), (0.0, 0.0, 1.0));
+
+        style.set_active_theme(THEME_DAY).unwrap();
+        assert_eq!(style.get_color(TEXT_PRIMARY_COLOR, (0.0, 0.0, 0.0)), day_text);
+        assert!(style.set_active_theme("dusk").is_err());
+        assert_eq!(style.active_theme(), Some(THEME_DAY));
+        style.set_active_theme("default").unwrap();
+        assert_eq!(style.active_theme(), None);
+    }
+
+    #[test]
+    fn test_resolve_font_path() {
+        let font_dir = std::env::temp_dir().join(format!("niva_fonts_{}", std::process::id()));
+        std::fs::create_dir_all(&font_dir).unwrap();
+        std::fs::write(font_dir.join("DSEG7ClassicMini-Regular.ttf"), b"").unwrap();
+
+        // Env dir wins over the hardcoded absolute path
+        let resolved = resolve_font_path_in(DIGITAL_DISPLAY_FONT_PATH, font_dir.to_str());
+        assert_eq!(resolved, font_dir.join("DSEG7ClassicMini-Regular.ttf").to_string_lossy());
+
+        // Tests run from the crate directory, so the checkout's fonts are found relatively
+        assert_eq!(resolve_font_path_in(DEFAULT_GLOBAL_FONT_PATH, font_dir.to_str()), "../fonts/OpenGostTypeB.ttf");
+        assert_eq!(resolve_font_path_in(DEFAULT_GLOBAL_FONT_PATH, None), "../fonts/OpenGostTypeB.ttf");
+
+        // Nothing found anywhere: keep the original so the load error names it
+        assert_eq!(resolve_font_path_in("/nonexistent/Missing.ttf", font_dir.to_str()), "/nonexistent/Missing.ttf");
+
+        std::fs::remove_dir_all(&font_dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_icon_path() {
+        let icon_dir = std::env::temp_dir().join(format!("niva_icons_{}", std::process::id()));
+        std::fs::create_dir_all(&icon_dir).unwrap();
+        std::fs::write(icon_dir.join("high_beam.png"), b"").unwrap();
+
+        // The hardcoded absolute path gives way to the icon directory
+        let resolved = resolve_asset_path_in(ICON_HIGH_BEAM_PATH, icon_dir.to_str(), ["icons", "../icons"]);
+        assert_eq!(resolved, icon_dir.join("high_beam.png").to_string_lossy());
+        assert_eq!(resolve_asset_path_in(ICON_PARK_BRAKE_PATH, icon_dir.to_str(), ["icons", "../icons"]),
+                   ICON_PARK_BRAKE_PATH);
+
+        std::fs::remove_dir_all(&icon_dir).unwrap();
+    }
+
+    #[test]
+    fn test_watch_file_reloads_on_change() {
+        let path = std::env::temp_dir().join(format!("niva_ui_style_{}.json", std::process::id()));
+        let write_style = |json: &str, secs: u64| {
+            std::fs::write(&path, json).unwrap();
+            // Pin the mtime so the test doesn't depend on filesystem timestamp resolution
+            let file = std::fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs)).unwrap();
+        };
+
+        write_style(r##"{"gauge_border_color": {"Color": "#FF0000"}}"##, 1000);
+        let mut watcher = UIStyle::watch_file(path.to_str().unwrap());
+        assert!(watcher.poll().is_none(), "unchanged file must not reload");
+
+        write_style(r##"{"gauge_border_color": {"Color": "#00FF00"}, "global_brightness": {"Float": 0.4}}"##, 2000);
+        let style = watcher.poll().expect("changed file should reload");
+        assert_eq!(style.get_color(GAUGE_BORDER_COLOR, (0.0, 0.0, 0.0)), (0.0, 1.0, 0.0));
+        assert_eq!(style.get_float(GLOBAL_BRIGHTNESS, 1.0), 0.4);
+        // Keys missing from the file keep their defaults
+        assert_eq!(style.get_float(GAUGE_BORDER_WIDTH, 0.0), 2.0);
+        assert!(watcher.poll().is_none());
+
+        // A broken edit is skipped, and picked up again once fixed
+        write_style(r##"{"gauge_border_color": {"Color": "#00FF00""##, 3000);
+        assert!(watcher.poll().is_none());
+        write_style(r##"{"gauge_border_color": {"Color": "#0000FF"}}"##, 4000);
+        let style = watcher.poll().unwrap();
+        assert_eq!(style.get_color(GAUGE_BORDER_COLOR, (0.0, 0.0, 0.0)), (0.0, 0.0, 1.0));
+        assert!(style.get(GLOBAL_BRIGHTNESS).is_none());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_dump_defaults_round_trip() {
+        let path = std::env::temp_dir().join(format!("niva_default_style_{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        UIStyle::dump_defaults_to_file(path).unwrap();
+
+        let loaded = UIStyle::from_file(path).unwrap();
+        assert_eq!(loaded, UIStyle::new());
+        assert!(loaded.validate().is_empty());
+        std::fs::remove_file(path).unwrap();
+    }
+
     #[test]
     fn test_warning_messages() {
         let style = UIStyle::new();
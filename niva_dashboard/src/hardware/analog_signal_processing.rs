@@ -1,9 +1,18 @@
 #![allow(dead_code)]
- 
+use std::time::Duration;
+
 // Raw analog data processors
 
 pub trait AnalogSignalProcessor {
     fn read(&mut self, input: u16) -> Result<u16, String>;
+
+    // Forget past samples, so the next read starts as if it were the first
+    fn reset(&mut self) {}
+
+    // Filters are bypassed when sensor filtering is switched off; calibration steps are not
+    fn is_filter(&self) -> bool {
+        true
+    }
 }
 
 pub struct AnalogSignalProcessorMovingAverage {
@@ -13,11 +22,28 @@ pub struct AnalogSignalProcessorMovingAverage {
 
 impl AnalogSignalProcessorMovingAverage {
     pub fn new(window_size: usize) -> Self {
+        // A zero window would leave nothing to average (and divide by zero)
+        let window_size = window_size.max(1);
         AnalogSignalProcessorMovingAverage {
             window_size,
             values: Vec::with_capacity(window_size),
         }
     }
+
+    // Resize the window at runtime. Shrinking drops the oldest samples; growing keeps all
+    // current ones and lets the window fill up with new reads. The average is recomputed
+    // from the buffer on every read, so there's no running sum to fix up.
+    pub fn set_window(&mut self, window_size: usize) {
+        self.window_size = window_size.max(1);
+        if self.values.len() > self.window_size {
+            let excess = self.values.len() - self.window_size;
+            self.values.drain(..excess);
+        }
+    }
+
+    pub fn window(&self) -> usize {
+        self.window_size
+    }
 }
 
 impl AnalogSignalProcessor for AnalogSignalProcessorMovingAverage {
@@ -30,12 +56,60 @@ impl AnalogSignalProcessor for AnalogSignalProcessorMovingAverage {
             self.values.remove(0);
         }
         
-        // Calculate moving average
+        // Calculate moving average over the samples seen so far - during warm-up that is
+        // fewer than the window, and dividing by the window would pull readings toward zero
         let sum: u32 = self.values.iter().map(|&x| x as u32).sum();
         let average = sum / self.values.len() as u32;
         
         Ok(average as u16)
     }
+
+    fn reset(&mut self) {
+        self.values.clear();
+    }
+}
+
+// Median over a sliding window - rejects single-sample spikes (e.g. ignition noise)
+// which a moving average would only smear out. Best placed ahead of the moving average.
+pub struct AnalogSignalProcessorMedian {
+    window_size: usize,
+    values: Vec<u16>,
+}
+
+impl AnalogSignalProcessorMedian {
+    pub fn new(window_size: usize) -> Self {
+        // A zero window would leave an empty buffer to take the median of
+        let window_size = window_size.max(1);
+        AnalogSignalProcessorMedian {
+            window_size,
+            values: Vec::with_capacity(window_size),
+        }
+    }
+}
+
+impl AnalogSignalProcessor for AnalogSignalProcessorMedian {
+    fn read(&mut self, input: u16) -> Result<u16, String> {
+        self.values.push(input);
+        if self.values.len() > self.window_size {
+            self.values.remove(0);
+        }
+
+        // During warm-up the window is only partially filled - use what we have
+        let mut sorted = self.values.clone();
+        sorted.sort_unstable();
+        let mid = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 1 {
+            sorted[mid]
+        } else {
+            ((sorted[mid - 1] as u32 + sorted[mid] as u32) / 2) as u16
+        };
+
+        Ok(median)
+    }
+
+    fn reset(&mut self) {
+        self.values.clear();
+    }
 }
 
 pub struct AnalogSignalProcessorDampener {
@@ -57,6 +131,114 @@ impl AnalogSignalProcessor for AnalogSignalProcessorDampener {
         self.last_value = (self.alpha * input as f32 + (1.0 - self.alpha) * self.last_value as f32) as u16;
         Ok(self.last_value)
     }
+
+    fn reset(&mut self) {
+        self.last_value = 0;
+    }
+}
+
+// Exponential moving average (first-order IIR low-pass): y = alpha*x + (1-alpha)*y_prev.
+// Unlike the dampener, the filter is seeded with the first sample so there is no
+// startup ramp from zero, and the state is kept in f32 so small steps aren't truncated away.
+pub struct AnalogSignalProcessorExponential {
+    alpha: f32, // Smoothing factor in (0.0, 1.0)
+    last_value: Option<f32>,
+}
+
+impl AnalogSignalProcessorExponential {
+    pub fn new(alpha: f32) -> Self {
+        AnalogSignalProcessorExponential {
+            alpha: alpha.clamp(f32::EPSILON, 1.0),
+            last_value: None,
+        }
+    }
+
+    // Build the filter from a desired time constant and the rate it will be fed at.
+    pub fn with_time_constant(time_constant: Duration, update_interval: Duration) -> Self {
+        Self::new(Self::alpha_from_time_constant(time_constant, update_interval))
+    }
+
+    // alpha = 1 - e^(-dt/tau): after one time constant the output covers ~63% of a step.
+    pub fn alpha_from_time_constant(time_constant: Duration, update_interval: Duration) -> f32 {
+        let tau = time_constant.as_secs_f32();
+        if tau <= 0.0 {
+            return 1.0;
+        }
+        1.0 - (-update_interval.as_secs_f32() / tau).exp()
+    }
+}
+
+impl AnalogSignalProcessor for AnalogSignalProcessorExponential {
+    fn read(&mut self, input: u16) -> Result<u16, String> {
+        let value = match self.last_value {
+            Some(prev) => self.alpha * input as f32 + (1.0 - self.alpha) * prev,
+            None => input as f32,
+        };
+        self.last_value = Some(value);
+        Ok(value.round() as u16)
+    }
+
+    fn reset(&mut self) {
+        self.last_value = None;
+    }
+}
+
+// Rate-of-change limiter. Clamps how far the output may move per update, so that an
+// intermittently open connector can't snap a gauge to zero and back. Rate is given in raw
+// input units per second and converted to a per-update step using the expected update interval.
+pub struct AnalogSignalProcessorSlewLimiter {
+    max_step: f32,
+    last_value: Option<f32>,
+}
+
+impl AnalogSignalProcessorSlewLimiter {
+    pub fn new(max_rate_per_second: f32, update_interval: Duration) -> Self {
+        AnalogSignalProcessorSlewLimiter {
+            max_step: (max_rate_per_second * update_interval.as_secs_f32()).max(0.0),
+            last_value: None,
+        }
+    }
+}
+
+impl AnalogSignalProcessor for AnalogSignalProcessorSlewLimiter {
+    fn read(&mut self, input: u16) -> Result<u16, String> {
+        let target = input as f32;
+        let value = match self.last_value {
+            Some(prev) => prev + (target - prev).clamp(-self.max_step, self.max_step),
+            None => target,
+        };
+        self.last_value = Some(value);
+        Ok(value.round() as u16)
+    }
+
+    fn reset(&mut self) {
+        self.last_value = None;
+    }
+}
+
+// Linear fix-up inside the chain: out = in * scale + offset, rounded and clamped to the
+// u16 range. For compensating a known wiring offset or divider without touching the
+// sensor's own calibration.
+pub struct AnalogSignalProcessorScaleOffset {
+    scale: f32,
+    offset: f32,
+}
+
+impl AnalogSignalProcessorScaleOffset {
+    pub fn new(scale: f32, offset: f32) -> Self {
+        AnalogSignalProcessorScaleOffset { scale, offset }
+    }
+}
+
+impl AnalogSignalProcessor for AnalogSignalProcessorScaleOffset {
+    fn read(&mut self, input: u16) -> Result<u16, String> {
+        let output = (input as f32 * self.scale + self.offset).round();
+        Ok(output.clamp(0.0, u16::MAX as f32) as u16)
+    }
+
+    fn is_filter(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
@@ -181,6 +363,190 @@ mod tests {
         assert_eq!(results[3], 550);  // [1000, 100, 1000, 100] = 550
     }
 
+    #[test]
+    fn test_moving_average_warm_up_not_biased_to_zero() {
+        // Boot with a steady 2000: the very first readings must already be 2000, not
+        // 2000/10, 2*2000/10, ... as they would be divided by the full window
+        let mut processor = AnalogSignalProcessorMovingAverage::new(10);
+        for count in 1..=9 {
+            assert_eq!(processor.read(2000).unwrap(), 2000, "after {} sample(s)", count);
+        }
+
+        // Partially filled window averages only what it holds
+        let mut processor = AnalogSignalProcessorMovingAverage::new(10);
+        processor.read(1000).unwrap();
+        processor.read(2000).unwrap();
+        assert_eq!(processor.read(3000).unwrap(), 2000);
+    }
+
+    #[test]
+    fn test_moving_average_stable_over_many_updates() {
+        // The sum is rebuilt from the window every read, so nothing accumulates or drifts
+        let mut processor = AnalogSignalProcessorMovingAverage::new(20);
+        for i in 0..200_000u32 {
+            processor.read(if i % 2 == 0 { u16::MAX } else { 0 }).unwrap();
+        }
+        for _ in 0..20 {
+            processor.read(4095).unwrap();
+        }
+        assert_eq!(processor.read(4095).unwrap(), 4095);
+        assert_eq!(processor.values.len(), 20);
+    }
+
+    #[test]
+    fn test_moving_average_zero_window() {
+        let mut processor = AnalogSignalProcessorMovingAverage::new(0);
+        assert_eq!(processor.window(), 1);
+        assert_eq!(processor.read(1234).unwrap(), 1234);
+    }
+
+    #[test]
+    fn test_median_rejects_single_spike() {
+        let mut processor = AnalogSignalProcessorMedian::new(5);
+
+        for _ in 0..4 {
+            processor.read(100).unwrap();
+        }
+        // A single spike should not get through once the window is filled
+        assert_eq!(processor.read(4000).unwrap(), 100);
+        assert_eq!(processor.read(100).unwrap(), 100);
+    }
+
+    #[test]
+    fn test_median_warm_up() {
+        let mut processor = AnalogSignalProcessorMedian::new(5);
+
+        // [100]
+        assert_eq!(processor.read(100).unwrap(), 100);
+        // [100, 300] - even count averages the two middle values
+        assert_eq!(processor.read(300).unwrap(), 200);
+        // [100, 300, 200]
+        assert_eq!(processor.read(200).unwrap(), 200);
+        assert_eq!(processor.values.len(), 3);
+    }
+
+    #[test]
+    fn test_median_window_overflow() {
+        let mut processor = AnalogSignalProcessorMedian::new(3);
+
+        processor.read(10).unwrap();
+        processor.read(20).unwrap();
+        processor.read(30).unwrap();
+        // Window: [20, 30, 40]
+        assert_eq!(processor.read(40).unwrap(), 30);
+        assert_eq!(processor.values.len(), 3);
+        assert_eq!(processor.values[0], 20);
+    }
+
+    #[test]
+    fn test_median_maximum_values() {
+        let mut processor = AnalogSignalProcessorMedian::new(2);
+
+        processor.read(u16::MAX).unwrap();
+        assert_eq!(processor.read(u16::MAX).unwrap(), u16::MAX);
+    }
+
+    #[test]
+    fn test_median_zero_window() {
+        let mut processor = AnalogSignalProcessorMedian::new(0);
+        assert_eq!(processor.read(1234).unwrap(), 1234);
+        assert_eq!(processor.read(42).unwrap(), 42);
+    }
+
+    #[test]
+    fn test_exponential_seeds_with_first_sample() {
+        let mut processor = AnalogSignalProcessorExponential::new(0.1);
+
+        // No ramp from zero - first reading passes straight through
+        assert_eq!(processor.read(1000).unwrap(), 1000);
+        assert_eq!(processor.read(1000).unwrap(), 1000);
+    }
+
+    #[test]
+    fn test_exponential_alpha_from_time_constant() {
+        let alpha = AnalogSignalProcessorExponential::alpha_from_time_constant(
+            Duration::from_secs(1), Duration::from_millis(100));
+        // 1 - e^(-0.1) = 0.0952
+        assert!((alpha - 0.0952).abs() < 0.0005, "Unexpected alpha {}", alpha);
+
+        // Zero time constant means no filtering at all
+        let alpha = AnalogSignalProcessorExponential::alpha_from_time_constant(
+            Duration::ZERO, Duration::from_millis(100));
+        assert_eq!(alpha, 1.0);
+    }
+
+    #[test]
+    fn test_exponential_step_response_settling() {
+        let mut processor = AnalogSignalProcessorExponential::with_time_constant(
+            Duration::from_secs(1), Duration::from_millis(100));
+
+        processor.read(0).unwrap();
+
+        // After one time constant (10 updates) output should be ~63% of the step
+        let mut result = 0;
+        for _ in 0..10 {
+            result = processor.read(1000).unwrap();
+        }
+        assert!((620..=645).contains(&result), "Expected ~632 after 1 tau, got {}", result);
+
+        // After five time constants output should be within 1% of the target
+        for _ in 0..40 {
+            result = processor.read(1000).unwrap();
+        }
+        assert!(result >= 990, "Expected settled value near 1000, got {}", result);
+    }
+
+    #[test]
+    fn test_exponential_monotonic_approach() {
+        let mut processor = AnalogSignalProcessorExponential::new(0.3);
+
+        processor.read(2000).unwrap();
+        let mut previous = 2000;
+        for _ in 0..20 {
+            let result = processor.read(500).unwrap();
+            assert!(result <= previous && result >= 500);
+            previous = result;
+        }
+    }
+
+    #[test]
+    fn test_slew_limiter_passes_small_changes() {
+        // 100 units/s at 100ms updates = max 10 units per update
+        let mut limiter = AnalogSignalProcessorSlewLimiter::new(100.0, Duration::from_millis(100));
+
+        assert_eq!(limiter.read(500).unwrap(), 500);
+        assert_eq!(limiter.read(505).unwrap(), 505);
+        assert_eq!(limiter.read(495).unwrap(), 495);
+    }
+
+    #[test]
+    fn test_slew_limiter_abrupt_change() {
+        let mut limiter = AnalogSignalProcessorSlewLimiter::new(100.0, Duration::from_millis(100));
+
+        limiter.read(500).unwrap();
+
+        // Connector drops out: input snaps to zero, output only moves by the max step
+        assert_eq!(limiter.read(0).unwrap(), 490);
+        assert_eq!(limiter.read(0).unwrap(), 480);
+
+        // Connector recovers: output heads back up at the same rate
+        assert_eq!(limiter.read(500).unwrap(), 490);
+        assert_eq!(limiter.read(500).unwrap(), 500);
+        assert_eq!(limiter.read(500).unwrap(), 500);
+    }
+
+    #[test]
+    fn test_slew_limiter_reaches_target() {
+        let mut limiter = AnalogSignalProcessorSlewLimiter::new(1000.0, Duration::from_millis(10));
+
+        limiter.read(0).unwrap();
+        let mut result = 0;
+        for _ in 0..100 {
+            result = limiter.read(1000).unwrap();
+        }
+        assert_eq!(result, 1000);
+    }
+
     #[test]
     fn test_dampener_creation() {
         let dampener = AnalogSignalProcessorDampener::new(0.5);
@@ -215,6 +581,17 @@ mod tests {
         assert_eq!(result3, 507);
     }
 
+    #[test]
+    fn test_dampener_reset() {
+        let mut dampener = AnalogSignalProcessorDampener::new(0.5);
+        dampener.read(1000).unwrap();
+        dampener.read(1000).unwrap();
+
+        // Starts over from zero, like a new dampener
+        dampener.reset();
+        assert_eq!(dampener.read(1000).unwrap(), 500);
+    }
+
     #[test]
     fn test_dampener_high_alpha() {
         let mut dampener = AnalogSignalProcessorDampener::new(0.9); // Very responsive
@@ -333,6 +710,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_moving_average_set_window() {
+        let mut processor = AnalogSignalProcessorMovingAverage::new(4);
+        for value in [100, 200, 300, 400] {
+            processor.read(value).unwrap();
+        }
+
+        // Shrink: oldest two samples dropped, newest kept
+        processor.set_window(2);
+        assert_eq!(processor.window(), 2);
+        assert_eq!(processor.values, vec![300, 400]);
+        assert_eq!(processor.read(500).unwrap(), 450); // (400 + 500) / 2
+
+        // Grow: existing samples kept, window fills with new reads
+        processor.set_window(4);
+        assert_eq!(processor.read(600).unwrap(), 500); // (400 + 500 + 600) / 3
+        assert_eq!(processor.read(700).unwrap(), 550); // (400 + 500 + 600 + 700) / 4
+        assert_eq!(processor.read(800).unwrap(), 650); // 400 evicted
+
+        // Zero is clamped to a single-sample window
+        processor.set_window(0);
+        assert_eq!(processor.window(), 1);
+        assert_eq!(processor.read(900).unwrap(), 900);
+    }
+
+    #[test]
+    fn test_scale_offset_transform() {
+        let mut processor = AnalogSignalProcessorScaleOffset::new(2.0, 10.0);
+        assert_eq!(processor.read(0).unwrap(), 10);
+        assert_eq!(processor.read(100).unwrap(), 210);
+
+        // Rounded, and clamped at both ends of the u16 range
+        let mut processor = AnalogSignalProcessorScaleOffset::new(0.5, -20.0);
+        assert_eq!(processor.read(101).unwrap(), 31); // 30.5 rounds away from zero
+        assert_eq!(processor.read(10).unwrap(), 0);
+        let mut processor = AnalogSignalProcessorScaleOffset::new(100.0, 0.0);
+        assert_eq!(processor.read(1000).unwrap(), u16::MAX);
+    }
+
     #[test]
     fn test_dampener_alpha_edge_cases() {
         // Alpha = 0 (no new input influence)
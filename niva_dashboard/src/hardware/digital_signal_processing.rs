@@ -6,54 +6,105 @@ use std::time::{Duration, Instant};
 
 pub trait DigitalSignalProcessor {
     fn read(&mut self, input: Level) -> Result<Level, String>;
+
+    // Forget past samples, so the next read starts as if it were the first
+    fn reset(&mut self) {}
+
+    // Filters are bypassed when sensor filtering is switched off; counters keep counting
+    fn is_filter(&self) -> bool {
+        true
+    }
 }
 
 pub struct DigitalSignalDebouncer {
-    required_stable_count: u8,
+    // Stable samples needed to confirm a Low -> High (assert) and High -> Low (clear) change.
+    required_assert_count: u8,
+    required_clear_count: u8,
     required_stable_delay: Duration,
     last_stable_state: Level,
     last_confirmed_state: Level,
     stable_count: u8,
     timer: Instant,
+    // Set by reset: take the next sample as the confirmed state instead of debouncing
+    // toward it from a state that may be long out of date
+    reseed: bool,
 }
 
 impl DigitalSignalDebouncer {
     pub fn new(required_stable_count: u8, required_stable_delay: Duration) -> Self {
+        Self::new_asymmetric(required_stable_count, required_stable_count, required_stable_delay)
+    }
+
+    // Separate thresholds per edge, so e.g. a warning lamp can light up quickly but only
+    // go out after the input has been steadily inactive for longer, avoiding flicker.
+    // Assert is the transition to Level::High - the active level for STM32-normalized
+    // inputs; swap the counts for active-low chains.
+    pub fn new_asymmetric(required_assert_count: u8, required_clear_count: u8,
+                          required_stable_delay: Duration) -> Self {
         DigitalSignalDebouncer {
-            required_stable_count,
+            required_assert_count,
+            required_clear_count,
             required_stable_delay,
             last_stable_state: Level::Low,
             last_confirmed_state: Level::Low,
             stable_count: 0,
             timer: Instant::now(),
+            reseed: false,
         }
     }
-}
 
-impl DigitalSignalProcessor for DigitalSignalDebouncer {
-    fn read(&mut self, input: Level) -> Result<Level, String> {
+    fn required_stable_count(&self, state: Level) -> u8 {
+        match state {
+            Level::High => self.required_assert_count,
+            Level::Low => self.required_clear_count,
+        }
+    }
+
+    // A change is confirmed once the input has read the same for the required number of
+    // samples AND at least the stable delay has passed since it first read that way;
+    // any sample of the other level restarts both.
+    fn read_at(&mut self, input: Level, now: Instant) -> Level {
         let current_state = input;
 
+        if self.reseed {
+            self.reseed = false;
+            self.last_stable_state = current_state;
+            self.last_confirmed_state = current_state;
+            self.stable_count = 1;
+            self.timer = now;
+            return current_state;
+        }
+
         if current_state == self.last_stable_state {
             // State is same as what we're tracking
             if self.stable_count < u8::MAX {
                 self.stable_count += 1;
             }
-            
-            // If state has been stable for required duration, confirm it
-            if self.stable_count >= self.required_stable_count 
-               && self.timer.elapsed() >= self.required_stable_delay {
-                self.last_confirmed_state = self.last_stable_state;
-            }
         } else {
             // State changed, reset counter and start tracking new state
             self.stable_count = 1; // Start counting the new state
             self.last_stable_state = current_state;
-            self.timer = Instant::now();
+            self.timer = now;
+        }
+
+        // Checked on the changing sample too, so a required count of 1 takes one sample
+        if self.stable_count >= self.required_stable_count(self.last_stable_state)
+           && now.duration_since(self.timer) >= self.required_stable_delay {
+            self.last_confirmed_state = self.last_stable_state;
         }
         
         // Always return the last confirmed stable state
-        Ok(self.last_confirmed_state)
+        self.last_confirmed_state
+    }
+}
+
+impl DigitalSignalProcessor for DigitalSignalDebouncer {
+    fn read(&mut self, input: Level) -> Result<Level, String> {
+        Ok(self.read_at(input, Instant::now()))
+    }
+
+    fn reset(&mut self) {
+        self.reseed = true;
     }
 }
 
@@ -88,6 +139,14 @@ impl DigitalSignalProcessor for DigitalSignalProcessorPulseCounter {
         }
         Ok(input)
     }
+
+    fn reset(&mut self) {
+        DigitalSignalProcessorPulseCounter::reset(self);
+    }
+
+    fn is_filter(&self) -> bool {
+        false
+    }
 }
 
 pub struct DigitalSignalProcessorPulsePerSecond {
@@ -111,19 +170,17 @@ impl DigitalSignalProcessorPulsePerSecond {
         }
     }
 
-    pub fn pulses_per_second(&mut self) -> f32 {
-        let now = Instant::now();
+    pub fn pulses_per_second_at(&mut self, now: Instant) -> f32 {
         let elapsed = now.duration_since(self.last_update);
         
         // Only update the rate if enough time has passed
         if elapsed >= self.update_interval {
             let elapsed_secs = elapsed.as_secs_f32();
             if elapsed_secs > 0.0 {
-                //let new_pps = self.counter.count() as f32 / elapsed_secs;
-                //println!("PPS Debug: Elapsed: {:.3}s, Count: {}, Old PPS: {:.2}, New PPS: {:.2}", 
-                //         elapsed_secs, self.counter.count(), self.current_pps, new_pps);
-                //self.current_pps = new_pps;
-                self.current_pps = self.counter.count() as f32 / elapsed_secs;
+                let new_pps = self.counter.count() as f32 / elapsed_secs;
+                log::trace!("PPS: elapsed {:.3}s, count {}, old {:.2}, new {:.2}",
+                            elapsed_secs, self.counter.count(), self.current_pps, new_pps);
+                self.current_pps = new_pps;
             }
             self.counter.reset();
             self.last_update = now;
@@ -138,6 +195,10 @@ impl DigitalSignalProcessor for DigitalSignalProcessorPulsePerSecond {
     fn read(&mut self, input: Level) -> Result<Level, String> {
         self.counter.read(input)
     }
+
+    fn is_filter(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
@@ -153,7 +214,8 @@ mod tests {
         assert_eq!(debouncer.last_stable_state, Level::Low);
         assert_eq!(debouncer.last_confirmed_state, Level::Low);
         assert_eq!(debouncer.stable_count, 0);
-        assert_eq!(debouncer.required_stable_count, 3);
+        assert_eq!(debouncer.required_assert_count, 3);
+        assert_eq!(debouncer.required_clear_count, 3);
         assert_eq!(debouncer.required_stable_delay, Duration::from_millis(50));
     }
 
@@ -231,6 +293,108 @@ mod tests {
         assert_eq!(debouncer.read(Level::High).unwrap(), Level::Low);
     }
 
+    #[test]
+    fn test_digital_signal_debouncer_asymmetric() {
+        let mut debouncer = DigitalSignalDebouncer::new_asymmetric(2, 5, Duration::ZERO);
+
+        // Warning turns on after 2 consistent High samples
+        assert_eq!(debouncer.read(Level::High).unwrap(), Level::Low);
+        assert_eq!(debouncer.read(Level::High).unwrap(), Level::High);
+
+        // ...but needs 5 consistent Low samples to turn off
+        for _ in 0..4 {
+            assert_eq!(debouncer.read(Level::Low).unwrap(), Level::High);
+        }
+        assert_eq!(debouncer.read(Level::Low).unwrap(), Level::Low);
+    }
+
+    #[test]
+    fn test_digital_signal_debouncer_asymmetric_clear_interrupted() {
+        let mut debouncer = DigitalSignalDebouncer::new_asymmetric(1, 4, Duration::ZERO);
+
+        debouncer.read(Level::High).unwrap();
+        assert_eq!(debouncer.read(Level::High).unwrap(), Level::High);
+
+        // A short dropout restarts the clear count - lamp stays lit
+        for _ in 0..3 {
+            assert_eq!(debouncer.read(Level::Low).unwrap(), Level::High);
+        }
+        assert_eq!(debouncer.read(Level::High).unwrap(), Level::High);
+        for _ in 0..3 {
+            assert_eq!(debouncer.read(Level::Low).unwrap(), Level::High);
+        }
+        assert_eq!(debouncer.read(Level::Low).unwrap(), Level::Low);
+    }
+
+    // Feeds (ms since start, level) samples through read_at, returning the debounced outputs
+    fn debounce_at(debouncer: &mut DigitalSignalDebouncer, start: Instant, samples: &[(u64, Level)]) -> Vec<Level> {
+        samples.iter()
+            .map(|&(ms, level)| debouncer.read_at(level, start + Duration::from_millis(ms)))
+            .collect()
+    }
+
+    #[test]
+    fn test_debouncer_reset_takes_next_sample() {
+        use Level::{High, Low};
+        let mut debouncer = DigitalSignalDebouncer::new(3, Duration::from_millis(50));
+        let start = Instant::now();
+        assert_eq!(debounce_at(&mut debouncer, start, &[(0, Low), (60, Low), (120, Low)]), [Low, Low, Low]);
+
+        // After a reset there's no old state to debounce away from, then debouncing resumes
+        debouncer.reset();
+        assert_eq!(debounce_at(&mut debouncer, start, &[(200, High), (210, Low), (220, High)]), [High, High, High]);
+    }
+
+    #[test]
+    fn test_debouncer_needs_both_count_and_delay() {
+        use Level::{High, Low};
+        let start = Instant::now();
+
+        // Count reached at 20 ms, but the level has only been High for 20 of the 50 ms
+        let mut debouncer = DigitalSignalDebouncer::new(3, Duration::from_millis(50));
+        assert_eq!(debounce_at(&mut debouncer, start, &[(0, High), (10, High), (20, High), (40, High), (50, High)]),
+                   vec![Low, Low, Low, Low, High]);
+
+        // Delay long passed, but only the second sample: slow polling doesn't skip the count
+        let mut debouncer = DigitalSignalDebouncer::new(3, Duration::from_millis(50));
+        assert_eq!(debounce_at(&mut debouncer, start, &[(0, High), (200, High), (210, High)]),
+                   vec![Low, Low, High]);
+    }
+
+    #[test]
+    fn test_debouncer_rejects_bounce_within_window() {
+        use Level::{High, Low};
+        let start = Instant::now();
+        let mut debouncer = DigitalSignalDebouncer::new(3, Duration::from_millis(50));
+
+        // A contact bouncing Low at 30 ms restarts the window from the next High at 40 ms
+        let outputs = debounce_at(&mut debouncer, start, &[
+            (0, High), (10, High), (20, High), (30, Low), (40, High), (50, High), (60, High), (80, High), (90, High),
+        ]);
+        assert_eq!(outputs, vec![Low, Low, Low, Low, Low, Low, Low, Low, High]);
+
+        // Same on the way back: a single High blip doesn't clear the pending Low
+        let outputs = debounce_at(&mut debouncer, start, &[
+            (100, Low), (110, Low), (120, High), (130, Low), (140, Low), (150, Low), (170, Low),
+        ]);
+        assert_eq!(outputs, vec![High, High, High, High, High, High, High]);
+        assert_eq!(debouncer.read_at(Low, start + Duration::from_millis(185)), Low);
+    }
+
+    #[test]
+    fn test_debouncer_count_of_one_takes_one_sample() {
+        use Level::{High, Low};
+        let start = Instant::now();
+
+        // Used to need a second sample because only repeats were checked against the count
+        let mut debouncer = DigitalSignalDebouncer::new(1, Duration::ZERO);
+        assert_eq!(debounce_at(&mut debouncer, start, &[(0, High), (1, Low), (2, High)]), vec![High, Low, High]);
+
+        let mut debouncer = DigitalSignalDebouncer::new(2, Duration::ZERO);
+        assert_eq!(debounce_at(&mut debouncer, start, &[(0, High), (1, High), (2, Low), (3, Low)]),
+                   vec![Low, High, High, Low]);
+    }
+
     #[test]
     fn test_pulse_counter_creation() {
         let counter = DigitalSignalProcessorPulseCounter::new();
@@ -284,6 +448,11 @@ mod tests {
         // Counter should work normally after reset
         counter.read(Level::Low).unwrap();
         assert_eq!(counter.count(), 1);
+
+        // Reset through the processor trait clears it too
+        DigitalSignalProcessor::reset(&mut counter);
+        assert_eq!(counter.count(), 0);
+        assert!(!counter.is_filter());
     }
 
     #[test]
@@ -321,7 +490,7 @@ mod tests {
         let mut pps = DigitalSignalProcessorPulsePerSecond::new();
         
         // Initial rate should be 0
-        assert_eq!(pps.pulses_per_second(), 0.0);
+        assert_eq!(pps.pulses_per_second_at(Instant::now()), 0.0);
     }
 
     #[test]
@@ -334,12 +503,10 @@ mod tests {
             pps.read(Level::Low).unwrap();
         }
         
-        // Wait for update interval
-        thread::sleep(Duration::from_millis(110));
-        
-        // Check rate calculation (should be around 100 Hz for 10 transitions in 0.1s)
-        let rate = pps.pulses_per_second();
-        assert!(rate > 90.0 && rate < 110.0, "Rate was {}, expected between 90-110", rate);
+        // Check rate calculation once the update interval is up (100 Hz for 10 transitions in 0.1s)
+        let start = pps.last_update;
+        let rate = pps.pulses_per_second_at(start + Duration::from_millis(100));
+        assert!((rate - 100.0).abs() < 0.01, "Rate was {}, expected 100", rate);
     }
 
     #[test]
@@ -352,9 +519,9 @@ mod tests {
             pps.read(Level::Low).unwrap();
         }
         
-        // Wait for update and get rate
-        thread::sleep(Duration::from_millis(60));
-        let rate1 = pps.pulses_per_second();
+        // Get rate once the update interval is up
+        let start = pps.last_update;
+        let rate1 = pps.pulses_per_second_at(start + Duration::from_millis(60));
         assert!(rate1 > 0.0);
         
         // Generate more pulses
@@ -363,9 +530,8 @@ mod tests {
             pps.read(Level::Low).unwrap();
         }
         
-        // Wait for another update
-        thread::sleep(Duration::from_millis(60));
-        let rate2 = pps.pulses_per_second();
+        // And again after another interval
+        let rate2 = pps.pulses_per_second_at(start + Duration::from_millis(120));
         
         // Rates should be different and both positive
         assert!(rate2 > 0.0);
@@ -376,11 +542,9 @@ mod tests {
     fn test_pulse_per_second_no_pulses() {
         let mut pps = DigitalSignalProcessorPulsePerSecond::with_update_interval(Duration::from_millis(50));
         
-        // Wait without generating pulses
-        thread::sleep(Duration::from_millis(60));
-        
-        // Rate should be 0
-        let rate = pps.pulses_per_second();
+        // Rate should be 0 after an interval without pulses
+        let start = pps.last_update;
+        let rate = pps.pulses_per_second_at(start + Duration::from_millis(60));
         assert_eq!(rate, 0.0);
     }
 
@@ -393,8 +557,8 @@ mod tests {
             pps.read(Level::High).unwrap();
         }
         
-        thread::sleep(Duration::from_millis(110));
-        let rate = pps.pulses_per_second();
+        let start = pps.last_update;
+        let rate = pps.pulses_per_second_at(start + Duration::from_millis(110));
         
         // First reading creates 1 transition from initial Low to High
         // Subsequent readings at High don't create transitions
@@ -0,0 +1,180 @@
+#![allow(dead_code)]
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::util::totals_file::{load_totals, save_totals};
+
+/// Gaps between updates longer than this are not integrated — after a stall (ADC
+/// reconnect, system suspend) the last speed says nothing about the missing interval.
+const MAX_INTEGRATION_STEP: Duration = Duration::from_secs(2);
+/// Write the totals to disk every this many km, so a power cut loses at most this much
+const SAVE_INTERVAL_KM: f64 = 0.1;
+
+/// Integrates speed over time into a resettable trip meter and a lifetime odometer,
+/// and times the trip.
+/// With persistence enabled both totals are loaded on creation and saved periodically
+/// and on drop.
+pub struct DistanceAccumulator {
+    trip_km: f64,
+    odometer_km: f64,
+    // Time with speed readings since the trip reset, stalls excluded like the distance
+    trip_secs: f64,
+    last_update: Option<(Instant, f32)>,
+    persist_path: Option<PathBuf>,
+    last_saved_odometer_km: f64,
+}
+
+impl DistanceAccumulator {
+    pub fn new() -> Self {
+        DistanceAccumulator {
+            trip_km: 0.0,
+            odometer_km: 0.0,
+            trip_secs: 0.0,
+            last_update: None,
+            persist_path: None,
+            last_saved_odometer_km: 0.0,
+        }
+    }
+
+    /// Loads saved totals from `path` if it exists; a missing file starts from zero
+    pub fn with_persistence(path: impl Into<PathBuf>) -> Result<Self, String> {
+        let path = path.into();
+        let totals = load_totals(&path)?;
+        let mut accumulator = Self::new();
+        accumulator.odometer_km = totals.get("odometer_km").copied().unwrap_or(0.0);
+        accumulator.trip_km = totals.get("trip_km").copied().unwrap_or(0.0);
+        accumulator.trip_secs = totals.get("trip_secs").copied().unwrap_or(0.0);
+        accumulator.last_saved_odometer_km = accumulator.odometer_km;
+        accumulator.persist_path = Some(path);
+        Ok(accumulator)
+    }
+
+    /// Adds the distance covered since the previous update (trapezoidal rule)
+    pub fn update(&mut self, speed_kmh: f32, now: Instant) {
+        let speed_kmh = if speed_kmh.is_finite() { speed_kmh.max(0.0) } else { 0.0 };
+        if let Some((last_time, last_speed)) = self.last_update {
+            let dt = now.saturating_duration_since(last_time);
+            if dt <= MAX_INTEGRATION_STEP {
+                let hours = dt.as_secs_f64() / 3600.0;
+                let distance = (last_speed as f64 + speed_kmh as f64) / 2.0 * hours;
+                self.trip_km += distance;
+                self.odometer_km += distance;
+                self.trip_secs += dt.as_secs_f64();
+            }
+        }
+        self.last_update = Some((now, speed_kmh));
+
+        if self.odometer_km - self.last_saved_odometer_km >= SAVE_INTERVAL_KM {
+            if let Err(e) = self.save() {
+                log::warn!("Failed to save odometer: {}", e);
+            }
+        }
+    }
+
+    pub fn trip_km(&self) -> f64 {
+        self.trip_km
+    }
+
+    pub fn odometer_km(&self) -> f64 {
+        self.odometer_km
+    }
+
+    pub fn trip_hours(&self) -> f64 {
+        self.trip_secs / 3600.0
+    }
+
+    /// Trip distance over trip time; 0 before any time has been counted
+    pub fn trip_average_speed(&self) -> f64 {
+        if self.trip_secs > 0.0 { self.trip_km / self.trip_hours() } else { 0.0 }
+    }
+
+    pub fn reset_trip(&mut self) {
+        self.trip_km = 0.0;
+        self.trip_secs = 0.0;
+    }
+
+    pub fn save(&mut self) -> Result<(), String> {
+        let Some(path) = &self.persist_path else {
+            return Ok(());
+        };
+        save_totals(path, &[("odometer_km", self.odometer_km), ("trip_km", self.trip_km), ("trip_secs", self.trip_secs)])?;
+        self.last_saved_odometer_km = self.odometer_km;
+        Ok(())
+    }
+}
+
+impl Drop for DistanceAccumulator {
+    fn drop(&mut self) {
+        if let Err(e) = self.save() {
+            log::warn!("Failed to save odometer: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("niva_odometer_{}_{}", name, std::process::id()))
+            .join("odometer.txt");
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+        path
+    }
+
+    #[test]
+    fn test_distance_constant_speed() {
+        let mut accumulator = DistanceAccumulator::new();
+        let start = Instant::now();
+        // 60 km/h for 60 s in 100 ms steps = 1 km
+        for step in 0..=600 {
+            accumulator.update(60.0, start + Duration::from_millis(step * 100));
+        }
+        assert!((accumulator.trip_km() - 1.0).abs() < 1e-6, "Got {} km", accumulator.trip_km());
+        assert!((accumulator.odometer_km() - 1.0).abs() < 1e-6);
+        assert!((accumulator.trip_hours() * 3600.0 - 60.0).abs() < 1e-6);
+        assert!((accumulator.trip_average_speed() - 60.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_distance_trip_reset() {
+        let mut accumulator = DistanceAccumulator::new();
+        let start = Instant::now();
+        accumulator.update(36.0, start);
+        accumulator.update(36.0, start + Duration::from_secs(1)); // 10 m
+        accumulator.reset_trip();
+        accumulator.update(36.0, start + Duration::from_secs(2));
+
+        assert!((accumulator.trip_km() - 0.01).abs() < 1e-9);
+        assert!((accumulator.odometer_km() - 0.02).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_distance_skips_long_gaps() {
+        let mut accumulator = DistanceAccumulator::new();
+        let start = Instant::now();
+        accumulator.update(100.0, start);
+        accumulator.update(100.0, start + Duration::from_secs(60));
+        assert_eq!(accumulator.odometer_km(), 0.0);
+    }
+
+    #[test]
+    fn test_distance_persistence() {
+        let path = test_path("persist");
+        {
+            let mut accumulator = DistanceAccumulator::with_persistence(&path).unwrap();
+            assert_eq!(accumulator.odometer_km(), 0.0);
+            let start = Instant::now();
+            accumulator.update(36.0, start);
+            accumulator.update(36.0, start + Duration::from_secs(1));
+        } // Saved on drop
+
+        let accumulator = DistanceAccumulator::with_persistence(&path).unwrap();
+        assert!((accumulator.odometer_km() - 0.01).abs() < 1e-6);
+        assert!((accumulator.trip_km() - 0.01).abs() < 1e-6);
+        assert!((accumulator.trip_hours() * 3600.0 - 1.0).abs() < 1e-3);
+
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+}
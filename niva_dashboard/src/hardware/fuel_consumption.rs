@@ -0,0 +1,181 @@
+#![allow(dead_code)]
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use crate::util::totals_file::{load_totals, save_totals};
+
+/// Level drops smaller than this are sender noise, not fuel burned
+const DEADBAND_LITERS: f64 = 0.2;
+/// A rise this large is a refuel; smaller rises are fuel sloshing back over the float
+const REFUEL_LITERS: f64 = 2.0;
+/// Distance the recent consumption is averaged over
+const RECENT_WINDOW_KM: f64 = 2.0;
+/// Below this distance a per-100 km figure is meaningless
+const MIN_DISTANCE_KM: f64 = 0.5;
+/// Distance between samples in the recent window, so idling adds nothing to it
+const RECENT_STEP_KM: f64 = 0.05;
+/// Upper bound on the recent window; a bit more than RECENT_WINDOW_KM / RECENT_STEP_KM
+const MAX_RECENT_SAMPLES: usize = 64;
+const SAVE_INTERVAL_LITERS: f64 = 0.1;
+
+/// Fuel consumption estimated from the tank level sender, in L/100 km. There is no flow
+/// meter, so fuel used is the sum of level drops; the "current" figure is averaged over
+/// the last couple of km since the float is far too coarse for a per-second reading.
+/// The trip's fuel is persisted alongside the distance totals.
+pub struct FuelConsumption {
+    tank_liters: f64,
+    trip_fuel_liters: f64,
+    // Lowest level since the last refuel; only drops below it count as fuel used
+    reference_liters: Option<f64>,
+    // (trip km, trip fuel liters), oldest first, spanning about RECENT_WINDOW_KM
+    recent: VecDeque<(f64, f64)>,
+    persist_path: Option<PathBuf>,
+    last_saved_liters: f64,
+}
+
+impl FuelConsumption {
+    pub fn new(tank_liters: f64) -> Self {
+        FuelConsumption {
+            tank_liters,
+            trip_fuel_liters: 0.0,
+            reference_liters: None,
+            recent: VecDeque::new(),
+            persist_path: None,
+            last_saved_liters: 0.0,
+        }
+    }
+
+    /// Loads the saved trip fuel from `path` if it exists; saved periodically and on drop
+    pub fn with_persistence(mut self, path: impl Into<PathBuf>) -> Result<Self, String> {
+        let path = path.into();
+        self.trip_fuel_liters = load_totals(&path)?.get("trip_fuel_l").copied().unwrap_or(0.0);
+        self.last_saved_liters = self.trip_fuel_liters;
+        self.persist_path = Some(path);
+        Ok(self)
+    }
+
+    /// Feed the tank level (percent) and the trip distance covered so far
+    pub fn update(&mut self, level_percent: f32, trip_km: f64) {
+        if !level_percent.is_finite() {
+            return;
+        }
+        let liters = level_percent.clamp(0.0, 100.0) as f64 / 100.0 * self.tank_liters;
+        match self.reference_liters {
+            Some(reference) if liters <= reference - DEADBAND_LITERS => {
+                self.trip_fuel_liters += reference - liters;
+                self.reference_liters = Some(liters);
+            }
+            Some(reference) if liters >= reference + REFUEL_LITERS => self.reference_liters = Some(liters),
+            Some(_) => {}
+            None => self.reference_liters = Some(liters),
+        }
+
+        let advanced = self.recent.back().is_none_or(|&(last_km, _)| trip_km - last_km >= RECENT_STEP_KM);
+        if advanced {
+            self.recent.push_back((trip_km, self.trip_fuel_liters));
+            // Keep one sample at or beyond the window start, so the window spans it fully
+            while self.recent.len() > 2 && trip_km - self.recent[1].0 >= RECENT_WINDOW_KM {
+                self.recent.pop_front();
+            }
+            while self.recent.len() > MAX_RECENT_SAMPLES {
+                self.recent.pop_front();
+            }
+        }
+
+        if self.trip_fuel_liters - self.last_saved_liters >= SAVE_INTERVAL_LITERS {
+            if let Err(e) = self.save() {
+                log::warn!("Failed to save trip fuel: {}", e);
+            }
+        }
+    }
+
+    pub fn trip_fuel_liters(&self) -> f64 {
+        self.trip_fuel_liters
+    }
+
+    /// Consumption over the last RECENT_WINDOW_KM; None until enough distance is covered
+    pub fn recent_per_100km(&self) -> Option<f64> {
+        let (first_km, first_fuel) = *self.recent.front()?;
+        let (last_km, last_fuel) = *self.recent.back()?;
+        per_100km(last_fuel - first_fuel, last_km - first_km)
+    }
+
+    pub fn trip_average_per_100km(&self, trip_km: f64) -> Option<f64> {
+        per_100km(self.trip_fuel_liters, trip_km)
+    }
+
+    pub fn reset_trip(&mut self) {
+        self.trip_fuel_liters = 0.0;
+        self.recent.clear();
+    }
+
+    pub fn save(&mut self) -> Result<(), String> {
+        let Some(path) = &self.persist_path else {
+            return Ok(());
+        };
+        save_totals(path, &[("trip_fuel_l", self.trip_fuel_liters)])?;
+        self.last_saved_liters = self.trip_fuel_liters;
+        Ok(())
+    }
+}
+
+fn per_100km(liters: f64, km: f64) -> Option<f64> {
+    (km >= MIN_DISTANCE_KM).then(|| liters / km * 100.0)
+}
+
+impl Drop for FuelConsumption {
+    fn drop(&mut self) {
+        if let Err(e) = self.save() {
+            log::warn!("Failed to save trip fuel: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consumption_from_level_drops() {
+        // 40 L tank, 1% = 0.4 L
+        let mut fuel = FuelConsumption::new(40.0);
+        fuel.update(50.0, 0.0);
+        // Noise around the start level is not fuel used
+        fuel.update(50.3, 0.1);
+        fuel.update(49.8, 0.2);
+        assert_eq!(fuel.trip_fuel_liters(), 0.0);
+
+        // 1% every 5 km: 4 L over 50 km -> 8 L/100 km
+        for step in 1..=10 {
+            fuel.update(50.0 - step as f32, step as f64 * 5.0);
+        }
+        assert!((fuel.trip_fuel_liters() - 4.0).abs() < 1e-3, "Got {} L", fuel.trip_fuel_liters());
+        assert!((fuel.trip_average_per_100km(50.0).unwrap() - 8.0).abs() < 0.01);
+        assert!((fuel.recent_per_100km().unwrap() - 8.0).abs() < 0.01);
+
+        // Refuel doesn't count, and the drops after it do
+        fuel.update(90.0, 51.0);
+        fuel.update(89.0, 52.0);
+        assert!((fuel.trip_fuel_liters() - 4.4).abs() < 1e-3);
+
+        fuel.reset_trip();
+        assert_eq!(fuel.trip_fuel_liters(), 0.0);
+        assert_eq!(fuel.trip_average_per_100km(0.0), None);
+        assert_eq!(fuel.recent_per_100km(), None);
+    }
+
+    #[test]
+    fn test_recent_window_does_not_grow_while_parked() {
+        let mut fuel = FuelConsumption::new(40.0);
+        for _ in 0..10_000 {
+            fuel.update(50.0, 12.0);
+        }
+        assert_eq!(fuel.recent.len(), 1);
+
+        // Driving samples every 10 m still stays bounded
+        for step in 0..1_000 {
+            fuel.update(50.0, 12.0 + step as f64 * 0.01);
+        }
+        assert!(fuel.recent.len() <= MAX_RECENT_SAMPLES);
+    }
+}
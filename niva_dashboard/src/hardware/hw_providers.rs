@@ -20,8 +20,12 @@
 //   -> AnalogSensor(convert raw data to logical values) -> UI Rendering
 
 use crate::util::adc_data_provider::ADCFrame;
+use crate::util::can_data_provider::CanValues;
 
 use rppal::gpio::Level;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /////////////////////////////////////////////////////////////////////////
@@ -60,6 +64,26 @@ pub enum HWInput {
     HwAdcLink,
 }
 
+impl HWInput {
+    /// Every input, in declaration order - for pages that list inputs whether or not
+    /// a sensor chain is configured for them
+    pub const ALL: [HWInput; 26] = [
+        HWInput::Hw12v, HWInput::HwFuelLvl, HWInput::HwOilPress, HWInput::HwEngineCoolantTemp,
+        HWInput::HwBrakeFluidLvlLow, HWInput::HwCharge, HWInput::HwCheckEngine, HWInput::HwDiffLock,
+        HWInput::HwExtLights, HWInput::HwFuelLvlLow, HWInput::HwHighBeam, HWInput::HwInstrIllum,
+        HWInput::HwOilPressLow, HWInput::HwParkBrake, HWInput::HwSpeed, HWInput::HwTacho,
+        HWInput::HwTurnSignal,
+        HWInput::HwButton0, HWInput::HwButton1, HWInput::HwButton2, HWInput::HwButton3,
+        HWInput::HwButton4, HWInput::HwButton5, HWInput::HwButton6, HWInput::HwButton7,
+        HWInput::HwAdcLink,
+    ];
+
+    /// Input by its variant name, e.g. "HwOilPress"
+    pub fn from_name(name: &str) -> Option<HWInput> {
+        HWInput::ALL.iter().copied().find(|input| format!("{:?}", input) == name)
+    }
+}
+
 // Generic interface for reading input data.
 pub trait HWAnalogProvider {
     fn input(&self) -> HWInput;
@@ -103,6 +127,47 @@ impl HWDigitalProvider for ADCChannelProvider {
     }
 }
 
+/// Serves one decoded CAN signal from the shared CanValues. The analog interface only
+/// carries u16 counts, so the value goes out in units of `step` (the signal's scale) above
+/// `offset` (the signal's minimum), and the sensor scales and offsets it back. Values
+/// below the offset read as the offset.
+pub struct CanSignalProvider {
+    input: HWInput,
+    step: f32,
+    offset: f32,
+    values: CanValues,
+}
+
+impl CanSignalProvider {
+    pub fn new(input: HWInput, step: f32, values: CanValues) -> Self {
+        CanSignalProvider { input, step, offset: 0.0, values }
+    }
+
+    /// Encode values relative to `offset`, so signals that go negative survive the u16
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+impl HWAnalogProvider for CanSignalProvider {
+    fn input(&self) -> HWInput { self.input }
+
+    fn read_analog(&self, _input: HWInput) -> Result<u16, String> {
+        self.values.get(self.input)
+            .map(|value| ((value - self.offset) / self.step).round().clamp(0.0, u16::MAX as f32) as u16)
+    }
+}
+
+impl HWDigitalProvider for CanSignalProvider {
+    fn input(&self) -> HWInput { self.input }
+
+    fn read_digital(&self, _input: HWInput) -> Result<Level, String> {
+        self.values.get(self.input)
+            .map(|value| if value != 0.0 { Level::High } else { Level::Low })
+    }
+}
+
 /// Reports Level::High ("problem") when no ADC frame has been received recently — either
 /// because the STM32 module was never connected (frame is None) or because a previously
 /// live connection has gone stale. Feeds a Watchdog so this surfaces as a visible alert
@@ -269,6 +334,8 @@ pub struct TestPulseDataProvider {
     input: HWInput,
     start_time: Instant,
     max_frequency: f32,
+    // When set, pulses are generated at this constant rate instead of the sweep
+    fixed_frequency: Option<f32>,
 }
 
 impl TestPulseDataProvider {
@@ -277,6 +344,15 @@ impl TestPulseDataProvider {
             input,
             start_time: Instant::now(),
             max_frequency: 83.3, // pulses per second at 100 km/h
+            fixed_frequency: None,
+        }
+    }
+
+    /// Constant pulse rate, for checking pulse-to-value conversions against a known input
+    pub fn with_fixed_frequency(input: HWInput, frequency: f32) -> Self {
+        TestPulseDataProvider {
+            fixed_frequency: Some(frequency),
+            ..Self::new(input)
         }
     }
 }
@@ -355,6 +431,9 @@ impl HWAnalogProvider for TestMaxAnalogDataProvider {
 
 impl TestPulseDataProvider {
     fn get_current_frequency(&self) -> f32 {
+        if let Some(frequency) = self.fixed_frequency {
+            return frequency;
+        }
         let elapsed = self.start_time.elapsed();
         let cycle_duration = Duration::from_millis(5000); // 5 seconds total cycle
         let half_cycle = Duration::from_millis(2500); // 2.5 seconds per half
@@ -397,6 +476,11 @@ impl HWDigitalProvider for TestPulseDataProvider {
         
         // Calculate total elapsed time in seconds
         let elapsed_secs = self.start_time.elapsed().as_secs_f32();
+
+        if let Some(frequency) = self.fixed_frequency {
+            let phase = frequency * elapsed_secs;
+            return Ok(if (phase as u32) % 2 == 1 { Level::High } else { Level::Low });
+        }
         
         // Calculate instantaneous phase based on integral of frequency over time
         // Since frequency changes linearly within each half-cycle, we need to integrate
@@ -425,6 +509,136 @@ impl HWDigitalProvider for TestPulseDataProvider {
     }
 }
 
+/// Sensor log recorded by SensorLogger, loaded for playback. Timestamps are stored
+/// relative to the first row; empty cells are kept as None.
+pub struct CsvRecording {
+    timestamps: Vec<f64>,
+    columns: HashMap<String, Vec<Option<f32>>>,
+}
+
+impl CsvRecording {
+    pub fn load(path: impl AsRef<Path>) -> Result<Arc<Self>, String> {
+        let contents = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| format!("Failed to read {}: {}", path.as_ref().display(), e))?;
+        Self::parse(&contents).map(Arc::new)
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+        let header: Vec<&str> = lines.next().ok_or("Recording is empty")?.split(',').collect();
+        if header.first().map(|h| h.trim()) != Some("timestamp") {
+            return Err("Recording must start with a timestamp column".to_string());
+        }
+
+        let mut timestamps = Vec::new();
+        let mut columns: Vec<Vec<Option<f32>>> = vec![Vec::new(); header.len() - 1];
+        for (line_no, line) in lines.enumerate() {
+            let cells: Vec<&str> = line.split(',').collect();
+            let timestamp: f64 = cells[0].trim().parse()
+                .map_err(|_| format!("Bad timestamp on data row {}", line_no + 1))?;
+            timestamps.push(timestamp);
+            for (i, column) in columns.iter_mut().enumerate() {
+                column.push(cells.get(i + 1).and_then(|cell| cell.trim().parse().ok()));
+            }
+        }
+        if timestamps.is_empty() {
+            return Err("Recording has no data rows".to_string());
+        }
+
+        let start = timestamps[0];
+        timestamps.iter_mut().for_each(|t| *t -= start);
+        let columns = header[1..].iter().map(|name| name.trim().to_string()).zip(columns).collect();
+        Ok(CsvRecording { timestamps, columns })
+    }
+
+    pub fn has_column(&self, column: &str) -> bool {
+        self.columns.contains_key(column)
+    }
+
+    // Time after which playback wraps when looping; the last row gets as long as the one before it
+    fn period(&self) -> f64 {
+        match self.timestamps.as_slice() {
+            [.., prev, last] => last + (last - prev),
+            _ => 0.0,
+        }
+    }
+}
+
+/// Replays one column of a recorded sensor log in real time, following the logged
+/// timestamps. Point it at a `<sensor>_raw` column to feed a chain the same raw input it
+/// saw in the car. Implements both provider traits; digital playback treats values above
+/// 0.5 as High.
+pub struct CsvPlaybackDataProvider {
+    input: HWInput,
+    recording: Arc<CsvRecording>,
+    column: String,
+    start_time: Instant,
+    looping: bool,
+    speed: f64,
+}
+
+impl CsvPlaybackDataProvider {
+    pub fn new(input: HWInput, recording: Arc<CsvRecording>, column: &str) -> Result<Self, String> {
+        if !recording.has_column(column) {
+            return Err(format!("Recording has no column '{}'", column));
+        }
+        Ok(CsvPlaybackDataProvider {
+            input,
+            recording,
+            column: column.to_string(),
+            start_time: Instant::now(),
+            looping: false,
+            speed: 1.0,
+        })
+    }
+
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Playback rate multiplier, e.g. 4.0 replays a drive four times faster
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed.max(0.0);
+        self
+    }
+
+    /// Value at a playback position. Holds the last logged value for cells left empty
+    /// (sensor read failed that cycle), and the final row once a non-looping recording ends.
+    fn value_at(&self, elapsed: Duration) -> Result<f32, String> {
+        let mut position = elapsed.as_secs_f64() * self.speed;
+        let period = self.recording.period();
+        if self.looping && period > 0.0 {
+            position %= period;
+        }
+
+        let row = self.recording.timestamps.partition_point(|&t| t <= position).saturating_sub(1);
+        self.recording.columns[&self.column][..=row].iter().rev()
+            .find_map(|value| *value)
+            .ok_or_else(|| format!("No value recorded yet for '{}'", self.column))
+    }
+
+    fn current_value(&self) -> Result<f32, String> {
+        self.value_at(self.start_time.elapsed())
+    }
+}
+
+impl HWAnalogProvider for CsvPlaybackDataProvider {
+    fn input(&self) -> HWInput { self.input }
+
+    fn read_analog(&self, _input: HWInput) -> Result<u16, String> {
+        self.current_value().map(|value| value.round().clamp(0.0, u16::MAX as f32) as u16)
+    }
+}
+
+impl HWDigitalProvider for CsvPlaybackDataProvider {
+    fn input(&self) -> HWInput { self.input }
+
+    fn read_digital(&self, _input: HWInput) -> Result<Level, String> {
+        self.current_value().map(|value| if value > 0.5 { Level::High } else { Level::Low })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -432,6 +646,12 @@ mod tests {
     use std::time::{Duration, Instant};
     use std::sync::Arc;
 
+    #[test]
+    fn test_hw_input_all_unique() {
+        let unique: std::collections::HashSet<HWInput> = HWInput::ALL.iter().copied().collect();
+        assert_eq!(unique.len(), HWInput::ALL.len());
+    }
+
     // Test HWInput enum
     #[test]
     fn test_hw_input_enum_completeness() {
@@ -911,4 +1131,93 @@ mod tests {
             handle.join().unwrap();
         }
     }
+
+    const PLAYBACK_CSV: &str = "timestamp,brake_raw,brake,fuel_raw,fuel\n\
+        1000.000,0,0.000,100,10.000\n\
+        1000.500,1,1.000,200,20.000\n\
+        1001.000,1,1.000,,\n\
+        1001.500,0,0.000,400,40.000\n";
+
+    #[test]
+    fn test_csv_recording_parse() {
+        let recording = CsvRecording::parse(PLAYBACK_CSV).unwrap();
+        assert_eq!(recording.timestamps, vec![0.0, 0.5, 1.0, 1.5]);
+        assert!(recording.has_column("fuel_raw"));
+        assert!(!recording.has_column("timestamp"));
+        assert_eq!(recording.columns["fuel_raw"], vec![Some(100.0), Some(200.0), None, Some(400.0)]);
+
+        assert!(CsvRecording::parse("").is_err());
+        assert!(CsvRecording::parse("time,a\n0,1\n").is_err());
+        assert!(CsvRecording::parse("timestamp,a\n").is_err());
+    }
+
+    #[test]
+    fn test_csv_playback_values_in_order() {
+        let recording = Arc::new(CsvRecording::parse(PLAYBACK_CSV).unwrap());
+        let fuel = CsvPlaybackDataProvider::new(HWInput::HwFuelLvl, recording.clone(), "fuel_raw").unwrap();
+        let brake = CsvPlaybackDataProvider::new(HWInput::HwBrakeFluidLvlLow, recording.clone(), "brake_raw").unwrap();
+
+        let at = |ms| Duration::from_millis(ms);
+        assert_eq!(fuel.value_at(at(0)).unwrap(), 100.0);
+        assert_eq!(fuel.value_at(at(499)).unwrap(), 100.0);
+        assert_eq!(fuel.value_at(at(500)).unwrap(), 200.0);
+        // Empty cell holds the previous value
+        assert_eq!(fuel.value_at(at(1200)).unwrap(), 200.0);
+        assert_eq!(fuel.value_at(at(1500)).unwrap(), 400.0);
+        // Non-looping playback holds the last row
+        assert_eq!(fuel.value_at(at(10_000)).unwrap(), 400.0);
+
+        assert_eq!(brake.value_at(at(0)).unwrap(), 0.0);
+        assert_eq!(brake.value_at(at(600)).unwrap(), 1.0);
+        assert_eq!(brake.value_at(at(1600)).unwrap(), 0.0);
+
+        assert!(CsvPlaybackDataProvider::new(HWInput::HwSpeed, recording, "speed_raw").is_err());
+    }
+
+    #[test]
+    fn test_csv_playback_loop_and_speed() {
+        let recording = Arc::new(CsvRecording::parse(PLAYBACK_CSV).unwrap());
+        let looping = CsvPlaybackDataProvider::new(HWInput::HwFuelLvl, recording.clone(), "fuel_raw").unwrap()
+            .with_looping(true);
+        // Period is 2.0s: 1.5s of rows plus the last row's 0.5s
+        assert_eq!(looping.value_at(Duration::from_millis(2100)).unwrap(), 100.0);
+        assert_eq!(looping.value_at(Duration::from_millis(2600)).unwrap(), 200.0);
+
+        let fast = CsvPlaybackDataProvider::new(HWInput::HwFuelLvl, recording, "fuel_raw").unwrap()
+            .with_speed(4.0);
+        assert_eq!(fast.value_at(Duration::from_millis(125)).unwrap(), 200.0);
+        assert_eq!(fast.value_at(Duration::from_millis(375)).unwrap(), 400.0);
+    }
+
+    #[test]
+    fn test_csv_playback_provider_traits() {
+        let recording = Arc::new(CsvRecording::parse(PLAYBACK_CSV).unwrap());
+        let fuel = CsvPlaybackDataProvider::new(HWInput::HwFuelLvl, recording.clone(), "fuel_raw").unwrap();
+        let brake = CsvPlaybackDataProvider::new(HWInput::HwBrakeFluidLvlLow, recording, "brake_raw").unwrap();
+        assert_eq!(fuel.read_analog(HWInput::HwFuelLvl).unwrap(), 100);
+        assert_eq!(brake.read_digital(HWInput::HwBrakeFluidLvlLow).unwrap(), Level::Low);
+        assert_eq!(HWAnalogProvider::input(&fuel), HWInput::HwFuelLvl);
+    }
+
+    #[test]
+    fn test_can_signal_below_zero() {
+        use crate::hardware::sensors::{AnalogSensor, GenericAnalogSensor};
+        use crate::hardware::sensor_value::ValueConstraints;
+        use crate::util::can_data_provider::parse_can_config;
+
+        // Coolant as configured on the car: raw - 48 °C, gauge from -40
+        let config = parse_can_config(r#"{ "interface": "can0", "signals": [
+            { "sensor": "HwEngineCoolantTemp", "id": 648, "offset": 1, "length": 1,
+              "value_offset": -48, "min": -40, "max": 130 } ] }"#).unwrap();
+        let signal = &config.signals[0];
+        let values = CanValues::new();
+        values.set(signal.input, signal.decode(&[0x00, 18]).unwrap());
+
+        let provider = CanSignalProvider::new(signal.input, signal.scale, values).with_offset(signal.min);
+        let mut sensor = GenericAnalogSensor::new("coolant".to_string(), "ТЕМП".to_string(), "°C".to_string(),
+                                                  ValueConstraints::analog(signal.min, signal.max), signal.scale)
+            .with_value_offset(signal.min);
+        let raw = provider.read_analog(signal.input).unwrap();
+        assert_eq!(sensor.read(raw).unwrap().as_f32(), -30.0);
+    }
 }
\ No newline at end of file
@@ -7,5 +7,10 @@ pub mod analog_signal_processing;
 pub mod sensors;
 pub mod sensor_manager;
 pub mod sensor_value;
+pub mod sensor_history;
+pub mod sensor_logger;
+pub mod distance_accumulator;
+pub mod fuel_consumption;
+pub mod thermistor;
 
 pub use gpio_input::GpioInput;
@@ -0,0 +1,116 @@
+#![allow(dead_code)]
+use std::collections::VecDeque;
+use std::time::Instant;
+
+// Bounds memory on the Pi: 256 samples * ~24 bytes per sensor
+pub const DEFAULT_HISTORY_CAPACITY: usize = 256;
+
+/// Fixed-capacity ring buffer of recent (timestamp, value) samples for one sensor.
+/// Once full, each new sample evicts the oldest one.
+#[derive(Clone)]
+pub struct SensorHistory {
+    samples: VecDeque<(Instant, f32)>,
+    capacity: usize,
+}
+
+impl SensorHistory {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        SensorHistory {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, timestamp: Instant, value: f32) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((timestamp, value));
+    }
+
+    /// Samples from oldest to newest
+    pub fn iter(&self) -> impl Iterator<Item = &(Instant, f32)> + '_ {
+        self.samples.iter()
+    }
+
+    pub fn latest(&self) -> Option<&(Instant, f32)> {
+        self.samples.back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+impl Default for SensorHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_history_creation() {
+        let history = SensorHistory::default();
+        assert_eq!(history.capacity(), DEFAULT_HISTORY_CAPACITY);
+        assert!(history.is_empty());
+        assert!(history.latest().is_none());
+    }
+
+    #[test]
+    fn test_history_keeps_order() {
+        let mut history = SensorHistory::new(4);
+        let start = Instant::now();
+        for i in 0..3 {
+            history.push(start + Duration::from_millis(i * 10), i as f32);
+        }
+
+        let values: Vec<f32> = history.iter().map(|&(_, v)| v).collect();
+        assert_eq!(values, vec![0.0, 1.0, 2.0]);
+        assert_eq!(history.latest().unwrap().1, 2.0);
+    }
+
+    #[test]
+    fn test_history_evicts_oldest() {
+        let mut history = SensorHistory::new(4);
+        let start = Instant::now();
+        for i in 0..10 {
+            history.push(start + Duration::from_millis(i * 10), i as f32);
+        }
+
+        assert_eq!(history.len(), 4);
+        let values: Vec<f32> = history.iter().map(|&(_, v)| v).collect();
+        assert_eq!(values, vec![6.0, 7.0, 8.0, 9.0]);
+
+        // Timestamps stay paired with their values
+        let (oldest_ts, _) = history.iter().next().unwrap();
+        assert_eq!(*oldest_ts, start + Duration::from_millis(60));
+    }
+
+    #[test]
+    fn test_history_zero_capacity() {
+        // Clamped to one sample so push never has to special-case an empty buffer
+        let mut history = SensorHistory::new(0);
+        history.push(Instant::now(), 1.0);
+        history.push(Instant::now(), 2.0);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.latest().unwrap().1, 2.0);
+    }
+}
@@ -0,0 +1,238 @@
+#![allow(dead_code)]
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const DEFAULT_MAX_FILE_SIZE_BYTES: u64 = 10 * 1024 * 1024;
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+/// After a write/open failure, wait this long before trying to open a new file — a full
+/// or unmounted disk would otherwise be retried (and logged) on every update cycle.
+const REOPEN_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// One logged sensor: raw input (digital level as 0/1, or ADC counts) and processed value
+pub struct SensorLogEntry {
+    pub id: String,
+    pub raw: Option<f32>,
+    pub value: Option<f32>,
+}
+
+/// Flight recorder writing one CSV row per sensor update cycle, for diagnosing
+/// intermittent faults after a drive. Files go to `<dir>/<basename>_<unix time>.csv` and
+/// roll over to a new file once `max_file_size` is reached. Write errors are logged and
+/// otherwise ignored, so a failing SD card never takes the dashboard down with it.
+pub struct SensorLogger {
+    dir: PathBuf,
+    basename: String,
+    max_file_size: u64,
+    writer: Option<BufWriter<File>>,
+    current_path: Option<PathBuf>,
+    bytes_written: u64,
+    // Column ids of the current file's header; a change in registered sensors starts a new file
+    columns: Vec<String>,
+    last_flush: Instant,
+    // Set after a failure to suppress retries until REOPEN_RETRY_INTERVAL has passed
+    failed_at: Option<Instant>,
+    // Disambiguates files opened within the same second
+    file_counter: u32,
+}
+
+impl SensorLogger {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        SensorLogger {
+            dir: dir.into(),
+            basename: "sensors".to_string(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE_BYTES,
+            writer: None,
+            current_path: None,
+            bytes_written: 0,
+            columns: Vec::new(),
+            last_flush: Instant::now(),
+            failed_at: None,
+            file_counter: 0,
+        }
+    }
+
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    pub fn with_basename(mut self, basename: impl Into<String>) -> Self {
+        self.basename = basename.into();
+        self
+    }
+
+    /// Path of the file currently being written, if any
+    pub fn current_path(&self) -> Option<&Path> {
+        self.current_path.as_deref()
+    }
+
+    /// Appends one row. Opens (or rotates to) a new file with a fresh header as needed.
+    pub fn log(&mut self, entries: &[SensorLogEntry]) {
+        let columns_changed = self.columns.len() != entries.len()
+            || self.columns.iter().zip(entries).any(|(column, entry)| *column != entry.id);
+        if columns_changed || self.bytes_written >= self.max_file_size {
+            self.close();
+        }
+
+        if self.writer.is_none() {
+            if self.failed_at.is_some_and(|t| t.elapsed() < REOPEN_RETRY_INTERVAL) {
+                return;
+            }
+            if let Err(e) = self.open(entries) {
+                log::warn!("Sensor log: failed to open file in {}: {}", self.dir.display(), e);
+                self.failed_at = Some(Instant::now());
+                return;
+            }
+        }
+
+        let row = format_row(entries);
+        if let Err(e) = self.write(&row) {
+            log::warn!("Sensor log: write failed, logging suspended: {}", e);
+            self.writer = None;
+            self.failed_at = Some(Instant::now());
+            return;
+        }
+
+        if self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            self.flush();
+        }
+    }
+
+    pub fn flush(&mut self) {
+        if let Some(writer) = self.writer.as_mut() {
+            if let Err(e) = writer.flush() {
+                log::warn!("Sensor log: flush failed: {}", e);
+            }
+        }
+        self.last_flush = Instant::now();
+    }
+
+    fn open(&mut self, entries: &[SensorLogEntry]) -> Result<(), std::io::Error> {
+        fs::create_dir_all(&self.dir)?;
+        let unix_secs = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let path = self.dir.join(format!("{}_{}_{}.csv", self.basename, unix_secs, self.file_counter));
+        self.file_counter += 1;
+
+        self.writer = Some(BufWriter::new(File::create(&path)?));
+        self.current_path = Some(path);
+        self.bytes_written = 0;
+        self.columns = entries.iter().map(|entry| entry.id.clone()).collect();
+        self.failed_at = None;
+
+        let mut header = String::from("timestamp");
+        for entry in entries {
+            header.push_str(&format!(",{}_raw,{}", entry.id, entry.id));
+        }
+        header.push('\n');
+        self.write(&header)
+    }
+
+    fn write(&mut self, text: &str) -> Result<(), std::io::Error> {
+        if let Some(writer) = self.writer.as_mut() {
+            writer.write_all(text.as_bytes())?;
+            self.bytes_written += text.len() as u64;
+        }
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        self.flush();
+        self.writer = None;
+    }
+}
+
+impl Drop for SensorLogger {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+fn format_row(entries: &[SensorLogEntry]) -> String {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs_f64();
+    let mut row = format!("{:.3}", timestamp);
+    for entry in entries {
+        row.push(',');
+        if let Some(raw) = entry.raw {
+            row.push_str(&raw.to_string());
+        }
+        row.push(',');
+        if let Some(value) = entry.value.filter(|v| !v.is_nan()) {
+            row.push_str(&format!("{:.3}", value));
+        }
+    }
+    row.push('\n');
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("niva_sensor_log_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn entries(value: f32) -> Vec<SensorLogEntry> {
+        vec![
+            SensorLogEntry { id: "oil".to_string(), raw: Some(512.0), value: Some(value) },
+            SensorLogEntry { id: "brake".to_string(), raw: Some(1.0), value: None },
+        ]
+    }
+
+    #[test]
+    fn test_sensor_logger_writes_header_and_rows() {
+        let dir = test_dir("rows");
+        let mut logger = SensorLogger::new(&dir);
+        logger.log(&entries(1.5));
+        logger.log(&entries(2.25));
+        logger.flush();
+
+        let contents = fs::read_to_string(logger.current_path().unwrap()).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines[0], "timestamp,oil_raw,oil,brake_raw,brake");
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].ends_with(",512,1.500,1,"), "Unexpected row: {}", lines[1]);
+        assert!(lines[2].ends_with(",512,2.250,1,"), "Unexpected row: {}", lines[2]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_sensor_logger_rotates_on_size() {
+        let dir = test_dir("rotate");
+        let mut logger = SensorLogger::new(&dir).with_max_file_size(100);
+        for i in 0..10 {
+            logger.log(&entries(i as f32));
+        }
+        drop(logger);
+
+        let files: Vec<_> = fs::read_dir(&dir).unwrap().map(|e| e.unwrap().path()).collect();
+        assert!(files.len() > 1, "Expected rotation, got {} file(s)", files.len());
+        for file in files {
+            let contents = fs::read_to_string(&file).unwrap();
+            assert!(contents.starts_with("timestamp,"), "Every file needs its own header");
+        }
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_sensor_logger_tolerates_unwritable_path() {
+        let dir = test_dir("unwritable");
+        fs::create_dir_all(&dir).unwrap();
+        // A regular file where the log directory should be
+        let blocker = dir.join("not_a_dir");
+        fs::write(&blocker, "x").unwrap();
+
+        let mut logger = SensorLogger::new(&blocker);
+        logger.log(&entries(1.0));
+        logger.log(&entries(2.0));
+        assert!(logger.current_path().is_none());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
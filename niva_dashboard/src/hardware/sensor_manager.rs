@@ -37,6 +37,7 @@
 //! - Maintains collections of configured sensor chains
 //! - Routes read requests to appropriate chains by input type
 //! - Executes the full processing pipeline for each sensor read
+//! - Skips chains with a `min_update_interval` until they are due, reusing their last value
 //! - Returns processed, ready-to-display values to the UI layer
 //!
 //! ### Usage
@@ -55,15 +56,33 @@
 //! // Read processed sensor value
 //! let brake_active = manager.read_digital_sensor(HWInput::ParkBrake(Level::Low))?;
 //! ```
+#![allow(dead_code)]
 
-use crate::hardware::sensors::{AnalogSensor, DigitalSensor};
+use crate::hardware::sensors::{AnalogSensor, DerivedSensor, DigitalSensor, EngineHoursSensor, Sensor};
 use crate::hardware::hw_providers::{HWInput, HWAnalogProvider, HWDigitalProvider};
 use crate::hardware::analog_signal_processing::AnalogSignalProcessor;
 use crate::hardware::digital_signal_processing::DigitalSignalProcessor;
-use crate::hardware::sensor_value::SensorValue;
+use crate::hardware::sensor_value::{SensorFault, SensorValue, ValueConstraints, ValueData, ValueMetadata};
+use crate::hardware::sensor_history::{SensorHistory, DEFAULT_HISTORY_CAPACITY};
+use crate::hardware::sensor_logger::{SensorLogEntry, SensorLogger};
+use crate::hardware::distance_accumulator::DistanceAccumulator;
+use crate::hardware::fuel_consumption::FuelConsumption;
 use crate::util::adc_data_provider::ADCFrame;
+use crate::error::DashboardError;
+use rppal::gpio::Level;
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Derived value ids published from the distance accumulator and fuel consumption
+pub const TRIP_DISTANCE_ID: &str = "trip_distance";
+pub const TRIP_TIME_ID: &str = "trip_time";
+pub const TRIP_AVG_SPEED_ID: &str = "trip_avg_speed";
+pub const FUEL_CONSUMPTION_ID: &str = "fuel_consumption";
+pub const TRIP_AVG_CONSUMPTION_ID: &str = "trip_avg_consumption";
 
 // Sensor management - chains hardware providers, signal processors, and logical sensors
 pub struct SensorDigitalInputChain {
@@ -71,6 +90,17 @@ pub struct SensorDigitalInputChain {
     // Signal processors are applied in sequence
     signal_processors: Vec<Box<dyn DigitalSignalProcessor + Send>>,
     sensor: Box<dyn DigitalSensor + Send>,
+    history: SensorHistory,
+    // Readings older than this are reported as stale; None disables the check
+    max_age: Option<Duration>,
+    // Last time the chain produced a value, fresh or reused
+    last_update: Option<Instant>,
+    // Raw provider reading from the last successful read, for the sensor log
+    last_raw: Option<f32>,
+    // Slow sensors are read at most this often; the last value is reused in between
+    min_update_interval: Option<Duration>,
+    last_read: Option<Instant>,
+    last_value: Option<SensorValue>,
 }
 
 impl SensorDigitalInputChain {
@@ -83,8 +113,30 @@ impl SensorDigitalInputChain {
             hw_provider,
             signal_processors,
             sensor,
+            history: SensorHistory::new(DEFAULT_HISTORY_CAPACITY),
+            max_age: None,
+            last_update: None,
+            last_raw: None,
+            min_update_interval: None,
+            last_read: None,
+            last_value: None,
         }
     }
+
+    pub fn with_history_capacity(mut self, capacity: usize) -> Self {
+        self.history = SensorHistory::new(capacity);
+        self
+    }
+
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn with_min_update_interval(mut self, interval: Duration) -> Self {
+        self.min_update_interval = Some(interval);
+        self
+    }
 }
 
 // Analog sensor input chain, similar to SensorDigitalInputChain
@@ -93,6 +145,17 @@ pub struct SensorAnalogInputChain {
     // Signal processors are applied in sequence
     signal_processors: Vec<Box<dyn AnalogSignalProcessor + Send>>,
     sensor: Box<dyn AnalogSensor + Send>,
+    history: SensorHistory,
+    // Readings older than this are reported as stale; None disables the check
+    max_age: Option<Duration>,
+    // Last time the chain produced a value, fresh or reused
+    last_update: Option<Instant>,
+    // Raw provider reading from the last successful read, for the sensor log
+    last_raw: Option<f32>,
+    // Slow sensors are read at most this often; the last value is reused in between
+    min_update_interval: Option<Duration>,
+    last_read: Option<Instant>,
+    last_value: Option<SensorValue>,
 }
 
 impl SensorAnalogInputChain {
@@ -105,6 +168,74 @@ impl SensorAnalogInputChain {
             hw_provider,
             signal_processors,
             sensor,
+            history: SensorHistory::new(DEFAULT_HISTORY_CAPACITY),
+            max_age: None,
+            last_update: None,
+            last_raw: None,
+            min_update_interval: None,
+            last_read: None,
+            last_value: None,
+        }
+    }
+
+    pub fn with_history_capacity(mut self, capacity: usize) -> Self {
+        self.history = SensorHistory::new(capacity);
+        self
+    }
+
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn with_min_update_interval(mut self, interval: Duration) -> Self {
+        self.min_update_interval = Some(interval);
+        self
+    }
+}
+
+/// Result of a value lookup that accounts for how old the reading is
+#[derive(Debug, PartialEq)]
+pub enum SensorValueState<'a> {
+    Live(&'a SensorValue),
+    /// Last successful read is older than the chain's max_age
+    Stale(&'a SensorValue),
+    /// No chain for the input, it has never read, or its last read failed and it has no max_age
+    Unavailable,
+}
+
+/// Everything a page needs to render one sensor, see SensorManager::snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorReading {
+    /// Hardware input for chain sensors; None for derived ones
+    pub input: Option<HWInput>,
+    /// None until the sensor has produced a value
+    pub value: Option<f32>,
+    pub unit: String,
+    pub label: String,
+    pub fault: Option<SensorFault>,
+    pub stale: bool,
+    pub warning: bool,
+    pub critical: bool,
+    /// Last raw provider reading: ADC counts for analog chains, the input level (1.0 high,
+    /// 0.0 low) for digital ones. None for derived sensors and before the first read.
+    pub raw: Option<f32>,
+    pub digital: bool,
+}
+
+impl SensorReading {
+    fn from_value(input: Option<HWInput>, value: Option<&SensorValue>, unit: &str, label: &str, stale: bool) -> Self {
+        SensorReading {
+            input,
+            value: value.map(|v| v.as_f32()).filter(|v| !v.is_nan()),
+            unit: unit.to_string(),
+            label: label.to_string(),
+            fault: value.and_then(|v| v.fault),
+            stale,
+            warning: value.is_some_and(|v| v.is_warning()),
+            critical: value.is_some_and(|v| v.is_critical()),
+            raw: None,
+            digital: false,
         }
     }
 }
@@ -118,6 +249,22 @@ pub struct SensorManager {
     // transport layer rather than through a chain read, since a chain read is exactly
     // what fails (with "channel not in frame") while the link is down.
     adc_frame: Option<ADCFrame>,
+    // Time source for staleness checks - replaceable so tests can advance time
+    clock: Box<dyn Fn() -> Instant + Send>,
+    logger: Option<SensorLogger>,
+    // Evaluated after the chains, from their values; keyed by sensor id in derived_values
+    derived_sensors: Vec<DerivedSensor>,
+    derived_values: HashMap<String, SensorValue>,
+    // Integrates the HwSpeed value into trip/odometer totals on each read cycle
+    distance: Option<DistanceAccumulator>,
+    // Estimated from the HwFuelLvl value over the trip distance; needs `distance`
+    fuel_consumption: Option<FuelConsumption>,
+    // Reported through derived_values under its sensor id
+    engine_hours: Option<EngineHoursSensor>,
+    // Sensor id -> input, so string lookups resolve to the HWInput-keyed values
+    input_by_id: HashMap<String, HWInput>,
+    // When off, provider values skip the signal processors, for looking at raw signals
+    filtering_enabled: bool,
 }
 
 impl SensorManager {
@@ -127,17 +274,99 @@ impl SensorManager {
             analog_sensors: Vec::new(),
             sensor_values: HashMap::new(),
             adc_frame: None,
+            clock: Box::new(Instant::now),
+            logger: None,
+            derived_sensors: Vec::new(),
+            derived_values: HashMap::new(),
+            distance: None,
+            fuel_consumption: None,
+            engine_hours: None,
+            input_by_id: HashMap::new(),
+            filtering_enabled: true,
         }
     }
 
+    pub fn with_clock(mut self, clock: Box<dyn Fn() -> Instant + Send>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     pub fn add_digital_sensor_chain(&mut self, chain: SensorDigitalInputChain) {
+        self.input_by_id.insert(chain.sensor.id().clone(), chain.hw_provider.input());
         self.digital_sensors.push(chain);
     }
 
     pub fn add_analog_sensor_chain(&mut self, chain: SensorAnalogInputChain) {
+        self.input_by_id.insert(chain.sensor.id().clone(), chain.hw_provider.input());
         self.analog_sensors.push(chain);
     }
 
+    /// Bypass the filtering signal processors of every chain, or put them back. Calibration
+    /// and counting processors keep running. Filters are reset when filtering comes back on,
+    /// so they don't resume from values held before the bypass but restart from the next sample.
+    pub fn set_filtering_enabled(&mut self, enabled: bool) {
+        if enabled && !self.filtering_enabled {
+            for processor in self.digital_sensors.iter_mut().flat_map(|chain| chain.signal_processors.iter_mut())
+                .filter(|p| p.is_filter()) {
+                processor.reset();
+            }
+            for processor in self.analog_sensors.iter_mut().flat_map(|chain| chain.signal_processors.iter_mut())
+                .filter(|p| p.is_filter()) {
+                processor.reset();
+            }
+        }
+        self.filtering_enabled = enabled;
+    }
+
+    pub fn filtering_enabled(&self) -> bool {
+        self.filtering_enabled
+    }
+
+    /// Drop every chain reading `input`, so another source can take the input over
+    pub fn remove_sensor_chains(&mut self, input: HWInput) {
+        self.digital_sensors.retain(|chain| chain.hw_provider.input() != input);
+        self.analog_sensors.retain(|chain| chain.hw_provider.input() != input);
+        self.input_by_id.retain(|_, chain_input| *chain_input != input);
+        self.sensor_values.remove(&input);
+    }
+
+    pub fn add_derived_sensor(&mut self, sensor: DerivedSensor) {
+        self.derived_sensors.push(sensor);
+    }
+
+    pub fn set_distance_accumulator(&mut self, accumulator: Option<DistanceAccumulator>) {
+        self.distance = accumulator;
+    }
+
+    pub fn distance(&self) -> Option<&DistanceAccumulator> {
+        self.distance.as_ref()
+    }
+
+    pub fn set_fuel_consumption(&mut self, fuel_consumption: Option<FuelConsumption>) {
+        self.fuel_consumption = fuel_consumption;
+    }
+
+    pub fn reset_trip(&mut self) {
+        if let Some(distance) = self.distance.as_mut() {
+            distance.reset_trip();
+        }
+        if let Some(fuel) = self.fuel_consumption.as_mut() {
+            fuel.reset_trip();
+        }
+        // Consumption has no value again until enough distance is covered
+        self.derived_values.remove(FUEL_CONSUMPTION_ID);
+        self.derived_values.remove(TRIP_AVG_CONSUMPTION_ID);
+    }
+
+    pub fn set_engine_hours_sensor(&mut self, sensor: Option<EngineHoursSensor>) {
+        self.engine_hours = sensor;
+    }
+
+    /// Attaches a CSV logger that records every sensor after each read_all_sensors() cycle
+    pub fn set_logger(&mut self, logger: Option<SensorLogger>) {
+        self.logger = logger;
+    }
+
     /// Registers the ADC frame this manager's chains read from, so adc_link_down() can
     /// report connectivity. Only needed when chains include ADCChannelProviders.
     pub fn set_adc_frame(&mut self, frame: Option<ADCFrame>) {
@@ -152,48 +381,75 @@ impl SensorManager {
         self.adc_frame.as_ref().is_some_and(|frame| frame.is_stale())
     }
 
-    fn read_digital_sensor(&mut self, input: HWInput) -> Result<SensorValue, String> {
+    fn read_digital_sensor(&mut self, input: HWInput) -> Result<SensorValue, DashboardError> {
+        let now = (self.clock)();
+        let filtering = self.filtering_enabled;
         for chain in &mut self.digital_sensors {
             if chain.hw_provider.input() != input {
                 continue;
             }
+            if let Some(value) = cached_value(chain.last_value.as_ref(), chain.last_read, chain.min_update_interval, now) {
+                chain.last_update = Some(now);
+                return Ok(value);
+            }
             // Read raw input from hardware provider
-            let mut level = chain.hw_provider.read_digital(input.clone())?;
+            let mut level = chain.hw_provider.read_digital(input.clone()).map_err(DashboardError::Sensor)?;
+            let raw = if level == Level::High { 1.0 } else { 0.0 };
             
             // Process through signal processors
-            for processor in &mut chain.signal_processors {
-                level = processor.read(level)?;
+            for processor in chain.signal_processors.iter_mut().filter(|p| filtering || !p.is_filter()) {
+                level = processor.read(level).map_err(DashboardError::Sensor)?;
             }
             
             // Convert to logical sensor value
-            return Ok(chain.sensor.read(level)?.clone());
+            let value = chain.sensor.read(level).map_err(DashboardError::Sensor)?.clone();
+            chain.last_update = Some(now);
+            chain.last_read = Some(now);
+            chain.last_raw = Some(raw);
+            record_history(&mut chain.history, &value, now);
+            chain.last_value = Some(value.clone());
+            return Ok(value);
         }
-        Err(format!("Digital sensor chain not found for input: {:?}", input))
+        Err(DashboardError::Sensor(format!("Digital sensor chain not found for input: {:?}", input)))
     }
 
-    fn read_analog_sensor(&mut self, input: HWInput) -> Result<SensorValue, String> {
+    fn read_analog_sensor(&mut self, input: HWInput) -> Result<SensorValue, DashboardError> {
+        let now = (self.clock)();
+        let filtering = self.filtering_enabled;
         for chain in &mut self.analog_sensors {
             if chain.hw_provider.input() != input {
                 continue;
             }
+            if let Some(value) = cached_value(chain.last_value.as_ref(), chain.last_read, chain.min_update_interval, now) {
+                chain.last_update = Some(now);
+                return Ok(value);
+            }
             // Read raw input from hardware provider
-            let mut value = chain.hw_provider.read_analog(input.clone())?;
+            let mut value = chain.hw_provider.read_analog(input.clone()).map_err(DashboardError::Sensor)?;
+            let raw = value as f32;
             
             // Process through signal processors
-            for processor in &mut chain.signal_processors {
-                value = processor.read(value)?;
+            for processor in chain.signal_processors.iter_mut().filter(|p| filtering || !p.is_filter()) {
+                value = processor.read(value).map_err(DashboardError::Sensor)?;
             }
             
             // Convert to logical sensor value
-            return Ok(chain.sensor.read(value)?.clone());
+            let value = chain.sensor.read(value).map_err(DashboardError::Sensor)?.clone();
+            chain.last_update = Some(now);
+            chain.last_read = Some(now);
+            chain.last_raw = Some(raw);
+            record_history(&mut chain.history, &value, now);
+            chain.last_value = Some(value.clone());
+            return Ok(value);
         }
-        Err("Analog sensor chain not found".to_string())
+        Err(DashboardError::Sensor("Analog sensor chain not found".to_string()))
     }
 
-    // Should be called periodically from event loop to update all sensors
-    pub fn read_all_sensors(&mut self) -> Result<(), String> {
-        self.sensor_values.clear();
-
+    // Should be called periodically from event loop to update all sensors.
+    // A failing chain doesn't stop the others (ADC and CAN fail independently) and the first
+    // error is returned. A chain with a max_age keeps its last good value, flagged stale once
+    // it's older than that; other chains' values are left out until they read again.
+    pub fn read_all_sensors(&mut self) -> Result<(), DashboardError> {
         // Collect inputs first to avoid borrowing issues
         let digital_inputs: Vec<HWInput> = self.digital_sensors.iter()
             .map(|chain| chain.hw_provider.input())
@@ -202,21 +458,129 @@ impl SensorManager {
             .map(|chain| chain.hw_provider.input())
             .collect();
 
+        let mut first_error = None;
+
         // Read digital sensors
         for input in digital_inputs {
-            let value = self.read_digital_sensor(input)?;
-            //print!("Read digital sensor {:?}: {:?}\r\n", input, value);
-            self.sensor_values.insert(input, value);
+            match self.read_digital_sensor(input) {
+                Ok(value) => { self.sensor_values.insert(input, value); }
+                Err(e) => {
+                    self.drop_failed_value(input);
+                    first_error.get_or_insert(e);
+                }
+            }
         }
 
         // Read analog sensors  
         for input in analog_inputs {
-            let value = self.read_analog_sensor(input)?;
-            //print!("Read analog sensor {:?}: {:?}\r\n", input, value);
-            self.sensor_values.insert(input, value);
+            match self.read_analog_sensor(input) {
+                Ok(value) => { self.sensor_values.insert(input, value); }
+                Err(e) => {
+                    self.drop_failed_value(input);
+                    first_error.get_or_insert(e);
+                }
+            }
         }
 
-        Ok(())
+        self.update_derived_sensors();
+        self.update_distance();
+        self.update_fuel_consumption();
+        self.update_engine_hours();
+        self.log_sensor_values();
+        first_error.map_or(Ok(()), Err)
+    }
+
+    fn drop_failed_value(&mut self, input: HWInput) {
+        if !matches!(self.chain_timing(&input), Some((Some(_), _))) {
+            self.sensor_values.remove(&input);
+        }
+    }
+
+    fn update_derived_sensors(&mut self) {
+        for sensor in &mut self.derived_sensors {
+            let inputs: Option<Vec<f32>> = sensor.sources().iter()
+                .map(|input| self.sensor_values.get(input)
+                    .map(|value| value.as_f32())
+                    .filter(|value| !value.is_nan()))
+                .collect();
+            // Skip the cycle if any source is missing; the last derived value is kept
+            if let Some(inputs) = inputs {
+                let value = sensor.evaluate(&inputs).clone();
+                self.derived_values.insert(sensor.id().clone(), value);
+            }
+        }
+    }
+
+    fn update_distance(&mut self) {
+        let Some(distance) = self.distance.as_mut() else {
+            return;
+        };
+        if let Some(speed) = self.sensor_values.get(&HWInput::HwSpeed) {
+            distance.update(speed.as_f32(), (self.clock)());
+        }
+        let trip = [
+            (TRIP_DISTANCE_ID, "ПРОБЕГ", "км", distance.trip_km(), 100000.0),
+            (TRIP_TIME_ID, "ВРЕМЯ", "ч", distance.trip_hours(), 100000.0),
+            (TRIP_AVG_SPEED_ID, "СР СКОР", "км/ч", distance.trip_average_speed(), 180.0),
+        ];
+        for (id, label, unit, value, max) in trip {
+            self.derived_values.insert(id.to_string(), trip_value(value, max, unit, label, id));
+        }
+    }
+
+    fn update_fuel_consumption(&mut self) {
+        let (Some(fuel), Some(distance)) = (self.fuel_consumption.as_mut(), self.distance.as_ref()) else {
+            return;
+        };
+        let Some(level) = self.sensor_values.get(&HWInput::HwFuelLvl) else {
+            return;
+        };
+        let trip_km = distance.trip_km();
+        fuel.update(level.as_f32(), trip_km);
+        let consumption = [
+            (FUEL_CONSUMPTION_ID, "РАСХОД", fuel.recent_per_100km()),
+            (TRIP_AVG_CONSUMPTION_ID, "СР РАСХОД", fuel.trip_average_per_100km(trip_km)),
+        ];
+        for (id, label, value) in consumption {
+            if let Some(value) = value {
+                self.derived_values.insert(id.to_string(), trip_value(value, 99.9, "л/100км", label, id));
+            }
+        }
+    }
+
+    fn update_engine_hours(&mut self) {
+        let Some(sensor) = self.engine_hours.as_mut() else {
+            return;
+        };
+        // A missing source reading counts as "not running"
+        let source_value = self.sensor_values.get(&sensor.source())
+            .map(|value| value.as_f32())
+            .filter(|value| !value.is_nan())
+            .unwrap_or(0.0);
+        let value = sensor.update(source_value, (self.clock)()).clone();
+        self.derived_values.insert(sensor.id().clone(), value);
+    }
+
+    pub fn get_derived_value(&self, id: &str) -> Option<&SensorValue> {
+        self.derived_values.get(id)
+    }
+
+    fn log_sensor_values(&mut self) {
+        let Some(logger) = self.logger.as_mut() else {
+            return;
+        };
+        let digital = self.digital_sensors.iter()
+            .map(|chain| (chain.hw_provider.input(), chain.sensor.id(), chain.last_raw));
+        let analog = self.analog_sensors.iter()
+            .map(|chain| (chain.hw_provider.input(), chain.sensor.id(), chain.last_raw));
+        let entries: Vec<SensorLogEntry> = digital.chain(analog)
+            .map(|(input, id, raw)| SensorLogEntry {
+                id: id.clone(),
+                raw,
+                value: self.sensor_values.get(&input).map(|value| value.as_f32()),
+            })
+            .collect();
+        logger.log(&entries);
     }
 
     pub fn get_sensor_values(&self) -> &HashMap<HWInput, SensorValue> {
@@ -226,6 +590,255 @@ impl SensorManager {
     pub fn get_sensor_value(&self, input: &HWInput) -> Option<&SensorValue> {
         self.sensor_values.get(input)
     }
+
+    /// Lookup by sensor id (e.g. "HwOilPress", "speed_sensor"). Prefer get_sensor_value
+    /// where the HWInput is known - this resolves the id through an index first.
+    pub fn get_sensor_value_by_id(&self, id: &str) -> Option<&SensorValue> {
+        self.input_by_id.get(id).and_then(|input| self.sensor_values.get(input))
+    }
+
+    pub fn input_for_id(&self, id: &str) -> Option<HWInput> {
+        self.input_by_id.get(id).copied()
+    }
+
+    /// All registered sensors (chains and derived) keyed by sensor id, as of the last
+    /// read_all_sensors() cycle. Taken in one go so a frame renders a consistent set.
+    pub fn snapshot(&self) -> HashMap<String, SensorReading> {
+        let mut readings = HashMap::new();
+        let digital = self.digital_sensors.iter()
+            .map(|chain| (chain.hw_provider.input(), chain.sensor.metadata(), chain.last_raw, true));
+        let analog = self.analog_sensors.iter()
+            .map(|chain| (chain.hw_provider.input(), chain.sensor.metadata(), chain.last_raw, false));
+        for (input, metadata, raw, digital) in digital.chain(analog) {
+            readings.insert(metadata.sensor_id.clone(), SensorReading {
+                raw,
+                digital,
+                ..SensorReading::from_value(Some(input), self.sensor_values.get(&input),
+                                            &metadata.unit, &metadata.label, self.is_stale(&input))
+            });
+        }
+
+        let trip = [TRIP_DISTANCE_ID, TRIP_TIME_ID, TRIP_AVG_SPEED_ID, FUEL_CONSUMPTION_ID, TRIP_AVG_CONSUMPTION_ID]
+            .into_iter()
+            .filter_map(|id| self.derived_values.get(id).map(|value| &value.metadata));
+        let derived = self.derived_sensors.iter().map(|sensor| sensor.metadata())
+            .chain(self.engine_hours.iter().map(|sensor| sensor.metadata()))
+            .chain(trip);
+        for metadata in derived {
+            readings.insert(metadata.sensor_id.clone(), SensorReading::from_value(
+                None, self.derived_values.get(&metadata.sensor_id),
+                &metadata.unit, &metadata.label, false));
+        }
+        readings
+    }
+
+    /// Everything pages read from the manager, copied out so they can render from it while
+    /// the next read cycle runs
+    pub fn values_snapshot(&self) -> SensorValuesSnapshot {
+        let digital = self.digital_sensors.iter()
+            .map(|chain| (chain.hw_provider.input(), chain.history.clone()));
+        let analog = self.analog_sensors.iter()
+            .map(|chain| (chain.hw_provider.input(), chain.history.clone()));
+        SensorValuesSnapshot {
+            cycle: 0,
+            updated_at: Some((self.clock)()),
+            values: self.sensor_values.clone(),
+            derived_values: self.derived_values.clone(),
+            readings: self.snapshot(),
+            histories: digital.chain(analog).collect(),
+            filtering_enabled: self.filtering_enabled,
+        }
+    }
+
+    /// Moves the manager onto its own thread, reading all sensors every `interval` so
+    /// provider latency (I2C, serial) doesn't stall the render loop. Values are published
+    /// to the returned handle after each cycle.
+    pub fn start_background_updates(self, interval: Duration) -> SensorUpdateThread {
+        let should_stop = Arc::new(AtomicBool::new(false));
+        // Until the first cycle completes, readers get the manager's state as handed over
+        let latest = Arc::new(Mutex::new(Arc::new(SensorValuesSnapshot {
+            updated_at: None,
+            ..self.values_snapshot()
+        })));
+        let manager = Arc::new(Mutex::new(self));
+
+        let thread_should_stop = Arc::clone(&should_stop);
+        let thread_latest = Arc::clone(&latest);
+        let thread_manager = Arc::clone(&manager);
+        let thread = thread::spawn(move || {
+            let mut cycle = 0u64;
+            // Last read error, so a persistent one is logged once
+            let mut last_error: Option<String> = None;
+            while !thread_should_stop.load(Ordering::Relaxed) {
+                let cycle_start = Instant::now();
+                let snapshot = {
+                    let mut manager = thread_manager.lock().unwrap();
+                    if let Err(e) = manager.read_all_sensors().map_err(String::from) {
+                        // Suppress: while the ADC link is down, the first ADC-backed chain fails
+                        // with "channel not in frame" until AdcDataProvider's reconnect loop
+                        // recovers it — expected and already surfaced via the ADC LINK alert.
+                        // Logged once per distinct error: a CAN ECU that's switched off fails every cycle.
+                        if !manager.adc_link_down() && last_error.as_ref() != Some(&e) {
+                            log::error!("Sensor read error: {}", e);
+                        }
+                        last_error = Some(e);
+                    } else if last_error.take().is_some() {
+                        log::info!("Sensor reads recovered");
+                    }
+                    cycle += 1;
+                    SensorValuesSnapshot { cycle, ..manager.values_snapshot() }
+                };
+                *thread_latest.lock().unwrap() = Arc::new(snapshot);
+
+                thread::sleep(interval.saturating_sub(cycle_start.elapsed()));
+            }
+        });
+
+        SensorUpdateThread { should_stop, thread: Some(thread), latest, manager }
+    }
+
+    /// Like get_sensor_value, but flags readings whose chain hasn't updated within its max_age
+    pub fn get_value(&self, input: &HWInput) -> SensorValueState<'_> {
+        let Some(value) = self.sensor_values.get(input) else {
+            return SensorValueState::Unavailable;
+        };
+        if self.is_stale(input) {
+            SensorValueState::Stale(value)
+        } else {
+            SensorValueState::Live(value)
+        }
+    }
+
+    pub fn is_stale(&self, input: &HWInput) -> bool {
+        match self.chain_timing(input) {
+            Some((Some(max_age), Some(last_update))) =>
+                (self.clock)().saturating_duration_since(last_update) > max_age,
+            Some((Some(_), None)) => true, // Never read successfully
+            _ => false,
+        }
+    }
+
+    // (max_age, last_update) of the chain reading this input
+    fn chain_timing(&self, input: &HWInput) -> Option<(Option<Duration>, Option<Instant>)> {
+        self.digital_sensors.iter()
+            .find(|chain| chain.hw_provider.input() == *input)
+            .map(|chain| (chain.max_age, chain.last_update))
+            .or_else(|| self.analog_sensors.iter()
+                .find(|chain| chain.hw_provider.input() == *input)
+                .map(|chain| (chain.max_age, chain.last_update)))
+    }
+
+    /// Recent (timestamp, value) samples for the chain reading this input, oldest first
+    pub fn get_history(&self, input: &HWInput) -> Option<&SensorHistory> {
+        self.digital_sensors.iter()
+            .find(|chain| chain.hw_provider.input() == *input)
+            .map(|chain| &chain.history)
+            .or_else(|| self.analog_sensors.iter()
+                .find(|chain| chain.hw_provider.input() == *input)
+                .map(|chain| &chain.history))
+    }
+}
+
+/// Sensor values as of one background update cycle, with the per-sensor details pages
+/// render alongside them. Lookups mirror SensorManager's.
+#[derive(Clone)]
+pub struct SensorValuesSnapshot {
+    /// Number of completed update cycles; 0 until the first one finishes
+    pub cycle: u64,
+    pub updated_at: Option<Instant>,
+    pub values: HashMap<HWInput, SensorValue>,
+    pub derived_values: HashMap<String, SensorValue>,
+    readings: HashMap<String, SensorReading>,
+    histories: HashMap<HWInput, SensorHistory>,
+    filtering_enabled: bool,
+}
+
+impl SensorValuesSnapshot {
+    pub fn get_sensor_values(&self) -> &HashMap<HWInput, SensorValue> {
+        &self.values
+    }
+
+    pub fn get_sensor_value(&self, input: &HWInput) -> Option<&SensorValue> {
+        self.values.get(input)
+    }
+
+    pub fn get_derived_value(&self, id: &str) -> Option<&SensorValue> {
+        self.derived_values.get(id)
+    }
+
+    /// All registered sensors keyed by sensor id, see SensorManager::snapshot
+    pub fn readings(&self) -> &HashMap<String, SensorReading> {
+        &self.readings
+    }
+
+    pub fn get_history(&self, input: &HWInput) -> Option<&SensorHistory> {
+        self.histories.get(input)
+    }
+
+    pub fn filtering_enabled(&self) -> bool {
+        self.filtering_enabled
+    }
+}
+
+/// Handle to a SensorManager running on a background thread (see start_background_updates)
+pub struct SensorUpdateThread {
+    should_stop: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<()>>,
+    latest: Arc<Mutex<Arc<SensorValuesSnapshot>>>,
+    manager: Arc<Mutex<SensorManager>>,
+}
+
+impl SensorUpdateThread {
+    /// The most recently published values. Only holds the lock to clone the Arc, so it
+    /// never waits on a sensor read in progress.
+    pub fn latest(&self) -> Arc<SensorValuesSnapshot> {
+        Arc::clone(&self.latest.lock().unwrap())
+    }
+
+    /// Runs `f` on the manager between read cycles, for control actions (trip reset,
+    /// filtering, swapping the sensor set). Visible in the snapshot after the next cycle.
+    pub fn with_manager<R>(&self, f: impl FnOnce(&mut SensorManager) -> R) -> R {
+        f(&mut self.manager.lock().unwrap())
+    }
+
+    pub fn stop(&mut self) {
+        self.should_stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for SensorUpdateThread {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn trip_value(value: f64, max: f32, unit: &str, label: &str, id: &str) -> SensorValue {
+    SensorValue::analog_with_constraints_and_metadata(
+        (value as f32).clamp(0.0, max),
+        ValueConstraints::analog(0.0, max),
+        ValueMetadata::new(unit, label, id),
+    )
+}
+
+/// The chain's last value while it isn't due for another read yet
+fn cached_value(last_value: Option<&SensorValue>, last_read: Option<Instant>,
+                min_update_interval: Option<Duration>, now: Instant) -> Option<SensorValue> {
+    let (interval, last_read) = (min_update_interval?, last_read?);
+    if now.saturating_duration_since(last_read) < interval {
+        last_value.cloned()
+    } else {
+        None
+    }
+}
+
+fn record_history(history: &mut SensorHistory, value: &SensorValue, now: Instant) {
+    // Sensors that haven't produced a value yet would only add NaN gaps
+    if value.value != ValueData::Empty {
+        history.push(now, value.as_f32());
+    }
 }
 
 #[cfg(test)]
@@ -233,7 +846,7 @@ mod tests {
     use super::*;
     use crate::hardware::hw_providers::{TestDigitalDataProvider, TestAnalogDataProvider};
     use crate::hardware::digital_signal_processing::DigitalSignalDebouncer;
-    use crate::hardware::analog_signal_processing::AnalogSignalProcessorMovingAverage;
+    use crate::hardware::analog_signal_processing::{AnalogSignalProcessorMovingAverage, AnalogSignalProcessorScaleOffset};
     use crate::hardware::sensors::{GenericDigitalSensor, GenericAnalogSensor};
     use crate::hardware::sensor_value::ValueConstraints;
     use rppal::gpio::Level;
@@ -379,7 +992,7 @@ mod tests {
         
         assert!(result.is_err(), "Reading non-existent sensor should fail");
         
-        let error_msg = result.unwrap_err();
+        let error_msg = result.unwrap_err().to_string();
         log::error!("Expected error: {}", error_msg);
         assert!(error_msg.contains("Digital sensor chain not found"), 
                "Error should indicate missing chain");
@@ -387,6 +1000,536 @@ mod tests {
         log::info!("✓ Non-existent sensor chain test passed");
     }
 
+    #[test]
+    fn test_sensor_manager_history() {
+        let mut manager = SensorManager::new();
+        let fuel_input = HWInput::HwFuelLvl;
+        let chain = SensorAnalogInputChain::new(
+            Box::new(TestAnalogDataProvider::new(fuel_input)),
+            vec![],
+            Box::new(GenericAnalogSensor::new(
+                "test_fuel".to_string(), "Test Fuel Level".to_string(), "%".to_string(),
+                ValueConstraints::analog(0.0, 100.0), 1.0
+            )),
+        ).with_history_capacity(8);
+        manager.add_analog_sensor_chain(chain);
+
+        assert!(manager.get_history(&fuel_input).unwrap().is_empty());
+        assert!(manager.get_history(&HWInput::HwSpeed).is_none());
+
+        for _ in 0..20 {
+            manager.read_all_sensors().unwrap();
+        }
+
+        // Oldest samples evicted, newest matches the current value
+        let history = manager.get_history(&fuel_input).unwrap();
+        assert_eq!(history.len(), 8);
+        let latest = history.latest().unwrap().1;
+        assert_eq!(latest, manager.get_sensor_value(&fuel_input).unwrap().as_f32());
+
+        let timestamps: Vec<_> = history.iter().map(|&(ts, _)| ts).collect();
+        assert!(timestamps.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_sensor_manager_derived_sensor() {
+        let mut manager = SensorManager::new();
+        for input in [HWInput::HwFuelLvl, HWInput::HwOilPress] {
+            manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+                Box::new(TestAnalogDataProvider::new(input)),
+                vec![],
+                Box::new(GenericAnalogSensor::new("test".to_string(), "Test".to_string(), "".to_string(),
+                                                 ValueConstraints::analog(0.0, 1000.0), 1.0)),
+            ));
+        }
+        manager.add_derived_sensor(DerivedSensor::new(
+            "sum".to_string(), "Sum".to_string(), "".to_string(),
+            ValueConstraints::analog(0.0, 2000.0),
+            vec![HWInput::HwFuelLvl, HWInput::HwOilPress],
+            Box::new(|values| values[0] + values[1]),
+        ));
+        // Source without a chain - never evaluated
+        manager.add_derived_sensor(DerivedSensor::new(
+            "orphan".to_string(), "Orphan".to_string(), "".to_string(),
+            ValueConstraints::analog(0.0, 100.0),
+            vec![HWInput::HwSpeed],
+            Box::new(|values| values[0]),
+        ));
+
+        assert!(manager.get_derived_value("sum").is_none());
+        manager.read_all_sensors().unwrap();
+
+        let fuel = manager.get_sensor_value(&HWInput::HwFuelLvl).unwrap().as_f32();
+        let oil = manager.get_sensor_value(&HWInput::HwOilPress).unwrap().as_f32();
+        assert_eq!(manager.get_derived_value("sum").unwrap().as_f32(), fuel + oil);
+        assert!(manager.get_derived_value("orphan").is_none());
+    }
+
+    #[test]
+    fn test_sensor_manager_distance() {
+        use crate::hardware::hw_providers::TestMaxAnalogDataProvider;
+        use std::sync::{Arc, Mutex};
+
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock_now = now.clone();
+        let mut manager = SensorManager::new()
+            .with_clock(Box::new(move || *clock_now.lock().unwrap()));
+        // Constant 36 km/h on the speed input
+        manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+            Box::new(TestMaxAnalogDataProvider::new(HWInput::HwSpeed)),
+            vec![],
+            Box::new(GenericAnalogSensor::new("speed".to_string(), "Speed".to_string(), "km/h".to_string(),
+                                             ValueConstraints::analog(0.0, 180.0), 36.0 / 1023.0)),
+        ));
+        manager.set_distance_accumulator(Some(DistanceAccumulator::new()));
+
+        // 11 reads 100 ms apart = 1 s = 10 m
+        for _ in 0..11 {
+            manager.read_all_sensors().unwrap();
+            *now.lock().unwrap() += Duration::from_millis(100);
+        }
+        let distance = manager.distance().unwrap();
+        assert!((distance.odometer_km() - 0.01).abs() < 1e-5, "Got {} km", distance.odometer_km());
+
+        assert!((manager.get_derived_value(TRIP_AVG_SPEED_ID).unwrap().as_f32() - 36.0).abs() < 0.01);
+        assert!((manager.get_derived_value(TRIP_TIME_ID).unwrap().as_f32() * 3600.0 - 1.0).abs() < 1e-3);
+
+        manager.reset_trip();
+        assert_eq!(manager.distance().unwrap().trip_km(), 0.0);
+        assert!(manager.distance().unwrap().odometer_km() > 0.0);
+    }
+
+    #[test]
+    fn test_min_update_interval_skips_reads() {
+        use std::sync::{Arc, Mutex};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct CountingProvider(Arc<AtomicUsize>);
+        impl HWAnalogProvider for CountingProvider {
+            fn input(&self) -> HWInput { HWInput::HwFuelLvl }
+            fn read_analog(&self, _input: HWInput) -> Result<u16, String> {
+                Ok(self.0.fetch_add(1, Ordering::SeqCst) as u16 * 10)
+            }
+        }
+
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock_now = now.clone();
+        let mut manager = SensorManager::new()
+            .with_clock(Box::new(move || *clock_now.lock().unwrap()));
+        let reads = Arc::new(AtomicUsize::new(0));
+        manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+            Box::new(CountingProvider(reads.clone())),
+            vec![],
+            Box::new(GenericAnalogSensor::new("fuel".to_string(), "Fuel".to_string(), "%".to_string(),
+                                             ValueConstraints::analog(0.0, 100.0), 1.0)),
+        ).with_min_update_interval(Duration::from_secs(1)));
+
+        // A frame's worth of cycles inside the second: one hardware read, the value kept
+        for _ in 0..60 {
+            manager.read_all_sensors().unwrap();
+            assert_eq!(manager.get_sensor_value(&HWInput::HwFuelLvl).unwrap().as_f32(), 0.0);
+            *now.lock().unwrap() += Duration::from_millis(16);
+        }
+        assert_eq!(reads.load(Ordering::SeqCst), 1);
+        assert_eq!(manager.get_history(&HWInput::HwFuelLvl).unwrap().len(), 1);
+        assert!(!manager.is_stale(&HWInput::HwFuelLvl));
+
+        // Due again once the second is up
+        *now.lock().unwrap() += Duration::from_millis(40);
+        manager.read_all_sensors().unwrap();
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+        assert_eq!(manager.get_sensor_value(&HWInput::HwFuelLvl).unwrap().as_f32(), 10.0);
+    }
+
+    #[test]
+    fn test_filtering_toggle() {
+        use std::sync::{Arc, Mutex};
+
+        struct SettableProvider(Arc<Mutex<u16>>);
+        impl HWAnalogProvider for SettableProvider {
+            fn input(&self) -> HWInput { HWInput::HwOilPress }
+            fn read_analog(&self, _input: HWInput) -> Result<u16, String> {
+                Ok(*self.0.lock().unwrap())
+            }
+        }
+
+        let raw = Arc::new(Mutex::new(100));
+        let mut manager = SensorManager::new();
+        manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+            Box::new(SettableProvider(raw.clone())),
+            vec![Box::new(AnalogSignalProcessorMovingAverage::new(4)),
+                 Box::new(AnalogSignalProcessorScaleOffset::new(1.0, 10.0))],
+            Box::new(GenericAnalogSensor::new("oil".to_string(), "Oil".to_string(), "".to_string(),
+                                             ValueConstraints::analog(0.0, 1023.0), 1.0)),
+        ));
+        let read = |manager: &mut SensorManager| {
+            manager.read_all_sensors().unwrap();
+            manager.get_sensor_value(&HWInput::HwOilPress).unwrap().as_f32()
+        };
+        for _ in 0..4 {
+            read(&mut manager);
+        }
+
+        // Filtered, a step only moves the average a quarter of the way
+        *raw.lock().unwrap() = 500;
+        assert_eq!(read(&mut manager), 210.0);
+
+        // Unfiltered, the provider value comes straight through, still calibrated
+        manager.set_filtering_enabled(false);
+        assert!(!manager.filtering_enabled());
+        *raw.lock().unwrap() = 700;
+        assert_eq!(read(&mut manager), 710.0);
+
+        // Back on, the average restarts from the current signal rather than the old samples
+        manager.set_filtering_enabled(true);
+        assert_eq!(read(&mut manager), 710.0);
+    }
+
+    #[test]
+    fn test_scale_offset_pipeline_order() {
+        use std::sync::{Arc, Mutex};
+
+        struct SettableProvider(HWInput, Arc<Mutex<u16>>);
+        impl HWAnalogProvider for SettableProvider {
+            fn input(&self) -> HWInput { self.0 }
+            fn read_analog(&self, _input: HWInput) -> Result<u16, String> {
+                Ok(*self.1.lock().unwrap())
+            }
+        }
+
+        let raw = Arc::new(Mutex::new(50));
+        let mut manager = SensorManager::new();
+        let chains: [(HWInput, Vec<Box<dyn AnalogSignalProcessor + Send>>); 3] = [
+            (HWInput::HwOilPress, vec![Box::new(AnalogSignalProcessorScaleOffset::new(2.0, 0.0)),
+                                       Box::new(AnalogSignalProcessorScaleOffset::new(1.0, 100.0))]),
+            (HWInput::HwFuelLvl, vec![Box::new(AnalogSignalProcessorScaleOffset::new(1.0, 100.0)),
+                                      Box::new(AnalogSignalProcessorScaleOffset::new(2.0, 0.0))]),
+            // Composes with the moving average like any other stage
+            (HWInput::Hw12v, vec![Box::new(AnalogSignalProcessorScaleOffset::new(1.0, -100.0)),
+                                  Box::new(AnalogSignalProcessorMovingAverage::new(2))]),
+        ];
+        for (input, processors) in chains {
+            manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+                Box::new(SettableProvider(input, raw.clone())),
+                processors,
+                Box::new(GenericAnalogSensor::new(format!("{:?}", input), "".to_string(), "".to_string(),
+                                                 ValueConstraints::analog(0.0, 1023.0), 1.0)),
+            ));
+        }
+        let value = |manager: &SensorManager, input| manager.get_sensor_value(&input).unwrap().as_f32();
+
+        // Processors run in the order they were given
+        manager.read_all_sensors().unwrap();
+        assert_eq!(value(&manager, HWInput::HwOilPress), 200.0);
+        assert_eq!(value(&manager, HWInput::HwFuelLvl), 300.0);
+
+        *raw.lock().unwrap() = 300;
+        manager.read_all_sensors().unwrap();
+        *raw.lock().unwrap() = 500;
+        manager.read_all_sensors().unwrap();
+        assert_eq!(value(&manager, HWInput::Hw12v), 300.0); // (200 + 400) / 2
+    }
+
+    #[test]
+    fn test_sensor_manager_engine_hours() {
+        use crate::hardware::hw_providers::TestMaxAnalogDataProvider;
+        use std::sync::{Arc, Mutex};
+
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock_now = now.clone();
+        let mut manager = SensorManager::new()
+            .with_clock(Box::new(move || *clock_now.lock().unwrap()));
+        manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+            Box::new(TestMaxAnalogDataProvider::new(HWInput::HwTacho)),
+            vec![],
+            Box::new(GenericAnalogSensor::new("rpm".to_string(), "RPM".to_string(), "".to_string(),
+                                             ValueConstraints::analog(0.0, 8000.0), 1.0)),
+        ));
+        manager.set_engine_hours_sensor(Some(EngineHoursSensor::new(HWInput::HwTacho, 0.0)));
+
+        for _ in 0..4 {
+            manager.read_all_sensors().unwrap();
+            *now.lock().unwrap() += Duration::from_secs(1);
+        }
+        let hours = manager.get_derived_value("engine_hours").unwrap().as_f32();
+        assert!((hours * 3600.0 - 3.0).abs() < 0.01, "Got {} h", hours);
+    }
+
+    #[test]
+    fn test_sensor_manager_engine_hours_from_tacho_counts() {
+        use crate::hardware::sensors::TachometerSensor;
+        use std::sync::{Arc, Mutex};
+
+        // ADC TACHO channel at idle: frames alternate between 0 and 1 pulse
+        struct IdleTacho(Mutex<u16>);
+        impl HWAnalogProvider for IdleTacho {
+            fn input(&self) -> HWInput { HWInput::HwTacho }
+            fn read_analog(&self, _input: HWInput) -> Result<u16, String> {
+                let mut frame = self.0.lock().unwrap();
+                *frame += 1;
+                Ok(*frame % 2)
+            }
+        }
+
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock_now = now.clone();
+        let mut manager = SensorManager::new()
+            .with_clock(Box::new(move || *clock_now.lock().unwrap()));
+        manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+            Box::new(IdleTacho(Mutex::new(0))),
+            vec![],
+            Box::new(TachometerSensor::new()),
+        ));
+        manager.set_engine_hours_sensor(Some(EngineHoursSensor::new(HWInput::HwTacho, 0.0)));
+
+        for _ in 0..4 {
+            manager.read_all_sensors().unwrap();
+            assert!(manager.get_sensor_value(&HWInput::HwTacho).unwrap().as_f32() > 0.0);
+            *now.lock().unwrap() += Duration::from_secs(1);
+        }
+        let hours = manager.get_derived_value("engine_hours").unwrap().as_f32();
+        assert!((hours * 3600.0 - 3.0).abs() < 0.01, "Got {} h", hours);
+    }
+
+    #[test]
+    fn test_sensor_manager_lookup_by_input_and_id() {
+        let mut manager = SensorManager::new();
+        manager.add_digital_sensor_chain(SensorDigitalInputChain::new(
+            Box::new(TestDigitalDataProvider::new(HWInput::HwHighBeam)),
+            vec![],
+            Box::new(GenericDigitalSensor::new("HwHighBeam".to_string(), "High Beam".to_string(),
+                                              Level::High, ValueConstraints::digital_default())),
+        ));
+        manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+            Box::new(TestAnalogDataProvider::new(HWInput::Hw12v)),
+            vec![],
+            Box::new(GenericAnalogSensor::new("Hw12v".to_string(), "12V".to_string(), "V".to_string(),
+                                             ValueConstraints::analog(0.0, 20.0), 0.02)),
+        ));
+        manager.read_all_sensors().unwrap();
+
+        for (input, id) in [(HWInput::HwHighBeam, "HwHighBeam"), (HWInput::Hw12v, "Hw12v")] {
+            let by_input = manager.get_sensor_value(&input).unwrap();
+            let by_id = manager.get_sensor_value_by_id(id).unwrap();
+            assert_eq!(by_input, by_id);
+            assert_eq!(by_input.metadata.sensor_id, id);
+            assert_eq!(manager.input_for_id(id), Some(input));
+        }
+        assert!(manager.get_sensor_value_by_id("missing").is_none());
+
+        // Every input can be queried, configured or not
+        let configured = HWInput::ALL.iter().filter(|input| manager.get_sensor_value(input).is_some()).count();
+        assert_eq!(configured, 2);
+    }
+
+    #[test]
+    fn test_sensor_manager_snapshot() {
+        let mut manager = SensorManager::new();
+        manager.add_digital_sensor_chain(SensorDigitalInputChain::new(
+            Box::new(TestDigitalDataProvider::new(HWInput::HwHighBeam)),
+            vec![],
+            Box::new(GenericDigitalSensor::new("high_beam".to_string(), "High Beam".to_string(),
+                                              Level::High, ValueConstraints::digital_default())),
+        ));
+        manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+            Box::new(TestAnalogDataProvider::new(HWInput::HwEngineCoolantTemp)),
+            vec![],
+            Box::new(GenericAnalogSensor::new("temp".to_string(), "Temp".to_string(), "°C".to_string(),
+                                             ValueConstraints::analog_with_thresholds(0.0, 130.0, None, None, Some(-1.0), None),
+                                             0.1)),
+        ));
+        manager.add_derived_sensor(DerivedSensor::new(
+            "double_temp".to_string(), "2x Temp".to_string(), "°C".to_string(),
+            ValueConstraints::analog(0.0, 1000.0),
+            vec![HWInput::HwEngineCoolantTemp],
+            Box::new(|values| values[0] * 2.0),
+        ));
+
+        // Registered sensors are present before the first read, without values
+        let snapshot = manager.snapshot();
+        assert_eq!(snapshot.len(), 3);
+        assert!(snapshot.values().all(|reading| reading.value.is_none()));
+
+        manager.read_all_sensors().unwrap();
+        let snapshot = manager.snapshot();
+        let mut ids: Vec<&String> = snapshot.keys().collect();
+        ids.sort();
+        assert_eq!(ids, vec!["double_temp", "high_beam", "temp"]);
+
+        let temp = &snapshot["temp"];
+        assert_eq!(temp.input, Some(HWInput::HwEngineCoolantTemp));
+        assert_eq!(temp.unit, "°C");
+        assert_eq!(temp.value, Some(manager.get_sensor_value(&HWInput::HwEngineCoolantTemp).unwrap().as_f32()));
+        assert!(temp.warning); // Any reading is above the -1.0 warning threshold
+        assert!(!temp.critical && !temp.stale && temp.fault.is_none());
+        assert_eq!(snapshot["double_temp"].value, Some(temp.value.unwrap() * 2.0));
+        assert_eq!(snapshot["double_temp"].input, None);
+        assert!(snapshot["high_beam"].digital && snapshot["high_beam"].raw.is_some());
+        assert!(!temp.digital && temp.raw.is_some());
+        assert_eq!(snapshot["double_temp"].raw, None);
+    }
+
+    #[test]
+    fn test_sensor_manager_background_updates() {
+        let mut manager = SensorManager::new();
+        manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+            Box::new(TestAnalogDataProvider::new(HWInput::HwFuelLvl)),
+            vec![],
+            Box::new(GenericAnalogSensor::new("fuel".to_string(), "Fuel".to_string(), "%".to_string(),
+                                             ValueConstraints::analog(0.0, 1023.0), 1.0)),
+        ));
+
+        let mut updates = manager.start_background_updates(Duration::from_millis(5));
+        std::thread::sleep(Duration::from_millis(50));
+        let first = updates.latest();
+        assert!(first.cycle > 0, "No update cycle completed");
+        assert!(first.get_sensor_value(&HWInput::HwFuelLvl).is_some());
+        assert!(first.get_history(&HWInput::HwFuelLvl).is_some_and(|history| !history.is_empty()));
+        assert!(first.readings().contains_key("fuel"));
+        assert!(first.filtering_enabled());
+
+        // Control actions reach the manager and show up in later snapshots
+        updates.with_manager(|manager| manager.set_filtering_enabled(false));
+        std::thread::sleep(Duration::from_millis(50));
+        let second = updates.latest();
+        assert!(second.cycle > first.cycle);
+        assert!(second.updated_at.unwrap() > first.updated_at.unwrap());
+        assert!(!second.filtering_enabled());
+
+        updates.stop();
+        let stopped = updates.latest();
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(updates.latest().cycle, stopped.cycle, "Thread kept running after stop()");
+    }
+
+    #[test]
+    fn test_sensor_manager_logging() {
+        let dir = std::env::temp_dir().join(format!("niva_manager_log_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut manager = SensorManager::new();
+        manager.add_digital_sensor_chain(SensorDigitalInputChain::new(
+            Box::new(TestDigitalDataProvider::new(HWInput::HwHighBeam)),
+            vec![],
+            Box::new(GenericDigitalSensor::new("high_beam".to_string(), "High Beam".to_string(),
+                                              Level::High, ValueConstraints::digital_default())),
+        ));
+        manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+            Box::new(TestAnalogDataProvider::new(HWInput::HwFuelLvl)),
+            vec![],
+            Box::new(GenericAnalogSensor::new("fuel".to_string(), "Fuel".to_string(), "%".to_string(),
+                                             ValueConstraints::analog(0.0, 100.0), 0.1)),
+        ));
+        manager.set_logger(Some(SensorLogger::new(&dir)));
+
+        manager.read_all_sensors().unwrap();
+        manager.read_all_sensors().unwrap();
+        let path = manager.logger.as_ref().unwrap().current_path().unwrap().to_path_buf();
+        manager.set_logger(None); // Drop flushes
+
+        let contents = std::fs::read_to_string(path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines[0], "timestamp,high_beam_raw,high_beam,fuel_raw,fuel");
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1..].iter().all(|line| line.split(',').count() == 5));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_sensor_manager_staleness() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::{Arc, Mutex};
+
+        // Reads fail while the link is down, like ADC channels with no frame
+        struct LinkProvider(HWInput, Arc<AtomicBool>);
+        impl HWAnalogProvider for LinkProvider {
+            fn input(&self) -> HWInput { self.0 }
+            fn read_analog(&self, _input: HWInput) -> Result<u16, String> {
+                if self.1.load(Ordering::SeqCst) { Ok(500) } else { Err("link down".to_string()) }
+            }
+        }
+
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock_now = now.clone();
+        let mut manager = SensorManager::new()
+            .with_clock(Box::new(move || *clock_now.lock().unwrap()));
+        let link_up = Arc::new(AtomicBool::new(true));
+        let advance = |ms| *now.lock().unwrap() += Duration::from_millis(ms);
+
+        let fuel_input = HWInput::HwFuelLvl;
+        let temp_input = HWInput::HwEngineCoolantTemp;
+        for (input, max_age) in [(fuel_input, Some(Duration::from_millis(500))), (temp_input, None)] {
+            let chain = SensorAnalogInputChain::new(
+                Box::new(LinkProvider(input, link_up.clone())),
+                vec![],
+                Box::new(GenericAnalogSensor::new(
+                    "test".to_string(), "Test".to_string(), "".to_string(),
+                    ValueConstraints::analog(0.0, 100.0), 0.1
+                )),
+            );
+            let chain = match max_age {
+                Some(max_age) => chain.with_max_age(max_age),
+                None => chain,
+            };
+            manager.add_analog_sensor_chain(chain);
+        }
+
+        assert_eq!(manager.get_value(&fuel_input), SensorValueState::Unavailable);
+        assert!(manager.is_stale(&fuel_input));
+
+        manager.read_all_sensors().unwrap();
+        assert!(matches!(manager.get_value(&fuel_input), SensorValueState::Live(_)));
+
+        // Reads fail from here on; within max_age the last good value is still live
+        link_up.store(false, Ordering::SeqCst);
+        advance(400);
+        assert!(manager.read_all_sensors().is_err());
+        let SensorValueState::Live(kept) = manager.get_value(&fuel_input) else {
+            panic!("Expected the last good value to stay live");
+        };
+        assert!((kept.as_f32() - 50.0).abs() < 1e-4);
+
+        // Past max_age the same value is flagged
+        advance(200);
+        assert!(manager.read_all_sensors().is_err());
+        let SensorValueState::Stale(kept) = manager.get_value(&fuel_input) else {
+            panic!("Expected the last good value to go stale");
+        };
+        assert!((kept.as_f32() - 50.0).abs() < 1e-4);
+        // Chains without max_age can't flag a kept value, so theirs is dropped
+        assert_eq!(manager.get_value(&temp_input), SensorValueState::Unavailable);
+
+        // A good read clears it
+        link_up.store(true, Ordering::SeqCst);
+        manager.read_all_sensors().unwrap();
+        assert!(matches!(manager.get_value(&fuel_input), SensorValueState::Live(_)));
+        assert!(matches!(manager.get_value(&temp_input), SensorValueState::Live(_)));
+    }
+
+    #[test]
+    fn test_sensor_manager_cached_value_not_stale() {
+        use std::sync::{Arc, Mutex};
+
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock_now = now.clone();
+        let mut manager = SensorManager::new()
+            .with_clock(Box::new(move || *clock_now.lock().unwrap()));
+        manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+            Box::new(TestAnalogDataProvider::new(HWInput::HwFuelLvl)),
+            vec![],
+            Box::new(GenericAnalogSensor::new("fuel".to_string(), "Fuel".to_string(), "%".to_string(),
+                                             ValueConstraints::analog(0.0, 100.0), 0.1)),
+        ).with_min_update_interval(Duration::from_secs(2)).with_max_age(Duration::from_millis(500)));
+
+        // Reusing the value between hardware reads still counts as an update
+        for _ in 0..10 {
+            manager.read_all_sensors().unwrap();
+            assert!(matches!(manager.get_value(&HWInput::HwFuelLvl), SensorValueState::Live(_)));
+            *now.lock().unwrap() += Duration::from_millis(150);
+        }
+    }
+
     #[test] 
     fn test_sensor_manager_signal_processing_pipeline() {
         log::info!("=== Testing Signal Processing Pipeline ===");
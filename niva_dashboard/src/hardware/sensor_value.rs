@@ -5,6 +5,11 @@ pub struct SensorValue {
     pub value: ValueData,
     pub constraints: ValueConstraints,
     pub metadata: ValueMetadata,
+    /// Zone as evaluated by a stateful sensor (see ValueConstraints::evaluate).
+    /// When None, is_critical()/is_warning() are computed from the value alone.
+    pub threshold_state: Option<ThresholdState>,
+    /// Set when the sensor detected its reading can't be trusted
+    pub fault: Option<SensorFault>,
 }
 
 /// The actual sensor value data
@@ -36,6 +41,11 @@ pub struct ValueConstraints {
     pub warning_high: Option<f32>,
     /// Critical high threshold (red zone)
     pub critical_high: Option<f32>,
+    /// Margin a value must move back past a threshold before its zone clears,
+    /// so readings hovering at a limit don't chatter between states
+    pub hysteresis: f32,
+    /// Values with magnitude below this are reported as exactly zero (noise floor)
+    pub deadband: f32,
 }
 
 impl ValueConstraints {
@@ -49,6 +59,8 @@ impl ValueConstraints {
             warning_low,
             warning_high,
             critical_high,
+            hysteresis: 0.0,
+            deadband: 0.0,
         }
     }
 
@@ -62,6 +74,8 @@ impl ValueConstraints {
             warning_low: None,
             warning_high: None,
             critical_high: None,
+            hysteresis: 0.0,
+            deadband: 0.0,
         }
     }
 
@@ -75,6 +89,8 @@ impl ValueConstraints {
             warning_low: None,
             warning_high: None,
             critical_high: Some(1.0),
+            hysteresis: 0.0,
+            deadband: 0.0,
         }
     }
 
@@ -89,6 +105,8 @@ impl ValueConstraints {
             warning_low: None,
             warning_high: Some(1.0),
             critical_high: None,
+            hysteresis: 0.0,
+            deadband: 0.0,
         }
     }
 
@@ -101,6 +119,8 @@ impl ValueConstraints {
             warning_low: None,
             warning_high: None,
             critical_high: None,
+            hysteresis: 0.0,
+            deadband: 0.0,
         }
     }
 
@@ -114,10 +134,69 @@ impl ValueConstraints {
             warning_low,
             warning_high,
             critical_high,
+            hysteresis: 0.0,
+            deadband: 0.0,
         }
     }
 }
 
+impl ValueConstraints {
+    pub fn with_hysteresis(mut self, margin: f32) -> Self {
+        self.hysteresis = margin.max(0.0);
+        self
+    }
+
+    pub fn with_deadband(mut self, threshold: f32) -> Self {
+        self.deadband = threshold.max(0.0);
+        self
+    }
+
+    /// Snaps sub-deadband values to zero. Sensors apply this to the converted value,
+    /// before range clamping and threshold evaluation.
+    pub fn apply_deadband(&self, value: f32) -> f32 {
+        if value.abs() < self.deadband { 0.0 } else { value }
+    }
+
+    /// Threshold zone for a value, given the zone of the previous reading.
+    /// Entering a zone uses the plain threshold; leaving it requires the value to move
+    /// back past the threshold by the hysteresis margin. With zero hysteresis this is
+    /// equivalent to the stateless is_critical()/is_warning() checks.
+    pub fn evaluate(&self, value: f32, previous: ThresholdState) -> ThresholdState {
+        let margin = self.hysteresis;
+
+        let crit_margin = if previous == ThresholdState::Critical { margin } else { 0.0 };
+        if self.critical_low.is_some_and(|t| value <= t + crit_margin)
+            || self.critical_high.is_some_and(|t| value >= t - crit_margin) {
+            return ThresholdState::Critical;
+        }
+
+        let warn_margin = if previous != ThresholdState::Normal { margin } else { 0.0 };
+        if self.warning_low.is_some_and(|t| value <= t + warn_margin)
+            || self.warning_high.is_some_and(|t| value >= t - warn_margin) {
+            return ThresholdState::Warning;
+        }
+
+        ThresholdState::Normal
+    }
+}
+
+/// Threshold zone a sensor value falls into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdState {
+    Normal,
+    Warning,
+    Critical,
+}
+
+/// Reasons a sensor reading is not trustworthy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorFault {
+    /// Raw input pinned at a rail - sender disconnected or shorted
+    OpenCircuit,
+    /// Value hasn't moved for longer than a live signal plausibly could
+    Stuck,
+}
+
 /// Additional metadata about the sensor value
 #[derive(Debug, Clone, PartialEq)]
 pub struct ValueMetadata {
@@ -142,7 +221,7 @@ impl ValueMetadata {
 impl SensorValue {
     /// Create a new sensor value with full context
     pub fn new(value: ValueData, constraints: ValueConstraints, metadata: ValueMetadata) -> Self {
-        Self { value, constraints, metadata }
+        Self { value, constraints, metadata, threshold_state: None, fault: None }
     }
 
     /// Create empty sensor value
@@ -155,6 +234,8 @@ impl SensorValue {
                 label: String::new(),
                 sensor_id: String::new(),
             },
+            threshold_state: None,
+            fault: None,
         }
     }
     
@@ -169,12 +250,16 @@ impl SensorValue {
                 warning_low: None,
                 warning_high: None,
                 critical_high: None,
+                hysteresis: 0.0,
+                deadband: 0.0,
             },
             metadata: ValueMetadata {
                 unit: String::new(),
                 label: label.into(),
                 sensor_id: sensor_id.into(),
             },
+            threshold_state: None,
+            fault: None,
         }
     }
 
@@ -187,6 +272,8 @@ impl SensorValue {
             value: ValueData::Digital(value),
             constraints,
             metadata,
+            threshold_state: None,
+            fault: None,
         }
     }
 
@@ -208,12 +295,16 @@ impl SensorValue {
                 warning_low: None,
                 warning_high: None,
                 critical_high: None,
+                hysteresis: 0.0,
+                deadband: 0.0,
             },
             metadata: ValueMetadata {
                 unit: unit.into(),
                 label: label.into(),
                 sensor_id: sensor_id.into(),
             },
+            threshold_state: None,
+            fault: None,
         }
     }
     
@@ -239,12 +330,16 @@ impl SensorValue {
                 warning_low,
                 warning_high,
                 critical_high,
+                hysteresis: 0.0,
+                deadband: 0.0,
             },
             metadata: ValueMetadata {
                 unit: unit.into(),
                 label: label.into(),
                 sensor_id: sensor_id.into(),
             },
+            threshold_state: None,
+            fault: None,
         }
     }
 
@@ -257,6 +352,8 @@ impl SensorValue {
             value: ValueData::Analog(value),
             constraints,
             metadata,
+            threshold_state: None,
+            fault: None,
         }
     }
     
@@ -283,8 +380,26 @@ impl SensorValue {
         }
     }
     
+    /// Attach the zone evaluated by a stateful sensor
+    pub fn with_threshold_state(mut self, state: ThresholdState) -> Self {
+        self.threshold_state = Some(state);
+        self
+    }
+
+    pub fn with_fault(mut self, fault: Option<SensorFault>) -> Self {
+        self.fault = fault;
+        self
+    }
+
+    pub fn is_faulted(&self) -> bool {
+        self.fault.is_some()
+    }
+
     /// Check if value is in critical range
     pub fn is_critical(&self) -> bool {
+        if let Some(state) = self.threshold_state {
+            return state == ThresholdState::Critical;
+        }
         let val = self.as_f32();
         if let Some(crit_low) = self.constraints.critical_low {
             if val <= crit_low { return true; }
@@ -297,6 +412,9 @@ impl SensorValue {
     
     /// Check if value is in warning range
     pub fn is_warning(&self) -> bool {
+        if let Some(state) = self.threshold_state {
+            return state == ThresholdState::Warning;
+        }
         if self.is_critical() { return false; } // Critical overrides warning
         let val = self.as_f32();
         if let Some(warn_low) = self.constraints.warning_low {
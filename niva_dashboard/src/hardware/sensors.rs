@@ -1,8 +1,15 @@
 #![allow(dead_code)]
 use rppal::gpio::Level;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
-use crate::hardware::sensor_value::{SensorValue, ValueConstraints, ValueMetadata};
+use crate::hardware::sensor_value::{SensorFault, SensorValue, ThresholdState, ValueConstraints, ValueMetadata};
 use crate::hardware::digital_signal_processing::{DigitalSignalProcessor, DigitalSignalProcessorPulsePerSecond};
+use crate::hardware::hw_providers::HWInput;
+use crate::hardware::thermistor::SteinhartHart;
+use crate::util::adc_data_provider::ADC_FRAME_INTERVAL;
+use crate::util::totals_file::{load_totals, save_totals};
 
 // Used by all sensor types
 pub trait Sensor {
@@ -100,6 +107,18 @@ pub struct GenericAnalogSensor {
     constraints: ValueConstraints,
     metadata: ValueMetadata,
     scale_factor: f32,
+    // Added after scaling, for inputs encoded relative to a non-zero minimum
+    value_offset: f32,
+    // When set, replaces the linear scale_factor conversion
+    thermistor: Option<SteinhartHart>,
+    // Zone of the last reading - needed to apply threshold hysteresis
+    threshold_state: ThresholdState,
+    // Raw readings at or beyond these bounds mean the sender is disconnected/shorted
+    open_circuit_low: Option<u16>,
+    open_circuit_high: Option<u16>,
+    // Stuck detection: (epsilon, timeout), plus the value and time movement was last seen
+    stuck_detection: Option<(f32, Duration)>,
+    stuck_reference: Option<(f32, Instant)>,
 }
 
 impl GenericAnalogSensor {
@@ -111,8 +130,85 @@ impl GenericAnalogSensor {
             constraints,
             metadata,
             scale_factor,
+            value_offset: 0.0,
+            thermistor: None,
+            threshold_state: ThresholdState::Normal,
+            open_circuit_low: None,
+            open_circuit_high: None,
+            stuck_detection: None,
+            stuck_reference: None,
         }
     }
+
+    /// Temperature sensor reading an NTC thermistor divider, in °C
+    pub fn ntc_thermistor(id: String, name: String, constraints: ValueConstraints,
+                          thermistor: SteinhartHart) -> Self {
+        let mut sensor = Self::new(id, name, "°C".to_string(), constraints, 1.0);
+        sensor.thermistor = Some(thermistor);
+        sensor
+    }
+
+    fn convert(&self, input: u16) -> f32 {
+        match &self.thermistor {
+            Some(thermistor) => thermistor.temperature_c(input),
+            None => (input as f32) * self.scale_factor + self.value_offset,
+        }
+    }
+
+    /// Add `offset` to the scaled input, e.g. a CAN signal's minimum (see CanSignalProvider)
+    pub fn with_value_offset(mut self, offset: f32) -> Self {
+        self.value_offset = offset;
+        self
+    }
+
+    /// Flag OpenCircuit when the raw input is at or below `low` / at or above `high`
+    pub fn with_open_circuit_detection(mut self, low: Option<u16>, high: Option<u16>) -> Self {
+        self.open_circuit_low = low;
+        self.open_circuit_high = high;
+        self
+    }
+
+    /// Flag Stuck when the value stays within `epsilon` for `timeout`.
+    /// Only useful on signals that always carry some noise - a clean, legitimately
+    /// constant reading (e.g. fuel level while parked) would trip it.
+    pub fn with_stuck_detection(mut self, epsilon: f32, timeout: Duration) -> Self {
+        self.stuck_detection = Some((epsilon, timeout));
+        self
+    }
+
+    fn detect_fault(&mut self, input: u16, value: f32, now: Instant) -> Option<SensorFault> {
+        if self.open_circuit_low.is_some_and(|low| input <= low)
+            || self.open_circuit_high.is_some_and(|high| input >= high) {
+            // Rail readings are constant by nature; don't let them count towards Stuck
+            self.stuck_reference = None;
+            return Some(SensorFault::OpenCircuit);
+        }
+
+        let (epsilon, timeout) = self.stuck_detection?;
+        match self.stuck_reference {
+            Some((reference, since)) if (value - reference).abs() <= epsilon => {
+                if now.duration_since(since) >= timeout {
+                    return Some(SensorFault::Stuck);
+                }
+            }
+            _ => self.stuck_reference = Some((value, now)),
+        }
+        None
+    }
+
+    fn read_at(&mut self, input: u16, now: Instant) -> Result<&SensorValue, String> {
+        let value = self.constraints.apply_deadband(self.convert(input))
+            .clamp(self.min_value(), self.max_value());
+        self.threshold_state = self.constraints.evaluate(value, self.threshold_state);
+        let fault = self.detect_fault(input, value, now);
+        self.value = SensorValue::analog_with_constraints_and_metadata(
+            value,
+            self.constraints.clone(),
+            self.metadata.clone(),
+        ).with_threshold_state(self.threshold_state)
+         .with_fault(fault);
+        Ok(&self.value)
+    }
 }
 
 impl Sensor for GenericAnalogSensor {
@@ -147,13 +243,7 @@ impl Sensor for GenericAnalogSensor {
 
 impl AnalogSensor for GenericAnalogSensor {
     fn read(&mut self, input: u16) -> Result<&SensorValue, String> {
-        let value = (input as f32) * self.scale_factor;
-        self.value = SensorValue::analog_with_constraints_and_metadata(
-            value.clamp(self.min_value(), self.max_value()),
-            self.constraints.clone(),
-            self.metadata.clone(),
-        );
-        Ok(&self.value)
+        self.read_at(input, Instant::now())
     }
 }
 
@@ -225,10 +315,13 @@ impl AnalogSensor for EngineTemperatureSensor {
     }
 }
 
+/// Circumference of the stock 235/75/15 tire, which pulse-per-km figures are quoted against
+pub const NOMINAL_TIRE_CIRCUMFERENCE_M: f32 = 2.304;
+
 pub struct SpeedSensor {
     speed: SensorValue,
     pulse_counter: DigitalSignalProcessorPulsePerSecond,
-    pulses_per_revolution: u32,
+    pulses_per_revolution: f32,
     wheel_circumference_m: f32,
     constraints: ValueConstraints,
     metadata: ValueMetadata,
@@ -245,20 +338,35 @@ impl SpeedSensor {
         SpeedSensor {
             speed: SensorValue::analog(0.0, 0.0, 180.0, &metadata.unit, &metadata.label, &metadata.sensor_id),
             pulse_counter: DigitalSignalProcessorPulsePerSecond::new(),
-            pulses_per_revolution: 6, // 6 pulses per wheel rotation
-            wheel_circumference_m: 2.304, // meters
+            pulses_per_revolution: 6.0, // 6 pulses per wheel rotation
+            wheel_circumference_m: NOMINAL_TIRE_CIRCUMFERENCE_M,
             constraints: ValueConstraints::analog(0.0, 180.0),
             metadata,
         }
     }
+
+    /// Speed sensor for a specific gearbox/tire combination.
+    /// pulses_per_km is the pulse count over one km on the stock tire (NOMINAL_TIRE_CIRCUMFERENCE_M),
+    /// as given by the speedometer drive spec. That fixes the pulses per wheel revolution;
+    /// the fitted tire's circumference then sets how far each revolution goes.
+    pub fn with_calibration(pulses_per_km: f32, tire_circumference_m: f32) -> Self {
+        let mut sensor = Self::new();
+        sensor.pulses_per_revolution = pulses_per_km * NOMINAL_TIRE_CIRCUMFERENCE_M / 1000.0;
+        sensor.wheel_circumference_m = tire_circumference_m;
+        sensor
+    }
     
     /// Process a digital input pulse and return current speed
     fn process_pulse(&mut self, pulse: Level) -> f32 {
+        self.process_pulse_at(pulse, Instant::now())
+    }
+
+    fn process_pulse_at(&mut self, pulse: Level, now: Instant) -> f32 {
         // Process the pulse through the counter (using DigitalSignalProcessor trait)
         let _ = self.pulse_counter.read(pulse);
         
         // Get current pulses per second
-        let pulses_per_second = self.pulse_counter.pulses_per_second();
+        let pulses_per_second = self.pulse_counter.pulses_per_second_at(now);
         
         // Debug: Log pulse activity
         // static mut PULSE_COUNT: u32 = 0;
@@ -279,12 +387,12 @@ impl SpeedSensor {
     
     /// Calculate speed in km/h from pulses per second
     fn calculate_speed_kmh(&self, pulses_per_second: f32) -> f32 {
-        if pulses_per_second <= 0.0 {
+        if pulses_per_second <= 0.0 || self.pulses_per_revolution <= 0.0 {
             return 0.0;
         }
         
         // Revolutions per second = pulses_per_second / pulses_per_revolution
-        let revolutions_per_second = pulses_per_second / self.pulses_per_revolution as f32;
+        let revolutions_per_second = pulses_per_second / self.pulses_per_revolution;
         
         // Distance per second (m/s) = revolutions_per_second * wheel_circumference
         let meters_per_second = revolutions_per_second * self.wheel_circumference_m;
@@ -306,7 +414,6 @@ impl Sensor for SpeedSensor {
     }
 
     fn value(&self) -> Result<&SensorValue, String> {
-        //print!("fn value: Returning speed value: {:?}\r\n", self.speed.as_f32());
         Ok(&self.speed)
     }
 
@@ -334,15 +441,374 @@ impl DigitalSensor for SpeedSensor {
 
     fn read(&mut self, input: Level) -> Result<&SensorValue, String> {
         self.process_pulse(input);
-        //print!("fn read: Returning speed value: {:?}\r\n", self.speed.as_f32());
+        log::trace!("Speed: {:?}", self.speed.as_f32());
         Ok(&self.speed)
     }
 }
 
+// Frame counts averaged for RPM: at idle a 20 ms frame holds 0 or 1 pulses, so a single
+// frame only resolves 1500 RPM steps; 25 frames (0.5 s) bring that down to 60 RPM
+const TACHO_COUNT_WINDOW: usize = 25;
+
+/// Engine RPM from ignition pulses. Read as a digital sensor it counts edges of the pulse
+/// train itself; read as an analog sensor the input is a pulse count per `count_interval`,
+/// as the ADC module reports TACHO.
+pub struct TachometerSensor {
+    rpm: SensorValue,
+    pulse_counter: DigitalSignalProcessorPulsePerSecond,
+    pulses_per_revolution: f32,
+    count_interval: Duration,
+    recent_counts: VecDeque<u16>,
+    constraints: ValueConstraints,
+    metadata: ValueMetadata,
+}
+
+impl TachometerSensor {
+    pub fn new() -> Self {
+        // 4-cylinder engine: ignition fires twice per crankshaft revolution
+        Self::with_pulses_per_revolution(2.0)
+    }
+
+    pub fn with_pulses_per_revolution(pulses_per_revolution: f32) -> Self {
+        let metadata = ValueMetadata::new("об/мин", "ТАХОМЕТР", "tachometer_sensor");
+
+        TachometerSensor {
+            rpm: SensorValue::analog(0.0, 0.0, 8000.0, &metadata.unit, &metadata.label, &metadata.sensor_id),
+            pulse_counter: DigitalSignalProcessorPulsePerSecond::new(),
+            pulses_per_revolution,
+            count_interval: ADC_FRAME_INTERVAL,
+            recent_counts: VecDeque::with_capacity(TACHO_COUNT_WINDOW),
+            constraints: ValueConstraints::analog(0.0, 8000.0),
+            metadata,
+        }
+    }
+
+    pub fn with_count_interval(mut self, count_interval: Duration) -> Self {
+        self.count_interval = count_interval;
+        self
+    }
+
+    /// Calculate engine RPM from pulses per second
+    fn calculate_rpm(&self, pulses_per_second: f32) -> f32 {
+        if pulses_per_second <= 0.0 || self.pulses_per_revolution <= 0.0 {
+            return 0.0;
+        }
+        pulses_per_second / self.pulses_per_revolution * 60.0
+    }
+
+    fn read_at(&mut self, input: Level, now: Instant) -> Result<&SensorValue, String> {
+        let _ = self.pulse_counter.read(input);
+        let pulses_per_second = self.pulse_counter.pulses_per_second_at(now);
+        self.set_rpm(pulses_per_second)
+    }
+
+    fn set_rpm(&mut self, pulses_per_second: f32) -> Result<&SensorValue, String> {
+        self.rpm = SensorValue::analog_with_constraints_and_metadata(
+            self.calculate_rpm(pulses_per_second).clamp(self.constraints.min_value, self.constraints.max_value),
+            self.constraints.clone(), self.metadata.clone());
+        Ok(&self.rpm)
+    }
+}
+
+impl Sensor for TachometerSensor {
+    fn id(&self) -> &String {
+        &self.metadata.sensor_id
+    }
+
+    fn name(&self) -> &String {
+        &self.metadata.label
+    }
+
+    fn value(&self) -> Result<&SensorValue, String> {
+        Ok(&self.rpm)
+    }
+
+    fn constraints(&self) -> &ValueConstraints {
+        &self.constraints
+    }
+
+    fn metadata(&self) -> &ValueMetadata {
+        &self.metadata
+    }
+
+    fn min_value(&self) -> f32 {
+        self.constraints.min_value
+    }
+
+    fn max_value(&self) -> f32 {
+        self.constraints.max_value
+    }
+}
+
+impl DigitalSensor for TachometerSensor {
+    fn active_level(&self) -> Level {
+        Level::High
+    }
+
+    fn read(&mut self, input: Level) -> Result<&SensorValue, String> {
+        self.read_at(input, Instant::now())
+    }
+}
+
+impl AnalogSensor for TachometerSensor {
+    fn read(&mut self, input: u16) -> Result<&SensorValue, String> {
+        if self.recent_counts.len() == TACHO_COUNT_WINDOW {
+            self.recent_counts.pop_front();
+        }
+        self.recent_counts.push_back(input);
+        let total: u32 = self.recent_counts.iter().map(|&count| count as u32).sum();
+        let counted_secs = self.recent_counts.len() as f32 * self.count_interval.as_secs_f32();
+        self.set_rpm(total as f32 / counted_secs)
+    }
+}
+
+pub type DerivedFn = Box<dyn FnMut(&[f32]) -> f32 + Send>;
+
+/// Sensor computed from the current values of other sensors rather than from hardware.
+/// SensorManager evaluates it after all chains have been read, passing source values in
+/// the order the sources were given. The function may keep state (e.g. running averages).
+pub struct DerivedSensor {
+    value: SensorValue,
+    sources: Vec<HWInput>,
+    compute: DerivedFn,
+    constraints: ValueConstraints,
+    metadata: ValueMetadata,
+}
+
+impl DerivedSensor {
+    pub fn new(id: String, name: String, units: String, constraints: ValueConstraints,
+               sources: Vec<HWInput>, compute: DerivedFn) -> Self {
+        DerivedSensor {
+            value: SensorValue::empty(),
+            sources,
+            compute,
+            constraints,
+            metadata: ValueMetadata::new(units, name, id),
+        }
+    }
+
+    /// Mean speed over all updates since creation. Updates come once per event loop
+    /// iteration, so a sample mean is close enough to a time-weighted one.
+    pub fn trip_average_speed() -> Self {
+        let mut sum = 0.0f64;
+        let mut count = 0u64;
+        Self::new("trip_avg_speed".to_string(), "СР СКОР".to_string(), "км/ч".to_string(),
+                  ValueConstraints::analog(0.0, 180.0),
+                  vec![HWInput::HwSpeed],
+                  Box::new(move |values| {
+                      sum += values[0] as f64;
+                      count += 1;
+                      (sum / count as f64) as f32
+                  }))
+    }
+
+    pub fn sources(&self) -> &[HWInput] {
+        &self.sources
+    }
+
+    pub fn evaluate(&mut self, inputs: &[f32]) -> &SensorValue {
+        let value = (self.compute)(inputs).clamp(self.constraints.min_value, self.constraints.max_value);
+        self.value = SensorValue::analog_with_constraints_and_metadata(
+            value,
+            self.constraints.clone(),
+            self.metadata.clone(),
+        );
+        &self.value
+    }
+}
+
+impl Sensor for DerivedSensor {
+    fn id(&self) -> &String {
+        &self.metadata.sensor_id
+    }
+
+    fn name(&self) -> &String {
+        &self.metadata.label
+    }
+
+    fn value(&self) -> Result<&SensorValue, String> {
+        Ok(&self.value)
+    }
+
+    fn constraints(&self) -> &ValueConstraints {
+        &self.constraints
+    }
+
+    fn metadata(&self) -> &ValueMetadata {
+        &self.metadata
+    }
+
+    fn min_value(&self) -> f32 {
+        self.constraints.min_value
+    }
+
+    fn max_value(&self) -> f32 {
+        self.constraints.max_value
+    }
+}
+
+// Update gaps longer than this (thread stall, ADC reconnect) are not counted as run time
+const ENGINE_HOURS_MAX_STEP: Duration = Duration::from_secs(5);
+const ENGINE_HOURS_SAVE_INTERVAL_SECS: f64 = 60.0;
+
+/// Total engine run time in hours, accumulated while a source value (RPM, oil pressure)
+/// is above a threshold. A dropout shorter than the grace period - a stall and restart -
+/// is counted as running; only once it lasts longer does accumulation stop, and the grace
+/// time is discarded.
+pub struct EngineHoursSensor {
+    value: SensorValue,
+    source: HWInput,
+    running_threshold: f32,
+    dropout_grace: Duration,
+    total_secs: f64,
+    // Time accumulated since the condition dropped, committed if it comes back within grace
+    pending_secs: f64,
+    counting: bool,
+    dropped_since: Option<Instant>,
+    last_update: Option<Instant>,
+    persist_path: Option<PathBuf>,
+    last_saved_secs: f64,
+    constraints: ValueConstraints,
+    metadata: ValueMetadata,
+}
+
+impl EngineHoursSensor {
+    pub fn new(source: HWInput, running_threshold: f32) -> Self {
+        EngineHoursSensor {
+            value: SensorValue::empty(),
+            source,
+            running_threshold,
+            dropout_grace: Duration::from_secs(3),
+            total_secs: 0.0,
+            pending_secs: 0.0,
+            counting: false,
+            dropped_since: None,
+            last_update: None,
+            persist_path: None,
+            last_saved_secs: 0.0,
+            constraints: ValueConstraints::analog(0.0, 100000.0),
+            metadata: ValueMetadata::new("ч", "МОТОЧАСЫ", "engine_hours"),
+        }
+    }
+
+    pub fn with_dropout_grace(mut self, grace: Duration) -> Self {
+        self.dropout_grace = grace;
+        self
+    }
+
+    /// Loads the saved total from `path` if it exists; saved periodically and on drop
+    pub fn with_persistence(mut self, path: impl Into<PathBuf>) -> Result<Self, String> {
+        let path = path.into();
+        // Stored in seconds - totals are written with 3 decimals, too coarse for hours
+        self.total_secs = load_totals(&path)?.get("engine_secs").copied().unwrap_or(0.0);
+        self.last_saved_secs = self.total_secs;
+        self.persist_path = Some(path);
+        Ok(self)
+    }
+
+    pub fn source(&self) -> HWInput {
+        self.source
+    }
+
+    pub fn hours(&self) -> f64 {
+        self.total_secs / 3600.0
+    }
+
+    pub fn update(&mut self, source_value: f32, now: Instant) -> &SensorValue {
+        // The interval since the last update is attributed to the state seen at its start
+        if let Some(last_update) = self.last_update {
+            let dt = now.saturating_duration_since(last_update);
+            if self.counting && dt <= ENGINE_HOURS_MAX_STEP {
+                if self.dropped_since.is_some() {
+                    self.pending_secs += dt.as_secs_f64();
+                } else {
+                    self.total_secs += dt.as_secs_f64();
+                }
+            }
+        }
+        self.last_update = Some(now);
+
+        if source_value > self.running_threshold {
+            if self.dropped_since.take().is_some() {
+                self.total_secs += self.pending_secs;
+                self.pending_secs = 0.0;
+            }
+            self.counting = true;
+        } else if self.counting {
+            let dropped_since = *self.dropped_since.get_or_insert(now);
+            if now.saturating_duration_since(dropped_since) >= self.dropout_grace {
+                self.counting = false;
+                self.dropped_since = None;
+                self.pending_secs = 0.0;
+            }
+        }
+
+        if self.total_secs - self.last_saved_secs >= ENGINE_HOURS_SAVE_INTERVAL_SECS {
+            if let Err(e) = self.save() {
+                log::warn!("Failed to save engine hours: {}", e);
+            }
+        }
+
+        self.value = SensorValue::analog_with_constraints_and_metadata(
+            self.hours() as f32,
+            self.constraints.clone(),
+            self.metadata.clone(),
+        );
+        &self.value
+    }
+
+    pub fn save(&mut self) -> Result<(), String> {
+        let Some(path) = &self.persist_path else {
+            return Ok(());
+        };
+        save_totals(path, &[("engine_secs", self.total_secs)])?;
+        self.last_saved_secs = self.total_secs;
+        Ok(())
+    }
+}
+
+impl Drop for EngineHoursSensor {
+    fn drop(&mut self) {
+        if let Err(e) = self.save() {
+            log::warn!("Failed to save engine hours: {}", e);
+        }
+    }
+}
+
+impl Sensor for EngineHoursSensor {
+    fn id(&self) -> &String {
+        &self.metadata.sensor_id
+    }
+
+    fn name(&self) -> &String {
+        &self.metadata.label
+    }
+
+    fn value(&self) -> Result<&SensorValue, String> {
+        Ok(&self.value)
+    }
+
+    fn constraints(&self) -> &ValueConstraints {
+        &self.constraints
+    }
+
+    fn metadata(&self) -> &ValueMetadata {
+        &self.metadata
+    }
+
+    fn min_value(&self) -> f32 {
+        self.constraints.min_value
+    }
+
+    fn max_value(&self) -> f32 {
+        self.constraints.max_value
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::hardware::sensor_value::{ValueData, ValueConstraints};
+    use crate::hardware::sensor_value::{ValueData, ValueConstraints, ThresholdState};
 
     #[test]
     fn test_generic_digital_sensor_creation() {
@@ -471,6 +937,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_value_constraints_hysteresis_evaluation() {
+        let constraints = ValueConstraints::analog_with_thresholds(0.0, 20.0, Some(11.0), Some(12.0), Some(14.7), Some(15.0))
+            .with_hysteresis(0.2);
+
+        // Entering a zone uses the plain threshold
+        assert_eq!(constraints.evaluate(14.7, ThresholdState::Normal), ThresholdState::Warning);
+        assert_eq!(constraints.evaluate(14.6, ThresholdState::Normal), ThresholdState::Normal);
+        // Leaving it requires dropping below threshold - margin
+        assert_eq!(constraints.evaluate(14.6, ThresholdState::Warning), ThresholdState::Warning);
+        assert_eq!(constraints.evaluate(14.4, ThresholdState::Warning), ThresholdState::Normal);
+        // Critical falls back to warning rather than straight to normal
+        assert_eq!(constraints.evaluate(14.9, ThresholdState::Critical), ThresholdState::Critical);
+        assert_eq!(constraints.evaluate(14.7, ThresholdState::Critical), ThresholdState::Warning);
+        // Low-side thresholds clear upwards
+        assert_eq!(constraints.evaluate(12.1, ThresholdState::Warning), ThresholdState::Warning);
+        assert_eq!(constraints.evaluate(12.3, ThresholdState::Warning), ThresholdState::Normal);
+    }
+
+    #[test]
+    fn test_generic_analog_sensor_hysteresis_sweep() {
+        // 0.01 V per count, warning at 14.7 V with 0.2 V hysteresis
+        let constraints = ValueConstraints::analog_with_thresholds(0.0, 20.0, Some(11.0), Some(12.0), Some(14.7), Some(15.0))
+            .with_hysteresis(0.2);
+        let mut sensor = GenericAnalogSensor::new("Hw12v".to_string(), "БОРТ СЕТЬ".to_string(), "В".to_string(), constraints, 0.01);
+
+        // Sweep up through the threshold, then jitter inside the band around it
+        let mut transitions = 0;
+        let mut was_warning = false;
+        let sweep = [1450, 1460, 1470, 1468, 1471, 1465, 1469, 1472, 1466, 1460, 1455];
+        for input in sweep {
+            let warning = sensor.read(input).unwrap().is_warning();
+            if warning != was_warning {
+                transitions += 1;
+                was_warning = warning;
+            }
+        }
+        // Only the initial upward crossing - no chatter while inside the band
+        assert_eq!(transitions, 1);
+        assert!(was_warning);
+
+        // Dropping below threshold - margin clears the warning
+        assert!(!sensor.read(1449).unwrap().is_warning());
+        // And it stays clear until the plain threshold is reached again
+        assert!(!sensor.read(1469).unwrap().is_warning());
+        assert!(sensor.read(1470).unwrap().is_warning());
+    }
+
     #[test]
     fn test_engine_temperature_sensor_creation() {
         let sensor = EngineTemperatureSensor::new();
@@ -507,7 +1021,7 @@ mod tests {
     fn test_speed_sensor_creation() {
         let sensor = SpeedSensor::new();
         
-        assert_eq!(sensor.pulses_per_revolution, 6);
+        assert_eq!(sensor.pulses_per_revolution, 6.0);
         assert!((sensor.wheel_circumference_m - 2.304).abs() < 0.001);
         assert_eq!(sensor.constraints.min_value, 0.0);
         assert_eq!(sensor.constraints.max_value, 180.0);
@@ -540,6 +1054,252 @@ mod tests {
         assert!((speed - 60.0).abs() < 2.0); // Allow 2 km/h tolerance
     }
 
+    #[test]
+    fn test_speed_sensor_calibration() {
+        // 50 pulses/s = 180000 pulses/h = 60 km/h at 3000 pulses/km on the stock tire
+        let stock = SpeedSensor::with_calibration(3000.0, NOMINAL_TIRE_CIRCUMFERENCE_M);
+        let speed = stock.calculate_speed_kmh(50.0);
+        assert!((speed - 60.0).abs() < 0.01, "Expected 60 km/h, got {}", speed);
+
+        // Same wheel revolutions on a smaller 2.2 m tire cover less ground
+        let small = SpeedSensor::with_calibration(3000.0, 2.2);
+        let speed = small.calculate_speed_kmh(50.0);
+        assert!((speed - 60.0 * 2.2 / NOMINAL_TIRE_CIRCUMFERENCE_M).abs() < 0.01, "Expected ~57.3 km/h, got {}", speed);
+    }
+
+    // Feeds a square wave with `edges_per_second` level changes to `read_at`, sampled every
+    // 500 µs of simulated time over `duration` (the same waveform TestPulseDataProvider makes)
+    fn feed_pulse_train(edges_per_second: f32, duration: Duration, mut read_at: impl FnMut(Level, Instant)) {
+        let start = Instant::now();
+        let step = Duration::from_micros(500);
+        let mut elapsed = Duration::ZERO;
+        while elapsed <= duration {
+            let phase = edges_per_second * elapsed.as_secs_f32();
+            read_at(if (phase as u32) % 2 == 1 { Level::High } else { Level::Low }, start + elapsed);
+            elapsed += step;
+        }
+    }
+
+    #[test]
+    fn test_speed_sensor_calibrated_pulse_train() {
+        let mut sensor = SpeedSensor::with_calibration(3000.0, NOMINAL_TIRE_CIRCUMFERENCE_M);
+
+        // PPS is recomputed once per second - run the train just past that
+        feed_pulse_train(50.0, Duration::from_millis(1100), |level, now| {
+            sensor.process_pulse_at(level, now);
+        });
+
+        let speed = sensor.value().unwrap().as_f32();
+        assert!((speed - 60.0).abs() <= 1.2, "Expected ~60 km/h, got {}", speed);
+    }
+
+    #[test]
+    fn test_generic_analog_sensor_open_circuit() {
+        let mut sensor = GenericAnalogSensor::new(
+            "oil".to_string(), "Oil".to_string(), "kgf/cm²".to_string(),
+            ValueConstraints::analog(0.0, 8.0), 0.002,
+        ).with_open_circuit_detection(Some(20), Some(4080));
+
+        assert_eq!(sensor.read(1000).unwrap().fault, None);
+        assert_eq!(sensor.read(0).unwrap().fault, Some(SensorFault::OpenCircuit));
+        assert_eq!(sensor.read(20).unwrap().fault, Some(SensorFault::OpenCircuit));
+        assert_eq!(sensor.read(4095).unwrap().fault, Some(SensorFault::OpenCircuit));
+        // Clears as soon as the reading is back in band
+        assert!(!sensor.read(1000).unwrap().is_faulted());
+    }
+
+    #[test]
+    fn test_generic_analog_sensor_stuck() {
+        let mut sensor = GenericAnalogSensor::new(
+            "temp".to_string(), "Temp".to_string(), "°C".to_string(),
+            ValueConstraints::analog(0.0, 130.0), 0.1,
+        ).with_stuck_detection(0.5, Duration::from_secs(5));
+
+        let start = Instant::now();
+        // Small jitter within epsilon doesn't reset the timer
+        assert_eq!(sensor.read_at(500, start).unwrap().fault, None);
+        assert_eq!(sensor.read_at(503, start + Duration::from_secs(3)).unwrap().fault, None);
+        assert_eq!(sensor.read_at(498, start + Duration::from_secs(5)).unwrap().fault,
+                   Some(SensorFault::Stuck));
+
+        // Movement clears the fault and restarts the timer
+        assert_eq!(sensor.read_at(520, start + Duration::from_secs(6)).unwrap().fault, None);
+        assert_eq!(sensor.read_at(520, start + Duration::from_secs(10)).unwrap().fault, None);
+        assert_eq!(sensor.read_at(520, start + Duration::from_secs(11)).unwrap().fault,
+                   Some(SensorFault::Stuck));
+    }
+
+    #[test]
+    fn test_generic_analog_sensor_no_fault_detection_by_default() {
+        let mut sensor = GenericAnalogSensor::new(
+            "fuel".to_string(), "Fuel".to_string(), "%".to_string(),
+            ValueConstraints::analog(0.0, 100.0), 0.1,
+        );
+        let start = Instant::now();
+        assert_eq!(sensor.read_at(0, start).unwrap().fault, None);
+        assert_eq!(sensor.read_at(0, start + Duration::from_secs(3600)).unwrap().fault, None);
+    }
+
+    #[test]
+    fn test_derived_sensor_evaluate() {
+        let mut sensor = DerivedSensor::new(
+            "diff".to_string(), "Diff".to_string(), "".to_string(),
+            ValueConstraints::analog(-10.0, 10.0),
+            vec![HWInput::HwFuelLvl, HWInput::HwOilPress],
+            Box::new(|values| values[0] - values[1]),
+        );
+        assert_eq!(sensor.sources(), &[HWInput::HwFuelLvl, HWInput::HwOilPress]);
+        assert_eq!(sensor.evaluate(&[5.0, 2.0]).as_f32(), 3.0);
+        // Clamped to constraints
+        assert_eq!(sensor.evaluate(&[50.0, 2.0]).as_f32(), 10.0);
+    }
+
+    #[test]
+    fn test_derived_sensor_trip_average_speed() {
+        let mut sensor = DerivedSensor::trip_average_speed();
+        assert_eq!(sensor.evaluate(&[60.0]).as_f32(), 60.0);
+        assert_eq!(sensor.evaluate(&[90.0]).as_f32(), 75.0);
+        assert_eq!(sensor.evaluate(&[0.0]).as_f32(), 50.0);
+        assert_eq!(Sensor::value(&sensor).unwrap().as_f32(), 50.0);
+    }
+
+    #[test]
+    fn test_engine_hours_accumulation() {
+        let mut sensor = EngineHoursSensor::new(HWInput::HwTacho, 0.0)
+            .with_dropout_grace(Duration::from_secs(3));
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        // Engine off, then running for 4 s
+        sensor.update(0.0, at(0));
+        sensor.update(800.0, at(1));
+        for t in 2..=5 {
+            sensor.update(800.0, at(t));
+        }
+        assert!((sensor.hours() * 3600.0 - 4.0).abs() < 1e-6);
+
+        // 2 s stall within grace - counted as running
+        sensor.update(0.0, at(6));
+        sensor.update(0.0, at(7));
+        sensor.update(900.0, at(8));
+        assert!((sensor.hours() * 3600.0 - 7.0).abs() < 1e-6);
+
+        // Stopped for longer than grace - grace time discarded, accumulation stops
+        sensor.update(0.0, at(9));
+        for t in 10..=20 {
+            sensor.update(0.0, at(t));
+        }
+        assert!((sensor.hours() * 3600.0 - 8.0).abs() < 1e-6);
+
+        // Restart continues from the previous total
+        sensor.update(800.0, at(21));
+        sensor.update(800.0, at(23));
+        let value = sensor.update(800.0, at(24)).as_f32();
+        assert!((sensor.hours() * 3600.0 - 11.0).abs() < 1e-6);
+        assert!((value - 11.0 / 3600.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_engine_hours_persistence() {
+        let dir = std::env::temp_dir().join(format!("niva_engine_hours_{}", std::process::id()));
+        let path = dir.join("engine_hours.txt");
+        let _ = std::fs::remove_dir_all(&dir);
+        {
+            let mut sensor = EngineHoursSensor::new(HWInput::HwTacho, 0.0).with_persistence(&path).unwrap();
+            let start = Instant::now();
+            sensor.update(800.0, start);
+            sensor.update(800.0, start + Duration::from_secs(2));
+            sensor.update(800.0, start + Duration::from_secs(4));
+        } // Saved on drop
+
+        let sensor = EngineHoursSensor::new(HWInput::HwTacho, 0.0).with_persistence(&path).unwrap();
+        assert!((sensor.hours() * 3600.0 - 4.0).abs() < 0.01);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_generic_analog_sensor_ntc_thermistor() {
+        let thermistor = SteinhartHart::from_points(
+            [(0.0, 33621.0), (25.0, 10000.0), (100.0, 698.0)], 10000.0, 4095).unwrap();
+        let mut sensor = GenericAnalogSensor::ntc_thermistor(
+            "coolant".to_string(), "Coolant".to_string(),
+            ValueConstraints::analog(-40.0, 130.0), thermistor,
+        );
+        assert_eq!(sensor.metadata().unit, "°C");
+        assert!((sensor.read(2048).unwrap().as_f32() - 25.0).abs() < 1.0);
+        assert!((sensor.read(1081).unwrap().as_f32() - 50.0).abs() < 1.0);
+        // Open sender reads as the coldest value in range, shorted as the hottest
+        assert_eq!(sensor.read(4095).unwrap().as_f32(), -40.0);
+        assert_eq!(sensor.read(0).unwrap().as_f32(), 130.0);
+    }
+
+    #[test]
+    fn test_generic_analog_sensor_deadband() {
+        let mut sensor = GenericAnalogSensor::new(
+            "oil".to_string(), "Oil".to_string(), "kgf/cm²".to_string(),
+            ValueConstraints::analog_with_thresholds(0.0, 8.0, Some(0.05), None, None, None)
+                .with_deadband(0.15),
+            0.01,
+        );
+        // 0.10 and 0.14 are below the deadband
+        assert_eq!(sensor.read(10).unwrap().as_f32(), 0.0);
+        assert_eq!(sensor.read(14).unwrap().as_f32(), 0.0);
+        // Deadband applies before thresholds: snapped 0.0 is below critical_low
+        assert!(sensor.read(10).unwrap().is_critical());
+        // Above the deadband values pass through unchanged
+        assert!((sensor.read(20).unwrap().as_f32() - 0.2).abs() < 1e-6);
+        assert!((sensor.read(250).unwrap().as_f32() - 2.5).abs() < 1e-6);
+        assert!(!sensor.read(250).unwrap().is_critical());
+    }
+
+    #[test]
+    fn test_tachometer_sensor_rpm_calculation() {
+        let sensor = TachometerSensor::with_pulses_per_revolution(2.0);
+        assert_eq!(sensor.calculate_rpm(0.0), 0.0);
+        assert!((sensor.calculate_rpm(50.0) - 1500.0).abs() < 0.01);
+        assert!((sensor.calculate_rpm(100.0) - 3000.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_tachometer_sensor_frame_counts() {
+        // An average of 1 pulse per 20 ms frame = 50 pulses/s = 1500 RPM at 2 pulses/rev
+        let mut sensor = TachometerSensor::with_pulses_per_revolution(2.0);
+        assert_eq!(AnalogSensor::read(&mut sensor, 0).unwrap().as_f32(), 0.0);
+        assert!((AnalogSensor::read(&mut sensor, 2).unwrap().as_f32() - 1500.0).abs() < 0.01);
+
+        // Idle at 750 RPM: frames alternate between 0 and 1 pulse, the window averages them
+        let mut sensor = TachometerSensor::new();
+        for frame in 0..TACHO_COUNT_WINDOW * 2 {
+            AnalogSensor::read(&mut sensor, (frame % 2) as u16).unwrap();
+        }
+        let rpm = sensor.value().unwrap().as_f32();
+        assert!((rpm - 750.0).abs() <= 30.0, "Expected ~750 RPM, got {}", rpm);
+
+        // Old frames leave the window, and the reading is clamped to the gauge range
+        for _ in 0..TACHO_COUNT_WINDOW {
+            AnalogSensor::read(&mut sensor, 10).unwrap();
+        }
+        assert_eq!(sensor.value().unwrap().as_f32(), 8000.0);
+
+        let mut sensor = TachometerSensor::new().with_count_interval(Duration::from_millis(100));
+        assert!((AnalogSensor::read(&mut sensor, 5).unwrap().as_f32() - 1500.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_tachometer_sensor_pulse_train() {
+        // 50 Hz at 2 pulses/rev = 25 rev/s = 1500 RPM
+        let mut sensor = TachometerSensor::with_pulses_per_revolution(2.0);
+
+        feed_pulse_train(50.0, Duration::from_millis(1100), |level, now| {
+            sensor.read_at(level, now).unwrap();
+        });
+
+        let rpm = sensor.value().unwrap().as_f32();
+        // Simulated time leaves only the one-edge quantisation of the counting window
+        assert!((rpm - 1500.0).abs() <= 30.0, "Expected ~1500 RPM, got {}", rpm);
+    }
+
     #[test]
     fn test_speed_sensor_pulse_processing() {
         let mut sensor = SpeedSensor::new();
@@ -0,0 +1,129 @@
+#![allow(dead_code)]
+//! NTC thermistor conversion for temperature senders wired as the low side of a voltage
+//! divider:
+//!
+//!   Vref ── R_series ──┬── NTC ── GND
+//!                      └── ADC
+//!
+//! The ADC sees V = Vref * R_ntc / (R_series + R_ntc). With the ADC referenced to the same
+//! Vref, ratio = raw / full_scale = R_ntc / (R_series + R_ntc), so
+//!
+//!   R_ntc = R_series * ratio / (1 - ratio)
+//!
+//! and the Steinhart-Hart equation gives the absolute temperature:
+//!
+//!   1/T = A + B*ln(R) + C*ln(R)^3
+
+const KELVIN_OFFSET: f64 = 273.15;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SteinhartHart {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    /// Pull-up resistor between Vref and the ADC input, ohms
+    pub series_resistor_ohms: f64,
+    /// Raw reading corresponding to Vref (4095 for the STM32's 12-bit ADC)
+    pub adc_full_scale: u16,
+}
+
+impl SteinhartHart {
+    pub fn new(a: f64, b: f64, c: f64, series_resistor_ohms: f64, adc_full_scale: u16) -> Self {
+        SteinhartHart { a, b, c, series_resistor_ohms, adc_full_scale }
+    }
+
+    /// Fits A, B, C exactly through three (°C, ohms) points from a sender's datasheet.
+    /// Points should span the range of interest (e.g. cold, mid, hot).
+    pub fn from_points(points: [(f64, f64); 3], series_resistor_ohms: f64, adc_full_scale: u16) -> Result<Self, String> {
+        let [(t1, r1), (t2, r2), (t3, r3)] = points;
+        if r1 <= 0.0 || r2 <= 0.0 || r3 <= 0.0 {
+            return Err("Thermistor resistances must be positive".to_string());
+        }
+        let (l1, l2, l3) = (r1.ln(), r2.ln(), r3.ln());
+        let (y1, y2, y3) = (1.0 / (t1 + KELVIN_OFFSET), 1.0 / (t2 + KELVIN_OFFSET), 1.0 / (t3 + KELVIN_OFFSET));
+
+        // Standard closed-form solution of the 3x3 system
+        let g2 = (y2 - y1) / (l2 - l1);
+        let g3 = (y3 - y1) / (l3 - l1);
+        let c = (g3 - g2) / (l3 - l2) / (l1 + l2 + l3);
+        let b = g2 - c * (l1 * l1 + l1 * l2 + l2 * l2);
+        let a = y1 - (b + l1 * l1 * c) * l1;
+        if !(a.is_finite() && b.is_finite() && c.is_finite()) {
+            return Err("Thermistor points must have distinct temperatures and resistances".to_string());
+        }
+        Ok(Self::new(a, b, c, series_resistor_ohms, adc_full_scale))
+    }
+
+    /// Thermistor resistance for a raw ADC reading. Infinite at full scale (open circuit),
+    /// zero at 0 (shorted to ground).
+    pub fn resistance_ohms(&self, raw: u16) -> f64 {
+        let ratio = raw as f64 / self.adc_full_scale as f64;
+        if ratio >= 1.0 {
+            return f64::INFINITY;
+        }
+        self.series_resistor_ohms * ratio / (1.0 - ratio)
+    }
+
+    pub fn temperature_for_resistance(&self, ohms: f64) -> f64 {
+        if ohms.is_infinite() {
+            return f64::NEG_INFINITY; // NTC resistance rises as it cools
+        }
+        if ohms <= 0.0 {
+            return f64::INFINITY;
+        }
+        let ln_r = ohms.ln();
+        1.0 / (self.a + self.b * ln_r + self.c * ln_r.powi(3)) - KELVIN_OFFSET
+    }
+
+    /// Temperature in °C for a raw ADC reading. Rail readings map to ±infinity, which
+    /// the sensor's range clamp turns into min/max.
+    pub fn temperature_c(&self, raw: u16) -> f32 {
+        self.temperature_for_resistance(self.resistance_ohms(raw)) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 10k B3950 NTC datasheet points: 0°C, 25°C, 100°C
+    fn ntc_10k() -> SteinhartHart {
+        SteinhartHart::from_points([(0.0, 33621.0), (25.0, 10000.0), (100.0, 698.0)], 10000.0, 4095).unwrap()
+    }
+
+    #[test]
+    fn test_steinhart_hart_fit_points() {
+        let sh = ntc_10k();
+        assert!((sh.temperature_for_resistance(33621.0) - 0.0).abs() < 0.01);
+        assert!((sh.temperature_for_resistance(10000.0) - 25.0).abs() < 0.01);
+        assert!((sh.temperature_for_resistance(698.0) - 100.0).abs() < 0.01);
+        // Datasheet point not used in the fit
+        assert!((sh.temperature_for_resistance(3588.0) - 50.0).abs() < 1.0);
+        assert!((sh.temperature_for_resistance(1087.0) - 85.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_steinhart_hart_divider() {
+        let sh = ntc_10k();
+        // Equal resistors - mid scale
+        assert!((sh.resistance_ohms(2048) - 10000.0).abs() < 10.0);
+        assert!((sh.temperature_c(2048) - 25.0).abs() < 1.0);
+        // 3588 ohm NTC against 10k: ratio 0.2641 -> raw 1081
+        assert!((sh.temperature_c(1081) - 50.0).abs() < 1.0);
+        // Hotter means lower resistance, lower reading
+        assert!(sh.temperature_c(500) > sh.temperature_c(1000));
+    }
+
+    #[test]
+    fn test_steinhart_hart_rails() {
+        let sh = ntc_10k();
+        assert_eq!(sh.temperature_c(4095), f32::NEG_INFINITY);
+        assert_eq!(sh.temperature_c(0), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_steinhart_hart_invalid_points() {
+        assert!(SteinhartHart::from_points([(0.0, 1000.0), (0.0, 1000.0), (10.0, 500.0)], 10000.0, 4095).is_err());
+        assert!(SteinhartHart::from_points([(0.0, -1.0), (25.0, 1000.0), (50.0, 500.0)], 10000.0, 4095).is_err());
+    }
+}
@@ -31,6 +31,7 @@ pub fn build_speed_digital(
 
     let speed_display = DigitalSegmentedIndicator::integer(digit_count)
         .with_inactive_segments(show_inactive_segments)
+        .with_shadow(true)
         .with_decorators(vec![
             // Unit label
             Box::new(LabelDecorator::new(
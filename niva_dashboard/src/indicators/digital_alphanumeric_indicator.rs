@@ -0,0 +1,202 @@
+#![allow(dead_code)]
+use crate::indicators::indicator::{Indicator, IndicatorBounds, IndicatorBase};
+use crate::graphics::context::{opaque, GraphicsContext};
+use crate::graphics::ui_style::*;
+use crate::hardware::sensor_value::{SensorValue, ValueData};
+
+// DSEG14 glyphs: '~' lights all 14 segments, '!' is a blank cell of full digit width
+const ALL_SEGMENTS_CHAR: char = '~';
+const BLANK_CELL_CHAR: char = '!';
+
+/// Digital indicator for short alphanumeric strings (gear position "P R N D", "OFF", ...)
+/// using 14-segment fonts
+pub struct DigitalAlphaNumericIndicator {
+    base: IndicatorBase,
+    /// Number of character cells on the display
+    cells: usize,
+    /// Text shown for each integer value (index = value); digital values use 0 = off, 1 = on
+    labels: Vec<String>,
+    /// Whether to show inactive segments (for realistic 14-segment display look)
+    show_inactive_segments: bool,
+}
+
+impl DigitalAlphaNumericIndicator {
+    /// Create a new alphanumeric indicator with the given number of character cells
+    pub fn new(cells: usize) -> Self {
+        Self {
+            base: IndicatorBase::new(),
+            cells,
+            labels: Vec::new(),
+            show_inactive_segments: true,
+        }
+    }
+
+    /// Map values to text: value N shows labels[N]
+    pub fn with_labels(mut self, labels: Vec<String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Enable/disable inactive segments display
+    pub fn with_inactive_segments(mut self, show: bool) -> Self {
+        self.show_inactive_segments = show;
+        self
+    }
+
+    /// Text for a value: the matching label, or the plain number without labels
+    fn value_text(&self, value: &ValueData) -> Option<String> {
+        let index = match value {
+            ValueData::Digital(on) => *on as i32,
+            ValueData::Integer(i) => *i,
+            ValueData::Analog(v) | ValueData::Percentage(v) => {
+                if self.labels.is_empty() {
+                    return Some(format!("{}", v.round() as i32));
+                }
+                v.round() as i32
+            }
+            ValueData::Empty => return None,
+        };
+        if self.labels.is_empty() {
+            return Some(index.to_string());
+        }
+        usize::try_from(index).ok().and_then(|i| self.labels.get(i)).cloned()
+    }
+
+    /// Lay text out over the character cells: uppercase (DSEG14 has no lowercase glyphs),
+    /// spaces as blank cells, right-aligned and truncated to the cell count
+    fn layout_cells(&self, text: &str) -> String {
+        let chars: Vec<char> = text.chars()
+            .map(|c| if c == ' ' { BLANK_CELL_CHAR } else { c.to_ascii_uppercase() })
+            .collect();
+        let visible = &chars[chars.len().saturating_sub(self.cells)..];
+        std::iter::repeat_n(BLANK_CELL_CHAR, self.cells - visible.len())
+            .chain(visible.iter().copied())
+            .collect()
+    }
+
+    /// Pattern with every segment of every cell lit, used as the ghosted background
+    fn generate_inactive_pattern(&self) -> String {
+        std::iter::repeat_n(ALL_SEGMENTS_CHAR, self.cells).collect()
+    }
+}
+
+impl Default for DigitalAlphaNumericIndicator {
+    fn default() -> Self {
+        Self::new(3) // Enough for "OFF"
+    }
+}
+
+impl Indicator for DigitalAlphaNumericIndicator {
+    fn with_decorators(mut self, decorators: Vec<Box<dyn crate::indicators::decorator::Decorator>>) -> Self {
+        self.base.decorators = decorators;
+        self
+    }
+
+    fn render(
+        &self,
+        value: &SensorValue,
+        bounds: IndicatorBounds,
+        style: &UIStyle,
+        context: &mut GraphicsContext,
+    ) -> Result<(), String> {
+        // Render decorators first, then the display itself over the decorators
+        self.base.render_decorators(bounds, style, context)?;
+
+        let Some(text) = self.value_text(&value.value) else {
+            return Ok(());
+        };
+
+        let font_path = style.get_string(DIGITAL_DISPLAY_14SEG_FONT, DIGITAL_DISPLAY_14SEG_FONT_PATH);
+        let font_size = style.get_integer(DIGITAL_DISPLAY_FONT_SIZE, 32);
+        let scale = style.get_float(DIGITAL_DISPLAY_SCALE, 2.0);
+
+        // Render border and background if enabled
+        let background_enabled = style.get_bool(DIGITAL_DISPLAY_BACKGROUND_ENABLED, false);
+        let border_enabled = style.get_bool(DIGITAL_DISPLAY_BORDER_ENABLED, true);
+
+        let mut background_color = style.get_color(DIGITAL_DISPLAY_BACKGROUND_COLOR, (1.0, 0.65, 0.0)); // Amber background
+
+        if background_enabled {
+            context.render_rectangle(
+                bounds.x, bounds.y, bounds.width, bounds.height,
+                opaque(background_color), true,
+                1.0,    // Width doesn't matter for filled
+                style.get_float(DIGITAL_DISPLAY_BORDER_RADIUS, 8.0),
+            )?;
+        } else if border_enabled {
+            context.render_rectangle(
+                bounds.x, bounds.y, bounds.width, bounds.height,
+                opaque(style.get_color(DIGITAL_DISPLAY_BORDER_COLOR, (1.0, 1.0, 1.0))), false,
+                style.get_float(DIGITAL_DISPLAY_BORDER_WIDTH, 2.0),
+                style.get_float(DIGITAL_DISPLAY_BORDER_RADIUS, 8.0),
+            )?;
+            background_color = (0.0, 0.0, 0.0); // Use black background if only border
+        }
+
+        // Every cell has the same width in DSEG fonts, so the ghost pattern and the text
+        // line up when drawn from the same origin
+        let inactive_pattern = self.generate_inactive_pattern();
+        let pattern_width = context.calculate_text_width_with_font(&inactive_pattern, scale, &font_path, font_size)?;
+        let pattern_height = context.calculate_text_height_with_font(&inactive_pattern, scale, &font_path, font_size)?;
+        let x = bounds.x + (bounds.width - pattern_width) / 2.0;
+        let y = bounds.y + (bounds.height - pattern_height) / 2.0;
+
+        if self.show_inactive_segments {
+            let inactive_color = blend_colors(
+                background_color,
+                style.get_color(DIGITAL_DISPLAY_INACTIVE_COLOR, (0.84, 0.41, 0.0)),
+                style.get_float(DIGITAL_DISPLAY_INACTIVE_COLOR_BLENDING, 1.0).clamp(0.0, 1.0)
+            );
+            context.render_text_with_font(&inactive_pattern, x, y, scale, inactive_color, &font_path, font_size)?;
+        }
+
+        let active_color = style.get_color(DIGITAL_DISPLAY_ACTIVE_COLOR, (0.0, 0.0, 0.0)); // Black by default
+        context.render_text_with_font(&self.layout_cells(&text), x, y, scale, active_color, &font_path, font_size)?;
+
+        Ok(())
+    }
+
+    fn indicator_type(&self) -> &'static str {
+        "DigitalAlphaNumericIndicator"
+    }
+
+    fn supports_value_type(&self, value: &ValueData) -> bool {
+        !matches!(value, ValueData::Empty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_cells_for_string() {
+        let display = DigitalAlphaNumericIndicator::new(4);
+        assert_eq!(display.generate_inactive_pattern().chars().count(), 4);
+
+        // Short strings are right-aligned with blank cells
+        assert_eq!(display.layout_cells("off"), "!OFF");
+        assert_eq!(display.layout_cells("P R"), "!P!R");
+        // Long strings keep their rightmost characters
+        assert_eq!(display.layout_cells("PRNDL"), "RNDL");
+        assert_eq!(display.layout_cells(""), "!!!!");
+        for text in ["N", "OFF", "P R N D"] {
+            assert_eq!(display.layout_cells(text).chars().count(), 4);
+        }
+    }
+
+    #[test]
+    fn test_value_text_uses_labels() {
+        let gear = DigitalAlphaNumericIndicator::new(1)
+            .with_labels(vec!["P".into(), "R".into(), "N".into(), "D".into()]);
+        assert_eq!(gear.value_text(&ValueData::Integer(2)), Some("N".to_string()));
+        assert_eq!(gear.value_text(&ValueData::Analog(3.2)), Some("D".to_string()));
+        assert_eq!(gear.value_text(&ValueData::Integer(7)), None);
+        assert_eq!(gear.value_text(&ValueData::Integer(-1)), None);
+
+        let switch = DigitalAlphaNumericIndicator::new(3).with_labels(vec!["OFF".into(), "ON".into()]);
+        assert_eq!(switch.value_text(&ValueData::Digital(false)), Some("OFF".to_string()));
+
+        assert_eq!(DigitalAlphaNumericIndicator::new(3).value_text(&ValueData::Integer(42)), Some("42".to_string()));
+    }
+}
@@ -1,8 +1,70 @@
 use crate::indicators::indicator::{Indicator, IndicatorBounds, IndicatorBase};
-use crate::graphics::context::GraphicsContext;
+use crate::graphics::context::{opaque, GraphicsContext, TextStyle};
 use crate::graphics::ui_style::*;
 use crate::hardware::sensor_value::{SensorValue, ValueData};
 
+// DSEG renders '!' as an unlit digit as wide as the others; a space is narrower
+const BLANK_CELL: char = '!';
+const OVERFLOW_CELL: char = '-';
+
+/// How a number is laid out on the display cells
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumberFormat {
+    /// Total cells, the decimal point included
+    pub width: usize,
+    /// Digits after the decimal point, 0 for integers
+    pub decimals: usize,
+    /// Integer part zero padded to at least this many digits
+    pub leading_zeros: usize,
+}
+
+impl NumberFormat {
+    /// Value rounded to the decimals and right-aligned in the cells, unlit cells on the left.
+    /// A value that doesn't fit, or isn't a number, lights a dash in every digit cell.
+    pub fn cells(&self, value: f32) -> String {
+        if !value.is_finite() {
+            return self.overflow();
+        }
+        let digits = format!("{:.decimals$}", value.abs(), decimals = self.decimals);
+        let (integer, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+        // No "-0.0" when the value rounds to zero
+        let negative = value < 0.0 && digits.chars().any(|c| c.is_ascii_digit() && c != '0');
+        let mut text = format!("{}{:0>zeros$}", if negative { "-" } else { "" }, integer, zeros = self.leading_zeros);
+        if self.decimals > 0 {
+            text.push('.');
+            text.push_str(fraction);
+        }
+
+        let used = text.chars().count();
+        if used > self.width {
+            return self.overflow();
+        }
+        let mut cells = BLANK_CELL.to_string().repeat(self.width - used);
+        cells.push_str(&text);
+        cells
+    }
+
+    /// Every segment of every cell, drawn under the value as unlit segments
+    pub fn inactive_pattern(&self) -> String {
+        self.pattern('8')
+    }
+
+    fn overflow(&self) -> String {
+        self.pattern(OVERFLOW_CELL)
+    }
+
+    fn pattern(&self, digit: char) -> String {
+        let digit = digit.to_string();
+        if self.decimals == 0 {
+            digit.repeat(self.width)
+        } else {
+            // The decimal point takes a cell
+            let integer_digits = self.width.saturating_sub(self.decimals + 1);
+            format!("{}.{}", digit.repeat(integer_digits), digit.repeat(self.decimals))
+        }
+    }
+}
+
 /// Simple digital numeric indicator using 7-segment fonts
 pub struct DigitalSegmentedIndicator {
     base: IndicatorBase,
@@ -10,8 +72,12 @@ pub struct DigitalSegmentedIndicator {
     digits: usize,
     /// Number of decimal places (0 for integers)
     decimals: usize,
+    /// Minimum integer digits, zero padded (0 for no padding)
+    leading_zeros: usize,
     /// Whether to show inactive segments (for realistic 7-segment display look)
     show_inactive_segments: bool,
+    /// Whether to draw a drop shadow under the active digits
+    show_shadow: bool,
 }
 
 impl DigitalSegmentedIndicator {
@@ -23,7 +89,9 @@ impl DigitalSegmentedIndicator {
             base: IndicatorBase::new(),
             digits, 
             decimals,
+            leading_zeros: 0,
             show_inactive_segments: true,
+            show_shadow: false,
         }
     }
 
@@ -43,47 +111,40 @@ impl DigitalSegmentedIndicator {
         self
     }
 
-    /// Format numeric value
-    fn format_value(&self, value: f32) -> String {
-        if self.decimals == 0 {
-            // For integers, don't pad with spaces as DSEG fonts may not handle spaces well
-            format!("{}", value as i32)
-        } else {
-            format!("{:.decimals$}", value, decimals = self.decimals)
-        }
+    /// Zero pad the integer part to at least `digits` digits (e.g. "05" for 2)
+    pub fn with_leading_zeros(mut self, digits: usize) -> Self {
+        self.leading_zeros = digits;
+        self
     }
-    
-    /// Generate inactive segments display pattern
-    /// For 7-segment displays, show all segments active (8 pattern) to simulate background
-    fn generate_inactive_pattern(&self) -> String {
-        let digit_pattern = if self.decimals == 0 {
-            // For integers, show all 8s
-            "8".repeat(self.digits)
-        } else {
-            // For floats, show 8s with decimal point
-            let integer_digits = self.digits - self.decimals - 1; // -1 for decimal point
-            format!("{}.{}", "8".repeat(integer_digits), "8".repeat(self.decimals))
-        };
-        
-        digit_pattern
+
+    /// Enable/disable a drop shadow under the active digits
+    pub fn with_shadow(mut self, show: bool) -> Self {
+        self.show_shadow = show;
+        self
     }
-    
-    /// Render inactive segments as background
+
+    /// The indicator's format, with any `DIGITAL_DISPLAY_*` format keys set in the style taking over
+    pub fn number_format(&self, style: &UIStyle) -> NumberFormat {
+        NumberFormat {
+            width: style.get_integer(DIGITAL_DISPLAY_WIDTH, self.digits as u32) as usize,
+            decimals: style.get_integer(DIGITAL_DISPLAY_DECIMALS, self.decimals as u32) as usize,
+            leading_zeros: style.get_integer(DIGITAL_DISPLAY_LEADING_ZEROS, self.leading_zeros as u32) as usize,
+        }
+    }
+
+    /// Render inactive segments as background; measured even when hidden, since the
+    /// value is aligned to them
     fn render_inactive_segments(
         &self,
+        format: &NumberFormat,
         bounds: IndicatorBounds,
-        _style: &UIStyle,
         context: &mut GraphicsContext,
         font_path: &str,
         scale: f32,
         font_size: u32,
         inactive_color: (f32, f32, f32),
     ) -> Result<(f32, f32), String> {
-        if !self.show_inactive_segments {
-            return Ok((0.0, bounds.x));
-        }
-        
-        let inactive_pattern = self.generate_inactive_pattern();
+        let inactive_pattern = format.inactive_pattern();
         
         // Calculate text position (centered within bounds)
         let text_width = context.calculate_text_width_with_font(
@@ -98,9 +159,11 @@ impl DigitalSegmentedIndicator {
         let y = bounds.y + (bounds.height - text_height) / 2.0;
         
         // Render inactive segments centered
-        context.render_text_with_font(
-            &inactive_pattern, x, y, scale, inactive_color, font_path, font_size
-        )?;
+        if self.show_inactive_segments {
+            context.render_text_with_font(
+                &inactive_pattern, x, y, scale, inactive_color, font_path, font_size
+            )?;
+        }
         
         Ok((text_width, x))
     }
@@ -151,14 +214,14 @@ impl Indicator for DigitalSegmentedIndicator {
         if background_enabled {
             context.render_rectangle(
                 bounds.x, bounds.y, bounds.width, bounds.height,
-                background_color, true,
+                opaque(background_color), true,
                 1.0,    // Width doesn't matter for filled
                 style.get_float(DIGITAL_DISPLAY_BORDER_RADIUS, 8.0),
             )?;
         } else if border_enabled {
             context.render_rectangle(
                 bounds.x, bounds.y, bounds.width, bounds.height,
-                style.get_color(DIGITAL_DISPLAY_BORDER_COLOR, (1.0, 1.0, 1.0)), false,
+                opaque(style.get_color(DIGITAL_DISPLAY_BORDER_COLOR, (1.0, 1.0, 1.0))), false,
                 style.get_float(DIGITAL_DISPLAY_BORDER_WIDTH, 2.0),
                 style.get_float(DIGITAL_DISPLAY_BORDER_RADIUS, 8.0),
             )?;
@@ -175,10 +238,11 @@ impl Indicator for DigitalSegmentedIndicator {
         );
         
         // Render inactive segments as background
-        let (inactive_width, inactive_x) = self.render_inactive_segments(bounds, style, context, &font_path, scale, font_size, inactive_color)?;
+        let format = self.number_format(style);
+        let (inactive_width, inactive_x) = self.render_inactive_segments(&format, bounds, context, &font_path, scale, font_size, inactive_color)?;
 
         // Format and render the active value on top
-        let formatted_value = self.format_value(numeric_value);
+        let formatted_value = format.cells(numeric_value);
 
         // Calculate text position (right-aligned within the inactive pattern)
         let text_width = context.calculate_text_width_with_font(
@@ -194,9 +258,19 @@ impl Indicator for DigitalSegmentedIndicator {
         let y = bounds.y + (bounds.height - text_height) / 2.0;
 
         // Render the active digits
-        context.render_text_with_font(
-            &formatted_value, x, y, scale, active_color, &font_path, font_size
-        )?;
+        if self.show_shadow {
+            let shadow_offset = style.get_float(DIGITAL_DISPLAY_SHADOW_OFFSET, 3.0);
+            context.render_text_with_shadow(
+                &formatted_value, x, y,
+                &TextStyle::new(&font_path, font_size, active_color).with_scale(scale),
+                style.get_color(DIGITAL_DISPLAY_SHADOW_COLOR, (0.0, 0.0, 0.0)),
+                (shadow_offset, shadow_offset),
+            )?;
+        } else {
+            context.render_text_with_font(
+                &formatted_value, x, y, scale, active_color, &font_path, font_size
+            )?;
+        }
 
         Ok(())
     }
@@ -209,3 +283,42 @@ impl Indicator for DigitalSegmentedIndicator {
         matches!(value, ValueData::Analog(_) | ValueData::Integer(_) | ValueData::Percentage(_))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_format_cells() {
+        let voltage = NumberFormat { width: 4, decimals: 1, leading_zeros: 0 };
+        assert_eq!(voltage.cells(12.6), "12.6");
+        assert_eq!(voltage.inactive_pattern(), "88.8");
+        // Short values leave unlit cells on the left, padded with zeros when asked for
+        assert_eq!(voltage.cells(5.04), "!5.0");
+        assert_eq!(NumberFormat { leading_zeros: 2, ..voltage }.cells(5.04), "05.0");
+        // Rounding to the decimals, and no negative zero
+        assert_eq!(voltage.cells(12.66), "12.7");
+        assert_eq!(voltage.cells(-0.01), "!0.0");
+        assert_eq!(voltage.cells(-1.5), "-1.5");
+        // Too wide for the cells, or no number at all
+        assert_eq!(voltage.cells(123.4), "--.-");
+        assert_eq!(voltage.cells(f32::NAN), "--.-");
+
+        let fuel = NumberFormat { width: 2, decimals: 0, leading_zeros: 2 };
+        assert_eq!(fuel.cells(5.0), "05");
+        assert_eq!(fuel.inactive_pattern(), "88");
+    }
+
+    #[test]
+    fn test_style_keys_override_format() {
+        let indicator = DigitalSegmentedIndicator::float(4, 1);
+        let mut style = UIStyle::new();
+        assert_eq!(indicator.number_format(&style), NumberFormat { width: 4, decimals: 1, leading_zeros: 0 });
+
+        style.set(DIGITAL_DISPLAY_WIDTH, UIStyleValue::Integer(5));
+        style.set(DIGITAL_DISPLAY_DECIMALS, UIStyleValue::Integer(2));
+        style.set(DIGITAL_DISPLAY_LEADING_ZEROS, UIStyleValue::Integer(1));
+        assert_eq!(indicator.number_format(&style), NumberFormat { width: 5, decimals: 2, leading_zeros: 1 });
+        assert_eq!(indicator.number_format(&style).cells(0.5), "!0.50");
+    }
+}
@@ -1,5 +1,5 @@
 #![allow(dead_code)]
-use crate::graphics::context::GraphicsContext;
+use crate::graphics::context::{GraphicsContext, TextAlign, TextStyle};
 use crate::graphics::ui_style::*;
 use crate::indicators::indicator::{Indicator, IndicatorBounds};
 use crate::hardware::sensor_value::{SensorValue, ValueData};
@@ -32,6 +32,17 @@ impl GaugeIndicator {
     }
 }
 
+/// Where and how big the triangular needle is drawn, in screen coordinates
+struct NeedleGeometry {
+    center_x: f32,
+    center_y: f32,
+    length: f32,
+    base_width: f32,
+    tip_width: f32,
+    angle: f32,
+    color: (f32, f32, f32),
+}
+
 impl Indicator for GaugeIndicator {
     fn with_decorators(self, _decorators: Vec<Box<dyn crate::indicators::decorator::Decorator>>) -> Self {
         // Simple implementation - decorators not yet integrated
@@ -87,26 +98,40 @@ impl Indicator for GaugeIndicator {
             Self::get_vbos();
             let shader_program = Self::get_shader();
             
+            // Uniform values stay with the program, so one upload covers every component below
+            gl::UseProgram(shader_program);
+            context.set_projection_uniform(shader_program);
+            
             // Render gauge components
             self.render_gauge_circle_border(center_x, center_y, outer_radius, inner_radius, 
-                                          border_color, context.width as f32, context.height as f32, shader_program);
+                                          border_color, shader_program);
             
             self.render_gauge_marks(center_x, center_y, mark_radius, start_angle, end_angle, 
-                                  num_marks, mark_color, context.width as f32, context.height as f32, shader_program);
+                                  num_marks, mark_color, shader_program);
             
             self.render_gauge_numbers(context, center_x, center_y, number_radius, 
                                       start_angle, end_angle, min_value, max_value, 
                                       num_marks, text_color, style)?;
             
-            self.render_triangular_needle(center_x, center_y, needle_length, 
-                                        start_angle, end_angle, min_value, max_value, 
-                                        current_value, needle_color, needle_glow,
-                                        context.width as f32, context.height as f32,
-                                        shader_program);
+            // Needle angle follows the value across the scale
+            let value_ratio = if max_value == min_value {
+                0.0
+            } else {
+                ((current_value - min_value) / (max_value - min_value)).clamp(0.0, 1.0)
+            };
+            let needle = NeedleGeometry {
+                center_x,
+                center_y,
+                length: needle_length,
+                base_width: 16.0,
+                tip_width: 6.0,
+                angle: start_angle + value_ratio * (end_angle - start_angle),
+                color: needle_color,
+            };
+            self.render_triangular_needle(&needle, needle_glow, shader_program);
             
             // Render center circle
-            self.render_gauge_center_circle(center_x, center_y, 8.0, (0.4, 0.4, 0.5), 
-                                          context.width as f32, context.height as f32, shader_program);
+            self.render_gauge_center_circle(center_x, center_y, 8.0, (0.4, 0.4, 0.5), shader_program);
             
             // shader_program is a persistent cached resource — do not delete it
         }
@@ -131,9 +156,10 @@ impl GaugeIndicator {
             let vertex_shader_source = b"
 attribute vec2 position;
 attribute vec3 color;
+uniform mat4 projection;
 varying vec3 v_color;
 void main() {
-    gl_Position = vec4(position, 0.0, 1.0);
+    gl_Position = projection * vec4(position, 0.0, 1.0);
     v_color = color;
 }
 \0";
@@ -180,7 +206,7 @@ void main() {
     }
 
     /// Render circular border for the gauge
-    unsafe fn render_gauge_circle_border(&self, center_x: f32, center_y: f32, outer_radius: f32, inner_radius: f32, color: (f32, f32, f32), screen_w: f32, screen_h: f32, shader_program: u32) {
+    unsafe fn render_gauge_circle_border(&self, center_x: f32, center_y: f32, outer_radius: f32, inner_radius: f32, color: (f32, f32, f32), shader_program: u32) {
         gl::UseProgram(shader_program);
         
         let segments = 64;
@@ -193,13 +219,13 @@ void main() {
             let sin_a = angle.sin();
             
             // Outer vertex
-            let outer_x = (center_x + cos_a * outer_radius) / screen_w * 2.0 - 1.0;
-            let outer_y = 1.0 - (center_y + sin_a * outer_radius) / screen_h * 2.0;
+            let outer_x = center_x + cos_a * outer_radius;
+            let outer_y = center_y + sin_a * outer_radius;
             vertices.extend_from_slice(&[outer_x, outer_y, color.0, color.1, color.2]);
             
             // Inner vertex
-            let inner_x = (center_x + cos_a * inner_radius) / screen_w * 2.0 - 1.0;
-            let inner_y = 1.0 - (center_y + sin_a * inner_radius) / screen_h * 2.0;
+            let inner_x = center_x + cos_a * inner_radius;
+            let inner_y = center_y + sin_a * inner_radius;
             vertices.extend_from_slice(&[inner_x, inner_y, color.0, color.1, color.2]);
         }
         
@@ -219,7 +245,7 @@ void main() {
     }
     
     /// Render tick marks on the gauge
-    unsafe fn render_gauge_marks(&self, center_x: f32, center_y: f32, radius: f32, start_angle: f32, end_angle: f32, num_marks: i32, color: (f32, f32, f32), screen_w: f32, screen_h: f32, shader_program: u32) {
+    unsafe fn render_gauge_marks(&self, center_x: f32, center_y: f32, radius: f32, start_angle: f32, end_angle: f32, num_marks: i32, color: (f32, f32, f32), shader_program: u32) {
         gl::UseProgram(shader_program);
         
         let angle_range = end_angle - start_angle;
@@ -238,13 +264,8 @@ void main() {
             let x2 = center_x + cos_a * (radius + mark_length);
             let y2 = center_y + sin_a * (radius + mark_length);
             
-            let nx1 = x1 / screen_w * 2.0 - 1.0;
-            let ny1 = 1.0 - y1 / screen_h * 2.0;
-            let nx2 = x2 / screen_w * 2.0 - 1.0;
-            let ny2 = 1.0 - y2 / screen_h * 2.0;
-            
-            all_mark_vertices.extend_from_slice(&[nx1, ny1, color.0, color.1, color.2]);
-            all_mark_vertices.extend_from_slice(&[nx2, ny2, color.0, color.1, color.2]);
+            all_mark_vertices.extend_from_slice(&[x1, y1, color.0, color.1, color.2]);
+            all_mark_vertices.extend_from_slice(&[x2, y2, color.0, color.1, color.2]);
         }
         
         gl::BindBuffer(gl::ARRAY_BUFFER, GAUGE_MARKS_VBO);
@@ -284,53 +305,37 @@ void main() {
             let target_x = center_x + cos_a * radius;
             let target_y = center_y + sin_a * radius;
             
-            // Calculate text dimensions to find the center offset
-            let (text_width, text_height) = context.calculate_text_dimensions_with_font(
+            // Center the text vertically on the target position; render_text_aligned centers it horizontally
+            let text_height = context.calculate_text_height_with_font(
                 &text, 
                 text_scale, 
                 &font_path, 
                 font_size
             )?;
-            
-            // Calculate the top-left corner position to center the text at the target position
-            let text_x = target_x - text_width / 2.0;
             let text_y = target_y - text_height / 2.0;
             
-            context.render_text_with_font(
+            context.render_text_aligned(
                 &text, 
-                text_x, 
+                target_x, 
                 text_y, 
-                text_scale, 
-                color,
-                &font_path,
-                font_size
+                TextAlign::Center,
+                &TextStyle::new(&font_path, font_size, color).with_scale(text_scale)
             )?;
         }
         Ok(())
     }
     
     /// Render triangular needle with glow effect
-    unsafe fn render_triangular_needle(&self, center_x: f32, center_y: f32, length: f32,
-                                       start_angle: f32, end_angle: f32,
-                                       min_value: f32, max_value: f32, current_value: f32,
-                                       color: (f32, f32, f32), needle_glow: bool,
-                                       screen_w: f32, screen_h: f32, shader_program: u32) {
+    unsafe fn render_triangular_needle(&self, needle: &NeedleGeometry, needle_glow: bool, shader_program: u32) {
         gl::UseProgram(shader_program);
         
-        // Calculate needle angle based on value
-        let value_ratio = if max_value == min_value {
-            0.0
-        } else {
-            ((current_value - min_value) / (max_value - min_value)).clamp(0.0, 1.0)
-        };
-        let needle_angle = start_angle + value_ratio * (end_angle - start_angle);
-        
-        let cos_a = needle_angle.cos();
-        let sin_a = needle_angle.sin();
+        let NeedleGeometry { center_x, center_y, length, color, .. } = *needle;
+        let cos_a = needle.angle.cos();
+        let sin_a = needle.angle.sin();
         
         // Base needle parameters
-        let base_needle_width = 16.0;
-        let tip_needle_width = 6.0;
+        let base_needle_width = needle.base_width;
+        let tip_needle_width = needle.tip_width;
         let tip_x = center_x + cos_a * length;
         let tip_y = center_y + sin_a * length;
         
@@ -372,16 +377,6 @@ void main() {
             let tip2_x = tip_x - tip_perp_cos;
             let tip2_y = tip_y - tip_perp_sin;
             
-            // Convert to normalized coordinates
-            let base1_nx = base1_x / screen_w * 2.0 - 1.0;
-            let base1_ny = 1.0 - base1_y / screen_h * 2.0;
-            let base2_nx = base2_x / screen_w * 2.0 - 1.0;
-            let base2_ny = 1.0 - base2_y / screen_h * 2.0;
-            let tip1_nx = tip1_x / screen_w * 2.0 - 1.0;
-            let tip1_ny = 1.0 - tip1_y / screen_h * 2.0;
-            let tip2_nx = tip2_x / screen_w * 2.0 - 1.0;
-            let tip2_ny = 1.0 - tip2_y / screen_h * 2.0;
-            
             // Apply progressive color brightness for glow effect
             let glow_color = 
                 (
@@ -392,13 +387,13 @@ void main() {
             
             let vertices = [
                 // First triangle: base1 -> base2 -> tip1
-                base1_nx, base1_ny, glow_color.0, glow_color.1, glow_color.2,
-                base2_nx, base2_ny, glow_color.0, glow_color.1, glow_color.2,
-                tip1_nx, tip1_ny, glow_color.0, glow_color.1, glow_color.2,
+                base1_x, base1_y, glow_color.0, glow_color.1, glow_color.2,
+                base2_x, base2_y, glow_color.0, glow_color.1, glow_color.2,
+                tip1_x, tip1_y, glow_color.0, glow_color.1, glow_color.2,
                 // Second triangle: base2 -> tip2 -> tip1
-                base2_nx, base2_ny, glow_color.0, glow_color.1, glow_color.2,
-                tip2_nx, tip2_ny, glow_color.0, glow_color.1, glow_color.2,
-                tip1_nx, tip1_ny, glow_color.0, glow_color.1, glow_color.2,
+                base2_x, base2_y, glow_color.0, glow_color.1, glow_color.2,
+                tip2_x, tip2_y, glow_color.0, glow_color.1, glow_color.2,
+                tip1_x, tip1_y, glow_color.0, glow_color.1, glow_color.2,
             ];
             
             // Reuse the single persistent needle VBO — update data for each glow layer
@@ -428,26 +423,21 @@ void main() {
     }
     
     /// Render center circle
-    unsafe fn render_gauge_center_circle(&self, center_x: f32, center_y: f32, radius: f32, color: (f32, f32, f32), screen_w: f32, screen_h: f32, shader_program: u32) {
+    unsafe fn render_gauge_center_circle(&self, center_x: f32, center_y: f32, radius: f32, color: (f32, f32, f32), shader_program: u32) {
         gl::UseProgram(shader_program);
         
         let segments = 32;
         let mut vertices = Vec::new();
         
         // Center vertex
-        let center_nx = center_x / screen_w * 2.0 - 1.0;
-        let center_ny = 1.0 - center_y / screen_h * 2.0;
-        vertices.extend_from_slice(&[center_nx, center_ny, color.0, color.1, color.2]);
+        vertices.extend_from_slice(&[center_x, center_y, color.0, color.1, color.2]);
         
         // Circle vertices
         for i in 0..=segments {
             let angle = (i as f32 / segments as f32) * 2.0 * std::f32::consts::PI;
             let x = center_x + angle.cos() * radius;
             let y = center_y + angle.sin() * radius;
-            
-            let nx = x / screen_w * 2.0 - 1.0;
-            let ny = 1.0 - y / screen_h * 2.0;
-            vertices.extend_from_slice(&[nx, ny, color.0, color.1, color.2]);
+            vertices.extend_from_slice(&[x, y, color.0, color.1, color.2]);
         }
         
         gl::BindBuffer(gl::ARRAY_BUFFER, GAUGE_CENTER_CIRCLE_VBO);
@@ -0,0 +1,172 @@
+#![allow(dead_code)]
+use crate::indicators::indicator::{Indicator, IndicatorBounds, IndicatorBase};
+use crate::indicators::decorator::Decorator;
+use crate::indicators::vertical_bar_indicator::bar_segment_color;
+use crate::graphics::context::{opaque, GraphicsContext};
+use crate::graphics::ui_style::*;
+use crate::hardware::sensor_value::{SensorValue, ValueData};
+
+/// Horizontal bar indicator that fills from left to right
+/// Uses the same BAR_* style keys and threshold coloring as VerticalBarIndicator
+pub struct HorizontalBarIndicator {
+    base: IndicatorBase,
+    /// Number of segments in the bar
+    segments: usize,
+    /// Gap between segments (in pixels)
+    segment_gap: f32,
+}
+
+impl HorizontalBarIndicator {
+    /// Create a new horizontal bar indicator
+    pub fn new(segments: usize) -> Self {
+        Self {
+            base: IndicatorBase::new(),
+            segments: segments.max(1),
+            segment_gap: 2.0, // Default 2px gap between segments
+        }
+    }
+
+    /// Set the gap between segments
+    pub fn with_segment_gap(mut self, gap: f32) -> Self {
+        self.segment_gap = gap;
+        self
+    }
+
+    /// Calculate which segments should be filled based on normalized value (0.0 to 1.0)
+    fn calculate_filled_segments(&self, normalized_value: f32) -> usize {
+        let clamped_value = normalized_value.clamp(0.0, 1.0);
+        (clamped_value * self.segments as f32).round() as usize
+    }
+
+    /// Width of a single segment within the available width
+    fn segment_width(&self, available_width: f32) -> f32 {
+        let total_gaps = (self.segments - 1) as f32 * self.segment_gap;
+        (available_width - total_gaps) / self.segments as f32
+    }
+
+    /// Width covered by the filled segments (including the gaps between them)
+    fn calculate_fill_width(&self, normalized_value: f32, available_width: f32) -> f32 {
+        let filled_segments = self.calculate_filled_segments(normalized_value);
+        if filled_segments == 0 {
+            return 0.0;
+        }
+        filled_segments as f32 * (self.segment_width(available_width) + self.segment_gap) - self.segment_gap
+    }
+}
+
+impl Default for HorizontalBarIndicator {
+    fn default() -> Self {
+        Self::new(10) // Default to 10 segments
+    }
+}
+
+impl Indicator for HorizontalBarIndicator {
+    fn with_decorators(mut self, decorators: Vec<Box<dyn Decorator>>) -> Self {
+        self.base.decorators = decorators;
+        self
+    }
+
+    fn render(
+        &self,
+        value: &SensorValue,
+        bounds: IndicatorBounds,
+        style: &UIStyle,
+        context: &mut GraphicsContext,
+    ) -> Result<(), String> {
+        // Extract numeric value
+        let numeric_value = match &value.value {
+            ValueData::Analog(v) => *v,
+            ValueData::Integer(i) => *i as f32,
+            ValueData::Percentage(p) => *p,
+            _ => return Ok(()), // Skip non-numeric values
+        };
+
+        // Render decorators first, then the display itself over the decorators
+        self.base.render_decorators(bounds, style, context)?;
+
+        let background_enabled = style.get_bool(BAR_BACKGROUND_ENABLED, true);
+        let border_enabled = style.get_bool(BAR_BORDER_ENABLED, true);
+        let border_width = style.get_float(BAR_BORDER_WIDTH, 4.0);
+
+        if background_enabled {
+            let bg_color = style.get_color(BAR_BACKGROUND_COLOR, (1.0, 0.65, 0.0)); // Default amber
+            context.render_rectangle(bounds.x, bounds.y, bounds.width, bounds.height,
+                opaque(bg_color), true, 1.0,
+                style.get_float(BAR_CORNER_RADIUS, 8.0))?;
+        } else if border_enabled {
+            let border_color = style.get_color(BAR_BORDER_COLOR, (1.0, 0.65, 0.0)); // Default amber
+            context.render_rectangle(bounds.x, bounds.y, bounds.width, bounds.height,
+                opaque(border_color), false, border_width,
+                style.get_float(BAR_CORNER_RADIUS, 8.0))?;
+        }
+
+        // Normalize the value to 0.0-1.0 range
+        let normalized_value = ((numeric_value - value.constraints.min_value) /
+                               (value.constraints.max_value - value.constraints.min_value)).clamp(0.0, 1.0);
+
+        let filled_segments = self.calculate_filled_segments(normalized_value);
+
+        // Same margins as the vertical bar
+        let margin = if background_enabled || border_enabled {
+            if border_enabled {
+                self.segment_gap + border_width  // Add border width to prevent overlap
+            } else {
+                self.segment_gap
+            }
+        } else {
+            0.0
+        };
+
+        let available_width = bounds.width - (2.0 * margin);
+        let segment_height = bounds.height - (2.0 * margin);
+        let segments_start_x = bounds.x + margin;
+        let segments_start_y = bounds.y + margin;
+        let segment_width = self.segment_width(available_width);
+
+        let empty_color = style.get_color(BAR_EMPTY_COLOR, (0.2, 0.2, 0.2)); // Dark gray for empty
+
+        // Segments from left (index 0) to right
+        for i in 0..self.segments {
+            let segment_x = segments_start_x + i as f32 * (segment_width + self.segment_gap);
+            let color = if i < filled_segments {
+                bar_segment_color(i, self.segments, normalized_value, value, style)
+            } else {
+                empty_color
+            };
+            context.render_rectangle(segment_x, segments_start_y, segment_width, segment_height,
+                opaque(color), true, 1.0, 0.0)?;
+        }
+
+        Ok(())
+    }
+
+    fn indicator_type(&self) -> &'static str {
+        "HorizontalBarIndicator"
+    }
+
+    fn supports_value_type(&self, value: &ValueData) -> bool {
+        matches!(value, ValueData::Analog(_) | ValueData::Integer(_) | ValueData::Percentage(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_width_for_percentage() {
+        // 10 segments, 2px gaps over 218px: each segment is 20px wide
+        let bar = HorizontalBarIndicator::new(10);
+        assert_eq!(bar.segment_width(218.0), 20.0);
+
+        assert_eq!(bar.calculate_fill_width(0.0, 218.0), 0.0);
+        assert_eq!(bar.calculate_fill_width(0.5, 218.0), 5.0 * 20.0 + 4.0 * 2.0);
+        assert_eq!(bar.calculate_fill_width(1.0, 218.0), 218.0);
+        // Out of range values are clamped to the bar bounds
+        assert_eq!(bar.calculate_fill_width(1.5, 218.0), 218.0);
+        assert_eq!(bar.calculate_fill_width(-0.5, 218.0), 0.0);
+
+        let gapless = HorizontalBarIndicator::new(4).with_segment_gap(0.0);
+        assert_eq!(gapless.calculate_fill_width(0.25, 100.0), 25.0);
+    }
+}
@@ -0,0 +1,146 @@
+#![allow(dead_code)]
+use crate::indicators::indicator::{Indicator, IndicatorBounds, IndicatorBase};
+use crate::indicators::decorator::Decorator;
+use crate::graphics::context::GraphicsContext;
+use crate::graphics::ui_style::*;
+use crate::hardware::sensor_value::{SensorValue, ValueData};
+
+/// Tell-tale pictogram (turn signal arrow, high beam, ...) drawn from an image file and
+/// tinted by the sensor state. Icons should be white on transparent so the tint shows as-is.
+pub struct IconIndicator {
+    base: IndicatorBase,
+    /// Style key holding the image path for this indicator
+    icon_path_key: &'static str,
+    /// Keep the image aspect ratio inside the bounds instead of stretching
+    keep_aspect: bool,
+}
+
+impl IconIndicator {
+    pub fn new(icon_path_key: &'static str) -> Self {
+        Self {
+            base: IndicatorBase::new(),
+            icon_path_key,
+            keep_aspect: true,
+        }
+    }
+
+    /// Stretch the image to fill the bounds
+    pub fn with_stretch(mut self) -> Self {
+        self.keep_aspect = false;
+        self
+    }
+
+    /// Style key of the tint for the sensor state: critical, warning, active or off
+    fn state_color_key(value: &SensorValue) -> &'static str {
+        if value.is_critical() {
+            INDICATOR_CRITICAL_COLOR
+        } else if value.is_warning() {
+            INDICATOR_WARNING_COLOR
+        } else if value.is_active() {
+            INDICATOR_NORMAL_COLOR
+        } else {
+            INDICATOR_OFF_COLOR
+        }
+    }
+
+    /// Rectangle for an image of the given size centered in the bounds, scaled to fit
+    fn fit_rect(image_size: (u32, u32), bounds: IndicatorBounds) -> (f32, f32, f32, f32) {
+        let (image_w, image_h) = (image_size.0.max(1) as f32, image_size.1.max(1) as f32);
+        let scale = (bounds.width / image_w).min(bounds.height / image_h);
+        let (width, height) = (image_w * scale, image_h * scale);
+        (bounds.x + (bounds.width - width) / 2.0, bounds.y + (bounds.height - height) / 2.0, width, height)
+    }
+}
+
+impl Indicator for IconIndicator {
+    fn with_decorators(mut self, decorators: Vec<Box<dyn Decorator>>) -> Self {
+        self.base.decorators = decorators;
+        self
+    }
+
+    fn render(
+        &self,
+        value: &SensorValue,
+        bounds: IndicatorBounds,
+        style: &UIStyle,
+        context: &mut GraphicsContext,
+    ) -> Result<(), String> {
+        self.base.render_decorators(bounds, style, context)?;
+
+        // A missing icon only blanks this indicator; the load failure is logged once by the context
+        let path = style.get_string(self.icon_path_key, "");
+        if path.is_empty() {
+            return Ok(());
+        }
+        let Ok(texture) = context.load_texture(&path) else {
+            return Ok(());
+        };
+
+        let (x, y, width, height) = match context.texture_size(texture) {
+            Some(size) if self.keep_aspect => Self::fit_rect(size, bounds),
+            _ => (bounds.x, bounds.y, bounds.width, bounds.height),
+        };
+        let tint = style.get_color(Self::state_color_key(value), (1.0, 1.0, 1.0));
+        context.draw_texture(texture, x, y, width, height, tint).map_err(String::from)
+    }
+
+    fn indicator_type(&self) -> &'static str {
+        "IconIndicator"
+    }
+
+    fn supports_value_type(&self, value: &ValueData) -> bool {
+        matches!(value, ValueData::Digital(_) | ValueData::Analog(_) | ValueData::Integer(_) | ValueData::Percentage(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hardware::sensor_value::{ValueConstraints, ValueMetadata};
+
+    fn digital(on: bool, constraints: ValueConstraints) -> SensorValue {
+        SensorValue::new(ValueData::Digital(on), constraints, ValueMetadata::new("", "Icon", "icon_test"))
+    }
+
+    #[test]
+    fn test_tint_follows_state() {
+        let style = UIStyle::new();
+        let tint = |value: &SensorValue| style.get_color(IconIndicator::state_color_key(value), (1.0, 1.0, 1.0));
+
+        // Plain tell-tale (turn signal): off when inactive, normal when active
+        assert_eq!(tint(&digital(false, ValueConstraints::digital_default())), style.get_color(INDICATOR_OFF_COLOR, (0.0, 0.0, 0.0)));
+        assert_eq!(tint(&digital(true, ValueConstraints::digital_default())), style.get_color(INDICATOR_NORMAL_COLOR, (0.0, 0.0, 0.0)));
+        // Warning (parking brake) and critical (check engine) lamps
+        assert_eq!(tint(&digital(true, ValueConstraints::digital_warning())), style.get_color(INDICATOR_WARNING_COLOR, (0.0, 0.0, 0.0)));
+        assert_eq!(tint(&digital(true, ValueConstraints::digital_critical())), style.get_color(INDICATOR_CRITICAL_COLOR, (0.0, 0.0, 0.0)));
+        assert_ne!(style.get_color(INDICATOR_OFF_COLOR, (0.0, 0.0, 0.0)), style.get_color(INDICATOR_NORMAL_COLOR, (0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_fit_rect_keeps_aspect() {
+        let bounds = IndicatorBounds::new(10.0, 20.0, 100.0, 50.0);
+        assert_eq!(IconIndicator::fit_rect((64, 64), bounds), (35.0, 20.0, 50.0, 50.0));
+    }
+
+    #[test]
+    fn test_missing_icon_is_skipped() {
+        // Skipped where there is no EGL with the surfaceless platform (see new_headless)
+        let mut context = match GraphicsContext::new_headless(16, 16) {
+            Ok(context) => context,
+            Err(e) => {
+                eprintln!("Skipping headless icon test: {}", e);
+                return;
+            }
+        };
+        let mut style = UIStyle::new();
+        style.set(ICON_HIGH_BEAM, UIStyleValue::String("/nonexistent/missing_icon.png".to_string()));
+        let icon = IconIndicator::new(ICON_HIGH_BEAM);
+        let bounds = IndicatorBounds::new(0.0, 0.0, 16.0, 16.0);
+
+        // A missing image blanks the icon rather than failing the page, and isn't reloaded
+        for _ in 0..2 {
+            icon.render(&digital(true, ValueConstraints::digital_default()), bounds, &style, &mut context).unwrap();
+        }
+        assert!(context.load_texture("/nonexistent/missing_icon.png").is_err());
+    }
+}
@@ -2,10 +2,12 @@
 use crate::graphics::context::GraphicsContext;
 use crate::graphics::ui_style::UIStyle;
 use crate::hardware::sensor_value::{SensorValue, ValueData};
+use crate::hardware::sensor_history::SensorHistory;
 use crate::indicators::decorator::Decorator;
+use serde::Deserialize;
 
 /// Position and size information for indicator rendering
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Deserialize)]
 pub struct IndicatorBounds {
     pub x: f32,
     pub y: f32,
@@ -64,6 +66,18 @@ pub trait Indicator {
               bounds: IndicatorBounds, 
               style: &UIStyle, 
               context: &mut GraphicsContext) -> Result<(), String>;
+
+    /// Render with access to the sensor's recent history, for indicators that plot trends.
+    /// Indicators that only show the current value keep the default.
+    fn render_with_history(&self,
+                           value: &SensorValue,
+                           _history: Option<&SensorHistory>,
+                           bounds: IndicatorBounds,
+                           style: &UIStyle,
+                           context: &mut GraphicsContext) -> Result<(), String> {
+        self.render(value, bounds, style, context)
+    }
+
     /// Get indicator type name for debugging and configuration
     fn indicator_type(&self) -> &'static str;
     
@@ -2,8 +2,14 @@ pub mod indicator;
 pub mod text_indicator;
 pub mod gauge_indicator;
 pub mod digital_segmented_indicator;
+pub mod digital_alphanumeric_indicator;
 pub mod vertical_bar_indicator;
+pub mod horizontal_bar_indicator;
+pub mod sparkline_indicator;
+pub mod odometer_indicator;
+pub mod icon_indicator;
 pub mod needle_indicator;
+pub mod needle_gauge_indicator;
 pub mod decorator;
 
 // Re-export main types for convenience
@@ -0,0 +1,229 @@
+#![allow(dead_code)]
+use crate::graphics::context::{GraphicsContext, TextAlign, TextStyle};
+use crate::graphics::ui_style::*;
+use crate::hardware::sensor_value::{SensorValue, ValueData};
+use crate::indicators::decorator::Decorator;
+use crate::indicators::indicator::{Indicator, IndicatorBase, IndicatorBounds};
+use std::f32::consts::PI;
+
+// Gap between the ring and the outer end of the marks
+const MARK_INSET: f32 = 4.0;
+const LABEL_SCALE: f32 = 1.0;
+// Glow layers drawn under the needle, widest first: (width multiplier, brightness)
+const NEEDLE_GLOW_LAYERS: [(f32, f32); 3] = [(3.0, 0.15), (2.0, 0.25), (1.5, 0.40)];
+
+/// Style keys of one set of scale marks
+struct MarkStyle {
+    enabled: &'static str,
+    count: &'static str,
+    default_count: u32,
+    length: &'static str,
+    width: &'static str,
+    offset: &'static str,
+    color: &'static str,
+}
+
+const MINOR_MARKS: MarkStyle = MarkStyle {
+    enabled: GAUGE_MINOR_MARK_ENABLED, count: GAUGE_MINOR_MARK_COUNT, default_count: 37,
+    length: GAUGE_MINOR_MARK_LENGTH, width: GAUGE_MINOR_MARK_WIDTH,
+    offset: GAUGE_MINOR_MARK_OFFSET, color: GAUGE_MINOR_MARK_COLOR,
+};
+
+const MAJOR_MARKS: MarkStyle = MarkStyle {
+    enabled: GAUGE_MAJOR_MARK_ENABLED, count: GAUGE_MAJOR_MARK_COUNT, default_count: 10,
+    length: GAUGE_MAJOR_MARK_LENGTH, width: GAUGE_MAJOR_MARK_WIDTH,
+    offset: GAUGE_MAJOR_MARK_OFFSET, color: GAUGE_MAJOR_MARK_COLOR,
+};
+
+/// Complete round gauge: ring, major and minor marks, scale numbers and a tapered needle
+/// with optional glow, all sized from the bounds and styled by the `GAUGE_*` keys.
+/// Drawn with the GraphicsContext primitives only, so it needs no GL state of its own.
+pub struct NeedleGaugeIndicator {
+    /// Angle of the minimum value in radians (0 = right, angles grow clockwise on screen)
+    start_angle: f32,
+    /// Angle of the maximum value; may be below `start_angle` when the sweep crosses 0
+    end_angle: f32,
+    base: IndicatorBase,
+}
+
+impl NeedleGaugeIndicator {
+    pub fn new(start_angle: f32, end_angle: f32) -> Self {
+        Self {
+            start_angle,
+            end_angle,
+            base: IndicatorBase::new(),
+        }
+    }
+
+    fn sweep(&self) -> f32 {
+        if self.end_angle < self.start_angle {
+            (self.end_angle + 2.0 * PI) - self.start_angle
+        } else {
+            self.end_angle - self.start_angle
+        }
+    }
+
+    /// Angle for a position along the scale, 0.0 at the minimum and 1.0 at the maximum
+    fn angle_at(&self, t: f32) -> f32 {
+        self.start_angle + t.clamp(0.0, 1.0) * self.sweep()
+    }
+
+    /// Needle angle for a sensor value; out of range values stop at the ends, and a value
+    /// that can't be read as a number rests the needle at the start
+    pub fn needle_angle(&self, value: &SensorValue) -> f32 {
+        let normalized = value.as_normalized();
+        self.angle_at(if normalized.is_nan() { 0.0 } else { normalized })
+    }
+
+    fn render_marks(&self, marks: &MarkStyle, center_x: f32, center_y: f32, radius: f32,
+                    style: &UIStyle, context: &mut GraphicsContext) -> Result<(), String> {
+        let count = style.get_integer(marks.count, marks.default_count);
+        if !style.get_bool(marks.enabled, true) || count < 2 {
+            return Ok(());
+        }
+        let length = style.get_float(marks.length, 10.0);
+        let width = style.get_float(marks.width, 2.0);
+        let outer = radius - MARK_INSET - style.get_float(marks.offset, 0.0);
+        let color = style.get_color(marks.color, (1.0, 1.0, 1.0));
+        for i in 0..count {
+            let angle = self.angle_at(i as f32 / (count - 1) as f32);
+            let (sin_a, cos_a) = angle.sin_cos();
+            context.draw_line(center_x + cos_a * (outer - length), center_y + sin_a * (outer - length),
+                              center_x + cos_a * outer, center_y + sin_a * outer, width, color)?;
+        }
+        Ok(())
+    }
+
+    /// Scale numbers at the major marks, `GAUGE_LABEL_OFFSET` pixels out from the ring
+    fn render_labels(&self, value: &SensorValue, center_x: f32, center_y: f32, radius: f32,
+                     style: &UIStyle, context: &mut GraphicsContext) -> Result<(), String> {
+        let count = style.get_integer(GAUGE_MAJOR_MARK_COUNT, 10);
+        if count < 2 {
+            return Ok(());
+        }
+        let font = style.get_string(GAUGE_LABEL_FONT, DEFAULT_GLOBAL_FONT_PATH);
+        let font_size = style.get_integer(GAUGE_LABEL_FONT_SIZE, DEFAULT_GLOBAL_FONT_SIZE);
+        let color = style.get_color(GAUGE_LABEL_COLOR, (1.0, 1.0, 1.0));
+        let label_radius = radius + style.get_float(GAUGE_LABEL_OFFSET, -35.0);
+        let (min_value, max_value) = (value.constraints.min_value, value.constraints.max_value);
+        for i in 0..count {
+            let t = i as f32 / (count - 1) as f32;
+            let text = format!("{:.0}", min_value + t * (max_value - min_value));
+            let (sin_a, cos_a) = self.angle_at(t).sin_cos();
+            let text_height = context.calculate_text_height_with_font(&text, LABEL_SCALE, &font, font_size)?;
+            context.render_text_aligned(&text, center_x + cos_a * label_radius,
+                                        center_y + sin_a * label_radius - text_height / 2.0,
+                                        TextAlign::Center, &TextStyle::new(&font, font_size, color).with_scale(LABEL_SCALE))?;
+        }
+        Ok(())
+    }
+
+    fn render_needle(&self, angle: f32, center_x: f32, center_y: f32, radius: f32,
+                     style: &UIStyle, context: &mut GraphicsContext) -> Result<(), String> {
+        let length = radius * style.get_float(GAUGE_NEEDLE_LENGTH, 0.8);
+        let base_width = style.get_float(GAUGE_NEEDLE_WIDTH, 8.0);
+        let tip_width = style.get_float(GAUGE_NEEDLE_TIP_WIDTH, 2.0);
+        let color = style.get_color(GAUGE_NEEDLE_COLOR, (1.0, 0.0, 0.0));
+
+        if style.get_bool(GAUGE_NEEDLE_GLOW_ENABLED, false) {
+            for (multiplier, brightness) in NEEDLE_GLOW_LAYERS {
+                let glow = (color.0 * brightness, color.1 * brightness, color.2 * brightness);
+                context.fill_polygon(&needle_outline(center_x, center_y, angle, length,
+                                                     base_width * multiplier, tip_width * multiplier), glow)?;
+            }
+            context.fill_polygon(&needle_outline(center_x, center_y, angle, length, base_width, tip_width), color)?;
+            // Hot white core along the middle
+            context.fill_polygon(&needle_outline(center_x, center_y, angle, length, base_width * 0.25, tip_width * 0.25),
+                                 blend_colors(color, (1.0, 1.0, 1.0), 0.7))?;
+        } else {
+            context.fill_polygon(&needle_outline(center_x, center_y, angle, length, base_width, tip_width), color)?;
+        }
+
+        let center_radius = style.get_float(GAUGE_NEEDLE_CENTER_RADIUS, 8.0);
+        if center_radius > 0.0 {
+            context.fill_circle(center_x, center_y, center_radius,
+                                style.get_color(GAUGE_NEEDLE_CENTER_COLOR, (0.25, 0.25, 0.25)))?;
+        }
+        Ok(())
+    }
+}
+
+/// Tapered needle quad from the center out to `length`, as a convex outline for fill_polygon
+fn needle_outline(center_x: f32, center_y: f32, angle: f32, length: f32, base_width: f32, tip_width: f32) -> [(f32, f32); 4] {
+    let (sin_a, cos_a) = angle.sin_cos();
+    // Unit perpendicular to the needle
+    let (perp_x, perp_y) = (-sin_a, cos_a);
+    let (tip_x, tip_y) = (center_x + cos_a * length, center_y + sin_a * length);
+    let (base_half, tip_half) = (base_width / 2.0, tip_width / 2.0);
+    [
+        (center_x + perp_x * base_half, center_y + perp_y * base_half),
+        (tip_x + perp_x * tip_half, tip_y + perp_y * tip_half),
+        (tip_x - perp_x * tip_half, tip_y - perp_y * tip_half),
+        (center_x - perp_x * base_half, center_y - perp_y * base_half),
+    ]
+}
+
+impl Indicator for NeedleGaugeIndicator {
+    fn with_decorators(mut self, decorators: Vec<Box<dyn Decorator>>) -> Self where Self: Sized {
+        self.base.decorators = decorators;
+        self
+    }
+
+    fn render(&self,
+              value: &SensorValue,
+              bounds: IndicatorBounds,
+              style: &UIStyle,
+              context: &mut GraphicsContext) -> Result<(), String> {
+        let (center_x, center_y) = bounds.center();
+        let radius = bounds.width.min(bounds.height) / 2.0;
+
+        self.base.render_decorators(bounds, style, context)?;
+
+        let border_width = style.get_float(GAUGE_BORDER_WIDTH, 2.0);
+        if border_width > 0.0 {
+            context.stroke_ring(center_x, center_y, radius - border_width / 2.0, border_width,
+                                style.get_color(GAUGE_BORDER_COLOR, (1.0, 1.0, 1.0)))?;
+        }
+
+        self.render_marks(&MINOR_MARKS, center_x, center_y, radius, style, context)?;
+        self.render_marks(&MAJOR_MARKS, center_x, center_y, radius, style, context)?;
+        if style.get_bool(GAUGE_LABEL_ENABLED, true) {
+            self.render_labels(value, center_x, center_y, radius, style, context)?;
+        }
+
+        self.render_needle(self.needle_angle(value), center_x, center_y, radius, style, context)
+    }
+
+    fn indicator_type(&self) -> &'static str {
+        "NeedleGaugeIndicator"
+    }
+
+    fn supports_value_type(&self, value: &ValueData) -> bool {
+        matches!(value, ValueData::Analog(_) | ValueData::Integer(_) | ValueData::Percentage(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_to_needle_angle() {
+        // 270° sweep over the top, bottom-left to bottom-right, for 0-120 km/h
+        let gauge = NeedleGaugeIndicator::new(-225.0f32.to_radians(), 45.0f32.to_radians());
+        let speed = |kmh| SensorValue::analog(kmh, 0.0, 120.0, "km/h", "Speed", "speed");
+
+        assert!((gauge.needle_angle(&speed(0.0)) - (-225.0f32).to_radians()).abs() < 1e-5);
+        // Half the range is straight up
+        assert!((gauge.needle_angle(&speed(60.0)) - (-90.0f32).to_radians()).abs() < 1e-5);
+        assert!((gauge.needle_angle(&speed(120.0)) - 45.0f32.to_radians()).abs() < 1e-5);
+        // Pinned at the ends past the range, resting at the start with no reading
+        assert!((gauge.needle_angle(&speed(150.0)) - 45.0f32.to_radians()).abs() < 1e-5);
+        assert!((gauge.needle_angle(&speed(-10.0)) - (-225.0f32).to_radians()).abs() < 1e-5);
+        assert!((gauge.needle_angle(&SensorValue::empty()) - (-225.0f32).to_radians()).abs() < 1e-5);
+
+        // A sweep given across 0 keeps going clockwise
+        let wrapped = NeedleGaugeIndicator::new(135.0f32.to_radians(), 45.0f32.to_radians());
+        assert!((wrapped.needle_angle(&speed(60.0)) - 270.0f32.to_radians()).abs() < 1e-5);
+    }
+}
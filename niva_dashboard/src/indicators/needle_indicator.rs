@@ -1,7 +1,7 @@
 use crate::indicators::indicator::{Indicator, IndicatorBounds, IndicatorBase};
-use crate::graphics::context::GraphicsContext;
-use crate::graphics::ui_style::UIStyle;
-use crate::hardware::sensor_value::{SensorValue, ValueData};
+use crate::graphics::context::{GraphicsContext, TextAlign, TextStyle};
+use crate::graphics::ui_style::*;
+use crate::hardware::sensor_value::{SensorValue, ValueData, ValueConstraints};
 use crate::indicators::decorator::Decorator;
 use std::f32::consts::PI;
 use std::sync::Once;
@@ -75,9 +75,10 @@ impl NeedleIndicator {
             let vertex_shader_source = b"
 attribute vec2 position;
 attribute vec3 color;
+uniform mat4 projection;
 varying vec3 v_color;
 void main() {
-    gl_Position = vec4(position, 0.0, 1.0);
+    gl_Position = projection * vec4(position, 0.0, 1.0);
     v_color = color;
 }
 \0";
@@ -145,10 +146,77 @@ void main() {
         result_angle % (2.0 * PI)
     }
 
+    /// Angle along the gauge sweep for a value in the constraints range.
+    /// Not wrapped to 0-2π so that arcs between two such angles keep their direction.
+    fn value_to_angle(&self, value: f32, constraints: &ValueConstraints) -> f32 {
+        let range = constraints.max_value - constraints.min_value;
+        let normalized = if range == 0.0 {
+            0.0
+        } else {
+            ((value - constraints.min_value) / range).clamp(0.0, 1.0)
+        };
+        let angle_range = if self.end_angle < self.start_angle {
+            (self.end_angle + 2.0 * PI) - self.start_angle
+        } else {
+            self.end_angle - self.start_angle
+        };
+        self.start_angle + normalized * angle_range
+    }
+
+    /// Value ranges of the warning zones, bounded by the critical thresholds when present
+    fn warning_zones(constraints: &ValueConstraints) -> Vec<(f32, f32)> {
+        let mut zones = Vec::new();
+        if let Some(warning_low) = constraints.warning_low {
+            zones.push((constraints.critical_low.unwrap_or(constraints.min_value), warning_low));
+        }
+        if let Some(warning_high) = constraints.warning_high {
+            zones.push((warning_high, constraints.critical_high.unwrap_or(constraints.max_value)));
+        }
+        zones
+    }
+
+    /// Value ranges of the critical zones, out to the ends of the scale
+    fn critical_zones(constraints: &ValueConstraints) -> Vec<(f32, f32)> {
+        let mut zones = Vec::new();
+        if let Some(critical_low) = constraints.critical_low {
+            zones.push((constraints.min_value, critical_low));
+        }
+        if let Some(critical_high) = constraints.critical_high {
+            zones.push((critical_high, constraints.max_value));
+        }
+        zones
+    }
+
+    /// Draw warning/critical arcs along the inner edge of the gauge sweep
+    fn render_zones(&self, constraints: &ValueConstraints, center_x: f32, center_y: f32, radius: f32,
+                    style: &UIStyle, context: &mut GraphicsContext) -> Result<(), String> {
+        let zones = [
+            (Self::warning_zones(constraints), GAUGE_WARNING_ZONE_ENABLED, GAUGE_WARNING_ZONE_COLOR, GAUGE_WARNING_ZONE_WIDTH, (1.0, 0.67, 0.0)),
+            (Self::critical_zones(constraints), GAUGE_CRITICAL_ZONE_ENABLED, GAUGE_CRITICAL_ZONE_COLOR, GAUGE_CRITICAL_ZONE_WIDTH, (1.0, 0.0, 0.0)),
+        ];
+        for (ranges, enabled_key, color_key, width_key, default_color) in zones {
+            if !style.get_bool(enabled_key, false) {
+                continue;
+            }
+            let color = style.get_color(color_key, default_color);
+            let width = style.get_float(width_key, 4.0);
+            for (from, to) in ranges {
+                let start = self.value_to_angle(from, constraints);
+                let end = self.value_to_angle(to, constraints);
+                if end > start {
+                    context.render_circle_arc_outline(center_x, center_y, radius - width / 2.0, width,
+                                                      color, start, end, 64)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     unsafe fn render_needle(&self, center_x: f32, center_y: f32, length: f32,
                             needle_angle: f32, color: (f32, f32, f32),
-                            screen_w: f32, screen_h: f32, shader_program: u32) {
+                            context: &GraphicsContext, shader_program: u32) {
         gl::UseProgram(shader_program);
+        context.set_projection_uniform(shader_program);
         
         let cos_a = needle_angle.cos();
         let sin_a = needle_angle.sin();
@@ -179,25 +247,15 @@ void main() {
         let tip2_x = tip_x - tip_perp_cos;
         let tip2_y = tip_y - tip_perp_sin;
         
-        // Convert to normalized coordinates
-        let base1_nx = base1_x / screen_w * 2.0 - 1.0;
-        let base1_ny = 1.0 - base1_y / screen_h * 2.0;
-        let base2_nx = base2_x / screen_w * 2.0 - 1.0;
-        let base2_ny = 1.0 - base2_y / screen_h * 2.0;
-        let tip1_nx = tip1_x / screen_w * 2.0 - 1.0;
-        let tip1_ny = 1.0 - tip1_y / screen_h * 2.0;
-        let tip2_nx = tip2_x / screen_w * 2.0 - 1.0;
-        let tip2_ny = 1.0 - tip2_y / screen_h * 2.0;
-        
         let vertices = [
             // First triangle: base1 -> base2 -> tip1
-            base1_nx, base1_ny, color.0, color.1, color.2,
-            base2_nx, base2_ny, color.0, color.1, color.2,
-            tip1_nx, tip1_ny, color.0, color.1, color.2,
+            base1_x, base1_y, color.0, color.1, color.2,
+            base2_x, base2_y, color.0, color.1, color.2,
+            tip1_x, tip1_y, color.0, color.1, color.2,
             // Second triangle: base2 -> tip2 -> tip1
-            base2_nx, base2_ny, color.0, color.1, color.2,
-            tip2_nx, tip2_ny, color.0, color.1, color.2,
-            tip1_nx, tip1_ny, color.0, color.1, color.2,
+            base2_x, base2_y, color.0, color.1, color.2,
+            tip2_x, tip2_y, color.0, color.1, color.2,
+            tip1_x, tip1_y, color.0, color.1, color.2,
         ];
         
         // Reuse the persistent VBO — no glGenBuffers/glDeleteBuffers per frame.
@@ -246,6 +304,7 @@ impl Indicator for NeedleIndicator {
         
         // Render decorators before the needle so the needle draws on top
         self.base.render_decorators(bounds, style, context)?;
+        self.render_zones(&value.constraints, center_x, center_y, available_radius, style, context)?;
         
         unsafe {
             // Enable blending for smooth rendering
@@ -267,8 +326,7 @@ impl Indicator for NeedleIndicator {
             // Render the needle
             self.render_needle(center_x, center_y, actual_needle_length, 
                                needle_angle, needle_color,
-                               context.width as f32, context.height as f32,
-                               shader_program);
+                               context, shader_program);
         }
         
         Ok(())
@@ -320,9 +378,10 @@ impl NeedleGaugeMarksDecorator {
             let vertex_shader_source = b"
 attribute vec2 position;
 attribute vec3 color;
+uniform mat4 projection;
 varying vec3 v_color;
 void main() {
-    gl_Position = vec4(position, 0.0, 1.0);
+    gl_Position = projection * vec4(position, 0.0, 1.0);
     v_color = color;
 }
 \0";
@@ -374,7 +433,7 @@ void main() {
 
     /// Calculate vertices for a single mark (returns 30 floats: 6 vertices × 5 components each)
     fn calculate_mark_vertices(&self, center_x: f32, center_y: f32, radius: f32, angle: f32,
-                               screen_w: f32, screen_h: f32, color: (f32, f32, f32)) -> [f32; 30] {
+                               color: (f32, f32, f32)) -> [f32; 30] {
         let cos_a = angle.cos();
         let sin_a = angle.sin();
 
@@ -401,26 +460,16 @@ void main() {
         let outer2_x = outer_x - perp_cos;
         let outer2_y = outer_y - perp_sin;
 
-        // Convert to normalized coordinates (-1 to 1)
-        let inner1_nx = inner1_x / screen_w * 2.0 - 1.0;
-        let inner1_ny = 1.0 - inner1_y / screen_h * 2.0;
-        let inner2_nx = inner2_x / screen_w * 2.0 - 1.0;
-        let inner2_ny = 1.0 - inner2_y / screen_h * 2.0;
-        let outer1_nx = outer1_x / screen_w * 2.0 - 1.0;
-        let outer1_ny = 1.0 - outer1_y / screen_h * 2.0;
-        let outer2_nx = outer2_x / screen_w * 2.0 - 1.0;
-        let outer2_ny = 1.0 - outer2_y / screen_h * 2.0;
-
         // Return vertices for two triangles forming a rectangle
         [
             // First triangle: inner1 -> inner2 -> outer1
-            inner1_nx, inner1_ny, color.0, color.1, color.2,
-            inner2_nx, inner2_ny, color.0, color.1, color.2,
-            outer1_nx, outer1_ny, color.0, color.1, color.2,
+            inner1_x, inner1_y, color.0, color.1, color.2,
+            inner2_x, inner2_y, color.0, color.1, color.2,
+            outer1_x, outer1_y, color.0, color.1, color.2,
             // Second triangle: inner2 -> outer2 -> outer1
-            inner2_nx, inner2_ny, color.0, color.1, color.2,
-            outer2_nx, outer2_ny, color.0, color.1, color.2,
-            outer1_nx, outer1_ny, color.0, color.1, color.2,
+            inner2_x, inner2_y, color.0, color.1, color.2,
+            outer2_x, outer2_y, color.0, color.1, color.2,
+            outer1_x, outer1_y, color.0, color.1, color.2,
         ]
     }
 
@@ -468,6 +517,7 @@ impl Decorator for NeedleGaugeMarksDecorator {
             // Get cached shader program
             let shader_program = Self::get_mark_shader();
             gl::UseProgram(shader_program);
+            context.set_projection_uniform(shader_program);
 
             // Calculate center and use configured radius
             let center_x = bounds.x + bounds.width / 2.0;
@@ -497,8 +547,7 @@ impl Decorator for NeedleGaugeMarksDecorator {
 
                 // Calculate mark vertices
                 let mark_vertices = self.calculate_mark_vertices(
-                    center_x, center_y, radius, normalized_angle,
-                    context.width as f32, context.height as f32, color
+                    center_x, center_y, radius, normalized_angle, color
                 );
                 
                 // Append to batch buffer
@@ -597,28 +646,45 @@ impl Decorator for NeedleGaugeMarkLabelsDecorator {
             // Use the actual rendered text dimensions for accurate centering.
             // The estimated approach (len * font_size * 0.6) was systematically wrong
             // for variable-width fonts and labels of different lengths.
-            let (text_width, text_height) = context.calculate_text_dimensions_with_font(
+            let text_height = context.calculate_text_height_with_font(
                 label,
                 1.0,
                 &self.font_path,
                 self.font_size,
             )?;
 
-            let centered_x = label_x - text_width / 2.0;
             let centered_y = label_y - text_height / 2.0;
 
             // Render the text label using the graphics context
-            context.render_text_with_font(
+            context.render_text_aligned(
                 label,
-                centered_x,
+                label_x,
                 centered_y,
-                1.0, // scale
-                color,
-                &self.font_path,
-                self.font_size,
+                TextAlign::Center,
+                &TextStyle::new(&self.font_path, self.font_size, color),
             )?;
         }
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_value_to_arc_angle() {
+        // 270° sweep from 7 o'clock to 1 o'clock, 10-16V like the voltage gauge
+        let gauge = NeedleIndicator::new(-225.0f32.to_radians(), 45.0f32.to_radians(), 0.8, 8.0, 1.0, GAUGE_NEEDLE_COLOR);
+        let constraints = ValueConstraints::analog_with_thresholds(10.0, 16.0, None, None, Some(15.0), Some(15.5));
+
+        // 15V is 5/6 of the range: 225° into the sweep
+        let angle = gauge.value_to_angle(15.0, &constraints);
+        assert!((angle - 0.0f32.to_radians()).abs() < 1e-5);
+        assert!((gauge.value_to_angle(10.0, &constraints) - (-225.0f32).to_radians()).abs() < 1e-5);
+        assert!((gauge.value_to_angle(20.0, &constraints) - 45.0f32.to_radians()).abs() < 1e-5);
+
+        assert_eq!(NeedleIndicator::warning_zones(&constraints), vec![(15.0, 15.5)]);
+        assert_eq!(NeedleIndicator::critical_zones(&constraints), vec![(15.5, 16.0)]);
+    }
+}
@@ -0,0 +1,216 @@
+#![allow(dead_code)]
+use crate::indicators::indicator::{Indicator, IndicatorBounds, IndicatorBase};
+use crate::indicators::decorator::Decorator;
+use crate::graphics::context::{opaque, GraphicsContext};
+use crate::graphics::ui_style::*;
+use crate::hardware::sensor_value::{SensorValue, ValueData};
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+/// Number currently rolling on the wheels: from `from` towards `to`, starting at `started`
+struct RollState {
+    from: u64,
+    to: u64,
+    started: Option<Instant>,
+}
+
+/// Mechanical-style odometer: every digit sits in its own cell and scrolls vertically
+/// to the new value when the number changes
+pub struct OdometerIndicator {
+    base: IndicatorBase,
+    /// Number of digit cells (the value wraps like a real odometer)
+    digits: usize,
+    roll_duration: Duration,
+    // render() takes &self, the roll animation has to live behind a RefCell
+    state: RefCell<RollState>,
+}
+
+impl OdometerIndicator {
+    pub fn new(digits: usize) -> Self {
+        Self {
+            base: IndicatorBase::new(),
+            digits: digits.max(1),
+            roll_duration: Duration::from_millis(400),
+            state: RefCell::new(RollState { from: 0, to: 0, started: None }),
+        }
+    }
+
+    /// Set how long a digit takes to roll to its new value
+    pub fn with_roll_duration(mut self, duration: Duration) -> Self {
+        self.roll_duration = duration;
+        self
+    }
+
+    /// Value as shown on the wheels: whole units, wrapped to the number of digits
+    fn wrap_value(&self, value: f32) -> u64 {
+        let modulus = 10u64.saturating_pow(self.digits as u32);
+        (value.max(0.0) as u64) % modulus
+    }
+
+    /// Digit of `number` for a cell, counting cells from the left
+    fn digit_at(&self, number: u64, cell: usize) -> u64 {
+        let power = (self.digits - 1 - cell) as u32;
+        // Cells past the 20 digits a u64 can hold are leading zeros
+        10u64.checked_pow(power).map_or(0, |place| (number / place) % 10)
+    }
+
+    /// Fraction of the roll completed at `now` (1.0 when idle)
+    fn progress_at(&self, now: Instant) -> f32 {
+        let state = self.state.borrow();
+        match state.started {
+            Some(started) if !self.roll_duration.is_zero() => {
+                (now.duration_since(started).as_secs_f32() / self.roll_duration.as_secs_f32()).min(1.0)
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Start a new roll when the displayed number changes. A change during a roll restarts
+    /// it from the previous target so the wheels never jump backwards.
+    fn update_target(&self, target: u64, now: Instant) {
+        let mut state = self.state.borrow_mut();
+        if state.to != target {
+            state.from = state.to;
+            state.to = target;
+            state.started = Some(now);
+        }
+    }
+
+    /// Fractional wheel position (0.0 ..< 10.0) of a digit rolling from `old` to `new`.
+    /// Wheels only turn forwards, so 9 -> 0 passes through 9.5 rather than back through 5.
+    fn digit_roll_position(old: u64, new: u64, progress: f32) -> f32 {
+        let distance = (new as i64 - old as i64).rem_euclid(10) as f32;
+        (old as f32 + distance * progress.clamp(0.0, 1.0)) % 10.0
+    }
+}
+
+impl Default for OdometerIndicator {
+    fn default() -> Self {
+        Self::new(6)
+    }
+}
+
+impl Indicator for OdometerIndicator {
+    fn with_decorators(mut self, decorators: Vec<Box<dyn Decorator>>) -> Self {
+        self.base.decorators = decorators;
+        self
+    }
+
+    fn render(
+        &self,
+        value: &SensorValue,
+        bounds: IndicatorBounds,
+        style: &UIStyle,
+        context: &mut GraphicsContext,
+    ) -> Result<(), String> {
+        self.base.render_decorators(bounds, style, context)?;
+
+        let numeric_value = match &value.value {
+            ValueData::Analog(v) => *v,
+            ValueData::Integer(i) => *i as f32,
+            ValueData::Percentage(p) => *p,
+            _ => return Ok(()), // Skip non-numeric values
+        };
+
+        let now = Instant::now();
+        self.update_target(self.wrap_value(numeric_value), now);
+        let progress = self.progress_at(now);
+        let (from, to) = {
+            let state = self.state.borrow();
+            (state.from, state.to)
+        };
+
+        let font_path = style.get_string(DIGITAL_DISPLAY_FONT, DIGITAL_DISPLAY_FONT_PATH);
+        let font_size = style.get_integer(DIGITAL_DISPLAY_FONT_SIZE, 32);
+        let scale = style.get_float(DIGITAL_DISPLAY_SCALE, 2.0);
+        let color = style.get_color(DIGITAL_DISPLAY_ACTIVE_COLOR, (1.0, 0.65, 0.0));
+        let border_enabled = style.get_bool(DIGITAL_DISPLAY_BORDER_ENABLED, true);
+        let border_color = style.get_color(DIGITAL_DISPLAY_BORDER_COLOR, (1.0, 1.0, 1.0));
+        let border_width = style.get_float(DIGITAL_DISPLAY_BORDER_WIDTH, 2.0);
+
+        // DSEG digits share one width and height, measure once
+        let digit_width = context.calculate_text_width_with_font("8", scale, &font_path, font_size)?;
+        let digit_height = context.calculate_text_height_with_font("8", scale, &font_path, font_size)?;
+        let cell_width = bounds.width / self.digits as f32;
+        let cell_height = bounds.height;
+
+        for cell in 0..self.digits {
+            let cell_x = bounds.x + cell as f32 * cell_width;
+            let position = Self::digit_roll_position(self.digit_at(from, cell), self.digit_at(to, cell), progress);
+            let lower = position.floor();
+            let fraction = position - lower;
+
+            let text_x = cell_x + (cell_width - digit_width) / 2.0;
+            let text_y = bounds.y + (cell_height - digit_height) / 2.0;
+
+            // Outgoing digit scrolls up out of the cell while the next one scrolls in from below
+            context.push_clip(cell_x, bounds.y, cell_width, cell_height);
+            let lower_digit = lower as u32 % 10;
+            let result = context.render_text_with_font(&lower_digit.to_string(), text_x, text_y - fraction * cell_height,
+                                                       scale, color, &font_path, font_size)
+                .and_then(|_| if fraction > 0.0 {
+                    context.render_text_with_font(&((lower_digit + 1) % 10).to_string(), text_x,
+                                                  text_y + (1.0 - fraction) * cell_height,
+                                                  scale, color, &font_path, font_size)
+                } else {
+                    Ok(())
+                });
+            context.pop_clip();
+            result?;
+
+            if border_enabled {
+                context.render_rectangle(cell_x, bounds.y, cell_width, cell_height,
+                                         opaque(border_color), false, border_width, 0.0)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn indicator_type(&self) -> &'static str {
+        "OdometerIndicator"
+    }
+
+    fn supports_value_type(&self, value: &ValueData) -> bool {
+        matches!(value, ValueData::Analog(_) | ValueData::Integer(_) | ValueData::Percentage(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digit_mid_roll_position() {
+        assert_eq!(OdometerIndicator::digit_roll_position(3, 4, 0.5), 3.5);
+        assert_eq!(OdometerIndicator::digit_roll_position(3, 4, 0.0), 3.0);
+        assert_eq!(OdometerIndicator::digit_roll_position(3, 4, 1.0), 4.0);
+        // 9 -> 0 rolls forward through the wrap
+        assert_eq!(OdometerIndicator::digit_roll_position(9, 0, 0.5), 9.5);
+        assert_eq!(OdometerIndicator::digit_roll_position(8, 1, 0.5), 9.5);
+        assert_eq!(OdometerIndicator::digit_roll_position(5, 5, 0.5), 5.0);
+    }
+
+    #[test]
+    fn test_digit_at_wide_odometer() {
+        let odometer = OdometerIndicator::new(24);
+        assert_eq!(odometer.digit_at(123456, 0), 0);
+        assert_eq!(odometer.digit_at(123456, 23), 6);
+        assert_eq!(odometer.digit_at(u64::MAX, 4), 1);
+    }
+
+    #[test]
+    fn test_roll_progress_uses_elapsed_time() {
+        let odometer = OdometerIndicator::new(4).with_roll_duration(Duration::from_millis(200));
+        let start = Instant::now();
+        assert_eq!(odometer.progress_at(start), 1.0); // Idle
+
+        odometer.update_target(odometer.wrap_value(12349.0), start);
+        assert_eq!(odometer.state.borrow().to, 2349);
+        assert!((odometer.progress_at(start + Duration::from_millis(50)) - 0.25).abs() < 1e-6);
+        assert_eq!(odometer.progress_at(start + Duration::from_millis(500)), 1.0);
+
+        assert_eq!(odometer.digit_at(2349, 0), 2);
+        assert_eq!(odometer.digit_at(2349, 3), 9);
+    }
+}
@@ -0,0 +1,127 @@
+#![allow(dead_code)]
+use crate::indicators::indicator::{Indicator, IndicatorBounds, IndicatorBase};
+use crate::indicators::decorator::Decorator;
+use crate::graphics::context::GraphicsContext;
+use crate::graphics::ui_style::*;
+use crate::hardware::sensor_history::SensorHistory;
+use crate::hardware::sensor_value::{SensorValue, ValueData};
+
+/// Tiny trend line of a sensor's recent history, auto-scaled to the min/max in the window.
+/// Draws from SensorManager history via render_with_history; plain render only draws decorators.
+pub struct SparklineIndicator {
+    base: IndicatorBase,
+}
+
+impl SparklineIndicator {
+    pub fn new() -> Self {
+        Self { base: IndicatorBase::new() }
+    }
+
+    /// Map values to 0.0 (window minimum) ..= 1.0 (window maximum).
+    /// A flat series sits in the middle.
+    fn normalized_y_positions(values: &[f32]) -> Vec<f32> {
+        let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let range = max - min;
+        values.iter()
+            .map(|&v| if range > 0.0 { (v - min) / range } else { 0.5 })
+            .collect()
+    }
+
+    /// Screen points for the samples. X slots are spaced for a full history buffer with the
+    /// newest sample at the right edge, so a partly filled buffer grows in from the right.
+    fn trace_points(values: &[f32], capacity: usize, bounds: IndicatorBounds) -> Vec<(f32, f32)> {
+        let slots = capacity.max(values.len()).max(2);
+        let step = bounds.width / (slots - 1) as f32;
+        let first_slot = slots - values.len();
+        Self::normalized_y_positions(values).into_iter()
+            .enumerate()
+            .map(|(i, y)| (
+                bounds.x + (first_slot + i) as f32 * step,
+                bounds.y + bounds.height * (1.0 - y), // Screen Y grows downwards
+            ))
+            .collect()
+    }
+}
+
+impl Default for SparklineIndicator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for SparklineIndicator {
+    fn with_decorators(mut self, decorators: Vec<Box<dyn Decorator>>) -> Self {
+        self.base.decorators = decorators;
+        self
+    }
+
+    fn render(
+        &self,
+        value: &SensorValue,
+        bounds: IndicatorBounds,
+        style: &UIStyle,
+        context: &mut GraphicsContext,
+    ) -> Result<(), String> {
+        self.render_with_history(value, None, bounds, style, context)
+    }
+
+    fn render_with_history(
+        &self,
+        _value: &SensorValue,
+        history: Option<&SensorHistory>,
+        bounds: IndicatorBounds,
+        style: &UIStyle,
+        context: &mut GraphicsContext,
+    ) -> Result<(), String> {
+        self.base.render_decorators(bounds, style, context)?;
+
+        let Some(history) = history else {
+            return Ok(());
+        };
+        let values: Vec<f32> = history.iter().map(|&(_, v)| v).collect();
+        if values.len() < 2 {
+            return Ok(()); // Need two samples for a line segment
+        }
+
+        let color = style.get_color(SPARKLINE_COLOR, (1.0, 0.49, 0.0));
+        let thickness = style.get_float(SPARKLINE_THICKNESS, 2.0);
+        let points = Self::trace_points(&values, history.capacity(), bounds);
+        for segment in points.windows(2) {
+            let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+            context.draw_line(x1, y1, x2, y2, thickness, color)?;
+        }
+
+        Ok(())
+    }
+
+    fn indicator_type(&self) -> &'static str {
+        "SparklineIndicator"
+    }
+
+    fn supports_value_type(&self, value: &ValueData) -> bool {
+        matches!(value, ValueData::Analog(_) | ValueData::Integer(_) | ValueData::Percentage(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_series_maps_to_normalized_y() {
+        let y = SparklineIndicator::normalized_y_positions(&[10.0, 15.0, 20.0, 12.5]);
+        assert_eq!(y, vec![0.0, 0.5, 1.0, 0.25]);
+
+        // Flat series: centred rather than dividing by zero
+        assert_eq!(SparklineIndicator::normalized_y_positions(&[3.0, 3.0]), vec![0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_partial_history_is_right_aligned() {
+        let bounds = IndicatorBounds::new(100.0, 50.0, 40.0, 20.0);
+        // 3 of 5 slots filled: samples take the last three x positions
+        let points = SparklineIndicator::trace_points(&[0.0, 2.0, 1.0], 5, bounds);
+        assert_eq!(points, vec![(120.0, 70.0), (130.0, 50.0), (140.0, 60.0)]);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::indicators::indicator::{Indicator, IndicatorBounds, IndicatorBase};
 use crate::indicators::decorator::{Decorator, DecoratorAlignmentH};
-use crate::graphics::context::GraphicsContext;
+use crate::graphics::context::{opaque, GraphicsContext};
 use crate::graphics::ui_style::*;
 use crate::hardware::sensor_value::{SensorValue, ValueData};
 use std::sync::Once;
@@ -12,6 +12,30 @@ static VERTICAL_BAR_SHADER_INIT: Once = Once::new();
 static mut VERTICAL_BAR_VBO: u32 = 0;
 static VERTICAL_BAR_VBO_INIT: Once = Once::new();
 
+/// Color of a filled bar segment based on its position along the bar and the value constraints.
+/// Shared by the vertical and horizontal bar indicators.
+pub(crate) fn bar_segment_color(segment_index: usize, segments: usize, normalized_value: f32, value: &SensorValue, style: &UIStyle) -> (f32, f32, f32) {
+    let segment_position = (segment_index + 1) as f32 / segments as f32;
+    
+    // Check if we're in warning or critical range based on constraints
+    if let Some(critical_high) = value.constraints.critical_high {
+        let normalized_critical = (critical_high - value.constraints.min_value) / (value.constraints.max_value - value.constraints.min_value);
+        if segment_position <= normalized_critical && normalized_value >= normalized_critical {
+            return style.get_color("bar_critical_color", (1.0, 0.0, 0.0)); // Red for critical
+        }
+    }
+    
+    if let Some(warning_high) = value.constraints.warning_high {
+        let normalized_warning = (warning_high - value.constraints.min_value) / (value.constraints.max_value - value.constraints.min_value);
+        if segment_position <= normalized_warning && normalized_value >= normalized_warning {
+            return style.get_color("bar_warning_color", (1.0, 0.65, 0.0)); // Orange for warning
+        }
+    }
+    
+    // Default normal color
+    style.get_color("bar_normal_color", (0.0, 1.0, 0.0)) // Green for normal
+}
+
 /// Vertical bar indicator that fills from bottom to top
 pub struct VerticalBarIndicator {
     base: IndicatorBase,
@@ -43,38 +67,16 @@ impl VerticalBarIndicator {
         (clamped_value * self.segments as f32).round() as usize
     }
     
-    /// Get segment color based on normalized position and value constraints
-    fn get_segment_color(&self, segment_index: usize, normalized_value: f32, value: &SensorValue, style: &UIStyle) -> (f32, f32, f32) {
-        let segment_position = (segment_index + 1) as f32 / self.segments as f32;
-        
-        // Check if we're in warning or critical range based on constraints
-        if let Some(critical_high) = value.constraints.critical_high {
-            let normalized_critical = (critical_high - value.constraints.min_value) / (value.constraints.max_value - value.constraints.min_value);
-            if segment_position <= normalized_critical && normalized_value >= normalized_critical {
-                return style.get_color("bar_critical_color", (1.0, 0.0, 0.0)); // Red for critical
-            }
-        }
-        
-        if let Some(warning_high) = value.constraints.warning_high {
-            let normalized_warning = (warning_high - value.constraints.min_value) / (value.constraints.max_value - value.constraints.min_value);
-            if segment_position <= normalized_warning && normalized_value >= normalized_warning {
-                return style.get_color("bar_warning_color", (1.0, 0.65, 0.0)); // Orange for warning
-            }
-        }
-        
-        // Default normal color
-        style.get_color("bar_normal_color", (0.0, 1.0, 0.0)) // Green for normal
-    }
-
     /// Get cached shader program for batch rendering
     unsafe fn get_vertical_bar_shader() -> u32 {
         VERTICAL_BAR_SHADER_INIT.call_once(|| {
             let vertex_shader_source = b"
 attribute vec2 position;
 attribute vec3 color;
+uniform mat4 projection;
 varying vec3 v_color;
 void main() {
-    gl_Position = vec4(position, 0.0, 1.0);
+    gl_Position = projection * vec4(position, 0.0, 1.0);
     v_color = color;
 }
 \0";
@@ -124,23 +126,23 @@ void main() {
 
     /// Calculate vertices for a single rectangle segment (returns 30 floats: 6 vertices × 5 components each)
     fn calculate_segment_vertices(&self, x: f32, y: f32, width: f32, height: f32, 
-                                 color: (f32, f32, f32), screen_w: f32, screen_h: f32) -> [f32; 30] {
-        // Convert screen coordinates to normalized coordinates (-1 to 1)
-        let x1_norm = x / screen_w * 2.0 - 1.0;
-        let y1_norm = 1.0 - y / screen_h * 2.0;
-        let x2_norm = (x + width) / screen_w * 2.0 - 1.0;
-        let y2_norm = 1.0 - (y + height) / screen_h * 2.0;
+                                 color: (f32, f32, f32)) -> [f32; 30] {
+        // Screen coordinates; the shader's projection maps them to the panel
+        let x1 = x;
+        let y1 = y;
+        let x2 = x + width;
+        let y2 = y + height;
 
         // Return vertices for two triangles forming a rectangle
         [
             // First triangle: top-left -> top-right -> bottom-left
-            x1_norm, y1_norm, color.0, color.1, color.2,
-            x2_norm, y1_norm, color.0, color.1, color.2,
-            x1_norm, y2_norm, color.0, color.1, color.2,
+            x1, y1, color.0, color.1, color.2,
+            x2, y1, color.0, color.1, color.2,
+            x1, y2, color.0, color.1, color.2,
             // Second triangle: top-right -> bottom-right -> bottom-left  
-            x2_norm, y1_norm, color.0, color.1, color.2,
-            x2_norm, y2_norm, color.0, color.1, color.2,
-            x1_norm, y2_norm, color.0, color.1, color.2,
+            x2, y1, color.0, color.1, color.2,
+            x2, y2, color.0, color.1, color.2,
+            x1, y2, color.0, color.1, color.2,
         ]
     }
 
@@ -208,12 +210,12 @@ impl Indicator for VerticalBarIndicator {
         if background_enabled {
             let bg_color = style.get_color(BAR_BACKGROUND_COLOR, (1.0, 0.65, 0.0)); // Default amber
             context.render_rectangle(bounds.x, bounds.y, bounds.width, bounds.height,
-                bg_color, true, 1.0,
+                opaque(bg_color), true, 1.0,
                 style.get_float(BAR_CORNER_RADIUS, 8.0))?;
         } else if border_enabled {
             let border_color = style.get_color(BAR_BORDER_COLOR, (1.0, 0.65, 0.0)); // Default amber
             context.render_rectangle(bounds.x, bounds.y, bounds.width, bounds.height,
-                border_color, false, border_width,
+                opaque(border_color), false, border_width,
                 style.get_float(BAR_CORNER_RADIUS, 8.0))?;
         }
 
@@ -258,6 +260,7 @@ impl Indicator for VerticalBarIndicator {
             // Get cached shader program for batch rendering
             let shader_program = Self::get_vertical_bar_shader();
             gl::UseProgram(shader_program);
+            context.set_projection_uniform(shader_program);
 
             // Build all vertices in a single buffer for batch rendering
             let mut all_vertices = Vec::with_capacity(self.segments * 6 * 5); // 6 vertices per segment, 5 floats per vertex
@@ -274,15 +277,14 @@ impl Indicator for VerticalBarIndicator {
                 
                 // Get appropriate color
                 let color = if is_filled {
-                    context.apply_brightness(self.get_segment_color(segment_index_from_bottom, normalized_value, value, style))
+                    context.apply_brightness(bar_segment_color(segment_index_from_bottom, self.segments, normalized_value, value, style))
                 } else {
                     context.apply_brightness(empty_color)
                 };
                 
                 // Calculate segment vertices and append to batch buffer
                 let segment_vertices = self.calculate_segment_vertices(
-                    segments_start_x, segment_y, segment_width, segment_height, 
-                    color, context.width as f32, context.height as f32
+                    segments_start_x, segment_y, segment_width, segment_height, color
                 );
                 
                 all_vertices.extend_from_slice(&segment_vertices);
@@ -389,7 +391,7 @@ impl Decorator for VerticalBarScaleDecorator {
                 let y = bounds.y + i as f32 * segment_height + segment_height / 2.0;
                 context.render_rectangle(base_x_pos, y - self.marks_thickness / 2.0,
                                          self.marks_width, self.marks_thickness,
-                                         marks_color, true, 1.0, 0.0)?;
+                                         opaque(marks_color), true, 1.0, 0.0)?;
             }
             // context.render_rectangle(base_x_pos, bounds.y + segment_height / 2.0,
             //                          self.marks_thickness,
@@ -418,4 +420,37 @@ impl Decorator for VerticalBarScaleDecorator {
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::context::Rotation;
+    use crate::hardware::sensor_value::SensorValue;
+
+    #[test]
+    fn test_segments_follow_display_rotation() {
+        // Skipped where there is no EGL with the surfaceless platform (see new_headless)
+        let mut context = match GraphicsContext::new_headless(32, 16) {
+            Ok(context) => context,
+            Err(e) => {
+                eprintln!("Skipping headless bar rotation test: {}", e);
+                return;
+            }
+        };
+        context.set_rotation(Rotation::Deg180);
+
+        let mut style = UIStyle::new();
+        style.set(BAR_BACKGROUND_ENABLED, UIStyleValue::Boolean(false));
+        style.set(BAR_BORDER_ENABLED, UIStyleValue::Boolean(false));
+        let bar = VerticalBarIndicator::new(1).with_segment_gap(0.0);
+        let value = SensorValue::analog(100.0, 0.0, 100.0, "%", "Level", "level");
+
+        context.clear();
+        bar.render(&value, IndicatorBounds::new(0.0, 0.0, 8.0, 16.0), &style, &mut context).unwrap();
+        let pixels = context.read_pixels();
+        let lit = |x: usize| pixels[(8 * 32 + x) * 4..(8 * 32 + x) * 4 + 3].iter().any(|&c| c > 0);
+        // The logical left edge lands on the right of the panel when it is upside down
+        assert!(lit(28));
+        assert!(!lit(4));
+    }
+}
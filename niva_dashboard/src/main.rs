@@ -6,19 +6,28 @@ mod indicators;
 mod indicator_builders;
 mod alerts;
 mod util;
+mod error;
 
 use crate::test::run_test::run_test;
-use crate::graphics::context::GraphicsContext;
+use crate::graphics::context::{GraphicsContext, Mirror, Rotation};
 use crate::page_framework::page_manager::PageManager;
 use crate::page_framework::events::UIEvent;
-use crate::page_framework::input::{InputSource, PhysicalButtonInput, KeyboardInput};
+use crate::page_framework::input::{InputSource, PhysicalButtonInput, KeyboardInput, RotaryEncoderInput,
+                                      ENCODER_PIN_A, ENCODER_PIN_B, ENCODER_BUTTON_PIN};
+use crate::page_framework::touch_input::TouchInput;
 use crate::hardware::sensor_manager::{SensorManager, SensorDigitalInputChain, SensorAnalogInputChain};
 use crate::hardware::hw_providers::*;
 use crate::hardware::digital_signal_processing::DigitalSignalDebouncer;
-use crate::hardware::analog_signal_processing::AnalogSignalProcessorMovingAverage;
-use crate::hardware::sensors::{GenericDigitalSensor, GenericAnalogSensor, SpeedSensor, EngineTemperatureSensor};
+use crate::hardware::analog_signal_processing::{AnalogSignalProcessorMovingAverage, AnalogSignalProcessorMedian,
+                                               AnalogSignalProcessorSlewLimiter};
+use crate::hardware::sensors::{GenericDigitalSensor, GenericAnalogSensor, SpeedSensor, TachometerSensor,
+                               EngineTemperatureSensor, EngineHoursSensor, DerivedSensor};
 use crate::hardware::sensor_value::ValueConstraints;
+use crate::hardware::sensor_logger::SensorLogger;
+use crate::hardware::distance_accumulator::DistanceAccumulator;
+use crate::hardware::fuel_consumption::FuelConsumption;
 use crate::util::adc_data_provider::{ADCDataProvider, ADCFrame};
+use crate::util::can_data_provider::{CanDataProvider, load_can_config};
 use crate::util::logging::init_logging;
 use crate::util::ups_monitor::UpsMonitor;
 use rppal::gpio::Level;
@@ -26,8 +35,16 @@ use std::env;
 use std::thread;
 use std::time::Duration;
 
-fn setup_context() -> GraphicsContext {
-    let context = GraphicsContext::new_dashboard("Niva Dashboard").expect("Failed to create graphics context");
+fn setup_context(display_mode: Option<(usize, usize)>, rotation: Rotation, mirror: Mirror, vsync: bool) -> GraphicsContext {
+    let mut context = match display_mode {
+        Some((connector, mode)) => GraphicsContext::new_with_mode("Niva Dashboard", connector, mode),
+        None => GraphicsContext::new_dashboard("Niva Dashboard"),
+    }.expect("Failed to create graphics context");
+    context.set_rotation(rotation);
+    context.set_mirror(mirror);
+    if let Err(e) = context.set_vsync(vsync) {
+        log::warn!("{}", e);
+    }
 
     // Hide mouse cursor for dashboard application
     if let Err(e) = context.hide_cursor() {
@@ -150,10 +167,9 @@ fn setup_self_test_sensors() -> SensorManager {
 
     // Tachometer sensor (active high, pulse-based)
     let tacho_chain = SensorDigitalInputChain::new(
-        Box::new(TestDigitalDataProvider::new(HWInput::HwTacho)),
-        vec![Box::new(DigitalSignalDebouncer::new(3, std::time::Duration::from_millis(10)))],
-        Box::new(GenericDigitalSensor::new("HwTacho".to_string(), "ТАХОМЕТР".to_string(),
-                                           Level::High, ValueConstraints::digital_default())),
+        Box::new(TestPulseDataProvider::new(HWInput::HwTacho)),
+        vec![], // No signal processors - TachometerSensor handles pulse processing internally
+        Box::new(TachometerSensor::new()),
     );
     mgr.add_digital_sensor_chain(tacho_chain);
 
@@ -189,7 +205,8 @@ fn setup_self_test_sensors() -> SensorManager {
     // Oil pressure sensor (0-8 kgf/cm² range)
     let oil_pressure_chain = SensorAnalogInputChain::new(
         Box::new(TestAnalogDataProvider::new(HWInput::HwOilPress)),
-        vec![Box::new(AnalogSignalProcessorMovingAverage::new(10))],
+        vec![Box::new(AnalogSignalProcessorMedian::new(5)),
+             Box::new(AnalogSignalProcessorMovingAverage::new(10))],
         Box::new(GenericAnalogSensor::new("HwOilPress".to_string(), "ДАВЛ МАСЛА".to_string(), "кгс/см²".to_string(),
                                           ValueConstraints::analog_with_thresholds(0.0, 8.0, Some(0.5), Some(1.0), Some(7.0), Some(8.0)), 0.01)), // 0-8 kgf/cm² pressure range
     );
@@ -321,13 +338,32 @@ fn setup_sensors(adc: Option<ADCFrame>) -> SensorManager {
     );
     mgr.add_digital_sensor_chain(speed_chain);
 
-    let tacho_chain = SensorDigitalInputChain::new(
-        Box::new(ADCChannelProvider::new(HWInput::HwTacho, 4, frame.clone())),  // TACHO pulse count
-        vec![Box::new(DigitalSignalDebouncer::new(3, std::time::Duration::from_millis(10)))],
-        Box::new(GenericDigitalSensor::new("HwTacho".to_string(), "ТАХОМЕТР".to_string(),
-                                           Level::High, ValueConstraints::digital_default())),
+    let home = env::var("HOME").unwrap_or_else(|_| "/home/user".to_string());
+    let data_dir = format!("{home}/Work/Niva_Dashboard_Rpi/Niva_dashboard_rpi/Data");
+    match DistanceAccumulator::with_persistence(format!("{data_dir}/odometer.txt")) {
+        Ok(distance) => mgr.set_distance_accumulator(Some(distance)),
+        Err(e) => {
+            // Without the odometer the trip page still gets an average speed, just not a persisted one
+            log::warn!("Odometer unavailable: {}", e);
+            mgr.add_derived_sensor(DerivedSensor::trip_average_speed());
+        }
+    }
+    // VAZ-2121 tank: 42 L
+    match FuelConsumption::new(42.0).with_persistence(format!("{data_dir}/trip_fuel.txt")) {
+        Ok(fuel) => mgr.set_fuel_consumption(Some(fuel)),
+        Err(e) => log::warn!("Fuel consumption unavailable: {}", e),
+    }
+
+    let tacho_chain = SensorAnalogInputChain::new(
+        Box::new(ADCChannelProvider::new(HWInput::HwTacho, 4, frame.clone())),  // TACHO pulses per frame
+        vec![], // Averaged inside the sensor; integer filters would round idle counts down to 0
+        Box::new(TachometerSensor::new()),
     );
-    mgr.add_digital_sensor_chain(tacho_chain);
+    mgr.add_analog_sensor_chain(tacho_chain);
+    match EngineHoursSensor::new(HWInput::HwTacho, 0.0).with_persistence(format!("{data_dir}/engine_hours.txt")) {
+        Ok(engine_hours) => mgr.set_engine_hours_sensor(Some(engine_hours)),
+        Err(e) => log::warn!("Engine hours unavailable: {}", e),
+    }
 
     let turn_signal_chain = SensorDigitalInputChain::new(
         Box::new(ADCChannelProvider::new(HWInput::HwTurnSignal, 12, frame.clone())),  // D6
@@ -343,8 +379,11 @@ fn setup_sensors(adc: Option<ADCFrame>) -> SensorManager {
     let voltage_12v_chain = SensorAnalogInputChain::new(
         Box::new(ADCChannelProvider::new(HWInput::Hw12v, 3, frame.clone())),
         vec![Box::new(AnalogSignalProcessorMovingAverage::new(10))],
+        // Charging voltage routinely sits right at the thresholds - hysteresis keeps the
+        // warning from flickering on and off
         Box::new(GenericAnalogSensor::new("Hw12v".to_string(), "БОРТ СЕТЬ".to_string(), "В".to_string(),
-                                          ValueConstraints::analog_with_thresholds(0.0, 20.0, Some(11.0), Some(13.0), Some(14.7), Some(15.0)), 0.02)),
+                                          ValueConstraints::analog_with_thresholds(0.0, 20.0, Some(11.0), Some(13.0), Some(14.7), Some(15.0))
+                                              .with_hysteresis(0.2), 0.02)),
     );
     mgr.add_analog_sensor_chain(voltage_12v_chain);
 
@@ -353,20 +392,31 @@ fn setup_sensors(adc: Option<ADCFrame>) -> SensorManager {
         vec![Box::new(AnalogSignalProcessorMovingAverage::new(15))],
         Box::new(GenericAnalogSensor::new("HwFuelLvl".to_string(), "УРОВ ТОПЛ".to_string(), "%".to_string(),
                                           ValueConstraints::analog_with_thresholds(0.0, 100.0, Some(10.0), Some(20.0), None, None), 0.1)),
-    );
+    )
+    // Tank level moves over minutes; once a second is plenty, and the 15-sample average
+    // then smooths slosh over 15 s instead of a quarter second
+    .with_min_update_interval(Duration::from_secs(1));
     mgr.add_analog_sensor_chain(fuel_level_chain);
 
     let oil_pressure_chain = SensorAnalogInputChain::new(
         Box::new(ADCChannelProvider::new(HWInput::HwOilPress, 0, frame.clone())),
-        vec![Box::new(AnalogSignalProcessorMovingAverage::new(10))],
+        // Median first to drop ignition-noise spikes before they get averaged in
+        vec![Box::new(AnalogSignalProcessorMedian::new(5)),
+             Box::new(AnalogSignalProcessorMovingAverage::new(10))],
+        // Deadband keeps ADC jitter from twitching the gauge above zero with the engine off
         Box::new(GenericAnalogSensor::new("HwOilPress".to_string(), "ДАВЛ МАСЛА".to_string(), "кгс/см²".to_string(),
-                                          ValueConstraints::analog_with_thresholds(0.0, 8.0, Some(0.5), Some(1.0), Some(7.0), Some(8.0)), 0.01)),
+                                          ValueConstraints::analog_with_thresholds(0.0, 8.0, Some(0.5), Some(1.0), Some(7.0), Some(8.0))
+                                              .with_deadband(0.1), 0.01)),
     );
     mgr.add_analog_sensor_chain(oil_pressure_chain);
 
     let temperature_chain = SensorAnalogInputChain::new(
         Box::new(ADCChannelProvider::new(HWInput::HwEngineCoolantTemp, 2, frame.clone())),
-        vec![Box::new(AnalogSignalProcessorMovingAverage::new(20))],
+        // Coolant can't physically change faster than a few degrees per second, so a
+        // bigger jump is a wiring fault and shouldn't reach the gauge. 80 counts/s is
+        // ~10°C/s at EngineTemperatureSensor's scale; sensors are polled once per frame.
+        vec![Box::new(AnalogSignalProcessorSlewLimiter::new(80.0, Duration::from_millis(16))),
+             Box::new(AnalogSignalProcessorMovingAverage::new(20))],
         Box::new(EngineTemperatureSensor::new()),
     );
     mgr.add_analog_sensor_chain(temperature_chain);
@@ -430,10 +480,17 @@ fn setup_input_sources(button_sensors: SensorManager) -> Vec<Box<dyn InputSource
 }
 
 fn setup_ui_style() -> graphics::ui_style::UIStyle {
-    let ui_style = graphics::ui_style::UIStyle::new();
-    // ui_style.read_from_file("/etc/niva_dashboard/ui_style.json").unwrap_or_else(|e| {
-    //     print!("Warning: Failed to read UI style config: {}\r\n", e);
-    // });
+    use graphics::ui_style::{UIStyle, UI_STYLE_FILE_PATH};
+    if !std::path::Path::new(UI_STYLE_FILE_PATH).exists() {
+        return UIStyle::new();
+    }
+    let ui_style = UIStyle::from_file(UI_STYLE_FILE_PATH).unwrap_or_else(|e| {
+        log::warn!("Failed to read UI style config {}, using defaults: {}", UI_STYLE_FILE_PATH, e);
+        UIStyle::new()
+    });
+    for warning in ui_style.validate() {
+        log::warn!("{}: {}", UI_STYLE_FILE_PATH, warning);
+    }
     ui_style
 }
 
@@ -447,12 +504,69 @@ fn setup_adc_data_provider() -> Result<ADCDataProvider, std::string::String> {
     Ok(provider)
 }
 
+fn setup_can_data_provider(config_path: &str) -> Result<CanDataProvider, String> {
+    let mut provider = CanDataProvider::new(load_can_config(config_path)?);
+    provider.run()?;
+    Ok(provider)
+}
+
+// Inputs the CAN config covers are read from the bus instead of the ADC, e.g. RPM and
+// coolant temperature from a swapped-in ECU. The CAN values are already in physical
+// units, so they get plain sensors in place of the ADC chains' conversions.
+fn setup_can_sensors(mgr: &mut SensorManager, can: &CanDataProvider) {
+    for signal in can.signals() {
+        mgr.remove_sensor_chains(signal.input);
+        let id = format!("{:?}", signal.input);
+        let provider = CanSignalProvider::new(signal.input, signal.scale, can.values());
+        if signal.digital {
+            mgr.add_digital_sensor_chain(SensorDigitalInputChain::new(
+                Box::new(provider),
+                vec![],
+                Box::new(GenericDigitalSensor::new(id, signal.label.clone(), Level::High,
+                                                   ValueConstraints::digital_warning())),
+            ));
+        } else {
+            mgr.add_analog_sensor_chain(SensorAnalogInputChain::new(
+                Box::new(provider.with_offset(signal.min)),
+                vec![],
+                Box::new(GenericAnalogSensor::new(id, signal.label.clone(), signal.unit.clone(),
+                                                  ValueConstraints::analog(signal.min, signal.max), signal.scale)
+                    .with_value_offset(signal.min)),
+            ));
+        }
+    }
+    log::info!("✓ {} sensor(s) read from CAN", can.signals().len());
+}
+
 fn setup_ups_monitor() -> Result<UpsMonitor, String> {
     let mut monitor = UpsMonitor::new();
     monitor.run()?;
     Ok(monitor)
 }
 
+fn parse_display_mode(value: &str) -> Option<(usize, usize)> {
+    let (connector, mode) = value.split_once(':')?;
+    Some((connector.parse().ok()?, mode.parse().ok()?))
+}
+
+fn print_display_modes() {
+    match GraphicsContext::list_modes() {
+        Ok(connectors) => {
+            for (connector_index, modes) in connectors.iter().enumerate() {
+                if modes.is_empty() {
+                    log::info!("Connector {}: not connected", connector_index);
+                    continue;
+                }
+                log::info!("Connector {}:", connector_index);
+                for (mode_index, (width, height, refresh)) in modes.iter().enumerate() {
+                    log::info!("  {}:{}  {}x{}@{}Hz", connector_index, mode_index, width, height, refresh);
+                }
+            }
+        }
+        Err(e) => log::error!("Failed to list display modes: {}", e),
+    }
+}
+
 fn show_help() {
     log::info!("Available test modes:");
     log::info!("1. Basic OpenGL triangle test");
@@ -477,8 +591,15 @@ fn main() -> std::process::ExitCode {
     let args: Vec<String> = env::args().collect();
 
     log::info!("Niva Dashboard - Raspberry Pi Version (KMS/DRM Backend)");
-    log::info!("Usage: cargo run -- [help|test={{basic|gltext|dashboard|needle|gpio|sensors|digital|ind_zero_pos|ind_middle_pos|ind_max_pos}}]");
-
+    log::info!("Usage: cargo run -- [help|list_modes|display_mode=<connector>:<mode>|rotation={{0|90|180|270}}|mirror={{none|h|v|hv}}|vsync={{on|off}}|sensor_log=<dir>|layout=<file>|can=<config>|dump_style=<file>|test={{basic|gltext|dashboard|needle|gpio|sensors|digital|ind_zero_pos|ind_middle_pos|ind_max_pos}}]");
+
+    let mut sensor_log_dir: Option<String> = None;
+    let mut layout_path: Option<String> = None;
+    let mut can_config_path: Option<String> = None;
+    let mut display_mode: Option<(usize, usize)> = None;
+    let mut rotation = Rotation::Deg0;
+    let mut mirror = Mirror::default();
+    let mut vsync = true;
     for arg in args {
         let parm = arg.split("=").collect::<Vec<&str>>();
         if parm.len() == 2 {
@@ -487,6 +608,52 @@ fn main() -> std::process::ExitCode {
                     run_test(parm[1]);
                     return std::process::ExitCode::SUCCESS;
                 }
+                "sensor_log" => {
+                    sensor_log_dir = Some(parm[1].to_string());
+                }
+                "layout" => {
+                    layout_path = Some(parm[1].to_string());
+                }
+                "can" => {
+                    can_config_path = Some(parm[1].to_string());
+                }
+                "dump_style" => {
+                    return match graphics::ui_style::UIStyle::dump_defaults_to_file(parm[1]) {
+                        Ok(()) => {
+                            log::info!("Default UI style written to {}", parm[1]);
+                            std::process::ExitCode::SUCCESS
+                        }
+                        Err(e) => {
+                            log::error!("Failed to write default UI style to {}: {}", parm[1], e);
+                            std::process::ExitCode::FAILURE
+                        }
+                    };
+                }
+                "rotation" => {
+                    match parm[1].parse().map_err(|_| parm[1].to_string()).and_then(Rotation::from_degrees) {
+                        Ok(r) => rotation = r,
+                        Err(e) => log::warn!("Invalid rotation: {}", e),
+                    }
+                }
+                "mirror" => {
+                    match Mirror::from_name(parm[1]) {
+                        Ok(m) => mirror = m,
+                        Err(e) => log::warn!("Invalid mirror: {}", e),
+                    }
+                }
+                "vsync" => {
+                    match parm[1] {
+                        "on" => vsync = true,
+                        "off" => vsync = false,
+                        _ => log::warn!("Invalid vsync '{}', expected on or off", parm[1]),
+                    }
+                }
+                "display_mode" => {
+                    display_mode = parse_display_mode(parm[1]);
+                    if display_mode.is_none() {
+                        log::warn!("Invalid display_mode '{}', expected <connector>:<mode>", parm[1]);
+                    }
+                }
                 _ => {
                     log::warn!("Unknown argument: {}", parm[0]);
                 }
@@ -497,6 +664,10 @@ fn main() -> std::process::ExitCode {
                     show_help();
                     return std::process::ExitCode::SUCCESS;
                 }
+                "list_modes" => {
+                    print_display_modes();
+                    return std::process::ExitCode::SUCCESS;
+                }
                 _ => {
                     log::warn!("Unknown argument: {}", arg);
                 }
@@ -530,6 +701,18 @@ fn main() -> std::process::ExitCode {
             None
         }
     };
+    // Kept alive like adc; its Drop impl stops the reader thread
+    let can = can_config_path.and_then(|path| match setup_can_data_provider(&path) {
+        Ok(provider) => {
+            log::info!("✓ CAN data provider started");
+            Some(provider)
+        }
+        Err(e) => {
+            log::warn!("CAN data provider unavailable: {}", e);
+            None
+        }
+    });
+
     // Obtain a frame handle before moving adc into setup_sensors
     let adc_frame = adc.as_ref().map(|p| p.frame());
 
@@ -542,19 +725,43 @@ fn main() -> std::process::ExitCode {
     //     });
     // }
 
-    let context = setup_context();
+    let context = setup_context(display_mode, rotation, mirror, vsync);
     let self_test_sensors = setup_self_test_sensors();
     let button_sensors = setup_button_sensors(adc_frame.clone());
     let input_sources = setup_input_sources(button_sensors);
     // Keep a handle for the ADC diagnostic terminal page before the sensor-chain setup
     // consumes the rest of adc_frame's clones.
     let adc_frame_for_diag = adc_frame.clone();
-    let sensors = setup_sensors(adc_frame);
+    let mut sensors = setup_sensors(adc_frame);
+    if let Some(can) = &can {
+        setup_can_sensors(&mut sensors, can);
+    }
+    if let Some(dir) = sensor_log_dir {
+        log::info!("Logging sensor values to {}", dir);
+        sensors.set_logger(Some(SensorLogger::new(dir)));
+    }
     let ui_style = setup_ui_style();
+    let long_press_time = Duration::from_secs_f32(
+        ui_style.get_float(graphics::ui_style::PAGE_LONG_PRESS_TIME, 0.8).max(0.0));
 
+    // Touch coordinates are mapped to the logical screen, read before the context moves
+    let (screen_width, screen_height) = (context.width as f32, context.height as f32);
     let mut mgr = PageManager::new(context, self_test_sensors, ui_style, input_sources, ups_reading, adc_frame_for_diag);
+    if let Some(path) = &layout_path {
+        mgr.set_layout_file(path);
+    }
 
     mgr.setup().expect("Failed to setup page manager");
+    // Created even if the file doesn't exist yet, so a style dropped in over SSH gets picked up
+    mgr.watch_style_file(graphics::ui_style::UI_STYLE_FILE_PATH);
+    match RotaryEncoderInput::new(ENCODER_PIN_A, ENCODER_PIN_B, ENCODER_BUTTON_PIN, mgr.get_smart_event_sender()) {
+        Ok(encoder) => mgr.add_input_source(Box::new(encoder.with_long_press_threshold(long_press_time))),
+        Err(e) => log::info!("Rotary encoder unavailable: {}", e),
+    }
+    match TouchInput::detect(screen_width, screen_height, rotation, mirror) {
+        Some(touch) => mgr.set_touch_input(touch),
+        None => log::info!("No touchscreen found"),
+    }
 
     // Setup timer to switch self-tests sensor manager to functional set after 5 seconds
     let sender = mgr.get_smart_event_sender();
@@ -0,0 +1,161 @@
+#![allow(dead_code)]
+use std::time::{Duration, Instant};
+
+use crate::graphics::context::GraphicsContext;
+use crate::graphics::ui_style::*;
+use crate::hardware::sensor_manager::SensorValuesSnapshot;
+use crate::page_framework::events::{EventReceiver, SmartEventSender, UIEvent};
+use crate::page_framework::page_manager::{Page, PageBase, PageButton, ButtonPosition, MAIN_PAGE_ID};
+use crate::util::rtc_clock::{self, ClockTime, RtcClock};
+
+// Often enough that the seconds don't visibly lag, without an I2C transfer every frame
+const CLOCK_REFRESH_INTERVAL: Duration = Duration::from_millis(200);
+const DATE_GAP: f32 = 20.0;
+const SOURCE_MARGIN: f32 = 20.0;
+
+pub struct ClockPage {
+    base: PageBase,
+    event_receiver: EventReceiver,
+    smart_event_sender: SmartEventSender,
+    // None when the RTC couldn't be opened; the system clock is shown instead
+    rtc: Option<RtcClock>,
+    time: ClockTime,
+    // Whether `time` came from the RTC, as opposed to the system clock
+    from_rtc: bool,
+    last_refresh: Instant,
+}
+
+impl ClockPage {
+    pub fn new(id: u32, smart_event_sender: SmartEventSender, event_receiver: EventReceiver) -> Self {
+        let rtc = match RtcClock::open() {
+            Ok(rtc) => Some(rtc),
+            Err(e) => {
+                log::warn!("RTC unavailable, showing system time: {}", e);
+                None
+            }
+        };
+        let from_rtc = rtc.is_some();
+        let mut clock_page = ClockPage {
+            base: PageBase::new(id, "Clock".to_string()),
+            event_receiver,
+            smart_event_sender,
+            rtc,
+            time: rtc_clock::system_time(),
+            from_rtc,
+            last_refresh: Instant::now(),
+        };
+        clock_page.setup_buttons();
+        clock_page.refresh();
+        clock_page
+    }
+
+    fn setup_buttons(&mut self) {
+        let buttons = vec![
+            PageButton::new(ButtonPosition::Right4, "ВОЗВ".into(), Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::SwitchToPage(MAIN_PAGE_ID))
+            }) as Box<dyn FnMut()>),
+        ];
+        self.base.set_buttons(buttons);
+    }
+
+    fn refresh(&mut self) {
+        let rtc_time = self.rtc.as_mut().map(|rtc| rtc.read());
+        (self.time, self.from_rtc) = match rtc_time {
+            Some(Ok(time)) => (time, true),
+            Some(Err(e)) => {
+                // Logged once per failure streak, not every refresh
+                if self.from_rtc {
+                    log::warn!("{}", e);
+                }
+                (rtc_clock::system_time(), false)
+            }
+            None => (rtc_clock::system_time(), false),
+        };
+        self.last_refresh = Instant::now();
+    }
+}
+
+impl Page for ClockPage {
+    fn id(&self) -> u32 {
+        self.base.id()
+    }
+
+    fn name(&self) -> &str {
+        self.base.name()
+    }
+
+    fn set_buttons(&mut self, buttons: Vec<PageButton<Box<dyn FnMut()>>>) {
+        self.base.set_buttons(buttons);
+    }
+
+    fn buttons(&self) -> &Vec<PageButton<Box<dyn FnMut()>>> {
+        self.base.buttons()
+    }
+
+    fn button_by_position(&self, pos: ButtonPosition) -> Option<&PageButton<Box<dyn FnMut()>>> {
+        self.base.button_by_position(pos)
+    }
+
+    fn button_by_position_mut(&mut self, pos: ButtonPosition) -> Option<&mut PageButton<Box<dyn FnMut()>>> {
+        self.base.button_by_position_mut(pos)
+    }
+
+    fn render(&self, context: &mut GraphicsContext, _sensors: &SensorValuesSnapshot, ui_style: &UIStyle) -> Result<(), String> {
+        let digit_font = ui_style.get_string(DIGITAL_DISPLAY_FONT, DIGITAL_DISPLAY_FONT_PATH);
+        let digit_font_size = ui_style.get_integer(DIGITAL_DISPLAY_FONT_SIZE, 32);
+        let digit_scale = ui_style.get_float(DIGITAL_DISPLAY_SCALE, 2.0);
+        let digit_color = ui_style.get_color(TEXT_PRIMARY_COLOR, (1.0, 1.0, 1.0));
+        let text_font = ui_style.get_string(TEXT_SECONDARY_FONT, DEFAULT_GLOBAL_FONT_PATH);
+        let text_font_size = ui_style.get_integer(TEXT_SECONDARY_FONT_SIZE, 10);
+        let text_color = ui_style.get_color(TEXT_SECONDARY_COLOR, (0.8, 0.8, 0.8));
+
+        let time = self.time.time_text();
+        let date = self.time.date_text();
+        let time_width = context.calculate_text_width_with_font(&time, digit_scale, &digit_font, digit_font_size)?;
+        let time_height = context.calculate_text_height_with_font(&time, digit_scale, &digit_font, digit_font_size)?;
+        let date_width = context.calculate_text_width_with_font(&date, 2.0, &text_font, text_font_size)?;
+        let date_height = context.calculate_text_height_with_font(&date, 2.0, &text_font, text_font_size)?;
+
+        let center_x = context.width as f32 / 2.0;
+        let top = (context.height as f32 - time_height - DATE_GAP - date_height) / 2.0;
+        context.render_text_with_font(&time, center_x - time_width / 2.0, top, digit_scale, digit_color,
+                                      &digit_font, digit_font_size)?;
+        context.render_text_with_font(&date, center_x - date_width / 2.0, top + time_height + DATE_GAP, 2.0,
+                                      text_color, &text_font, text_font_size)?;
+
+        if !self.from_rtc {
+            // The system clock may be off by however long the Pi was powered down
+            let marker_width = context.calculate_text_width_with_font("sys", 1.0, &text_font, text_font_size)?;
+            let marker_height = context.calculate_text_height_with_font("sys", 1.0, &text_font, text_font_size)?;
+            context.render_text_with_font("sys", center_x + time_width / 2.0 - marker_width,
+                                          top - marker_height - SOURCE_MARGIN, 1.0,
+                                          ui_style.get_color(TEXT_WARNING_COLOR, (1.0, 1.0, 0.0)),
+                                          &text_font, text_font_size)?;
+        }
+
+        Ok(())
+    }
+
+    fn on_enter(&mut self) -> Result<(), String> {
+        self.refresh();
+        Ok(())
+    }
+
+    fn on_exit(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn on_button(&mut self, _button: char) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn process_events(&mut self) {
+        // No page-scoped events handled; drain them so they don't pile up
+        while self.event_receiver.try_recv().is_ok() {}
+
+        if self.last_refresh.elapsed() >= CLOCK_REFRESH_INTERVAL {
+            self.refresh();
+        }
+    }
+}
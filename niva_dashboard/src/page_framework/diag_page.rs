@@ -1,11 +1,15 @@
 #![allow(dead_code)]
+use std::cell::Cell;
 use std::time::{Duration, Instant};
 
-use crate::graphics::context::GraphicsContext;
+use crate::graphics::context::{GraphicsContext, TextStyle};
 use crate::graphics::ui_style::*;
 use crate::page_framework::events::{EventReceiver, SmartEventSender, UIEvent};
-use crate::page_framework::page_manager::{Page, PageBase, PageButton, ButtonPosition, MAIN_PAGE_ID, ADC_TERM_PAGE_ID, LOG_PAGE_ID};
-use crate::hardware::sensor_manager::SensorManager;
+use crate::page_framework::page_manager::{Page, PageBase, PageButton, ButtonPosition, MAIN_PAGE_ID, ADC_TERM_PAGE_ID, LOG_PAGE_ID,
+                                          SETTINGS_PAGE_ID, OSC_PAGE_ID};
+use crate::hardware::sensor_manager::{SensorReading, SensorValuesSnapshot};
+use crate::hardware::sensor_value::SensorFault;
+use std::collections::HashMap;
 use crate::util::diagnostics::{self, ThrottleStatus};
 
 // Build identity, embedded at compile time by build.rs. Useful because this project
@@ -20,6 +24,23 @@ const DIAG_REFRESH_INTERVAL: Duration = Duration::from_secs(3);
 const CONTENT_X_MARGIN: f32 = 40.0;
 const TITLE_Y: f32 = 20.0;
 const TITLE_CONTENT_GAP: f32 = 10.0;
+// Lines moved per press of the scroll buttons
+const SCROLL_STEP: usize = 5;
+
+/// Threshold state of a sensor row, picks its text color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowStatus {
+    Normal,
+    Warning,
+    Critical,
+}
+
+/// One line of the sensor table, already formatted for a monospace font
+#[derive(Debug, Clone, PartialEq)]
+struct SensorRow {
+    text: String,
+    status: RowStatus,
+}
 
 pub struct DiagPage {
     base: PageBase,
@@ -34,6 +55,9 @@ pub struct DiagPage {
     core_voltage: Option<f32>,
     arm_clock_mhz: Option<u32>,
     last_refresh: Instant,
+    // First visible content line, and its largest useful value as of the last render
+    scroll: usize,
+    max_scroll: Cell<usize>,
 }
 
 impl DiagPage {
@@ -49,6 +73,8 @@ impl DiagPage {
             core_voltage: None,
             arm_clock_mhz: None,
             last_refresh: Instant::now(),
+            scroll: 0,
+            max_scroll: Cell::new(0),
         };
 
         diag_page.setup_buttons();
@@ -67,6 +93,26 @@ impl DiagPage {
                 let sender = self.smart_event_sender.clone();
                 move || sender.send(UIEvent::SwitchToPage(LOG_PAGE_ID))
             }) as Box<dyn FnMut()>),
+            PageButton::new(ButtonPosition::Left3, "ОСЦ".into(), Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::SwitchToPage(OSC_PAGE_ID))
+            }) as Box<dyn FnMut()>),
+            PageButton::new(ButtonPosition::Left4, "НАСТР".into(), Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::SwitchToPage(SETTINGS_PAGE_ID))
+            }) as Box<dyn FnMut()>),
+            PageButton::new(ButtonPosition::Right1, "ВВЕРХ".into(), Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::ButtonPressed("scroll_up".into()))
+            }) as Box<dyn FnMut()>),
+            PageButton::new(ButtonPosition::Right2, "ВНИЗ".into(), Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::ButtonPressed("scroll_down".into()))
+            }) as Box<dyn FnMut()>),
+            PageButton::new(ButtonPosition::Right3, "ФИЛЬТР".into(), Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::ToggleSensorFiltering)
+            }) as Box<dyn FnMut()>),
             PageButton::new(ButtonPosition::Right4, "ВОЗВ".into(), Box::new({
                 let sender = self.smart_event_sender.clone();
                 move || sender.send(UIEvent::SwitchToPage(MAIN_PAGE_ID))
@@ -88,6 +134,48 @@ impl DiagPage {
     fn na() -> String {
         "н/д".to_string()
     }
+
+    /// Table header matching the column widths of sensor_rows
+    fn sensor_table_header() -> String {
+        format!("  {:<16} {:>7} {:>9} {:<8} {}", "ДАТЧИК", "ВХОД", "ЗНАЧ", "ЕД", "СОСТ")
+    }
+
+    /// One row per registered sensor, sorted by id: raw input (GPIO level for digital
+    /// inputs), processed value, unit and fault/stale/threshold state
+    fn sensor_rows(snapshot: &HashMap<String, SensorReading>) -> Vec<SensorRow> {
+        let mut ids: Vec<&String> = snapshot.keys().collect();
+        ids.sort();
+        ids.into_iter().map(|id| {
+            let reading = &snapshot[id];
+            let raw = match reading.raw {
+                Some(level) if reading.digital => if level > 0.0 { "HIGH".to_string() } else { "LOW".to_string() },
+                Some(raw) => format!("{:.0}", raw),
+                None => "-".to_string(),
+            };
+            let value = reading.value.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string());
+            let state = match (reading.fault, reading.value) {
+                (Some(SensorFault::OpenCircuit), _) => "ОБРЫВ",
+                (Some(SensorFault::Stuck), _) => "ЗАВИС",
+                (None, None) => "НЕТ ДАННЫХ",
+                _ if reading.stale => "УСТАР",
+                _ if reading.critical => "КРИТ",
+                _ if reading.warning => "ПРЕДУПР",
+                _ => "НОРМА",
+            };
+            let status = if reading.critical || reading.fault.is_some() {
+                RowStatus::Critical
+            } else if reading.warning || reading.stale {
+                RowStatus::Warning
+            } else {
+                RowStatus::Normal
+            };
+            let name: String = id.chars().take(16).collect();
+            SensorRow {
+                text: format!("  {:<16} {:>7} {:>9} {:<8} {}", name, raw, value, reading.unit, state),
+                status,
+            }
+        }).collect()
+    }
 }
 
 impl Page for DiagPage {
@@ -103,12 +191,14 @@ impl Page for DiagPage {
         self.base.set_buttons(buttons);
     }
 
-    fn render(&self, context: &mut GraphicsContext, _sensor_manager: &SensorManager, ui_style: &UIStyle) -> Result<(), String> {
+    fn render(&self, context: &mut GraphicsContext, sensors: &SensorValuesSnapshot, ui_style: &UIStyle) -> Result<(), String> {
         let title_font = ui_style.get_string(TEXT_PRIMARY_FONT, DEFAULT_GLOBAL_FONT_PATH);
         let title_font_size = ui_style.get_integer(TEXT_PRIMARY_FONT_SIZE, 24);
         let title_color = ui_style.get_color(TERMINAL_TEXT_COLOR, (1.0, 1.0, 1.0));
         let header_color = title_color;
         let text_color = ui_style.get_color(TERMINAL_TEXT_COLOR, (0.8, 0.8, 0.8));
+        let warning_color = ui_style.get_color(TEXT_WARNING_COLOR, (1.0, 1.0, 0.0));
+        let critical_color = ui_style.get_color(TEXT_ERROR_COLOR, (1.0, 0.0, 0.0));
 
         let font = ui_style.get_string(TEXT_MONOSPACE_FONT, TERMINAL_FONT_PATH);
         let font_size = ui_style.get_integer(TEXT_MONOSPACE_FONT_SIZE, 16);
@@ -122,7 +212,7 @@ impl Page for DiagPage {
         let mut y = TITLE_Y + title_height + TITLE_CONTENT_GAP;
 
         let disk = self.disk_usage_mb.map(|(total, avail)| format!("{} / {} МБ своб.", avail, total)).unwrap_or_else(Self::na);
-        let lines: [(String, bool); 12] = [
+        let system_lines: [(String, bool); 12] = [
             ("СБОРКА:".to_string(), true),
             (format!("  commit {}  {}", GIT_HASH, BUILD_TIME), false),
             (String::new(), false),
@@ -137,10 +227,36 @@ impl Page for DiagPage {
             (format!("  диск:    {}", disk), false),
         ];
 
-        for (text, is_header) in &lines {
+        // Sensors first - that's what the page is opened for - then the system details
+        let sensors_header = if sensors.filtering_enabled() {
+            ("ДАТЧИКИ:".to_string(), header_color)
+        } else {
+            ("ДАТЧИКИ: без фильтров".to_string(), warning_color)
+        };
+        let mut lines: Vec<(String, (f32, f32, f32))> = vec![
+            sensors_header,
+            (Self::sensor_table_header(), header_color),
+        ];
+        lines.extend(Self::sensor_rows(sensors.readings()).into_iter().map(|row| {
+            let color = match row.status {
+                RowStatus::Normal => text_color,
+                RowStatus::Warning => warning_color,
+                RowStatus::Critical => critical_color,
+            };
+            (row.text, color)
+        }));
+        lines.push((String::new(), text_color));
+        lines.extend(system_lines.into_iter()
+            .map(|(text, is_header)| (text, if is_header { header_color } else { text_color })));
+
+        let bottom = context.height as f32 - line_height;
+        let visible = (((bottom - y) / line_height).max(1.0)) as usize;
+        self.max_scroll.set(lines.len().saturating_sub(visible));
+        let first = self.scroll.min(self.max_scroll.get());
+        let max_width = context.width as f32 - CONTENT_X_MARGIN * 2.0;
+        for (text, color) in lines.iter().skip(first).take(visible) {
             if !text.is_empty() {
-                let color = if *is_header { header_color } else { text_color };
-                context.render_text_with_font(text, CONTENT_X_MARGIN, y, 1.0, color, &font, font_size)?;
+                context.render_text_truncated(text, CONTENT_X_MARGIN, y, max_width, &TextStyle::new(&font, font_size, *color))?;
             }
             y += line_height;
         }
@@ -150,6 +266,7 @@ impl Page for DiagPage {
 
     fn on_enter(&mut self) -> Result<(), String> {
         self.refresh();
+        self.scroll = 0;
         Ok(())
     }
 
@@ -162,7 +279,21 @@ impl Page for DiagPage {
     }
 
     fn process_events(&mut self) {
-        while self.event_receiver.try_recv().is_ok() {}
+        while let Ok(event) = self.event_receiver.try_recv() {
+            let scroll_down = match event {
+                UIEvent::ButtonPressed(action) if action == "scroll_up" => false,
+                UIEvent::ButtonPressed(action) if action == "scroll_down" => true,
+                // Dragging the table up reveals the rows below
+                UIEvent::SwipeUp => true,
+                UIEvent::SwipeDown => false,
+                _ => continue,
+            };
+            self.scroll = if scroll_down {
+                (self.scroll + SCROLL_STEP).min(self.max_scroll.get())
+            } else {
+                self.scroll.saturating_sub(SCROLL_STEP)
+            };
+        }
 
         if self.last_refresh.elapsed() >= DIAG_REFRESH_INTERVAL {
             self.refresh();
@@ -181,3 +312,45 @@ impl Page for DiagPage {
         self.base.button_by_position_mut(pos)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hardware::hw_providers::{HWInput, TestDigitalDataProvider, TestAnalogDataProvider};
+    use crate::hardware::sensor_manager::{SensorDigitalInputChain, SensorAnalogInputChain, SensorManager};
+    use crate::hardware::sensors::{GenericDigitalSensor, GenericAnalogSensor};
+    use crate::hardware::sensor_value::ValueConstraints;
+    use rppal::gpio::Level;
+
+    #[test]
+    fn test_one_row_per_sensor() {
+        let mut manager = SensorManager::new();
+        manager.add_digital_sensor_chain(SensorDigitalInputChain::new(
+            Box::new(TestDigitalDataProvider::new(HWInput::HwHighBeam)),
+            vec![],
+            Box::new(GenericDigitalSensor::new("high_beam".to_string(), "High Beam".to_string(),
+                                              Level::High, ValueConstraints::digital_default())),
+        ));
+        manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+            Box::new(TestAnalogDataProvider::new(HWInput::HwEngineCoolantTemp)),
+            vec![],
+            Box::new(GenericAnalogSensor::new("temp".to_string(), "Temp".to_string(), "°C".to_string(),
+                                             ValueConstraints::analog_with_thresholds(0.0, 130.0, None, None, Some(-1.0), None),
+                                             0.1)),
+        ));
+
+        // Listed before the first read, without values
+        let rows = DiagPage::sensor_rows(&manager.snapshot());
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|row| row.text.contains("НЕТ ДАННЫХ")));
+
+        manager.read_all_sensors().unwrap();
+        let rows = DiagPage::sensor_rows(&manager.snapshot());
+        assert_eq!(rows.len(), 2);
+        // Sorted by id; the digital input shows its GPIO level
+        assert!(rows[0].text.contains("high_beam"));
+        assert!(rows[0].text.contains("HIGH") || rows[0].text.contains("LOW"));
+        assert!(rows[1].text.contains("temp") && rows[1].text.contains("°C"));
+        assert_eq!(rows[1].status, RowStatus::Warning); // Any reading is above the -1.0 warning threshold
+    }
+}
@@ -8,14 +8,26 @@ pub enum UIEvent {
     BrightnessUp,
     BrightnessDown,
     SetBrightness(f32),
+    // Switch between the day and night style themes
+    ToggleTheme,
+    // Settings page controls
+    SetBacklightEnabled(bool),  // false = leave the backlight at full and dim in software
+    SetBloomEnabled(bool),
+    SaveSettings,               // Write brightness, theme, backlight and bloom to the style file
     
     // Page navigation
     SwitchToPage(u32),
+    NavigateBack,       // Return to the page shown before the last switch
 
     // Main page events
     NextIndicatorSet,
     PreviousIndicatorSet,
 
+    // Zero the trip distance, time and fuel
+    ResetTrip,
+    // Show raw sensor values, bypassing the signal processors, or back to filtered
+    ToggleSensorFiltering,
+
     // System events
     Shutdown,
     Restart,
@@ -23,6 +35,20 @@ pub enum UIEvent {
     // Custom button events
     ButtonPressed(String), // Generic button with custom action name
 
+    // Rotary encoder events
+    EncoderClockwise,
+    EncoderCounterClockwise,
+    EncoderPress,
+
+    // Touchscreen events, in screen coordinates
+    Touch { x: f32, y: f32 },
+    TouchRelease { x: f32, y: f32 },
+    // Fast drags, named by the direction the finger moved
+    SwipeLeft,
+    SwipeRight,
+    SwipeUp,
+    SwipeDown,
+
     // Diagnostic page events
     ShowSensorInfo,
     ShowECUInfo,
@@ -193,15 +219,31 @@ impl SmartEventSender {
             UIEvent::BrightnessUp |
             UIEvent::BrightnessDown |
             UIEvent::SetBrightness(_) |
+            UIEvent::ToggleTheme |
+            UIEvent::SetBacklightEnabled(_) |
+            UIEvent::SetBloomEnabled(_) |
+            UIEvent::SaveSettings |
             UIEvent::SwitchToPage(_) |
+            UIEvent::NavigateBack |
             UIEvent::SuppressAlerts |
-            UIEvent::SwitchSensorSet => {
+            UIEvent::SwitchSensorSet |
+            UIEvent::ResetTrip |
+            UIEvent::ToggleSensorFiltering |
+            UIEvent::EncoderClockwise |
+            UIEvent::EncoderCounterClockwise |
+            UIEvent::EncoderPress |
+            UIEvent::Touch { .. } |
+            UIEvent::TouchRelease { .. } |
+            UIEvent::SwipeLeft |
+            UIEvent::SwipeRight => {
                 self.global_sender.send(event);
             }
             // Page-specific events go to current page
             UIEvent::NextIndicatorSet |
             UIEvent::PreviousIndicatorSet |
             UIEvent::ButtonPressed(_) |
+            UIEvent::SwipeUp |
+            UIEvent::SwipeDown |
             UIEvent::ShowSensorInfo |
             UIEvent::ShowECUInfo |
             UIEvent::ShowOSCInfo |
@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+use std::time::{Duration, Instant};
+
+/// Brightness change requested by the idle manager
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IdleChange {
+    Dim,
+    /// Back to the brightness in effect when the screen was dimmed
+    Restore(f32),
+}
+
+/// Dims the screen after a period without input or vehicle motion, and brings the
+/// brightness back on the next sign of activity
+pub struct IdleManager {
+    last_activity: Instant,
+    // Brightness before dimming; Some while dimmed
+    dimmed_from: Option<f32>,
+}
+
+impl IdleManager {
+    pub fn new(now: Instant) -> Self {
+        IdleManager { last_activity: now, dimmed_from: None }
+    }
+
+    pub fn is_dimmed(&self) -> bool {
+        self.dimmed_from.is_some()
+    }
+
+    /// Input event or motion: restart the idle timer, undimming if needed
+    pub fn activity(&mut self, now: Instant) -> Option<IdleChange> {
+        self.last_activity = now;
+        self.dimmed_from.take().map(IdleChange::Restore)
+    }
+
+    /// Check the idle timer; a zero timeout never dims
+    pub fn poll(&mut self, now: Instant, timeout: Duration, current_brightness: f32) -> Option<IdleChange> {
+        if self.is_dimmed() || timeout.is_zero() || now.saturating_duration_since(self.last_activity) < timeout {
+            return None;
+        }
+        self.dimmed_from = Some(current_brightness);
+        Some(IdleChange::Dim)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inactivity_dims_and_activity_restores() {
+        let start = Instant::now();
+        let timeout = Duration::from_secs(60);
+        let mut idle = IdleManager::new(start);
+
+        assert_eq!(idle.poll(start + Duration::from_secs(59), timeout, 0.8), None);
+        assert_eq!(idle.poll(start + Duration::from_secs(60), timeout, 0.8), Some(IdleChange::Dim));
+        assert!(idle.is_dimmed());
+        // Dims once, not every frame
+        assert_eq!(idle.poll(start + Duration::from_secs(61), timeout, 0.1), None);
+
+        assert_eq!(idle.activity(start + Duration::from_secs(70)), Some(IdleChange::Restore(0.8)));
+        assert!(!idle.is_dimmed());
+        assert_eq!(idle.activity(start + Duration::from_secs(71)), None);
+        // The timer restarted with the activity
+        assert_eq!(idle.poll(start + Duration::from_secs(120), timeout, 0.8), None);
+        assert_eq!(idle.poll(start + Duration::from_secs(131), timeout, 0.8), Some(IdleChange::Dim));
+
+        // Zero timeout turns dimming off
+        let mut idle = IdleManager::new(start);
+        assert_eq!(idle.poll(start + Duration::from_secs(3600), Duration::ZERO, 0.8), None);
+    }
+}
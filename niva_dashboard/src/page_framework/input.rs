@@ -1,9 +1,18 @@
 #![allow(dead_code)]
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 use crossterm::terminal::{enable_raw_mode, disable_raw_mode};
 use crossterm::event::{self, Event, KeyCode};
+use rppal::gpio::Level;
 use crate::hardware::sensor_manager::SensorManager;
 use crate::hardware::hw_providers::HWInput;
+use crate::hardware::gpio_input::GpioInput;
+use crate::hardware::digital_signal_processing::{DigitalSignalDebouncer, DigitalSignalProcessor};
+use crate::page_framework::events::{SmartEventSender, UIEvent};
 
 // Page manager input is very simple: user can press one of the physical buttons
 // on the MFI, which selects a new page or delegated to the page-specific input handler.
@@ -12,16 +21,65 @@ use crate::hardware::hw_providers::HWInput;
 
 pub struct InputHandler {
     input_sources: Vec<Box<dyn InputSource>>,
+    long_press: LongPressDetector,
 }
 
 pub enum ButtonState {
     Pressed(char),
     Released(char),      // Generated just once after button is released.
+    LongPress(char),     // Held past the long press threshold, reported while still held.
+    LongReleased(char),  // Release of a button that was reported as long pressed.
+}
+
+/// Hold time for a long press until the style's PAGE_LONG_PRESS_TIME is applied
+pub const DEFAULT_LONG_PRESS_TIME: Duration = Duration::from_millis(800);
+
+/// Turns press/release pairs into long presses: a key held past the threshold reports
+/// a long press once, while still held, and its release is flagged as following one.
+pub struct LongPressDetector {
+    threshold: Duration,
+    // Held keys: when pressed, and whether the long press has been reported
+    held: HashMap<char, (Instant, bool)>,
+}
+
+impl LongPressDetector {
+    pub fn new(threshold: Duration) -> Self {
+        LongPressDetector { threshold, held: HashMap::new() }
+    }
+
+    pub fn set_threshold(&mut self, threshold: Duration) {
+        self.threshold = threshold;
+    }
+
+    pub fn press(&mut self, key: char, now: Instant) {
+        self.held.insert(key, (now, false));
+    }
+
+    /// Returns true if the key was long-pressed before this release.
+    /// Keys never seen pressed (the terminal only reports releases) count as short presses.
+    pub fn release(&mut self, key: char) -> bool {
+        self.held.remove(&key).is_some_and(|(_, reported)| reported)
+    }
+
+    /// Key that has just been held long enough, if any
+    pub fn poll(&mut self, now: Instant) -> Option<char> {
+        let threshold = self.threshold;
+        self.held.iter_mut()
+            .find(|(_, (since, reported))| !*reported && now.saturating_duration_since(*since) >= threshold)
+            .map(|(key, state)| {
+                state.1 = true;
+                *key
+            })
+    }
 }
 
 impl InputHandler {
     pub fn new(input_sources: Vec<Box<dyn InputSource>>) -> Self {
-        InputHandler { input_sources }
+        InputHandler { input_sources, long_press: LongPressDetector::new(DEFAULT_LONG_PRESS_TIME) }
+    }
+
+    pub fn set_long_press_threshold(&mut self, threshold: Duration) {
+        self.long_press.set_threshold(threshold);
     }
 
     // Add a new input source dynamically
@@ -29,15 +87,24 @@ impl InputHandler {
         self.input_sources.push(source);
     }
 
-    // Return the state of the first pressed or released button, if any.
+    // Return the state of the first pressed or released button, if any,
+    // otherwise a long press of a button still held.
     pub fn button_state(&mut self) -> Option<ButtonState> {
+        let now = Instant::now();
         for source in &mut self.input_sources {
             match source.button_state() {
+                Some(ButtonState::Pressed(key)) => {
+                    self.long_press.press(key, now);
+                    return Some(ButtonState::Pressed(key));
+                }
+                Some(ButtonState::Released(key)) if self.long_press.release(key) => {
+                    return Some(ButtonState::LongReleased(key));
+                }
                 Some(state) => return Some(state),
                 None => continue,
             }
         }
-        None
+        self.long_press.poll(now).map(ButtonState::LongPress)
     }
 }
 
@@ -149,4 +216,225 @@ impl InputSource for KeyboardInput {
         }
         None
     }
-}
\ No newline at end of file
+}
+// BCM pin numbers of the rotary encoder (GPIO2/3 are taken by the UPS I2C bus)
+pub const ENCODER_PIN_A: u8 = 17;
+pub const ENCODER_PIN_B: u8 = 27;
+pub const ENCODER_BUTTON_PIN: u8 = 22;
+// A quick flick produces a quarter step every couple of ms, far faster than the frame rate
+const ENCODER_SAMPLE_INTERVAL: Duration = Duration::from_millis(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncoderDirection {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// Quadrature decoder for a mechanical rotary encoder. Channel A leads B when turning
+/// clockwise: (A,B) goes 00 -> 10 -> 11 -> 01 -> 00. Invalid jumps (both channels changing
+/// at once, i.e. a missed sample or contact bounce) are ignored.
+pub struct QuadratureDecoder {
+    state: u8,
+    quarter_steps: i8,
+    steps_per_detent: i8,
+}
+
+impl QuadratureDecoder {
+    pub fn new(steps_per_detent: i8) -> Self {
+        QuadratureDecoder { state: 0, quarter_steps: 0, steps_per_detent: steps_per_detent.max(1) }
+    }
+
+    /// Feed the current channel levels; returns a direction once a full detent has been turned
+    pub fn update(&mut self, a: bool, b: bool) -> Option<EncoderDirection> {
+        let state = ((a as u8) << 1) | b as u8;
+        let delta = match (self.state, state) {
+            (0b00, 0b10) | (0b10, 0b11) | (0b11, 0b01) | (0b01, 0b00) => 1,
+            (0b00, 0b01) | (0b01, 0b11) | (0b11, 0b10) | (0b10, 0b00) => -1,
+            _ => 0,
+        };
+        self.state = state;
+        self.quarter_steps += delta;
+
+        if self.quarter_steps >= self.steps_per_detent {
+            self.quarter_steps = 0;
+            Some(EncoderDirection::Clockwise)
+        } else if self.quarter_steps <= -self.steps_per_detent {
+            self.quarter_steps = 0;
+            Some(EncoderDirection::CounterClockwise)
+        } else {
+            None
+        }
+    }
+}
+
+/// Rotary encoder with push button on three GPIO pins. Turning and pressing are sent as
+/// UI events; the encoder has no page button of its own, so button_state() never reports one.
+/// Holding the button goes back a page; a short press is reported on release.
+/// The A/B channels are sampled on their own thread, since once per frame would miss steps;
+/// the decoded steps come back over a channel and are forwarded from button_state().
+pub struct RotaryEncoderInput {
+    steps: Receiver<EncoderDirection>,
+    should_stop: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<()>>,
+    button: GpioInput,
+    button_debouncer: DigitalSignalDebouncer,
+    button_pressed: bool,
+    long_press: LongPressDetector,
+    sender: SmartEventSender,
+}
+
+// Key the encoder button is tracked under in its LongPressDetector
+const ENCODER_BUTTON_KEY: char = 'e';
+
+impl RotaryEncoderInput {
+    pub fn new(pin_a: u8, pin_b: u8, button_pin: u8, sender: SmartEventSender) -> rppal::gpio::Result<Self> {
+        let pin_a = GpioInput::new_with_pin(pin_a)?;
+        let pin_b = GpioInput::new_with_pin(pin_b)?;
+        let button = GpioInput::new_with_pin(button_pin)?;
+        let (step_tx, steps) = mpsc::channel();
+        let should_stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&should_stop);
+        let thread = thread::Builder::new()
+            .name("rotary-encoder".into())
+            .spawn(move || Self::sample_loop(pin_a, pin_b, step_tx, &thread_stop))?;
+        Ok(RotaryEncoderInput {
+            steps,
+            should_stop,
+            thread: Some(thread),
+            button,
+            button_debouncer: DigitalSignalDebouncer::new(3, Duration::from_millis(20)),
+            button_pressed: false,
+            long_press: LongPressDetector::new(DEFAULT_LONG_PRESS_TIME),
+            sender,
+        })
+    }
+
+    fn sample_loop(pin_a: GpioInput, pin_b: GpioInput, steps: Sender<EncoderDirection>, should_stop: &AtomicBool) {
+        let mut decoder = QuadratureDecoder::new(4);
+        // Start from the resting position so the first sample isn't taken as a step
+        decoder.update(pin_a.read_logical(), pin_b.read_logical());
+        while !should_stop.load(Ordering::Relaxed) {
+            if let Some(direction) = decoder.update(pin_a.read_logical(), pin_b.read_logical()) {
+                if steps.send(direction).is_err() {
+                    break;
+                }
+            }
+            thread::sleep(ENCODER_SAMPLE_INTERVAL);
+        }
+    }
+
+    /// Hold time before the button goes back a page instead of being a press
+    pub fn with_long_press_threshold(mut self, threshold: Duration) -> Self {
+        self.long_press.set_threshold(threshold);
+        self
+    }
+}
+
+impl InputSource for RotaryEncoderInput {
+    fn button_state(&mut self) -> Option<ButtonState> {
+        while let Ok(direction) = self.steps.try_recv() {
+            self.sender.send(match direction {
+                EncoderDirection::Clockwise => UIEvent::EncoderClockwise,
+                EncoderDirection::CounterClockwise => UIEvent::EncoderCounterClockwise,
+            });
+        }
+
+        let level = if self.button.read_logical() { Level::High } else { Level::Low };
+        let pressed = self.button_debouncer.read(level).map(|l| l == Level::High).unwrap_or(false);
+        let now = Instant::now();
+        match (self.button_pressed, pressed) {
+            (false, true) => self.long_press.press(ENCODER_BUTTON_KEY, now),
+            (true, true) => {
+                if self.long_press.poll(now).is_some() {
+                    self.sender.send(UIEvent::NavigateBack);
+                }
+            }
+            (true, false) => {
+                if !self.long_press.release(ENCODER_BUTTON_KEY) {
+                    self.sender.send(UIEvent::EncoderPress);
+                }
+            }
+            (false, false) => {}
+        }
+        self.button_pressed = pressed;
+        None
+    }
+}
+
+impl Drop for RotaryEncoderInput {
+    fn drop(&mut self) {
+        self.should_stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(decoder: &mut QuadratureDecoder, sequence: &[(bool, bool)]) -> Vec<EncoderDirection> {
+        sequence.iter().filter_map(|&(a, b)| decoder.update(a, b)).collect()
+    }
+
+    #[test]
+    fn test_long_press_after_threshold() {
+        let start = Instant::now();
+        let mut detector = LongPressDetector::new(Duration::from_millis(800));
+
+        // Short press: no long press, release acts normally
+        detector.press('1', start);
+        assert_eq!(detector.poll(start + Duration::from_millis(500)), None);
+        assert!(!detector.release('1'));
+
+        // Held past the threshold: reported once, release flagged
+        detector.press('1', start);
+        assert_eq!(detector.poll(start + Duration::from_millis(799)), None);
+        assert_eq!(detector.poll(start + Duration::from_millis(800)), Some('1'));
+        assert_eq!(detector.poll(start + Duration::from_millis(2000)), None);
+        assert!(detector.release('1'));
+
+        // Terminal keys arrive as releases only
+        assert!(!detector.release('q'));
+    }
+
+    struct ScriptedInput(Vec<ButtonState>);
+
+    impl InputSource for ScriptedInput {
+        fn button_state(&mut self) -> Option<ButtonState> {
+            (!self.0.is_empty()).then(|| self.0.remove(0))
+        }
+    }
+
+    #[test]
+    fn test_release_after_long_press_is_reported() {
+        let script = vec![ButtonState::Pressed('2')];
+        let mut handler = InputHandler::new(vec![Box::new(ScriptedInput(script))]);
+        handler.set_long_press_threshold(Duration::ZERO);
+
+        assert!(matches!(handler.button_state(), Some(ButtonState::Pressed('2'))));
+        assert!(matches!(handler.button_state(), Some(ButtonState::LongPress('2'))));
+        handler.add_input_source(Box::new(ScriptedInput(vec![ButtonState::Released('2')])));
+        // Left to the page manager: only buttons with a long press action drop it
+        assert!(matches!(handler.button_state(), Some(ButtonState::LongReleased('2'))));
+        assert!(handler.button_state().is_none());
+    }
+
+    #[test]
+    fn test_quadrature_direction() {
+        let clockwise = [(true, false), (true, true), (false, true), (false, false)];
+        let mut decoder = QuadratureDecoder::new(4);
+        assert_eq!(feed(&mut decoder, &clockwise), vec![EncoderDirection::Clockwise]);
+        assert_eq!(feed(&mut decoder, &clockwise), vec![EncoderDirection::Clockwise]);
+
+        let counter_clockwise = [(false, true), (true, true), (true, false), (false, false)];
+        assert_eq!(feed(&mut decoder, &counter_clockwise), vec![EncoderDirection::CounterClockwise]);
+
+        // Bounce back and forth within a detent does not produce a step
+        let bounce = [(true, false), (false, false), (true, false), (false, false)];
+        assert!(feed(&mut decoder, &bounce).is_empty());
+        // Invalid jump 00 -> 11 is ignored
+        assert!(feed(&mut decoder, &[(true, true), (false, false)]).is_empty());
+    }
+}
@@ -1,8 +1,8 @@
 use crate::graphics::context::GraphicsContext;
 use crate::graphics::ui_style::*;
-use crate::page_framework::page_manager::{Page, PageBase, PageButton, ButtonPosition, DIAG_PAGE_ID};
+use crate::page_framework::page_manager::{Page, PageBase, PageButton, ButtonPosition, DIAG_PAGE_ID, TRIP_PAGE_ID, CLOCK_PAGE_ID};
 use crate::page_framework::events::{EventReceiver, SmartEventSender};
-use crate::hardware::sensor_manager::SensorManager;
+use crate::hardware::sensor_manager::SensorValuesSnapshot;
 use crate::hardware::hw_providers::{*};
 use crate::indicators::{Indicator, IndicatorBounds};
 use crate::indicators::text_indicator::{TextIndicator, TextAlignment};
@@ -12,11 +12,22 @@ use crate::indicator_builders::{
     build_speed_digital
 };
 use crate::page_framework::events::UIEvent;
+use crate::page_framework::page_layout::LayoutView;
 
 struct IndicatorSet {
+    name: String,
     indicators: Vec<Box<dyn Indicator>>,
     inputs: Vec<HWInput>, // Corresponding hardware inputs for each indicator
     indicator_bounds: Vec<IndicatorBounds>,
+    // Per-indicator style override (layout "style" group); None renders with the page style
+    styles: Vec<Option<UIStyle>>,
+}
+
+impl IndicatorSet {
+    fn new(name: &str, indicators: Vec<Box<dyn Indicator>>, inputs: Vec<HWInput>, indicator_bounds: Vec<IndicatorBounds>) -> Self {
+        let styles = vec![None; indicators.len()];
+        Self { name: name.to_string(), indicators, inputs, indicator_bounds, styles }
+    }
 }
 
 pub struct MainPage {
@@ -47,6 +58,21 @@ impl MainPage {
         main_page
     }
 
+    /// Replace the built-in indicator sets with pages from a layout file
+    pub fn set_layout(&mut self, views: Vec<LayoutView>) {
+        if views.is_empty() {
+            log::warn!("MainPage: Layout has no pages, keeping built-in indicator sets");
+            return;
+        }
+        self.indicator_sets = views.into_iter()
+            .map(|view| IndicatorSet {
+                name: view.name, indicators: view.indicators, inputs: view.inputs,
+                indicator_bounds: view.bounds, styles: view.styles,
+            })
+            .collect();
+        self.current_indicator_set = 0;
+    }
+
     fn setup_test_indicators(ui_style: &UIStyle) -> IndicatorSet {
         let mut indicators: Vec<Box<dyn Indicator>> = Vec::new();
         let inputs: Vec<HWInput> = vec![
@@ -152,7 +178,7 @@ impl MainPage {
             indicator_color, indicator_warning_color, indicator_error_color,
         )));
         indicator_bounds.push(create_bounds_and_advance(&mut col, &mut row));
-        IndicatorSet { indicators, inputs, indicator_bounds }
+        IndicatorSet::new("Test", indicators, inputs, indicator_bounds)
     }
 
     fn setup_gauge_indicators(context: &GraphicsContext, ui_style: &UIStyle) -> IndicatorSet {
@@ -218,7 +244,7 @@ impl MainPage {
         indicators.push(voltage_gauge);
         indicator_bounds.push(voltage_bounds);
 
-        IndicatorSet { indicators, inputs, indicator_bounds }
+        IndicatorSet::new("Gauges", indicators, inputs, indicator_bounds)
     }
 
     fn setup_bar_indicators(context: &GraphicsContext, ui_style: &UIStyle) -> IndicatorSet {
@@ -298,7 +324,7 @@ impl MainPage {
         indicators.push(speed_digital);
         indicator_bounds.push(speed_bounds);
 
-        IndicatorSet { indicators, inputs, indicator_bounds }
+        IndicatorSet::new("Bars", indicators, inputs, indicator_bounds)
     }
 
     // Setup default buttons for main page using event system
@@ -325,6 +351,14 @@ impl MainPage {
                 let sender = smart_sender.clone();
                 move || sender.send(UIEvent::BrightnessDown)
             }) as Box<dyn FnMut()>),
+            PageButton::new(ButtonPosition::Left3, "ЧАСЫ".into(), Box::new({
+                let sender = smart_sender.clone();
+                move || sender.send(UIEvent::SwitchToPage(CLOCK_PAGE_ID))
+            }) as Box<dyn FnMut()>),
+            PageButton::new(ButtonPosition::Right3, "ПОЕЗД".into(), Box::new({
+                let sender = smart_sender.clone();
+                move || sender.send(UIEvent::SwitchToPage(TRIP_PAGE_ID))
+            }) as Box<dyn FnMut()>),
             PageButton::new(ButtonPosition::Right4, "ДИАГ".into(), Box::new({
                 let sender = smart_sender.clone();
                 move || sender.send(UIEvent::SwitchToPage(DIAG_PAGE_ID))
@@ -338,7 +372,8 @@ impl MainPage {
     fn next_indicator_set(&mut self) {
         if self.indicator_sets.len() > 1 {
             self.current_indicator_set = (self.current_indicator_set + 1) % self.indicator_sets.len();
-            log::info!("MainPage: Switched to indicator set {}", self.current_indicator_set);
+            log::info!("MainPage: Switched to indicator set {} ({})",
+                       self.current_indicator_set, self.indicator_sets[self.current_indicator_set].name);
         }
     }
 
@@ -349,7 +384,8 @@ impl MainPage {
             } else {
                 self.current_indicator_set -= 1;
             }
-            log::info!("MainPage: Switched to indicator set {}", self.current_indicator_set);
+            log::info!("MainPage: Switched to indicator set {} ({})",
+                       self.current_indicator_set, self.indicator_sets[self.current_indicator_set].name);
         }
     }
 
@@ -368,20 +404,24 @@ impl Page for MainPage {
         self.base.name()
     }
 
-    fn render(&self, context: &mut GraphicsContext, sensor_manager: &SensorManager, ui_style: &UIStyle) -> Result<(), String> {
+    fn render(&self, context: &mut GraphicsContext, sensors: &SensorValuesSnapshot, ui_style: &UIStyle) -> Result<(), String> {
         // Read sensor values and create SensorValue objects
-        let sensor_values = sensor_manager.get_sensor_values();
+        let sensor_values = sensors.get_sensor_values();
 
         // Render each indicator with its corresponding sensor value
         let indicators = self.indicator_sets[self.current_indicator_set].indicators.iter();
         let current_inputs = &self.indicator_sets[self.current_indicator_set].inputs;
         let indicator_bounds = &self.indicator_sets[self.current_indicator_set].indicator_bounds;
+        let styles = &self.indicator_sets[self.current_indicator_set].styles;
         
         for (i, indicator) in indicators.enumerate() {
             if let Some(sensor_value) = sensor_values.get(&current_inputs[i]) {
-                //print!("Rendering indicator {} for sensor {:?} with value {:?}\r\n", indicator.indicator_type(), sensor_value.metadata.sensor_id, sensor_value.value);
+                log::trace!("Rendering indicator {} for sensor {:?} with value {:?}",
+                            indicator.indicator_type(), sensor_value.metadata.sensor_id, sensor_value.value);
                 if let Some(bounds) = indicator_bounds.get(i) {
-                    indicator.render(sensor_value, bounds.clone(), ui_style, context)?;
+                    let history = sensors.get_history(&current_inputs[i]);
+                    let style = styles.get(i).and_then(Option::as_ref).unwrap_or(ui_style);
+                    indicator.render_with_history(sensor_value, history, bounds.clone(), style, context)?;
                 }
             }
         }
@@ -1,8 +1,17 @@
 pub mod events;
 pub mod input;
+pub mod touch_input;
+pub mod page_transition;
+pub mod page_layout;
+pub mod idle_manager;
+pub mod page_history;
 
 // Available pages
 pub mod main_page;
 pub mod diag_page;
+pub mod trip_page;
+pub mod clock_page;
+pub mod settings_page;
 pub mod terminal_page;
+pub mod osc_page;
 pub mod page_manager;
@@ -1,151 +1,458 @@
-use crate::graphics::context::GraphicsContext;
-use crate::page_framework::page_manager::{Page, PageBase, PageButton, ButtonPosition};
-use crate::page_framework::events::{UIEvent, EventSender, EventReceiver};
-use crate::hardware::sensor_manager::SensorManager;
+#![allow(dead_code)]
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
 
-/// Oscilloscope page for signal visualization
+use crate::graphics::context::{GraphicsContext, TextAlign, TextStyle};
+use crate::graphics::ui_style::*;
+use crate::hardware::hw_providers::HWInput;
+use crate::hardware::sensor_manager::SensorValuesSnapshot;
+use crate::page_framework::events::{EventReceiver, SmartEventSender, UIEvent};
+use crate::page_framework::page_manager::{Page, PageBase, PageButton, ButtonPosition, MAIN_PAGE_ID};
+
+// Plot area insets; the sides leave room for the button labels
+const PLOT_X_MARGIN: f32 = 90.0;
+const PLOT_TOP: f32 = 70.0;
+const PLOT_BOTTOM_MARGIN: f32 = 40.0;
+const GRID_DIVISIONS_X: usize = 8;
+const GRID_DIVISIONS_Y: usize = 4;
+const GRID_COLOR: (f32, f32, f32) = (0.25, 0.25, 0.25);
+const TRACE_THICKNESS: f32 = 2.0;
+const LEGEND_GAP: f32 = 20.0;
+// Sensor history is read once a frame, so its 256 samples span about 4 s
+const TIME_SPANS: [Duration; 4] = [
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(4),
+];
+// Where across the plot a triggered trace puts its trigger point
+const TRIGGER_POSITION: f32 = 0.5;
+// Trigger level change per encoder step, as a fraction of the sensor's range
+const TRIGGER_STEP: f32 = 0.02;
+const CHANNEL_COLORS: [(f32, f32, f32); 4] = [
+    (1.0, 0.9, 0.0),
+    (0.0, 0.9, 1.0),
+    (1.0, 0.3, 0.9),
+    (0.3, 1.0, 0.3),
+];
+
+// One channel's (timestamp, value) history, oldest first
+type Samples = Vec<(Instant, f32)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelScale {
+    /// Fit the samples on screen
+    Auto,
+    /// The sensor's full min..max range
+    Fixed,
+}
+
+struct OscChannel {
+    input: HWInput,
+    enabled: bool,
+    scale: ChannelScale,
+}
+
+/// Screen rectangle the traces are drawn in
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PlotArea {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+/// Screen points for samples over a `span` long window ending at `end`, with the value
+/// range spread over the plot height. Samples outside the window land past the plot
+/// edges, where the clip rectangle cuts them off.
+fn trace_points(samples: &[(Instant, f32)], end: Instant, span: Duration, range: (f32, f32), plot: PlotArea) -> Vec<(f32, f32)> {
+    let span = span.as_secs_f32().max(f32::EPSILON);
+    let (min, max) = range;
+    samples.iter()
+        .map(|&(timestamp, value)| {
+            // Seconds after the window end; negative inside the window
+            let offset = if timestamp <= end {
+                -(end - timestamp).as_secs_f32()
+            } else {
+                (timestamp - end).as_secs_f32()
+            };
+            let y = if max > min { (value - min) / (max - min) } else { 0.5 };
+            (plot.x + plot.width * (1.0 + offset / span), plot.y + plot.height * (1.0 - y))
+        })
+        .collect()
+}
+
+/// Smallest to largest sample, padded so the trace doesn't touch the plot edges
+fn auto_range(samples: &[(Instant, f32)]) -> Option<(f32, f32)> {
+    let min = samples.iter().map(|&(_, v)| v).fold(f32::INFINITY, f32::min);
+    let max = samples.iter().map(|&(_, v)| v).fold(f32::NEG_INFINITY, f32::max);
+    if !min.is_finite() || !max.is_finite() {
+        return None;
+    }
+    // A flat signal still gets some height
+    let padding = ((max - min) * 0.1).max(1.0);
+    Some((min - padding, max + padding))
+}
+
+/// Time of the latest rising crossing of `level` no later than `latest`, interpolated
+/// between the two samples either side of it
+fn find_rising_edge(samples: &[(Instant, f32)], level: f32, latest: Instant) -> Option<Instant> {
+    samples.windows(2).rev()
+        .find(|pair| pair[0].1 < level && pair[1].1 >= level && pair[1].0 <= latest)
+        .map(|pair| {
+            let ((t0, v0), (t1, v1)) = (pair[0], pair[1]);
+            t0 + (t1 - t0).mul_f32((level - v0) / (v1 - v0))
+        })
+}
+
+/// Scrolling plot of sensor histories, one trace per enabled channel. A rising-edge trigger
+/// on the first enabled channel holds a repeating signal still; STOP freezes the traces.
 pub struct OscPage {
     base: PageBase,
-    event_sender: EventSender,
     event_receiver: EventReceiver,
-    
-    // Oscilloscope state
-    is_running: bool,
-    sample_rate: f32,
-    time_scale: f32,
-    voltage_scale: f32,
+    smart_event_sender: SmartEventSender,
+    channels: Vec<OscChannel>,
+    selected: usize,
+    time_span_index: usize,
+    running: bool,
+    trigger_enabled: bool,
+    // Fraction of the trigger channel's sensor range
     trigger_level: f32,
-    channel_enabled: [bool; 4],
+    // Samples captured when stopped, per channel; taken by the first render after stopping
+    frozen: RefCell<Option<Vec<Samples>>>,
 }
 
 impl OscPage {
-    pub fn new(id: u32, event_sender: EventSender, event_receiver: EventReceiver) -> Self {
-        Self {
-            base: PageBase::new(id, "Osc"),
-            event_sender,
+    pub fn new(id: u32, smart_event_sender: SmartEventSender, event_receiver: EventReceiver) -> Self {
+        let channels = [HWInput::Hw12v, HWInput::HwOilPress, HWInput::HwEngineCoolantTemp, HWInput::HwFuelLvl]
+            .into_iter()
+            .enumerate()
+            .map(|(i, input)| OscChannel { input, enabled: i == 0, scale: ChannelScale::Auto })
+            .collect();
+        let mut osc_page = OscPage {
+            base: PageBase::new(id, "Osc".to_string()),
             event_receiver,
-            is_running: false,
-            sample_rate: 1000.0,
-            time_scale: 1.0,
-            voltage_scale: 1.0,
-            trigger_level: 0.0,
-            channel_enabled: [true, false, false, false],
+            smart_event_sender,
+            channels,
+            selected: 0,
+            time_span_index: 1,
+            running: true,
+            trigger_enabled: false,
+            trigger_level: 0.5,
+            frozen: RefCell::new(None),
+        };
+        osc_page.setup_buttons();
+        osc_page
+    }
+
+    fn setup_buttons(&mut self) {
+        let button = |position, label: &str, action: &'static str, sender: SmartEventSender| {
+            PageButton::new(position, label.into(), Box::new(
+                move || sender.send(UIEvent::ButtonPressed(action.into()))
+            ) as Box<dyn FnMut()>)
+        };
+        let sender = &self.smart_event_sender;
+        let buttons = vec![
+            button(ButtonPosition::Left1, "КАН", "select_channel", sender.clone()),
+            button(ButtonPosition::Left2, "ВКЛ", "toggle_channel", sender.clone()),
+            button(ButtonPosition::Left3, "МАСШ", "toggle_scale", sender.clone()),
+            button(ButtonPosition::Left4, "ВРЕМЯ", "next_time_span", sender.clone()),
+            button(ButtonPosition::Right1, "ТРИГ", "toggle_trigger", sender.clone()),
+            button(ButtonPosition::Right2, "СТОП", "toggle_run", sender.clone()),
+            PageButton::new(ButtonPosition::Right4, "ВОЗВ".into(), Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::SwitchToPage(MAIN_PAGE_ID))
+            }) as Box<dyn FnMut()>),
+        ];
+        self.base.set_buttons(buttons);
+    }
+
+    pub fn time_span(&self) -> Duration {
+        TIME_SPANS[self.time_span_index]
+    }
+
+    fn set_running(&mut self, running: bool) {
+        self.running = running;
+        if running {
+            self.frozen.replace(None);
         }
     }
-    
-    /// Process oscilloscope-specific events
-    pub fn process_events(&mut self) {
-        // Process events relevant to this page
-        while let Ok(event) = self.event_receiver.try_recv() {
-            match event {
-                UIEvent::OscStart => {
-                    self.is_running = true;
-                    log::info!("Oscilloscope started");
-                }
-                UIEvent::OscStop => {
-                    self.is_running = false;
-                    log::info!("Oscilloscope stopped");
-                }
-                UIEvent::OscSetSampleRate(rate) => {
-                    self.sample_rate = rate;
-                    log::info!("Sample rate set to: {} Hz", rate);
-                }
-                UIEvent::OscSetTimeScale(scale) => {
-                    self.time_scale = scale;
-                    log::info!("Time scale set to: {}", scale);
-                }
-                UIEvent::OscSetVoltageScale(scale) => {
-                    self.voltage_scale = scale;
-                    log::info!("Voltage scale set to: {}", scale);
-                }
-                UIEvent::OscSetTriggerLevel(level) => {
-                    self.trigger_level = level;
-                    log::info!("Trigger level set to: {}", level);
-                }
-                UIEvent::OscToggleChannel(channel) => {
-                    if (channel as usize) < self.channel_enabled.len() {
-                        self.channel_enabled[channel as usize] = !self.channel_enabled[channel as usize];
-                        log::info!("Channel {} toggled: {}", channel, self.channel_enabled[channel as usize]);
-                    }
-                }
-                _ => {} // Ignore other events
-            }
+
+    fn toggle_channel(&mut self, channel: usize) {
+        if let Some(channel) = self.channels.get_mut(channel) {
+            channel.enabled = !channel.enabled;
         }
     }
 
-    pub fn set_buttons(&mut self, buttons: Vec<PageButton<Box<dyn FnMut()>>>) {
-        self.base.set_buttons(buttons);
+    fn channel_label(sensors: &SensorValuesSnapshot, input: HWInput) -> String {
+        sensors.get_sensor_value(&input)
+            .map(|value| value.metadata.label.clone())
+            .filter(|label| !label.is_empty())
+            .unwrap_or_else(|| format!("{:?}", input))
+    }
+
+    /// The sensor's min..max, for fixed scale and the trigger level
+    fn sensor_range(sensors: &SensorValuesSnapshot, input: HWInput) -> Option<(f32, f32)> {
+        sensors.get_sensor_value(&input)
+            .map(|value| (value.constraints.min_value, value.constraints.max_value))
+            .filter(|(min, max)| max > min)
+    }
+
+    fn live_samples(&self, sensors: &SensorValuesSnapshot) -> Vec<Samples> {
+        self.channels.iter()
+            .map(|channel| sensors.get_history(&channel.input)
+                .map(|history| history.iter().copied().collect())
+                .unwrap_or_default())
+            .collect()
+    }
+
+    fn render_grid(&self, context: &mut GraphicsContext, plot: PlotArea, window_start: f32,
+                   font: &str, font_size: u32, text_color: (f32, f32, f32)) -> Result<(), String> {
+        for i in 1..GRID_DIVISIONS_X {
+            let x = plot.x + plot.width * i as f32 / GRID_DIVISIONS_X as f32;
+            context.draw_line(x, plot.y, x, plot.y + plot.height, 1.0, GRID_COLOR)?;
+        }
+        for i in 1..GRID_DIVISIONS_Y {
+            let y = plot.y + plot.height * i as f32 / GRID_DIVISIONS_Y as f32;
+            context.draw_line(plot.x, y, plot.x + plot.width, y, 1.0, GRID_COLOR)?;
+        }
+        context.stroke_rect(plot.x, plot.y, plot.width, plot.height, text_color, 1.0)?;
+
+        // Time axis, in seconds relative to the newest sample or to the trigger point
+        let span = self.time_span().as_secs_f32();
+        for i in (0..=GRID_DIVISIONS_X).step_by(2) {
+            let fraction = i as f32 / GRID_DIVISIONS_X as f32;
+            let label = format!("{:.1}с", window_start + span * fraction);
+            context.render_text_aligned(&label, plot.x + plot.width * fraction, plot.y + plot.height + 6.0,
+                                        TextAlign::Center, &TextStyle::new(font, font_size, text_color))?;
+        }
+        Ok(())
     }
 }
 
 impl Page for OscPage {
     fn id(&self) -> u32 {
-        self.base.id
+        self.base.id()
     }
 
     fn name(&self) -> &str {
-        &self.base.name
+        self.base.name()
     }
 
     fn set_buttons(&mut self, buttons: Vec<PageButton<Box<dyn FnMut()>>>) {
         self.base.set_buttons(buttons);
     }
 
-    fn render(&self, context: &mut GraphicsContext, sensor_manager: &SensorManager, ui_style: &UIStyle) -> Result<(), String> {
-        // Render oscilloscope UI
-        let status = if self.is_running { "RUNNING" } else { "STOPPED" };
-        let status_text = format!("OSC: {} | Rate: {:.0}Hz | Time: {:.1} | Volt: {:.1} | Trig: {:.2}", 
-                                 status, self.sample_rate, self.time_scale, self.voltage_scale, self.trigger_level);
-        
-        context.render_text(&status_text, 50.0, 50.0, 20.0, (1.0, 1.0, 1.0))?;
-        
-        // Render channel status
-        for (i, &enabled) in self.channel_enabled.iter().enumerate() {
-            let channel_text = format!("CH{}: {}", i + 1, if enabled { "ON" } else { "OFF" });
-            let color = if enabled { (0.0, 1.0, 0.0) } else { (0.5, 0.5, 0.5) };
-            context.render_text(&channel_text, 50.0 + (i as f32 * 100.0), 80.0, 16.0, color)?;
+    fn buttons(&self) -> &Vec<PageButton<Box<dyn FnMut()>>> {
+        self.base.buttons()
+    }
+
+    fn button_by_position(&self, pos: ButtonPosition) -> Option<&PageButton<Box<dyn FnMut()>>> {
+        self.base.button_by_position(pos)
+    }
+
+    fn button_by_position_mut(&mut self, pos: ButtonPosition) -> Option<&mut PageButton<Box<dyn FnMut()>>> {
+        self.base.button_by_position_mut(pos)
+    }
+
+    fn render(&self, context: &mut GraphicsContext, sensors: &SensorValuesSnapshot, ui_style: &UIStyle) -> Result<(), String> {
+        let title_font = ui_style.get_string(TEXT_PRIMARY_FONT, DEFAULT_GLOBAL_FONT_PATH);
+        let title_font_size = ui_style.get_integer(TEXT_PRIMARY_FONT_SIZE, 24);
+        let font = ui_style.get_string(TEXT_SECONDARY_FONT, DEFAULT_GLOBAL_FONT_PATH);
+        let font_size = ui_style.get_integer(TEXT_SECONDARY_FONT_SIZE, 10);
+        let text_color = ui_style.get_color(TEXT_SECONDARY_COLOR, (0.8, 0.8, 0.8));
+        let warning_color = ui_style.get_color(TEXT_WARNING_COLOR, (1.0, 1.0, 0.0));
+
+        let plot = PlotArea {
+            x: PLOT_X_MARGIN,
+            y: PLOT_TOP,
+            width: context.width as f32 - PLOT_X_MARGIN * 2.0,
+            height: context.height as f32 - PLOT_TOP - PLOT_BOTTOM_MARGIN,
+        };
+        let span = self.time_span();
+
+        let samples = if self.running {
+            self.live_samples(sensors)
+        } else {
+            self.frozen.borrow_mut().get_or_insert_with(|| self.live_samples(sensors)).clone()
+        };
+
+        let newest = self.channels.iter().zip(&samples)
+            .filter(|(channel, _)| channel.enabled)
+            .filter_map(|(_, samples)| samples.last().map(|&(timestamp, _)| timestamp))
+            .max();
+
+        // Trigger on the first enabled channel, keeping enough samples after the edge to fill
+        // the plot to the right of the trigger point
+        let post_trigger = span.mul_f32(1.0 - TRIGGER_POSITION);
+        let trigger = self.channels.iter().position(|channel| channel.enabled)
+            .filter(|_| self.trigger_enabled)
+            .and_then(|index| {
+                let (min, max) = Self::sensor_range(sensors, self.channels[index].input)?;
+                let level = min + self.trigger_level * (max - min);
+                let latest = newest?.checked_sub(post_trigger)?;
+                find_rising_edge(&samples[index], level, latest).map(|edge| (index, level, edge))
+            });
+        let (end, window_start) = match (trigger, newest) {
+            (Some((_, _, edge)), _) => (edge + post_trigger, -span.as_secs_f32() * TRIGGER_POSITION),
+            (None, Some(newest)) => (newest, -span.as_secs_f32()),
+            (None, None) => (Instant::now(), -span.as_secs_f32()),
+        };
+
+        context.render_text_with_font("ОСЦИЛЛОГРАФ", PLOT_X_MARGIN, 20.0, 1.0, text_color, &title_font, title_font_size)?;
+        let mut status = vec![if self.running { "ПУСК" } else { "СТОП" }.to_string()];
+        if self.trigger_enabled {
+            status.push(if trigger.is_some() { "ТРИГ" } else { "ТРИГ ОЖИД" }.to_string());
+        }
+        context.render_text_aligned(&status.join("  "), plot.x + plot.width, 20.0, TextAlign::Right,
+                                    &TextStyle::new(&font, font_size, if self.running { text_color } else { warning_color }))?;
+
+        self.render_grid(context, plot, window_start, &font, font_size, text_color)?;
+
+        context.push_clip(plot.x, plot.y, plot.width, plot.height);
+        let mut result = Ok(());
+        for (index, (channel, samples)) in self.channels.iter().zip(&samples).enumerate() {
+            if !channel.enabled || samples.len() < 2 {
+                continue;
+            }
+            let range = match channel.scale {
+                ChannelScale::Fixed => Self::sensor_range(sensors, channel.input).or_else(|| auto_range(samples)),
+                ChannelScale::Auto => auto_range(samples),
+            };
+            let Some(range) = range else { continue };
+            let color = CHANNEL_COLORS[index % CHANNEL_COLORS.len()];
+            let points = trace_points(samples, end, span, range, plot);
+            result = points.windows(2).try_for_each(|segment| {
+                let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+                context.draw_line(x1, y1, x2, y2, TRACE_THICKNESS, color)
+            });
+            if result.is_err() {
+                break;
+            }
+            if let Some((trigger_index, level, _)) = trigger.filter(|&(trigger_index, _, _)| trigger_index == index) {
+                let color = CHANNEL_COLORS[trigger_index % CHANNEL_COLORS.len()];
+                let y = trace_points(&[(end, level)], end, span, range, plot)[0].1;
+                let x = plot.x + plot.width * TRIGGER_POSITION;
+                result = context.draw_line(plot.x, y, plot.x + plot.width, y, 1.0, color)
+                    .and_then(|_| context.draw_line(x, plot.y, x, plot.y + plot.height, 1.0, color));
+            }
+        }
+        // Popped before reporting a draw error so later pages aren't left clipped
+        context.pop_clip();
+        result?;
+
+        // Legend: selected channel marked, scale mode, latest value
+        let mut x = plot.x;
+        let y = 20.0 + context.get_line_height_with_font(1.0, &title_font, title_font_size)?;
+        for (index, channel) in self.channels.iter().enumerate() {
+            let value = sensors.get_sensor_value(&channel.input)
+                .map(|value| format!("{:.1}{}", value.as_f32(), value.metadata.unit))
+                .unwrap_or_else(|| "н/д".to_string());
+            let scale = match channel.scale {
+                ChannelScale::Auto => "А",
+                ChannelScale::Fixed => "Ф",
+            };
+            let text = format!("{}{} {} {}", if index == self.selected { ">" } else { " " },
+                               Self::channel_label(sensors, channel.input), value, scale);
+            let color = if channel.enabled { CHANNEL_COLORS[index % CHANNEL_COLORS.len()] } else { GRID_COLOR };
+            context.render_text_with_font(&text, x, y, 1.0, color, &font, font_size)?;
+            x += context.calculate_text_width_with_font(&text, 1.0, &font, font_size)? + LEGEND_GAP;
         }
-        
-        // TODO: Render actual oscilloscope waveform
-        
+
         Ok(())
     }
 
     fn on_enter(&mut self) -> Result<(), String> {
-        log::info!("Entering Oscilloscope page");
+        self.set_running(true);
         Ok(())
     }
 
     fn on_exit(&mut self) -> Result<(), String> {
-        log::info!("Exiting Oscilloscope page");
         Ok(())
     }
 
-    fn on_button(&mut self, button: char) -> Result<(), String> {
-        // Handle oscilloscope-specific button presses
-        match button {
-            '1' => self.event_sender.send(UIEvent::OscStart),
-            '2' => self.event_sender.send(UIEvent::OscStop),
-            '3' => self.event_sender.send(UIEvent::OscToggleChannel(0)),
-            '4' => self.event_sender.send(UIEvent::OscToggleChannel(1)),
-            _ => {}
-        }
+    fn on_button(&mut self, _button: char) -> Result<(), String> {
         Ok(())
     }
 
+    fn on_encoder(&mut self, step: i32) -> bool {
+        self.trigger_level = (self.trigger_level + step as f32 * TRIGGER_STEP).clamp(0.0, 1.0);
+        true
+    }
+
     fn process_events(&mut self) {
-        self.process_events();
+        while let Ok(event) = self.event_receiver.try_recv() {
+            match event {
+                UIEvent::ButtonPressed(action) if action == "select_channel" => {
+                    self.selected = (self.selected + 1) % self.channels.len();
+                }
+                UIEvent::ButtonPressed(action) if action == "toggle_channel" => self.toggle_channel(self.selected),
+                UIEvent::ButtonPressed(action) if action == "toggle_scale" => {
+                    let channel = &mut self.channels[self.selected];
+                    channel.scale = match channel.scale {
+                        ChannelScale::Auto => ChannelScale::Fixed,
+                        ChannelScale::Fixed => ChannelScale::Auto,
+                    };
+                }
+                UIEvent::ButtonPressed(action) if action == "next_time_span" => {
+                    self.time_span_index = (self.time_span_index + 1) % TIME_SPANS.len();
+                }
+                UIEvent::ButtonPressed(action) if action == "toggle_trigger" => {
+                    self.trigger_enabled = !self.trigger_enabled;
+                }
+                UIEvent::ButtonPressed(action) if action == "toggle_run" => self.set_running(!self.running),
+                UIEvent::OscStart => self.set_running(true),
+                UIEvent::OscStop => self.set_running(false),
+                UIEvent::OscToggleChannel(channel) => self.toggle_channel(channel as usize),
+                UIEvent::OscSetTriggerLevel(level) => self.trigger_level = level.clamp(0.0, 1.0),
+                _ => {}
+            }
+        }
     }
+}
 
-    fn buttons(&self) -> &Vec<PageButton<Box<dyn FnMut()>>> {
-        &self.base.buttons
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_maps_history_to_plot() {
+        let plot = PlotArea { x: 100.0, y: 50.0, width: 400.0, height: 200.0 };
+        let end = Instant::now() + Duration::from_secs(10);
+        let at = |seconds_before_end: f32| end - Duration::from_secs_f32(seconds_before_end);
+        let samples = [(at(2.0), 10.0), (at(1.0), 15.0), (at(0.5), 12.5), (at(0.0), 20.0)];
+
+        // 2 s window over 10..20: oldest sample on the left edge at the bottom, newest top right
+        let points = trace_points(&samples, end, Duration::from_secs(2), (10.0, 20.0), plot);
+        assert_eq!(points, vec![(100.0, 250.0), (300.0, 150.0), (400.0, 200.0), (500.0, 50.0)]);
+
+        // Halving the window pushes older samples off the left edge, and samples after the
+        // window end (a trigger point) land off the right
+        let points = trace_points(&samples, at(0.5), Duration::from_secs(1), (10.0, 20.0), plot);
+        assert_eq!((points[0].0, points[2].0, points[3].0), (-100.0, 500.0, 700.0));
     }
 
-    fn button_by_position(&self, pos: ButtonPosition) -> Option<&PageButton<Box<dyn FnMut()>>> {
-        self.base.buttons.iter().find(|button| *button.position() == pos)
+    #[test]
+    fn test_rising_edge_trigger() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        // Two periods of a ramp that falls back: rising through 5.0 at 50 ms and 150 ms
+        let samples = [(at(0), 0.0), (at(40), 4.0), (at(60), 6.0), (at(100), 0.0), (at(140), 4.0), (at(160), 6.0), (at(200), 0.0)];
+
+        // Latest edge, interpolated halfway between the samples around it
+        assert_eq!(find_rising_edge(&samples, 5.0, at(200)), Some(at(150)));
+        // An edge too recent to fill the rest of the plot is skipped for the one before
+        assert_eq!(find_rising_edge(&samples, 5.0, at(155)), Some(at(50)));
+        // Falling crossings and levels the signal never reaches don't trigger
+        assert_eq!(find_rising_edge(&samples, 7.0, at(200)), None);
     }
 
-    fn button_by_position_mut(&mut self, pos: ButtonPosition) -> Option<&mut PageButton<Box<dyn FnMut()>>> {
-        self.base.buttons.iter_mut().find(|button| *button.position() == pos)
+    #[test]
+    fn test_auto_range_pads_flat_signal() {
+        let now = Instant::now();
+        assert_eq!(auto_range(&[(now, 13.8), (now, 13.8)]), Some((12.8, 14.8)));
+        assert_eq!(auto_range(&[]), None);
+        let (min, max) = auto_range(&[(now, 0.0), (now, 100.0)]).unwrap();
+        assert!((min + 10.0).abs() < 1e-4 && (max - 110.0).abs() < 1e-4);
     }
-}
\ No newline at end of file
+}
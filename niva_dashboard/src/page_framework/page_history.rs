@@ -0,0 +1,68 @@
+#![allow(dead_code)]
+
+/// Pages visited before the current one, most recent last. Bounded: once full,
+/// the oldest entry is dropped to make room.
+pub struct PageHistory {
+    stack: Vec<u32>,
+    max_depth: usize,
+}
+
+impl PageHistory {
+    pub fn new(max_depth: usize) -> Self {
+        PageHistory { stack: Vec::with_capacity(max_depth), max_depth }
+    }
+
+    pub fn push(&mut self, page_id: u32) {
+        if self.max_depth == 0 {
+            return;
+        }
+        if self.stack.len() == self.max_depth {
+            self.stack.remove(0);
+        }
+        self.stack.push(page_id);
+    }
+
+    pub fn pop(&mut self) -> Option<u32> {
+        self.stack.pop()
+    }
+
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.stack.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_back_returns_pages_in_lifo_order() {
+        let mut history = PageHistory::new(8);
+        for page_id in [0, 2, 1, 3] {
+            history.push(page_id);
+        }
+        assert_eq!(history.len(), 4);
+        assert_eq!(history.pop(), Some(3));
+        assert_eq!(history.pop(), Some(1));
+        assert_eq!(history.pop(), Some(2));
+        assert_eq!(history.pop(), Some(0));
+        assert_eq!(history.pop(), None);
+
+        // Over the bound the oldest pages are forgotten
+        let mut history = PageHistory::new(2);
+        for page_id in [0, 1, 2] {
+            history.push(page_id);
+        }
+        assert_eq!(history.pop(), Some(2));
+        assert_eq!(history.pop(), Some(1));
+        assert_eq!(history.pop(), None);
+    }
+}
@@ -0,0 +1,164 @@
+#![allow(dead_code)]
+use crate::graphics::ui_style::*;
+use crate::hardware::hw_providers::HWInput;
+use crate::indicators::{Indicator, IndicatorBounds};
+use crate::indicators::text_indicator::{TextIndicator, TextAlignment};
+use crate::indicators::needle_gauge_indicator::NeedleGaugeIndicator;
+use crate::indicator_builders::*;
+use serde::Deserialize;
+
+/// Indicator types a layout file can name
+pub const LAYOUT_INDICATOR_TYPES: [&str; 12] = [
+    "speedometer_gauge", "fuel_level_gauge", "oil_pressure_gauge", "temperature_gauge", "voltage_gauge",
+    "needle_gauge",
+    "oil_pressure_bar", "fuel_level_bar", "temperature_bar", "voltage_bar",
+    "speed_digital", "text",
+];
+
+// Layout file format:
+// { "pages": [ { "name": "Gauges", "indicators": [
+//     { "type": "speedometer_gauge", "sensor": "HwSpeed",
+//       "bounds": { "x": 250, "y": 40, "width": 300, "height": 300 }, "style": "night" } ] } ] }
+#[derive(Debug, Deserialize)]
+struct LayoutFile {
+    pages: Vec<PageLayout>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PageLayout {
+    name: String,
+    indicators: Vec<IndicatorLayout>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndicatorLayout {
+    #[serde(rename = "type")]
+    indicator_type: String,
+    sensor: String,
+    bounds: IndicatorBounds,
+    // Style group consulted before "default" while building and rendering the indicator
+    #[serde(default)]
+    style: Option<String>,
+}
+
+/// One page of a layout file with its indicators built, in file order
+pub struct LayoutView {
+    pub name: String,
+    pub indicators: Vec<Box<dyn Indicator>>,
+    pub inputs: Vec<HWInput>,
+    pub bounds: Vec<IndicatorBounds>,
+    /// Style with the entry's group active, for indicators that name one
+    pub styles: Vec<Option<UIStyle>>,
+}
+
+/// Read and build a layout file
+pub fn load_layout(path: &str, ui_style: &UIStyle) -> Result<Vec<LayoutView>, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read layout {}: {}", path, e))?;
+    build_layout(&json, ui_style).map_err(|e| format!("{}: {}", path, e))
+}
+
+/// Build every page of a layout. Fails on the first unknown sensor, indicator type or
+/// style group, so a typo doesn't leave a silently missing gauge.
+pub fn build_layout(json: &str, ui_style: &UIStyle) -> Result<Vec<LayoutView>, String> {
+    let layout: LayoutFile = serde_json::from_str(json).map_err(|e| format!("Invalid layout: {}", e))?;
+    layout.pages.into_iter().map(|page| {
+        let mut view = LayoutView {
+            name: page.name, indicators: Vec::new(), inputs: Vec::new(), bounds: Vec::new(), styles: Vec::new(),
+        };
+        for (i, entry) in page.indicators.into_iter().enumerate() {
+            let context = |e: String| format!("page '{}' indicator {}: {}", view.name, i, e);
+            let input = HWInput::from_name(&entry.sensor)
+                .ok_or_else(|| context(format!("unknown sensor '{}'", entry.sensor)))?;
+            let style = match &entry.style {
+                Some(group) => {
+                    let mut style = ui_style.clone();
+                    style.set_active_theme(group).map_err(|e| context(e.to_string()))?;
+                    Some(style)
+                }
+                None => None,
+            };
+            let (indicator, bounds) = build_indicator(&entry.indicator_type, entry.bounds, style.as_ref().unwrap_or(ui_style))
+                .map_err(context)?;
+            view.indicators.push(indicator);
+            view.inputs.push(input);
+            view.bounds.push(bounds);
+            view.styles.push(style);
+        }
+        Ok(view)
+    }).collect()
+}
+
+/// Construct an indicator of a named type filling `bounds`; gauges get the largest circle that fits
+fn build_indicator(indicator_type: &str, bounds: IndicatorBounds, style: &UIStyle)
+    -> Result<(Box<dyn Indicator>, IndicatorBounds), String> {
+    let (center_x, center_y) = bounds.center();
+    let radius = bounds.width.min(bounds.height) / 2.0;
+    let IndicatorBounds { x, y, width, height } = bounds;
+    Ok(match indicator_type {
+        "speedometer_gauge" => build_speedometer_gauge(center_x, center_y, radius, style),
+        "fuel_level_gauge" => build_fuel_level_gauge(center_x, center_y, radius, style),
+        "oil_pressure_gauge" => build_oil_pressure_gauge(center_x, center_y, radius, style),
+        "temperature_gauge" => build_temperature_gauge(center_x, center_y, radius, style),
+        "voltage_gauge" => build_voltage_gauge(center_x, center_y, radius, style),
+        // Generic gauge scaled from the sensor's own range, bottom-left round to bottom-right
+        "needle_gauge" => (Box::new(NeedleGaugeIndicator::new(-225.0f32.to_radians(), 45.0f32.to_radians())), bounds),
+        "oil_pressure_bar" => build_oil_pressure_bar(x, y, width, height, style),
+        "fuel_level_bar" => build_fuel_level_bar(x, y, width, height, style),
+        "temperature_bar" => build_temperature_bar(x, y, width, height, style),
+        "voltage_bar" => build_voltage_bar(x, y, width, height, style),
+        "speed_digital" => build_speed_digital(x, y, width, height, style),
+        "text" => (Box::new(TextIndicator::new(
+            1, true, true, TextAlignment::Center,
+            style.get_string(TEXT_SECONDARY_FONT, DEFAULT_GLOBAL_FONT_PATH),
+            style.get_integer(TEXT_SECONDARY_FONT_SIZE, 10), 1.0,
+            style.get_color(TEXT_SECONDARY_COLOR, (1.0, 1.0, 1.0)),
+            style.get_color(TEXT_WARNING_COLOR, (1.0, 1.0, 0.0)),
+            style.get_color(TEXT_ERROR_COLOR, (1.0, 0.0, 0.0)),
+        )), bounds),
+        _ => return Err(format!("unknown indicator type '{}' (expected one of {})",
+                                indicator_type, LAYOUT_INDICATOR_TYPES.join(", "))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_INDICATOR_LAYOUT: &str = r#"{
+        "pages": [{
+            "name": "Minimal",
+            "indicators": [
+                { "type": "speedometer_gauge", "sensor": "HwSpeed",
+                  "bounds": { "x": 250, "y": 40, "width": 300, "height": 300 } },
+                { "type": "voltage_bar", "sensor": "Hw12v",
+                  "bounds": { "x": 40, "y": 40, "width": 52, "height": 200 }, "style": "night" }
+            ]
+        }]
+    }"#;
+
+    #[test]
+    fn test_build_two_indicator_page() {
+        let views = build_layout(TWO_INDICATOR_LAYOUT, &UIStyle::new()).unwrap();
+        assert_eq!(views.len(), 1);
+        let view = &views[0];
+        assert_eq!(view.name, "Minimal");
+        assert_eq!(view.inputs, vec![HWInput::HwSpeed, HWInput::Hw12v]);
+        assert_eq!(view.indicators[0].indicator_type(), "NeedleIndicator");
+        assert_eq!(view.indicators[1].indicator_type(), "VerticalBarIndicator");
+        assert_eq!((view.bounds[0].x, view.bounds[0].width), (250.0, 300.0));
+        assert_eq!((view.bounds[1].x, view.bounds[1].height), (40.0, 200.0));
+        assert!(view.styles[0].is_none());
+        assert_eq!(view.styles[1].as_ref().and_then(|style| style.active_theme()), Some("night"));
+    }
+
+    #[test]
+    fn test_layout_rejects_unknown_names() {
+        let style = UIStyle::new();
+        let unknown_sensor = TWO_INDICATOR_LAYOUT.replace("HwSpeed", "HwWarpDrive");
+        assert!(build_layout(&unknown_sensor, &style).err().unwrap().contains("HwWarpDrive"));
+        let unknown_type = TWO_INDICATOR_LAYOUT.replace("voltage_bar", "voltage_dial");
+        assert!(build_layout(&unknown_type, &style).err().unwrap().contains("voltage_dial"));
+        let unknown_group = TWO_INDICATOR_LAYOUT.replace("night", "disco");
+        assert!(build_layout(&unknown_group, &style).is_err());
+    }
+}
@@ -2,31 +2,57 @@
 use crate::graphics::context::GraphicsContext;
 use crate::graphics::ui_style::*;
 use crate::page_framework::diag_page::DiagPage;
+use crate::page_framework::trip_page::TripPage;
+use crate::page_framework::clock_page::ClockPage;
+use crate::page_framework::settings_page::SettingsPage;
+use crate::page_framework::osc_page::OscPage;
 use crate::page_framework::events::{UIEvent, EventReceiver, EventBus, SmartEventSender, create_event_bus};
 use crate::page_framework::input::{InputHandler, InputSource, ButtonState};
+use crate::page_framework::touch_input::TouchInput;
+use crate::page_framework::page_transition::{PageTransition, TransitionKind};
+use crate::page_framework::page_layout::load_layout;
+use crate::page_framework::idle_manager::{IdleManager, IdleChange};
+use crate::page_framework::page_history::PageHistory;
 use crate::page_framework::main_page::MainPage;
 use crate::page_framework::terminal_page::TerminalPage;
-use crate::hardware::sensor_manager::SensorManager;
+use crate::hardware::sensor_manager::{SensorManager, SensorUpdateThread, SensorValuesSnapshot};
 use crate::hardware::hw_providers::HWInput;
 use crate::alerts::alert_manager::{AlertManager, Severity};
 use crate::alerts::watchdog::Watchdog;
 use crate::util::adc_data_provider::ADCFrame;
 use crate::util::ups_monitor::UpsReading;
+use crate::util::frame_limiter::FrameLimiter;
 
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use std::fs;
+use std::sync::Arc;
 use std::sync::mpsc::{Sender, Receiver};
 
+// Backstop above the panel's 60 Hz: page flips pace the loop normally, this only kicks in
+// when they don't (modeset fallback after a failed flip, headless rendering)
+const MAX_FPS: f32 = 75.0;
+
+// Sensors are read on their own thread at the rate the render loop used to poll them
+const SENSOR_UPDATE_INTERVAL: Duration = Duration::from_micros((1_000_000.0 / MAX_FPS) as u64);
+
 const STATUS_LINE_X_MARGIN : f32 = 20.0;
 const STATUS_LINE_Y_MARGIN : f32 = 25.0;
 
+// Glyphs rendered into every text font at startup: digits, units and the Cyrillic capitals
+// the sensor labels, alerts and buttons are written in
+const PRELOAD_GLYPHS: &str = "0123456789 .,:;-+/%°²кгсмч АБВГДЕЁЖЗИЙКЛМНОПРСТУФХЦЧШЩЪЫЬЭЮЯ";
+
 const PAGE_BUTTON_X_MARGIN: f32 = 4.0;      // Move a little from screen edge for better visibility.
 
 pub const MAIN_PAGE_ID: u32 = 0;
 pub const DIAG_PAGE_ID: u32 = 1;
 pub const ADC_TERM_PAGE_ID: u32 = 2;
 pub const LOG_PAGE_ID: u32 = 3;
+pub const TRIP_PAGE_ID: u32 = 4;
+pub const CLOCK_PAGE_ID: u32 = 5;
+pub const SETTINGS_PAGE_ID: u32 = 6;
+pub const OSC_PAGE_ID: u32 = 7;
 
 // ButtonPosition correspond to physical 2x4 buttons layout.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
@@ -47,6 +73,7 @@ pub struct PageButton<CB> {
     pos: ButtonPosition,
     pub label: String,
     callback: CB,
+    long_callback: Option<CB>,
 }
 
 impl<CB> PageButton<CB>
@@ -54,7 +81,13 @@ where
     CB: FnMut(),
 {
     pub fn new(pos: ButtonPosition, label: String, callback: CB) -> Self {
-        PageButton { pos, label, callback }
+        PageButton { pos, label, callback, long_callback: None }
+    }
+
+    // Action for holding the button, for things that shouldn't happen by accident.
+    pub fn with_long_press(mut self, callback: CB) -> Self {
+        self.long_callback = Some(callback);
+        self
     }
 
     // Invokes button-specific callback.
@@ -62,6 +95,17 @@ where
         (self.callback)();
     }
 
+    pub fn has_long_press(&self) -> bool {
+        self.long_callback.is_some()
+    }
+
+    // Invokes the long press callback, if the button has one.
+    pub fn trigger_long(&mut self) {
+        if let Some(callback) = self.long_callback.as_mut() {
+            callback();
+        }
+    }
+
     // Used to match buttons with hardware input.
     pub fn position(&self) -> &ButtonPosition {
         &self.pos
@@ -119,7 +163,7 @@ pub trait Page {
     fn id(&self) -> u32;
     fn name(&self) -> &str;
     // Render page-specific stuff (except button labels, which are PageManager responsibility).
-    fn render(&self, context: &mut GraphicsContext, sensor_manager: &SensorManager, ui_style: &UIStyle) -> Result<(), String>;
+    fn render(&self, context: &mut GraphicsContext, sensors: &SensorValuesSnapshot, ui_style: &UIStyle) -> Result<(), String>;
     // Trigger once on switching to this page.
     fn on_enter(&mut self) -> Result<(), String>;
     // Trigger once on switching from this page.
@@ -128,6 +172,11 @@ pub trait Page {
     fn on_button(&mut self, button: char) -> Result<(), String>;
     // Process events specific to this page (MPMC allows each page to have its own receiver)
     fn process_events(&mut self) {}
+    // Encoder turn while the encoder isn't adjusting brightness; return true to keep it
+    // from switching pages.
+    fn on_encoder(&mut self, _step: i32) -> bool {
+        false
+    }
 
     fn buttons(&self) -> &Vec<PageButton<Box<dyn FnMut()>>>;
     fn set_buttons(&mut self, buttons: Vec<PageButton<Box<dyn FnMut()>>>);
@@ -171,6 +220,14 @@ impl Pages {
     }
 }
 
+// Animated switch between two style themes.
+struct ThemeTransition {
+    target: UIStyle,    // Style with the destination theme active, installed when done
+    from: String,
+    to: String,
+    started: Instant,
+}
+
 // PageManager is responsible for managing multiple pages and their transitions,
 // as well as rendering button labels.
 pub struct PageManager {
@@ -178,19 +235,42 @@ pub struct PageManager {
     context: GraphicsContext,
     // Global UI style settings.
     ui_style: UIStyle,
+    // Optional style file watched for live edits, its mtime is checked once a second.
+    style_watcher: Option<StyleWatcher>,
+    style_last_check: Instant,
+    // Day/night switch in progress; ui_style holds the blended style until it finishes.
+    theme_transition: Option<ThemeTransition>,
+    // JSON layout replacing the main page's built-in indicator sets, read in setup().
+    layout_path: Option<String>,
 
     // Takes care of low-level hw input, signal processing, and conversion
-    // to actual sensor values.
-    sensor_manager: SensorManager,
+    // to actual sensor values, on a background thread.
+    sensor_updates: SensorUpdateThread,
+    // Values published by the last completed update cycle, taken once per frame.
+    sensors: Arc<SensorValuesSnapshot>,
 
     // UI pages related stuff.
     pg_id: u32,             // Page incremental id, depends on page creation order.
     current_page: Option<u32>,
     pages: Pages,
+    // Animation used by switch_page, and the switch currently animating if any.
+    transition_kind: TransitionKind,
+    transition_duration: Duration,
+    page_transition: Option<PageTransition>,
+    history: PageHistory,   // Pages to return to with navigate_back
 
     // Input handling from gpio buttons, external keyboard, etc.
     input_handler: InputHandler,
 
+    // Rotary encoder turns switch pages, or change brightness after a press of its button.
+    encoder_adjusts_brightness: bool,
+
+    // Touchscreen, if the panel has one; touches on button labels trigger the buttons.
+    touch_input: Option<TouchInput>,
+
+    // Dims the screen while parked with nobody touching the controls.
+    idle_manager: IdleManager,
+
     // Map hardware keys with UI buttons positions.
     buttons_map: HashMap<char, ButtonPosition>,
 
@@ -214,7 +294,7 @@ pub struct PageManager {
     // None when the ADC data provider failed to start.
     adc_frame: Option<ADCFrame>,
 
-    fps_counter: FpsCounter,
+    frame_limiter: FrameLimiter,
     start_time: Instant,
 
     // Cached /proc/stat snapshot for non-blocking CPU load calculation.
@@ -257,14 +337,29 @@ impl PageManager {
         // Event channel for switching self-test sequence sensors to real ones
         let (sensor_config_tx, sensor_config_rx) = std::sync::mpsc::channel::<SensorManager>();
 
+        let sensor_updates = sensor_manager.start_background_updates(SENSOR_UPDATE_INTERVAL);
+        let sensors = sensor_updates.latest();
+
         PageManager {
             context,
             ui_style,
-            sensor_manager,
+            style_watcher: None,
+            style_last_check: Instant::now(),
+            theme_transition: None,
+            layout_path: None,
+            sensor_updates,
+            sensors,
             pg_id: 0,
             current_page: None,
             pages: Pages::new(),
+            transition_kind: TransitionKind::Crossfade,
+            transition_duration: Duration::from_millis(250),
+            page_transition: None,
+            history: PageHistory::new(16),
             input_handler: InputHandler::new(input_sources),
+            encoder_adjusts_brightness: false,
+            touch_input: None,
+            idle_manager: IdleManager::new(Instant::now()),
             buttons_map,
             event_bus,
             global_event_receiver,
@@ -274,7 +369,7 @@ impl PageManager {
             alert_manager,
             ups_reading,
             adc_frame,
-            fps_counter: FpsCounter::new(),
+            frame_limiter: FrameLimiter::new(MAX_FPS),
             start_time: Instant::now(),
             last_cpu_stat: None,
             cpu_load_samples: Vec::new(),
@@ -334,9 +429,32 @@ impl PageManager {
     }
 
     fn render_current_page(&mut self) -> Result<(), String> {
+        let now = Instant::now();
+        if self.page_transition.as_ref().is_some_and(|transition| transition.is_complete(now)) {
+            self.page_transition = None;
+        }
+        if let (Some(transition), Some(page_id)) = (&self.page_transition, self.current_page) {
+            let (from_page, frame) = (transition.from_page, transition.frame_at(now));
+            // Both pages go to offscreen layers, then get blended into the frame
+            for (layer, id) in [(0, from_page), (1, page_id)] {
+                if let Err(e) = self.context.begin_page_layer(layer) {
+                    log::error!("Page transition disabled: {}", e);
+                    self.page_transition = None;
+                    self.transition_kind = TransitionKind::None;
+                    return self.render_current_page();
+                }
+                match self.pages.get_page_mut(id) {
+                    Some(page) => page.render(&mut self.context, &self.sensors, &self.ui_style),
+                    None => Err(format!("Page id {} not found", id)),
+                }?;
+            }
+            return self.context.composite_page_layers(frame.from_offset, frame.to_offset, frame.to_weight)
+                .map_err(String::from);
+        }
+
         if let Some(page_id) = self.current_page {
             match self.pages.get_page_mut(page_id) {
-                Some(page) => page.render(&mut self.context, &self.sensor_manager, &self.ui_style),
+                Some(page) => page.render(&mut self.context, &self.sensors, &self.ui_style),
                 None => Err(format!("Current page id {} not found", page_id)),
             }?;
         }
@@ -368,6 +486,14 @@ impl PageManager {
             current.on_exit()?;
         }
 
+        let animate = self.transition_kind != TransitionKind::None
+            && self.ui_style.get_bool(ANIMATION_SMOOTH_ENABLED, true);
+        self.page_transition = match self.current_page {
+            Some(from_page) if animate && from_page != page_id => {
+                Some(PageTransition::new(self.transition_kind, from_page, self.transition_duration, Instant::now()))
+            }
+            _ => None,
+        };
         self.current_page = Some(page_id);
 
         // Call on_enter for new page.
@@ -378,6 +504,40 @@ impl PageManager {
         Ok(())
     }
 
+    /// Switch to a page, remembering the current one for navigate_back
+    pub fn navigate_to(&mut self, page_id: u32) -> Result<(), String> {
+        match self.current_page {
+            Some(current) if current == page_id => return Ok(()),
+            Some(current) if self.get_page(page_id).is_some() => self.history.push(current),
+            _ => {}
+        }
+        self.switch_page(page_id)
+    }
+
+    /// Return to the page shown before the last navigate_to; does nothing with an empty history
+    pub fn navigate_back(&mut self) -> Result<(), String> {
+        match self.history.pop() {
+            Some(page_id) => self.switch_page(page_id),
+            None => Ok(()),
+        }
+    }
+
+    /// Animation for page switches; TransitionKind::None switches instantly.
+    /// Style key ANIMATION_SMOOTH_ENABLED = false turns it off for slow hardware.
+    pub fn set_transition(&mut self, kind: TransitionKind, duration: Duration) {
+        self.transition_kind = kind;
+        self.transition_duration = duration;
+    }
+
+    pub fn add_input_source(&mut self, source: Box<dyn InputSource>) {
+        self.input_handler.add_input_source(source);
+    }
+
+    pub fn set_touch_input(&mut self, touch_input: TouchInput) {
+        log::info!("Touch input from {}", touch_input.device_path().display());
+        self.touch_input = Some(touch_input);
+    }
+
     fn button_by_key(&mut self, key: &char) -> Option<&mut PageButton<Box<dyn FnMut()>>> {
         let pos = self.buttons_map.get(key).copied()?;
         self.get_current_page_mut()?.button_by_position_mut(pos)
@@ -385,15 +545,34 @@ impl PageManager {
 
     // Set up pages, buttons and watchdogs.
     pub fn setup(&mut self) -> Result<(), String> {
+        self.input_handler.set_long_press_threshold(
+            Duration::from_secs_f32(self.ui_style.get_float(PAGE_LONG_PRESS_TIME, 0.8).max(0.0)));
+        self.apply_saved_settings(&self.ui_style.clone());
+        if let Some(theme) = self.ui_style.get(GLOBAL_THEME).and_then(|v| v.as_string().ok()).map(str::to_string) {
+            if let Err(e) = self.ui_style.set_active_theme(&theme) {
+                log::warn!("Saved theme not applied: {}", e);
+            }
+        }
+
         // Get smart event sender for button callbacks
         let smart_sender = self.smart_event_sender.clone();
 
         // Create and add pages first to get their IDs
-        let main_page = Box::new(MainPage::new(MAIN_PAGE_ID,
-                                               smart_sender.clone(),
-                                               self.get_event_receiver(),
-                                               &self.context,
-                                               &self.ui_style));
+        let mut main_page = Box::new(MainPage::new(MAIN_PAGE_ID,
+                                                   smart_sender.clone(),
+                                                   self.get_event_receiver(),
+                                                   &self.context,
+                                                   &self.ui_style));
+        if let Some(path) = &self.layout_path {
+            // A broken layout shouldn't leave the cluster blank - fall back to the built-in sets
+            match load_layout(path, &self.ui_style) {
+                Ok(views) => {
+                    log::info!("Loaded {} layout page(s) from {}", views.len(), path);
+                    main_page.set_layout(views);
+                }
+                Err(e) => log::error!("Layout not loaded: {}", e),
+            }
+        }
 
         let diag_page = Box::new(DiagPage::new(DIAG_PAGE_ID,
                                                smart_sender.clone(),
@@ -403,11 +582,31 @@ impl PageManager {
                                                        smart_sender.clone(),
                                                        self.get_event_receiver()));
 
+        let trip_page = Box::new(TripPage::new(TRIP_PAGE_ID,
+                                               smart_sender.clone(),
+                                               self.get_event_receiver()));
+
+        let clock_page = Box::new(ClockPage::new(CLOCK_PAGE_ID,
+                                                 smart_sender.clone(),
+                                                 self.get_event_receiver()));
+
+        let settings_page = Box::new(SettingsPage::new(SETTINGS_PAGE_ID,
+                                                       smart_sender.clone(),
+                                                       self.get_event_receiver()));
+
+        let osc_page = Box::new(OscPage::new(OSC_PAGE_ID,
+                                             smart_sender.clone(),
+                                             self.get_event_receiver()));
+
         self.add_page(main_page);
         self.switch_page(MAIN_PAGE_ID)?;
 
         self.add_page(diag_page);
         self.add_page(log_page);
+        self.add_page(trip_page);
+        self.add_page(clock_page);
+        self.add_page(settings_page);
+        self.add_page(osc_page);
 
         // ADC terminal page only exists when the ADC data provider actually started —
         // without a frame handle there is nothing for it to display.
@@ -453,9 +652,32 @@ impl PageManager {
         // Enable watchdogs and alerts
         self.alert_manager.set_enabled(true);
 
+        self.preload_glyphs();
+
         Ok(())
     }
 
+    /// Fill the glyph caches of the fonts the pages render text with, so the first frame
+    /// showing a label doesn't load each of its glyphs on the spot
+    fn preload_glyphs(&mut self) {
+        let fonts = [
+            (TEXT_PRIMARY_FONT, TEXT_PRIMARY_FONT_SIZE, 24),
+            (TEXT_SECONDARY_FONT, TEXT_SECONDARY_FONT_SIZE, 10),
+            (GAUGE_LABEL_FONT, GAUGE_LABEL_FONT_SIZE, DEFAULT_GLOBAL_FONT_SIZE),
+            (PAGE_BUTTON_LABEL_FONT, PAGE_BUTTON_LABEL_FONT_SIZE, 14),
+            (PAGE_STATUS_FONT, PAGE_STATUS_FONT_SIZE, 14),
+        ];
+        let start = Instant::now();
+        for (font_key, size_key, default_size) in fonts {
+            let font = self.ui_style.get_string(font_key, DEFAULT_GLOBAL_FONT_PATH);
+            let font_size = self.ui_style.get_integer(size_key, default_size);
+            if let Err(e) = self.context.preload_glyphs(PRELOAD_GLYPHS, &font, font_size) {
+                log::warn!("Glyphs for {} ({}px) not preloaded: {}", font, font_size, e);
+            }
+        }
+        log::debug!("Glyph caches warmed in {:?}", start.elapsed());
+    }
+
     // Do first-time initialization and start main loop.
     // Normally, main loop should not exit until device shutdown on external power loss.
     pub fn start(&mut self) -> Result<(), String> {
@@ -474,7 +696,8 @@ impl PageManager {
 
     fn event_loop(&mut self) -> Result<(), String> {
         log::info!("Starting event loop");
-        self.toggle_bloom();    // Turn off for now
+        // Off unless turned on from the settings page
+        self.context.set_bloom_enabled(self.ui_style.get_bool(GLOBAL_BLOOM_ENABLED, false));
         
         while self.running {
             if crate::util::shutdown::shutdown_requested() {
@@ -488,21 +711,16 @@ impl PageManager {
                 continue;
             }
 
-            // Continuous sensor polling - poll sensors every loop iteration
-            // This ensures sensor data is always up to date regardless of render timing
-            if let Err(e) = self.sensor_manager.read_all_sensors() {
-                // Suppress: while the ADC link is down, the first ADC-backed chain fails
-                // with "channel not in frame" until AdcDataProvider's reconnect loop
-                // recovers it — expected and already surfaced via the ADC LINK alert.
-                if !self.sensor_manager.adc_link_down() {
-                    log::error!("Sensor read error: {}", e);
-                }
-            }
-            self.alert_manager.check_watchdogs(&self.sensor_manager);
-            
-            // Update FPS counter
-            self.fps_counter.update();
+            // Sensors are read on the update thread; the whole frame renders from one cycle's values
+            self.sensors = self.sensor_updates.latest();
+            self.alert_manager.check_watchdogs(&self.sensors);
+            self.reload_style_if_changed();
+            self.update_theme_transition();
             
+            // Follows the style every frame, so themes and their transitions apply to it
+            let (r, g, b) = self.ui_style.get_color(GLOBAL_BACKGROUND_COLOR, (0.0, 0.0, 0.0));
+            self.context.set_clear_color(r, g, b);
+
             // Begin bloom rendering if enabled
             let bloom_enabled = self.context.is_bloom_enabled();
             if bloom_enabled {
@@ -510,7 +728,7 @@ impl PageManager {
                     log::error!("Bloom render error: {}", e);
                 }
             } else {
-                // Clear screen with black for normal rendering
+                // Clear screen with the background color for normal rendering
                 self.context.clear_screen();
             }
         
@@ -528,18 +746,23 @@ impl PageManager {
             
             self.render_status_line()?;
             
-            // Apply bloom effect and swap buffers
+            // Apply post-processing effects and swap buffers
             if bloom_enabled {
                 if let Err(e) = self.context.end_bloom_render() {
                     log::error!("Bloom end render error: {}", e);
                 }
             }
+            if let Err(e) = self.context.apply_vignette() {
+                log::error!("Vignette error: {}", e);
+            }
             
             // Swap buffers - pacing is handled by the DRM page flip
             self.context.swap_buffers();
+            self.frame_limiter.wait();
 
             // Check for button state changes (processed every loop iteration for responsiveness)
             if let Some(state) = self.input_handler.button_state() {
+                self.note_activity();
                 match state {
                     ButtonState::Pressed(key) => {
                         log::info!("Button pressed: {}", key);
@@ -554,14 +777,38 @@ impl PageManager {
                             self.running = false;
                         }
                     }
+                    ButtonState::LongPress(key) => {
+                        log::info!("Button long press: {}", key);
+                        if let Some(button) = self.button_by_key(&key) {
+                            button.trigger_long();
+                        }
+                    }
+                    ButtonState::LongReleased(key) => {
+                        log::info!("Button released after long press: {}", key);
+                        // Buttons without a long press action still act on a slow release
+                        if let Some(button) = self.button_by_key(&key) {
+                            if !button.has_long_press() {
+                                button.trigger();
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Some(touch_input) = self.touch_input.as_mut() {
+                for event in touch_input.poll() {
+                    self.smart_event_sender.send(event);
                 }
             }
 
             // Process global UI events (PageManager events only)
             // With dual-channel system, PageManager only receives global events
             while let Ok(event) = self.global_event_receiver.try_recv() {
+                // Undim before handling, so brightness buttons act on the real brightness
+                self.note_activity();
                 self.handle_ui_event(event);
             }
+            self.update_idle_dimming();
 
             // Let the current page process its own events
             if let Some(current_page) = self.get_current_page_mut() {
@@ -576,7 +823,12 @@ impl PageManager {
 
     /// Handle UI events sent by buttons and other components
     fn handle_ui_event(&mut self, event: UIEvent) {
-        log::info!("Processing UI event: {:?}", event);
+        // Touch moves arrive continuously while dragging
+        if matches!(event, UIEvent::Touch { .. }) {
+            log::debug!("Processing UI event: {:?}", event);
+        } else {
+            log::info!("Processing UI event: {:?}", event);
+        }
         
         match event {
             UIEvent::BrightnessUp => {
@@ -589,10 +841,15 @@ impl PageManager {
                 self.set_brightness(level);
             }
             UIEvent::SwitchToPage(page_id) => {
-                if let Err(e) = self.switch_page(page_id) {
+                if let Err(e) = self.navigate_to(page_id) {
                     log::error!("Failed to switch to page {}: {}", page_id, e);
                 }
             }
+            UIEvent::NavigateBack => {
+                if let Err(e) = self.navigate_back() {
+                    log::error!("Failed to navigate back: {}", e);
+                }
+            }
             UIEvent::Shutdown => {
                 log::info!("Shutdown event received");
                 self.running = false;
@@ -600,6 +857,29 @@ impl PageManager {
             UIEvent::Restart => {
                 log::info!("Restart event received (not implemented)");
             }
+            UIEvent::ToggleTheme => {
+                self.finish_theme_transition();
+                let from = self.ui_style.active_theme().unwrap_or("default").to_string();
+                let to = if from == THEME_NIGHT { THEME_DAY } else { THEME_NIGHT };
+                let mut target = self.ui_style.clone();
+                match target.set_active_theme(to) {
+                    Ok(()) => {
+                        log::info!("Switching to {} theme", to);
+                        self.theme_transition = Some(ThemeTransition { target, from, to: to.to_string(), started: Instant::now() });
+                    }
+                    Err(e) => log::error!("Failed to switch theme: {}", e),
+                }
+            }
+            UIEvent::SetBacklightEnabled(enabled) => {
+                self.set_backlight_enabled(enabled);
+            }
+            UIEvent::SetBloomEnabled(enabled) => {
+                self.context.set_bloom_enabled(enabled);
+                log::info!("Bloom effect {}", if enabled { "enabled" } else { "disabled" });
+            }
+            UIEvent::SaveSettings => {
+                self.save_settings();
+            }
             UIEvent::SuppressAlerts => {
                 self.alert_manager.suppress_alerts();
             }
@@ -614,15 +894,89 @@ impl PageManager {
                     _ => log::info!("Unknown action: {}", action),
                 }
             }
+            UIEvent::ResetTrip => {
+                log::info!("Trip reset");
+                self.sensor_updates.with_manager(|manager| manager.reset_trip());
+            }
+            UIEvent::ToggleSensorFiltering => {
+                let enabled = self.sensor_updates.with_manager(|manager| {
+                    let enabled = !manager.filtering_enabled();
+                    manager.set_filtering_enabled(enabled);
+                    enabled
+                });
+                log::info!("Sensor filtering {}", if enabled { "enabled" } else { "disabled" });
+            }
             UIEvent::SwitchSensorSet => {
-                if let Ok(new_manager) = self.sensor_config_rx.try_recv() {
-                    self.sensor_manager = new_manager;
+                if let Ok(mut new_manager) = self.sensor_config_rx.try_recv() {
+                    self.sensor_updates.with_manager(|manager| {
+                        new_manager.set_filtering_enabled(manager.filtering_enabled());
+                        *manager = new_manager;
+                    });
+                }
+            }
+            UIEvent::EncoderClockwise | UIEvent::EncoderCounterClockwise => {
+                let step = if matches!(event, UIEvent::EncoderClockwise) { 1 } else { -1 };
+                if self.encoder_adjusts_brightness {
+                    if step > 0 { self.brightness_up() } else { self.brightness_down() }
+                } else if self.get_current_page_mut().is_some_and(|page| page.on_encoder(step)) {
+                    // Used by the page
+                } else if let Some(page_id) = self.adjacent_page_id(step) {
+                    if let Err(e) = self.switch_page(page_id) {
+                        log::error!("Failed to switch to page {}: {}", page_id, e);
+                    }
+                }
+            }
+            UIEvent::EncoderPress => {
+                self.encoder_adjusts_brightness = !self.encoder_adjusts_brightness;
+                log::info!("Encoder now adjusts {}", if self.encoder_adjusts_brightness { "brightness" } else { "pages" });
+            }
+            UIEvent::SwipeLeft | UIEvent::SwipeRight => {
+                // Pages lie side by side: dragging the current one left brings in the next
+                let step = if matches!(event, UIEvent::SwipeLeft) { 1 } else { -1 };
+                if let Some(page_id) = self.adjacent_page_id(step) {
+                    if let Err(e) = self.navigate_to(page_id) {
+                        log::error!("Failed to switch to page {}: {}", page_id, e);
+                    }
+                }
+            }
+            UIEvent::TouchRelease { x, y } => {
+                if let Some(pos) = self.button_position_at(x, y) {
+                    if let Some(button) = self.get_current_page_mut().and_then(|page| page.button_by_position_mut(pos)) {
+                        button.trigger();
+                    }
                 }
             }
             _ => {}
         }
     }
     
+    /// Page `step` places after the current one in registration order, wrapping around
+    fn adjacent_page_id(&self, step: i32) -> Option<u32> {
+        let pages = &self.pages.pages;
+        if pages.is_empty() {
+            return None;
+        }
+        let current = self.current_page
+            .and_then(|id| pages.iter().position(|page| page.id() == id))
+            .unwrap_or(0);
+        let next = (current as i32 + step).rem_euclid(pages.len() as i32) as usize;
+        Some(pages[next].id())
+    }
+
+    /// Button whose label anchor is closest to a touch point, if close enough to count as a tap on it
+    fn button_position_at(&self, x: f32, y: f32) -> Option<ButtonPosition> {
+        const TOUCH_RADIUS: f32 = 80.0;
+        let orientation = self.ui_style.get_string(PAGE_BUTTON_LABEL_ORIENTATION, "horizontal");
+        self.buttons_map.values()
+            .map(|pos| {
+                let (bx, by) = self.get_button_position(pos, &orientation);
+                (*pos, ((bx - x).powi(2) + (by - y).powi(2)).sqrt())
+            })
+            .filter(|&(_, distance)| distance <= TOUCH_RADIUS)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(pos, _)| pos)
+    }
+
     fn get_button_position(&self, pos: &ButtonPosition, _orientation: &String) -> (f32, f32) {
         let screen_width = self.context.width as f32;
         let screen_height = self.context.height as f32 - STATUS_LINE_Y_MARGIN;
@@ -749,8 +1103,7 @@ impl PageManager {
     
     fn render_status_line(&mut self) -> Result<(), String> {
         let elapsed = self.start_time.elapsed();
-        let fps = self.fps_counter.get_fps();
-        let _frame_count = self.fps_counter.get_frame_count();
+        let fps = self.frame_limiter.fps();
         
         // Get memory information
         let (mem_total, mem_available) = self.get_memory_info().unwrap_or((0, 0));
@@ -848,6 +1201,148 @@ impl PageManager {
     // Brightness Control for UI
     // =============================================================================
 
+    /// Build the main page indicators from a JSON layout file instead of the built-in
+    /// sets. Takes effect in setup().
+    pub fn set_layout_file(&mut self, path: &str) {
+        self.layout_path = Some(path.to_string());
+    }
+
+    /// Watch a style file and apply edits to it while the dashboard is running.
+    /// Indicators that resolve their style at construction keep their original values.
+    pub fn watch_style_file(&mut self, path: &str) {
+        log::info!("Watching UI style file {}", path);
+        self.style_watcher = Some(UIStyle::watch_file(path));
+    }
+
+    fn update_theme_transition(&mut self) {
+        const THEME_TRANSITION_DURATION: Duration = Duration::from_secs(1);
+        let Some(transition) = &self.theme_transition else {
+            return;
+        };
+        let t = transition.started.elapsed().as_secs_f32() / THEME_TRANSITION_DURATION.as_secs_f32();
+        if t >= 1.0 {
+            self.finish_theme_transition();
+        } else {
+            self.ui_style = transition.target.blend_themes(&transition.from, &transition.to, t);
+        }
+    }
+
+    /// Jump to the end of a running theme transition
+    fn finish_theme_transition(&mut self) {
+        if let Some(transition) = self.theme_transition.take() {
+            self.ui_style = transition.target;
+        }
+    }
+
+    fn reload_style_if_changed(&mut self) {
+        const STYLE_RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+        if self.style_last_check.elapsed() < STYLE_RELOAD_CHECK_INTERVAL {
+            return;
+        }
+        self.style_last_check = Instant::now();
+
+        let Some(mut style) = self.style_watcher.as_mut().and_then(|watcher| watcher.poll()) else {
+            return;
+        };
+        // Don't let a blended transition style stand in for the real one
+        self.finish_theme_transition();
+        self.apply_saved_settings(&style);
+        // Keep the selected day/night theme across reloads, unless the file picks one
+        let theme = style.get(GLOBAL_THEME).and_then(|v| v.as_string().ok())
+            .or(self.ui_style.active_theme())
+            .map(str::to_string);
+        if let Some(theme) = theme {
+            if let Err(e) = style.set_active_theme(&theme) {
+                log::warn!("Reloaded style dropped the active theme: {}", e);
+            }
+        }
+        self.ui_style = style;
+    }
+
+    /// Apply the runtime settings a style file sets explicitly, as saved by the settings page.
+    /// Brightness is runtime state (buttons, backlight, dimming); without the key it is left alone.
+    fn apply_saved_settings(&mut self, style: &UIStyle) {
+        if let Some(enabled) = style.get(GLOBAL_BACKLIGHT_ENABLED).and_then(|v| v.as_bool().ok()) {
+            self.set_backlight_enabled(enabled);
+        }
+        if let Some(brightness) = style.get(GLOBAL_BRIGHTNESS).and_then(|v| v.as_float().ok()) {
+            self.set_brightness(brightness);
+        }
+        if let Some(enabled) = style.get(GLOBAL_BLOOM_ENABLED).and_then(|v| v.as_bool().ok()) {
+            self.context.set_bloom_enabled(enabled);
+        }
+    }
+
+    /// Write the settings page choices into the watched style file. The file's own values
+    /// are edited, so a hand-written style doesn't get expanded into the full defaults.
+    fn save_settings(&mut self) {
+        let Some(path) = self.style_watcher.as_ref().map(|watcher| watcher.path().to_string_lossy().into_owned()) else {
+            log::warn!("No style file watched, settings not saved");
+            return;
+        };
+        let mut style = if std::path::Path::new(&path).exists() {
+            match UIStyle::from_file(&path) {
+                Ok(style) => style,
+                Err(e) => {
+                    log::error!("Settings not saved, failed to read {}: {}", path, e);
+                    return;
+                }
+            }
+        } else {
+            UIStyle::new()
+        };
+
+        self.finish_theme_transition();
+        style.set(GLOBAL_BRIGHTNESS, UIStyleValue::Float(self.get_brightness()));
+        style.set(GLOBAL_THEME, UIStyleValue::String(self.ui_style.active_theme().unwrap_or(THEME_DAY).to_string()));
+        style.set(GLOBAL_BACKLIGHT_ENABLED, UIStyleValue::Boolean(self.context.has_hardware_backlight()));
+        style.set(GLOBAL_BLOOM_ENABLED, UIStyleValue::Boolean(self.context.is_bloom_enabled()));
+        match style.to_file(&path) {
+            Ok(()) => log::info!("Settings saved to {}", path),
+            Err(e) => log::error!("Failed to save settings to {}: {}", path, e),
+        }
+    }
+
+    /// Drive brightness through the panel backlight, or leave it at full and dim in software
+    fn set_backlight_enabled(&mut self, enabled: bool) {
+        if enabled == self.context.has_hardware_backlight() {
+            return;
+        }
+        let brightness = self.get_brightness();
+        if enabled {
+            self.context.detect_backlight();
+        } else {
+            self.context.release_backlight();
+        }
+        // Carry the current level over to whichever now does the dimming
+        self.set_brightness(brightness);
+    }
+
+    /// Restart the idle timer, restoring the brightness if the screen was dimmed
+    fn note_activity(&mut self) {
+        if let Some(IdleChange::Restore(brightness)) = self.idle_manager.activity(Instant::now()) {
+            log::info!("Activity - restoring brightness");
+            self.set_brightness(brightness);
+        }
+    }
+
+    /// Dim the screen once it has been idle for the style's timeout with the vehicle stopped
+    fn update_idle_dimming(&mut self) {
+        // Below this the speed sensor is noise rather than motion
+        const STANDSTILL_SPEED: f32 = 1.0;
+        let moving = self.sensors.get_sensor_value(&HWInput::HwSpeed)
+            .is_some_and(|speed| speed.as_f32() > STANDSTILL_SPEED);
+        if moving {
+            self.note_activity();
+            return;
+        }
+        let timeout = Duration::from_secs_f32(self.ui_style.get_float(PAGE_IDLE_DIM_TIMEOUT, 300.0).max(0.0));
+        if let Some(IdleChange::Dim) = self.idle_manager.poll(Instant::now(), timeout, self.get_brightness()) {
+            log::info!("No activity for {:?} - dimming the screen", timeout);
+            self.set_brightness(self.ui_style.get_float(PAGE_IDLE_DIM_BRIGHTNESS, 0.2));
+        }
+    }
+
     /// Set display brightness (0.0 to 1.0)
     pub fn set_brightness(&mut self, brightness: f32) {
         self.context.set_brightness(brightness);
@@ -973,51 +1468,3 @@ impl PageManager {
     }
 
 }
-
-#[derive(Debug)]
-pub struct FpsCounter {
-    frame_count: u32,
-    last_time: Instant,
-    current_fps: f32,
-    frame_times: Vec<Duration>,
-    max_samples: usize,
-}
-
-impl FpsCounter {
-    pub fn new() -> Self {
-        Self {
-            frame_count: 0,
-            last_time: Instant::now(),
-            current_fps: 0.0,
-            frame_times: Vec::new(),
-            max_samples: 60, // Track last 60 frames for smoothing
-        }
-    }
-    
-    pub fn update(&mut self) {
-        let now = Instant::now();
-        let delta = now.duration_since(self.last_time);
-        
-        self.frame_times.push(delta);
-        if self.frame_times.len() > self.max_samples {
-            self.frame_times.remove(0);
-        }
-        
-        self.frame_count += 1;
-        self.last_time = now;
-        
-        // Calculate FPS from average frame time
-        if !self.frame_times.is_empty() {
-            let avg_frame_time: Duration = self.frame_times.iter().sum::<Duration>() / self.frame_times.len() as u32;
-            self.current_fps = 1.0 / avg_frame_time.as_secs_f32();
-        }
-    }
-    
-    pub fn get_fps(&self) -> f32 {
-        self.current_fps
-    }
-    
-    pub fn get_frame_count(&self) -> u32 {
-        self.frame_count
-    }
-}
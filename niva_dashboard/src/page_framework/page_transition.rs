@@ -0,0 +1,80 @@
+#![allow(dead_code)]
+use std::time::{Duration, Instant};
+
+/// How the outgoing page makes way for the incoming one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionKind {
+    None,        // Instant cut
+    SlideLeft,   // New page pushes the old one out to the left
+    SlideRight,  // New page pushes the old one out to the right
+    Crossfade,
+}
+
+/// Placement of the two pages at one moment of a transition, for
+/// GraphicsContext::composite_page_layers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransitionFrame {
+    pub from_offset: f32,  // Horizontal shift of the outgoing page, in screen widths
+    pub to_offset: f32,    // Horizontal shift of the incoming page, in screen widths
+    pub to_weight: f32,    // Opacity of the incoming page over the outgoing one
+}
+
+/// Page switch in progress: the page being left is still drawn until the transition completes
+pub struct PageTransition {
+    pub kind: TransitionKind,
+    pub from_page: u32,
+    started: Instant,
+    duration: Duration,
+}
+
+impl PageTransition {
+    pub fn new(kind: TransitionKind, from_page: u32, duration: Duration, started: Instant) -> Self {
+        PageTransition { kind, from_page, started, duration }
+    }
+
+    /// Fraction of the transition done at `now`, 0.0 ..= 1.0
+    pub fn progress_at(&self, now: Instant) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        (now.saturating_duration_since(self.started).as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    pub fn is_complete(&self, now: Instant) -> bool {
+        self.progress_at(now) >= 1.0
+    }
+
+    pub fn frame_at(&self, now: Instant) -> TransitionFrame {
+        let t = self.progress_at(now);
+        // Ease in and out so the slide doesn't start and stop with a jerk
+        let eased = t * t * (3.0 - 2.0 * t);
+        match self.kind {
+            TransitionKind::SlideLeft => TransitionFrame { from_offset: -eased, to_offset: 1.0 - eased, to_weight: 1.0 },
+            TransitionKind::SlideRight => TransitionFrame { from_offset: eased, to_offset: eased - 1.0, to_weight: 1.0 },
+            TransitionKind::Crossfade | TransitionKind::None => TransitionFrame { from_offset: 0.0, to_offset: 0.0, to_weight: t },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transition_completes_after_duration() {
+        let start = Instant::now();
+        let transition = PageTransition::new(TransitionKind::SlideLeft, 0, Duration::from_millis(300), start);
+        assert!(!transition.is_complete(start));
+        assert!(!transition.is_complete(start + Duration::from_millis(150)));
+        assert!((transition.progress_at(start + Duration::from_millis(150)) - 0.5).abs() < 1e-6);
+        assert!(transition.is_complete(start + Duration::from_millis(300)));
+        assert!(transition.is_complete(start + Duration::from_secs(5)));
+
+        // Halfway through a slide both pages share the screen
+        let frame = transition.frame_at(start + Duration::from_millis(150));
+        assert_eq!(frame, TransitionFrame { from_offset: -0.5, to_offset: 0.5, to_weight: 1.0 });
+        // Finished: only the new page, unshifted
+        let frame = transition.frame_at(start + Duration::from_millis(300));
+        assert_eq!(frame.to_offset, 0.0);
+    }
+}
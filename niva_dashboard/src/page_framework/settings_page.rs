@@ -0,0 +1,284 @@
+#![allow(dead_code)]
+use std::cell::Cell;
+
+use crate::graphics::context::{GraphicsContext, MIN_BRIGHTNESS, MAX_BRIGHTNESS};
+use crate::graphics::ui_style::*;
+use crate::hardware::sensor_manager::SensorValuesSnapshot;
+use crate::page_framework::events::{EventReceiver, SmartEventSender, UIEvent};
+use crate::page_framework::page_manager::{Page, PageBase, PageButton, ButtonPosition, MAIN_PAGE_ID};
+
+const CONTENT_X_MARGIN: f32 = 40.0;
+const TITLE_Y: f32 = 20.0;
+const ROW_GAP: f32 = 16.0;
+const VALUE_X: f32 = 360.0;
+const BRIGHTNESS_STEP: f32 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsControl {
+    Brightness,
+    Backlight,
+    Theme,
+    Bloom,
+}
+
+const CONTROLS: [SettingsControl; 4] = [
+    SettingsControl::Brightness,
+    SettingsControl::Backlight,
+    SettingsControl::Theme,
+    SettingsControl::Bloom,
+];
+
+impl SettingsControl {
+    fn label(&self) -> &'static str {
+        match self {
+            SettingsControl::Brightness => "ЯРКОСТЬ",
+            SettingsControl::Backlight => "ПОДСВЕТКА",
+            SettingsControl::Theme => "ТЕМА",
+            SettingsControl::Bloom => "СВЕЧЕНИЕ",
+        }
+    }
+}
+
+/// What the controls currently show, taken from the context and style on every render
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SettingsState {
+    brightness: f32,
+    backlight: bool,
+    night: bool,
+    bloom: bool,
+}
+
+/// On-device display settings. Changes go to PageManager as events and apply immediately;
+/// they are written to the style file when the page is left.
+pub struct SettingsPage {
+    base: PageBase,
+    event_receiver: EventReceiver,
+    smart_event_sender: SmartEventSender,
+    selected: usize,
+    state: Cell<SettingsState>,
+    // Something was changed since entering the page
+    changed: bool,
+}
+
+impl SettingsPage {
+    pub fn new(id: u32, smart_event_sender: SmartEventSender, event_receiver: EventReceiver) -> Self {
+        let mut settings_page = SettingsPage {
+            base: PageBase::new(id, "Settings".to_string()),
+            event_receiver,
+            smart_event_sender,
+            selected: 0,
+            state: Cell::new(SettingsState { brightness: MAX_BRIGHTNESS, backlight: false, night: false, bloom: false }),
+            changed: false,
+        };
+        settings_page.setup_buttons();
+        settings_page
+    }
+
+    fn setup_buttons(&mut self) {
+        let buttons = vec![
+            PageButton::new(ButtonPosition::Left1, "ВВЕРХ".into(), Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::ButtonPressed("select_prev".into()))
+            }) as Box<dyn FnMut()>),
+            PageButton::new(ButtonPosition::Left2, "ВНИЗ".into(), Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::ButtonPressed("select_next".into()))
+            }) as Box<dyn FnMut()>),
+            PageButton::new(ButtonPosition::Right1, "БОЛЬШЕ".into(), Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::ButtonPressed("adjust_up".into()))
+            }) as Box<dyn FnMut()>),
+            PageButton::new(ButtonPosition::Right2, "МЕНЬШЕ".into(), Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::ButtonPressed("adjust_down".into()))
+            }) as Box<dyn FnMut()>),
+            PageButton::new(ButtonPosition::Right4, "ВОЗВ".into(), Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::SwitchToPage(MAIN_PAGE_ID))
+            }) as Box<dyn FnMut()>),
+        ];
+        self.base.set_buttons(buttons);
+    }
+
+    pub fn selected_control(&self) -> SettingsControl {
+        CONTROLS[self.selected]
+    }
+
+    /// Move the selection by `step` rows, wrapping around
+    pub fn select(&mut self, step: i32) {
+        self.selected = (self.selected as i32 + step).rem_euclid(CONTROLS.len() as i32) as usize;
+    }
+
+    /// Change the selected control: brightness moves by `step` tenths, switches flip either way
+    pub fn adjust(&mut self, step: i32) {
+        if step == 0 {
+            return;
+        }
+        let mut state = self.state.get();
+        let event = match self.selected_control() {
+            SettingsControl::Brightness => {
+                state.brightness = (state.brightness + step as f32 * BRIGHTNESS_STEP).clamp(MIN_BRIGHTNESS, MAX_BRIGHTNESS);
+                UIEvent::SetBrightness(state.brightness)
+            }
+            SettingsControl::Backlight => {
+                state.backlight = !state.backlight;
+                UIEvent::SetBacklightEnabled(state.backlight)
+            }
+            SettingsControl::Theme => {
+                state.night = !state.night;
+                UIEvent::ToggleTheme
+            }
+            SettingsControl::Bloom => {
+                state.bloom = !state.bloom;
+                UIEvent::SetBloomEnabled(state.bloom)
+            }
+        };
+        // Held until the next render refreshes it, so quick repeated steps accumulate
+        self.state.set(state);
+        self.changed = true;
+        self.smart_event_sender.send(event);
+    }
+
+    fn value_text(control: SettingsControl, state: &SettingsState) -> String {
+        let on_off = |on: bool| if on { "ВКЛ" } else { "ВЫКЛ" }.to_string();
+        match control {
+            SettingsControl::Brightness => format!("{:.0}%", state.brightness * 100.0),
+            SettingsControl::Backlight => on_off(state.backlight),
+            SettingsControl::Theme => if state.night { "НОЧЬ" } else { "ДЕНЬ" }.to_string(),
+            SettingsControl::Bloom => on_off(state.bloom),
+        }
+    }
+}
+
+impl Page for SettingsPage {
+    fn id(&self) -> u32 {
+        self.base.id()
+    }
+
+    fn name(&self) -> &str {
+        self.base.name()
+    }
+
+    fn set_buttons(&mut self, buttons: Vec<PageButton<Box<dyn FnMut()>>>) {
+        self.base.set_buttons(buttons);
+    }
+
+    fn buttons(&self) -> &Vec<PageButton<Box<dyn FnMut()>>> {
+        self.base.buttons()
+    }
+
+    fn button_by_position(&self, pos: ButtonPosition) -> Option<&PageButton<Box<dyn FnMut()>>> {
+        self.base.button_by_position(pos)
+    }
+
+    fn button_by_position_mut(&mut self, pos: ButtonPosition) -> Option<&mut PageButton<Box<dyn FnMut()>>> {
+        self.base.button_by_position_mut(pos)
+    }
+
+    fn render(&self, context: &mut GraphicsContext, _sensors: &SensorValuesSnapshot, ui_style: &UIStyle) -> Result<(), String> {
+        let mut state = SettingsState {
+            brightness: context.get_brightness(),
+            backlight: context.has_hardware_backlight(),
+            night: self.state.get().night,
+            bloom: context.is_bloom_enabled(),
+        };
+        // The blended style of a running theme transition has no active theme; keep the last one
+        if let Some(theme) = ui_style.active_theme() {
+            state.night = theme == THEME_NIGHT;
+        }
+        self.state.set(state);
+
+        let font = ui_style.get_string(TEXT_PRIMARY_FONT, DEFAULT_GLOBAL_FONT_PATH);
+        let font_size = ui_style.get_integer(TEXT_PRIMARY_FONT_SIZE, 24);
+        let primary_color = ui_style.get_color(TEXT_PRIMARY_COLOR, (1.0, 1.0, 1.0));
+        let secondary_color = ui_style.get_color(TEXT_SECONDARY_COLOR, (0.8, 0.8, 0.8));
+        let selected_color = ui_style.get_color(TEXT_WARNING_COLOR, (1.0, 1.0, 0.0));
+
+        context.render_text_with_font("НАСТРОЙКИ", CONTENT_X_MARGIN, TITLE_Y, 1.0, primary_color, &font, font_size)?;
+        let line_height = context.get_line_height_with_font(1.0, &font, font_size)?;
+
+        let mut y = TITLE_Y + line_height + ROW_GAP * 2.0;
+        for (i, control) in CONTROLS.iter().enumerate() {
+            let color = if i == self.selected { selected_color } else { secondary_color };
+            context.render_text_with_font(control.label(), CONTENT_X_MARGIN, y, 1.0, color, &font, font_size)?;
+            context.render_text_with_font(&Self::value_text(*control, &state), VALUE_X, y, 1.0, color, &font, font_size)?;
+            y += line_height + ROW_GAP;
+        }
+
+        Ok(())
+    }
+
+    fn on_enter(&mut self) -> Result<(), String> {
+        self.changed = false;
+        Ok(())
+    }
+
+    fn on_exit(&mut self) -> Result<(), String> {
+        if self.changed {
+            self.smart_event_sender.send(UIEvent::SaveSettings);
+            self.changed = false;
+        }
+        Ok(())
+    }
+
+    fn on_button(&mut self, _button: char) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn on_encoder(&mut self, step: i32) -> bool {
+        self.adjust(step);
+        true
+    }
+
+    fn process_events(&mut self) {
+        while let Ok(event) = self.event_receiver.try_recv() {
+            match event {
+                UIEvent::ButtonPressed(action) if action == "select_prev" => self.select(-1),
+                UIEvent::ButtonPressed(action) if action == "select_next" => self.select(1),
+                UIEvent::ButtonPressed(action) if action == "adjust_up" => self.adjust(1),
+                UIEvent::ButtonPressed(action) if action == "adjust_down" => self.adjust(-1),
+                UIEvent::SwipeDown => self.select(-1),
+                UIEvent::SwipeUp => self.select(1),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page_framework::events::EventBus;
+
+    #[test]
+    fn test_brightness_control_sends_clamped_level() {
+        let bus = EventBus::unbounded();
+        let global = bus.global_receiver();
+        let mut page = SettingsPage::new(0, bus.smart_sender(), bus.page_receiver());
+        assert_eq!(page.selected_control(), SettingsControl::Brightness);
+
+        // Already at full: a step up stays at the maximum
+        page.adjust(1);
+        assert!(matches!(global.try_recv(), Ok(UIEvent::SetBrightness(level)) if level == MAX_BRIGHTNESS));
+
+        // Steps accumulate between renders and stop at the minimum
+        for _ in 0..20 {
+            page.adjust(-1);
+        }
+        let levels: Vec<f32> = global.try_iter()
+            .map(|event| match event {
+                UIEvent::SetBrightness(level) => level,
+                other => panic!("Unexpected event {:?}", other),
+            })
+            .collect();
+        assert_eq!(levels.len(), 20);
+        assert!((levels[0] - 0.9).abs() < 1e-6);
+        assert_eq!(*levels.last().unwrap(), MIN_BRIGHTNESS);
+
+        // Switch controls flip and wrap around
+        page.select(-1);
+        assert_eq!(page.selected_control(), SettingsControl::Bloom);
+        page.adjust(1);
+        assert!(matches!(global.try_recv(), Ok(UIEvent::SetBloomEnabled(true))));
+    }
+}
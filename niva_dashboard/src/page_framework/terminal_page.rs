@@ -6,7 +6,7 @@ use std::time::{Duration, Instant};
 use crate::graphics::context::GraphicsContext;
 use crate::graphics::text_box::TextBoxRenderer;
 use crate::graphics::ui_style::*;
-use crate::hardware::sensor_manager::SensorManager;
+use crate::hardware::sensor_manager::SensorValuesSnapshot;
 use crate::page_framework::events::{EventReceiver, SmartEventSender, UIEvent};
 use crate::page_framework::page_manager::{ButtonPosition, Page, PageBase, PageButton, DIAG_PAGE_ID};
 use crate::util::adc_data_provider::ADCFrame;
@@ -167,7 +167,7 @@ impl Page for TerminalPage {
         self.base.set_buttons(buttons);
     }
 
-    fn render(&self, context: &mut GraphicsContext, _sensor_manager: &SensorManager, ui_style: &UIStyle) -> Result<(), String> {
+    fn render(&self, context: &mut GraphicsContext, _sensors: &SensorValuesSnapshot, ui_style: &UIStyle) -> Result<(), String> {
         let x = CONTENT_X_MARGIN;
         let y = CONTENT_TOP_MARGIN;
         let width = (context.width as f32 - 2.0 * CONTENT_X_MARGIN).max(0.0);
@@ -0,0 +1,370 @@
+#![allow(dead_code)]
+use std::fs::{self, File};
+use std::io::Read;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use crate::page_framework::events::UIEvent;
+use crate::graphics::context::{Mirror, Rotation};
+
+const INPUT_CLASS_DIR: &str = "/sys/class/input";
+
+// Linux input event codes (linux/input-event-codes.h)
+const EV_SYN: u16 = 0x00;
+const EV_KEY: u16 = 0x01;
+const EV_ABS: u16 = 0x03;
+const SYN_REPORT: u16 = 0x00;
+const BTN_TOUCH: u16 = 0x14a;
+const ABS_X: u16 = 0x00;
+const ABS_Y: u16 = 0x01;
+const ABS_MT_POSITION_X: u16 = 0x35;
+const ABS_MT_POSITION_Y: u16 = 0x36;
+
+/// Maps raw touch controller coordinates to screen coordinates:
+/// screen = (raw - offset) * scale, with the axes swapped first for panels mounted rotated.
+/// A flipped axis has a negative scale and its offset at the raw maximum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchCalibration {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub swap_xy: bool,
+}
+
+impl TouchCalibration {
+    /// Calibration stretching the controller's reported axis ranges over the whole logical
+    /// screen. The controller is taken to be aligned with the panel, so its axes are turned
+    /// and flipped back the way the display rotation and mirror turn the image.
+    pub fn from_axis_range(x_range: (i32, i32), y_range: (i32, i32), screen_width: f32, screen_height: f32,
+                           rotation: Rotation, mirror: Mirror) -> Self {
+        // Whether logical x comes from the raw y axis, and which logical axes run backwards
+        let (swap_xy, flip_x, flip_y) = match rotation {
+            Rotation::Deg0 => (false, false, false),
+            Rotation::Deg90 => (true, false, true),
+            Rotation::Deg180 => (false, true, true),
+            Rotation::Deg270 => (true, true, false),
+        };
+        let (flip_x, flip_y) = (flip_x ^ mirror.horizontal, flip_y ^ mirror.vertical);
+        let (x_range, y_range) = if swap_xy { (y_range, x_range) } else { (x_range, y_range) };
+
+        let axis = |(min, max): (i32, i32), size: f32, flip: bool| {
+            let scale = size / ((max - min) as f32).max(1.0);
+            if flip { (max as f32, -scale) } else { (min as f32, scale) }
+        };
+        let (offset_x, scale_x) = axis(x_range, screen_width, flip_x);
+        let (offset_y, scale_y) = axis(y_range, screen_height, flip_y);
+        TouchCalibration { offset_x, offset_y, scale_x, scale_y, swap_xy }
+    }
+
+    pub fn with_swap_xy(mut self, swap: bool) -> Self {
+        self.swap_xy = swap;
+        self
+    }
+
+    pub fn apply(&self, raw_x: i32, raw_y: i32) -> (f32, f32) {
+        let (x, y) = if self.swap_xy { (raw_y, raw_x) } else { (raw_x, raw_y) };
+        ((x as f32 - self.offset_x) * self.scale_x, (y as f32 - self.offset_y) * self.scale_y)
+    }
+}
+
+/// Turns a stream of evdev (type, code, value) triples into touch UI events.
+/// Events are emitted on SYN_REPORT, once the whole packet has been seen.
+struct TouchDecoder {
+    calibration: TouchCalibration,
+    raw_x: i32,
+    raw_y: i32,
+    touching: bool,
+    // State as of the last emitted event
+    reported_touching: bool,
+    moved: bool,
+}
+
+impl TouchDecoder {
+    fn new(calibration: TouchCalibration) -> Self {
+        TouchDecoder { calibration, raw_x: 0, raw_y: 0, touching: false, reported_touching: false, moved: false }
+    }
+
+    fn process(&mut self, event_type: u16, code: u16, value: i32) -> Option<UIEvent> {
+        match (event_type, code) {
+            (EV_ABS, ABS_X | ABS_MT_POSITION_X) => { self.raw_x = value; self.moved = true; }
+            (EV_ABS, ABS_Y | ABS_MT_POSITION_Y) => { self.raw_y = value; self.moved = true; }
+            (EV_KEY, BTN_TOUCH) => self.touching = value != 0,
+            (EV_SYN, SYN_REPORT) => return self.report(),
+            _ => {}
+        }
+        None
+    }
+
+    fn report(&mut self) -> Option<UIEvent> {
+        let (x, y) = self.calibration.apply(self.raw_x, self.raw_y);
+        let event = match (self.reported_touching, self.touching) {
+            (false, true) => Some(UIEvent::Touch { x, y }),
+            (true, true) if self.moved => Some(UIEvent::Touch { x, y }),
+            (true, false) => Some(UIEvent::TouchRelease { x, y }),
+            _ => None,
+        };
+        self.reported_touching = self.touching;
+        self.moved = false;
+        event
+    }
+}
+
+/// Classifies each touch from press to release as a swipe or a tap. Swipes replace
+/// the release event, so a swipe starting on a button label doesn't press the button.
+struct GestureRecognizer {
+    // Where and when the finger went down; None while not touching
+    start: Option<(f32, f32, Instant)>,
+}
+
+impl GestureRecognizer {
+    fn new() -> Self {
+        GestureRecognizer { start: None }
+    }
+
+    fn process(&mut self, event: UIEvent, now: Instant) -> Option<UIEvent> {
+        const SWIPE_MIN_DISTANCE: f32 = 80.0;      // Pixels
+        const SWIPE_MIN_VELOCITY: f32 = 300.0;     // Pixels per second
+        const SWIPE_MAX_DURATION: Duration = Duration::from_millis(700);
+
+        match event {
+            UIEvent::Touch { x, y } => {
+                self.start.get_or_insert((x, y, now));
+                Some(event)
+            }
+            UIEvent::TouchRelease { x, y } => {
+                let (start_x, start_y, started) = self.start.take()?;
+                let (dx, dy) = (x - start_x, y - start_y);
+                let distance = dx.hypot(dy);
+                if distance < SWIPE_MIN_DISTANCE {
+                    return Some(event);
+                }
+                let duration = now.saturating_duration_since(started);
+                // Slow drags are neither swipes nor taps
+                if duration > SWIPE_MAX_DURATION || distance / duration.as_secs_f32().max(0.001) < SWIPE_MIN_VELOCITY {
+                    return None;
+                }
+                Some(if dx.abs() >= dy.abs() {
+                    if dx < 0.0 { UIEvent::SwipeLeft } else { UIEvent::SwipeRight }
+                } else if dy < 0.0 {
+                    UIEvent::SwipeUp
+                } else {
+                    UIEvent::SwipeDown
+                })
+            }
+            _ => Some(event),
+        }
+    }
+}
+
+/// Touchscreen read from a /dev/input/event* device. The device is opened non-blocking
+/// and drained once per frame from the page manager's event loop.
+pub struct TouchInput {
+    device: File,
+    device_path: PathBuf,
+    decoder: TouchDecoder,
+    gestures: GestureRecognizer,
+}
+
+impl TouchInput {
+    /// Find the first input device reporting absolute X/Y and BTN_TOUCH, calibrated to
+    /// cover the given logical screen size as rotated and mirrored on the display
+    pub fn detect(screen_width: f32, screen_height: f32, rotation: Rotation, mirror: Mirror) -> Option<Self> {
+        let device_path = find_touch_device(Path::new(INPUT_CLASS_DIR))?;
+        match Self::open(&device_path, screen_width, screen_height, rotation, mirror) {
+            Ok(touch) => Some(touch),
+            Err(e) => {
+                log::warn!("Touch device {} unusable: {}", device_path.display(), e);
+                None
+            }
+        }
+    }
+
+    pub fn open(device_path: &Path, screen_width: f32, screen_height: f32,
+                rotation: Rotation, mirror: Mirror) -> Result<Self, String> {
+        let device = fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(device_path)
+            .map_err(|e| format!("Failed to open {}: {}", device_path.display(), e))?;
+        let x_range = abs_axis_range(&device, ABS_X)?;
+        let y_range = abs_axis_range(&device, ABS_Y)?;
+        log::info!("Touch device {}: x {:?}, y {:?}", device_path.display(), x_range, y_range);
+        Ok(TouchInput {
+            device,
+            device_path: device_path.to_path_buf(),
+            decoder: TouchDecoder::new(TouchCalibration::from_axis_range(x_range, y_range, screen_width, screen_height,
+                                                                         rotation, mirror)),
+            gestures: GestureRecognizer::new(),
+        })
+    }
+
+    /// Replace the calibration derived from the controller's axis ranges
+    pub fn with_calibration(mut self, calibration: TouchCalibration) -> Self {
+        self.decoder.calibration = calibration;
+        self
+    }
+
+    pub fn device_path(&self) -> &Path {
+        &self.device_path
+    }
+
+    /// Read all pending input events without blocking
+    pub fn poll(&mut self) -> Vec<UIEvent> {
+        let mut events = Vec::new();
+        let mut buffer = [0u8; std::mem::size_of::<libc::input_event>() * 16];
+        loop {
+            let read = match self.device.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => break,
+                Err(e) => {
+                    log::error!("Touch device {} read error: {}", self.device_path.display(), e);
+                    break;
+                }
+            };
+            for chunk in buffer[..read].chunks_exact(std::mem::size_of::<libc::input_event>()) {
+                // The kernel only hands out whole events, chunks_exact drops nothing
+                let event: libc::input_event = unsafe { std::ptr::read_unaligned(chunk.as_ptr().cast()) };
+                if let Some(ui_event) = self.decoder.process(event.type_, event.code, event.value)
+                    .and_then(|ui_event| self.gestures.process(ui_event, Instant::now())) {
+                    events.push(ui_event);
+                }
+            }
+        }
+        events
+    }
+}
+
+/// (minimum, maximum) of an absolute axis, via the EVIOCGABS ioctl
+fn abs_axis_range(device: &File, axis: u16) -> Result<(i32, i32), String> {
+    let mut info: libc::input_absinfo = unsafe { std::mem::zeroed() };
+    // _IOR('E', 0x40 + axis, struct input_absinfo)
+    let request = (2 << 30) | ((std::mem::size_of::<libc::input_absinfo>() as u64) << 16)
+        | ((b'E' as u64) << 8) | (0x40 + axis as u64);
+    if unsafe { libc::ioctl(device.as_raw_fd(), request as _, &mut info) } < 0 {
+        return Err(format!("EVIOCGABS({}) failed: {}", axis, std::io::Error::last_os_error()));
+    }
+    Ok((info.minimum, info.maximum))
+}
+
+/// Whether bit `bit` is set in a sysfs capability bitmap ("hex words, most significant first")
+fn capability_bit(bitmap: &str, bit: usize) -> bool {
+    let words: Vec<&str> = bitmap.split_whitespace().collect();
+    let word_bits = usize::BITS as usize;
+    let Some(word) = words.len().checked_sub(1 + bit / word_bits).and_then(|i| words.get(i)) else {
+        return false;
+    };
+    usize::from_str_radix(word, 16).map(|w| w & (1 << (bit % word_bits)) != 0).unwrap_or(false)
+}
+
+fn find_touch_device(class_dir: &Path) -> Option<PathBuf> {
+    let mut devices: Vec<PathBuf> = fs::read_dir(class_dir).ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.file_name().is_some_and(|name| name.to_string_lossy().starts_with("event")))
+        .collect();
+    // read_dir order is unspecified; keep the pick stable across boots
+    devices.sort();
+
+    devices.into_iter().find(|dir| {
+        let capabilities = dir.join("device/capabilities");
+        let abs = fs::read_to_string(capabilities.join("abs")).unwrap_or_default();
+        let key = fs::read_to_string(capabilities.join("key")).unwrap_or_default();
+        capability_bit(&abs, ABS_X as usize) && capability_bit(&abs, ABS_Y as usize)
+            && capability_bit(&key, BTN_TOUCH as usize)
+    })
+    .map(|dir| Path::new("/dev/input").join(dir.file_name().unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibration_maps_raw_to_screen() {
+        // Controller reporting 0..4095 on both axes, 800x480 screen
+        let calibration = TouchCalibration::from_axis_range((0, 4095), (0, 4095), 800.0, 480.0,
+                                                            Rotation::Deg0, Mirror::default());
+        let (x, y) = calibration.apply(2048, 1024);
+        assert!((x - 400.1).abs() < 0.1);
+        assert!((y - 120.0).abs() < 0.1);
+
+        // Offset range and swapped axes
+        let calibration = TouchCalibration { offset_x: 100.0, offset_y: 50.0, scale_x: 0.5, scale_y: 2.0, swap_xy: true };
+        assert_eq!(calibration.apply(150, 300), (100.0, 200.0));
+    }
+
+    #[test]
+    fn test_calibration_follows_display_orientation() {
+        // 800x480 panel with a matching controller; corners of the raw range
+        let calibrate = |rotation: Rotation, mirror: Mirror| {
+            let (width, height) = if rotation.is_transposed() { (480.0, 800.0) } else { (800.0, 480.0) };
+            TouchCalibration::from_axis_range((0, 800), (0, 480), width, height, rotation, mirror)
+        };
+        let none = Mirror::default();
+
+        // Turned clockwise, the logical top-left is drawn at the panel's top-right
+        assert_eq!(calibrate(Rotation::Deg90, none).apply(800, 0), (0.0, 0.0));
+        assert_eq!(calibrate(Rotation::Deg90, none).apply(0, 480), (480.0, 800.0));
+        assert_eq!(calibrate(Rotation::Deg180, none).apply(800, 480), (0.0, 0.0));
+        assert_eq!(calibrate(Rotation::Deg270, none).apply(0, 480), (0.0, 0.0));
+
+        // Mirroring flips the logical axis whichever way the panel is turned
+        let horizontal = Mirror { horizontal: true, vertical: false };
+        assert_eq!(calibrate(Rotation::Deg0, horizontal).apply(800, 0), (0.0, 0.0));
+        assert_eq!(calibrate(Rotation::Deg90, horizontal).apply(800, 480), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_decoder_emits_touch_and_release() {
+        let mut decoder = TouchDecoder::new(TouchCalibration::from_axis_range((0, 800), (0, 480), 800.0, 480.0,
+                                                                              Rotation::Deg0, Mirror::default()));
+        assert!(decoder.process(EV_ABS, ABS_X, 100).is_none());
+        assert!(decoder.process(EV_ABS, ABS_Y, 200).is_none());
+        assert!(decoder.process(EV_KEY, BTN_TOUCH, 1).is_none());
+        assert!(matches!(decoder.process(EV_SYN, SYN_REPORT, 0), Some(UIEvent::Touch { x, y }) if x == 100.0 && y == 200.0));
+        // Packet without changes: nothing new
+        assert!(decoder.process(EV_SYN, SYN_REPORT, 0).is_none());
+        decoder.process(EV_KEY, BTN_TOUCH, 0);
+        assert!(matches!(decoder.process(EV_SYN, SYN_REPORT, 0), Some(UIEvent::TouchRelease { .. })));
+    }
+
+    #[test]
+    fn test_fast_horizontal_drag_is_swipe() {
+        let start = Instant::now();
+        let mut gestures = GestureRecognizer::new();
+        gestures.process(UIEvent::Touch { x: 600.0, y: 240.0 }, start);
+        gestures.process(UIEvent::Touch { x: 450.0, y: 250.0 }, start + Duration::from_millis(60));
+        let release = gestures.process(UIEvent::TouchRelease { x: 300.0, y: 260.0 }, start + Duration::from_millis(150));
+        assert!(matches!(release, Some(UIEvent::SwipeLeft)));
+
+        gestures.process(UIEvent::Touch { x: 100.0, y: 100.0 }, start);
+        let release = gestures.process(UIEvent::TouchRelease { x: 110.0, y: 300.0 }, start + Duration::from_millis(200));
+        assert!(matches!(release, Some(UIEvent::SwipeDown)));
+
+        // A small wobble is still a tap
+        gestures.process(UIEvent::Touch { x: 100.0, y: 100.0 }, start);
+        let release = gestures.process(UIEvent::TouchRelease { x: 105.0, y: 98.0 }, start + Duration::from_millis(100));
+        assert!(matches!(release, Some(UIEvent::TouchRelease { .. })));
+
+        // Same distance, too slow for a swipe
+        gestures.process(UIEvent::Touch { x: 600.0, y: 240.0 }, start);
+        assert!(gestures.process(UIEvent::TouchRelease { x: 300.0, y: 240.0 }, start + Duration::from_secs(2)).is_none());
+    }
+
+    #[test]
+    fn test_capability_bits() {
+        assert!(capability_bit("3", 0) && capability_bit("3", 1));
+        assert!(!capability_bit("2", 0));
+        // BTN_TOUCH (0x14a) is in a higher word; the kernel prints native-width words
+        let word_bits = usize::BITS as usize;
+        let bit = BTN_TOUCH as usize;
+        let mut words = vec!["0".to_string(); bit / word_bits + 1];
+        words[0] = format!("{:x}", 1usize << (bit % word_bits));
+        let key = words.join(" ");
+        assert!(capability_bit(&key, bit));
+        assert!(!capability_bit(&key, bit - 1));
+        assert!(!capability_bit("", 0));
+    }
+}
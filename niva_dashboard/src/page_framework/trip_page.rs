@@ -0,0 +1,191 @@
+#![allow(dead_code)]
+use crate::graphics::context::GraphicsContext;
+use crate::graphics::ui_style::*;
+use crate::hardware::sensor_manager::{SensorValuesSnapshot, TRIP_DISTANCE_ID, TRIP_TIME_ID, TRIP_AVG_SPEED_ID,
+                                      FUEL_CONSUMPTION_ID, TRIP_AVG_CONSUMPTION_ID};
+use crate::indicators::{Indicator, IndicatorBounds};
+use crate::indicators::digital_segmented_indicator::DigitalSegmentedIndicator;
+use crate::page_framework::events::{EventReceiver, SmartEventSender, UIEvent};
+use crate::page_framework::page_manager::{Page, PageBase, PageButton, ButtonPosition, MAIN_PAGE_ID};
+
+const CONTENT_X_MARGIN: f32 = 40.0;
+const TITLE_Y: f32 = 20.0;
+const LABEL_GAP: f32 = 6.0;
+
+/// Trip computer readouts: (label, derived value id, digits, decimals), laid out two per row
+const TRIP_READOUTS: [(&str, &str, usize, usize); 5] = [
+    ("ПРОБЕГ", TRIP_DISTANCE_ID, 6, 1),
+    ("ВРЕМЯ В ПУТИ", TRIP_TIME_ID, 5, 1),
+    ("СРЕДНЯЯ СКОРОСТЬ", TRIP_AVG_SPEED_ID, 3, 0),
+    ("РАСХОД", FUEL_CONSUMPTION_ID, 4, 1),
+    ("СРЕДНИЙ РАСХОД", TRIP_AVG_CONSUMPTION_ID, 4, 1),
+];
+
+pub struct TripPage {
+    base: PageBase,
+    event_receiver: EventReceiver,
+    smart_event_sender: SmartEventSender,
+    readouts: Vec<DigitalSegmentedIndicator>,
+}
+
+impl TripPage {
+    pub fn new(id: u32, smart_event_sender: SmartEventSender, event_receiver: EventReceiver) -> Self {
+        let mut trip_page = TripPage {
+            base: PageBase::new(id, "Trip".to_string()),
+            event_receiver,
+            smart_event_sender,
+            readouts: TRIP_READOUTS.iter()
+                .map(|&(_, _, digits, decimals)| DigitalSegmentedIndicator::new(digits, decimals).with_shadow(true))
+                .collect(),
+        };
+        trip_page.setup_buttons();
+        trip_page
+    }
+
+    /// Derived values the page shows, in layout order
+    pub fn sensor_ids() -> Vec<&'static str> {
+        TRIP_READOUTS.iter().map(|&(_, id, _, _)| id).collect()
+    }
+
+    fn setup_buttons(&mut self) {
+        let buttons = vec![
+            // Reset needs a long press, so a stray tap doesn't lose the trip
+            PageButton::new(ButtonPosition::Left4, "СБРОС".into(), Box::new(|| {
+                log::info!("Hold the button to reset the trip");
+            }) as Box<dyn FnMut()>).with_long_press(Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::ResetTrip)
+            }) as Box<dyn FnMut()>),
+            PageButton::new(ButtonPosition::Right4, "ВОЗВ".into(), Box::new({
+                let sender = self.smart_event_sender.clone();
+                move || sender.send(UIEvent::SwitchToPage(MAIN_PAGE_ID))
+            }) as Box<dyn FnMut()>),
+        ];
+        self.base.set_buttons(buttons);
+    }
+}
+
+impl Page for TripPage {
+    fn id(&self) -> u32 {
+        self.base.id()
+    }
+
+    fn name(&self) -> &str {
+        self.base.name()
+    }
+
+    fn set_buttons(&mut self, buttons: Vec<PageButton<Box<dyn FnMut()>>>) {
+        self.base.set_buttons(buttons);
+    }
+
+    fn buttons(&self) -> &Vec<PageButton<Box<dyn FnMut()>>> {
+        self.base.buttons()
+    }
+
+    fn button_by_position(&self, pos: ButtonPosition) -> Option<&PageButton<Box<dyn FnMut()>>> {
+        self.base.button_by_position(pos)
+    }
+
+    fn button_by_position_mut(&mut self, pos: ButtonPosition) -> Option<&mut PageButton<Box<dyn FnMut()>>> {
+        self.base.button_by_position_mut(pos)
+    }
+
+    fn render(&self, context: &mut GraphicsContext, sensors: &SensorValuesSnapshot, ui_style: &UIStyle) -> Result<(), String> {
+        let title_font = ui_style.get_string(TEXT_PRIMARY_FONT, DEFAULT_GLOBAL_FONT_PATH);
+        let title_font_size = ui_style.get_integer(TEXT_PRIMARY_FONT_SIZE, 24);
+        let title_color = ui_style.get_color(TEXT_PRIMARY_COLOR, (1.0, 1.0, 1.0));
+        let label_font = ui_style.get_string(TEXT_SECONDARY_FONT, DEFAULT_GLOBAL_FONT_PATH);
+        let label_font_size = ui_style.get_integer(TEXT_SECONDARY_FONT_SIZE, 10);
+        let label_color = ui_style.get_color(TEXT_SECONDARY_COLOR, (0.8, 0.8, 0.8));
+
+        context.render_text_with_font("ПОЕЗДКА", CONTENT_X_MARGIN, TITLE_Y, 1.0, title_color, &title_font, title_font_size)?;
+        let title_height = context.calculate_text_height_with_font("ПОЕЗДКА", 1.0, &title_font, title_font_size)?;
+        let label_height = context.get_line_height_with_font(1.0, &label_font, label_font_size)?;
+
+        let top = TITLE_Y + title_height + LABEL_GAP * 2.0;
+        let rows = TRIP_READOUTS.len().div_ceil(2);
+        let cell_width = (context.width as f32 - CONTENT_X_MARGIN * 2.0) / 2.0;
+        let cell_height = (context.height as f32 - top - CONTENT_X_MARGIN) / rows as f32;
+
+        for (i, ((label, id, _, _), readout)) in TRIP_READOUTS.iter().zip(&self.readouts).enumerate() {
+            let x = CONTENT_X_MARGIN + (i % 2) as f32 * cell_width;
+            let y = top + (i / 2) as f32 * cell_height;
+            let bounds = IndicatorBounds::new(x, y + label_height + LABEL_GAP, cell_width * 0.8,
+                                              cell_height - label_height - LABEL_GAP * 3.0);
+
+            let value = sensors.get_derived_value(id);
+            let label = match value {
+                Some(value) => format!("{}, {}", label, value.metadata.unit),
+                None => label.to_string(),
+            };
+            context.render_text_with_font(&label, x, y, 1.0, label_color, &label_font, label_font_size)?;
+            match value {
+                Some(value) => readout.render(value, bounds, ui_style, context)?,
+                // No reading yet (no distance covered since the reset)
+                None => context.render_text_with_font("--", bounds.x, bounds.y, 1.0, label_color,
+                                                      &title_font, title_font_size)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn on_enter(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn on_exit(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn on_button(&mut self, _button: char) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn process_events(&mut self) {
+        // No page-scoped events handled; drain them so they don't pile up
+        while self.event_receiver.try_recv().is_ok() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hardware::hw_providers::{HWInput, TestMaxAnalogDataProvider};
+    use crate::hardware::sensor_manager::{SensorAnalogInputChain, SensorManager};
+    use crate::hardware::sensors::GenericAnalogSensor;
+    use crate::hardware::sensor_value::ValueConstraints;
+    use crate::hardware::distance_accumulator::DistanceAccumulator;
+    use crate::hardware::fuel_consumption::FuelConsumption;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_trip_page_sensor_ids() {
+        assert_eq!(TripPage::sensor_ids(),
+                   vec!["trip_distance", "trip_time", "trip_avg_speed", "fuel_consumption", "trip_avg_consumption"]);
+
+        // The sensor manager publishes every one of them once a trip is under way
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock_now = now.clone();
+        let mut manager = SensorManager::new().with_clock(Box::new(move || *clock_now.lock().unwrap()));
+        for (input, scale) in [(HWInput::HwSpeed, 36.0 / 1023.0), (HWInput::HwFuelLvl, 50.0 / 1023.0)] {
+            manager.add_analog_sensor_chain(SensorAnalogInputChain::new(
+                Box::new(TestMaxAnalogDataProvider::new(input)),
+                vec![],
+                Box::new(GenericAnalogSensor::new(format!("{:?}", input), String::new(), String::new(),
+                                                 ValueConstraints::analog(0.0, 180.0), scale)),
+            ));
+        }
+        manager.set_distance_accumulator(Some(DistanceAccumulator::new()));
+        manager.set_fuel_consumption(Some(FuelConsumption::new(42.0)));
+        // 36 km/h for 60 s = 0.6 km, enough for a consumption figure
+        for _ in 0..=60 {
+            manager.read_all_sensors().unwrap();
+            *now.lock().unwrap() += Duration::from_secs(1);
+        }
+        for id in TripPage::sensor_ids() {
+            assert!(manager.get_derived_value(id).is_some(), "{} missing", id);
+        }
+    }
+}
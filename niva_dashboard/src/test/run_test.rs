@@ -274,9 +274,10 @@ fn render_time_example(
 ) -> Result<(), String> {
     // Create time indicator with inactive segments for realistic 7-segment look
     let time_indicator = DigitalSegmentedIndicator::integer(4)
+        .with_leading_zeros(4)
         .with_inactive_segments(true);
     
-    // Convert hours to HHMM format (e.g., 10:43 -> 1043)
+    // Convert hours to HHMM format (e.g., 10:43 -> 1043, 09:43 -> 0943)
     let time_as_int = hours * 100 + 43; // Hardcoded minutes for demo
     let time_value = SensorValue::analog(
         time_as_int as f32,
@@ -17,6 +17,10 @@ const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
 /// so the two stay in agreement about what counts as "down".
 pub const ADC_LINK_MAX_AGE: Duration = Duration::from_millis(500);
 
+/// The STM32 module sends one frame per 50 Hz tick (`TICK_HZ` in its main.cpp). Counter
+/// channels such as TACHO carry the number of pulses seen during that tick.
+pub const ADC_FRAME_INTERVAL: Duration = Duration::from_millis(20);
+
 /// USB hub location for the STM32 ADC module, as reported by `uhubctl` (see
 /// PROJECT_CONTEXT.md "ADC module connectivity"). Hardware-specific — must be updated if
 /// the module is rewired to a different hub.
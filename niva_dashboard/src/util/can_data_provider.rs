@@ -0,0 +1,387 @@
+#![allow(dead_code)]
+use crate::hardware::hw_providers::HWInput;
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a decoded value stays valid without a fresh frame. ECUs broadcast their
+/// frames every 10-100 ms, so a second of silence means the bus or the ECU is gone.
+pub const CAN_SIGNAL_MAX_AGE: Duration = Duration::from_secs(1);
+
+/// Socket receive timeout, so the thread notices a stop request while the bus is quiet
+const READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Wait between attempts to open the interface (not up yet, adapter unplugged)
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ByteOrder {
+    #[default]
+    LittleEndian,
+    BigEndian,
+}
+
+/// One value carried in a CAN frame: where it sits, how to scale it, and how to show it
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanSignal {
+    pub input: HWInput,
+    // 11-bit or 29-bit identifier, without the SocketCAN flag bits
+    pub can_id: u32,
+    pub offset: usize,
+    pub length: usize,
+    pub byte_order: ByteOrder,
+    // value = raw * scale + value_offset
+    pub scale: f32,
+    pub value_offset: f32,
+    pub label: String,
+    pub unit: String,
+    pub min: f32,
+    pub max: f32,
+    // Served as an on/off input (non-zero = active) rather than a gauge value
+    pub digital: bool,
+}
+
+impl CanSignal {
+    /// Physical value from a frame's payload; None if the frame is too short to hold it
+    pub fn decode(&self, data: &[u8]) -> Option<f32> {
+        let bytes = data.get(self.offset..self.offset + self.length)?;
+        let raw = match self.byte_order {
+            ByteOrder::LittleEndian => bytes.iter().rev().fold(0u64, |acc, &b| acc << 8 | b as u64),
+            ByteOrder::BigEndian => bytes.iter().fold(0u64, |acc, &b| acc << 8 | b as u64),
+        };
+        Some(raw as f32 * self.scale + self.value_offset)
+    }
+}
+
+/// Every configured value carried by a frame, as (input, value)
+pub fn decode_frame(signals: &[CanSignal], can_id: u32, data: &[u8]) -> Vec<(HWInput, f32)> {
+    signals.iter()
+        .filter(|signal| signal.can_id == can_id)
+        .filter_map(|signal| signal.decode(data).map(|value| (signal.input, value)))
+        .collect()
+}
+
+// CAN config file format:
+// { "interface": "can0", "signals": [
+//     { "sensor": "HwTacho", "id": 640, "offset": 2, "length": 2, "scale": 0.25,
+//       "label": "ОБОРОТЫ", "unit": "об/мин", "max": 8000 },
+//     { "sensor": "HwEngineCoolantTemp", "id": 648, "offset": 1, "length": 1,
+//       "value_offset": -48, "byte_order": "big_endian", "min": -40, "max": 130 } ] }
+#[derive(Debug, Deserialize)]
+struct CanConfigFile {
+    interface: String,
+    signals: Vec<CanSignalEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CanSignalEntry {
+    sensor: String,
+    id: u32,
+    offset: usize,
+    length: usize,
+    #[serde(default)]
+    byte_order: ByteOrder,
+    #[serde(default)]
+    scale: Option<f32>,
+    #[serde(default)]
+    value_offset: f32,
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    unit: String,
+    #[serde(default)]
+    min: f32,
+    #[serde(default)]
+    max: Option<f32>,
+    #[serde(default)]
+    digital: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanConfig {
+    pub interface: String,
+    pub signals: Vec<CanSignal>,
+}
+
+/// Read and check a CAN config file
+pub fn load_can_config(path: &str) -> Result<CanConfig, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read CAN config {}: {}", path, e))?;
+    parse_can_config(&json).map_err(|e| format!("{}: {}", path, e))
+}
+
+/// Fails on the first unknown sensor or impossible field, so a typo doesn't leave a gauge
+/// silently stuck on "no data"
+pub fn parse_can_config(json: &str) -> Result<CanConfig, String> {
+    let file: CanConfigFile = serde_json::from_str(json).map_err(|e| format!("Invalid CAN config: {}", e))?;
+    let signals = file.signals.into_iter().enumerate().map(|(i, entry)| {
+        let context = |e: String| format!("signal {}: {}", i, e);
+        let input = HWInput::from_name(&entry.sensor)
+            .ok_or_else(|| context(format!("unknown sensor '{}'", entry.sensor)))?;
+        if entry.id > libc::CAN_EFF_MASK {
+            return Err(context(format!("id {:#x} is not a CAN identifier", entry.id)));
+        }
+        if !(1..=8).contains(&entry.length) || entry.offset + entry.length > 8 {
+            return Err(context(format!("bytes {}..{} don't fit an 8-byte frame", entry.offset, entry.offset + entry.length)));
+        }
+        let scale = entry.scale.unwrap_or(1.0);
+        if scale == 0.0 || !scale.is_finite() {
+            return Err(context(format!("invalid scale {}", scale)));
+        }
+        let raw_max = (1u64 << (entry.length * 8)).saturating_sub(1) as f32;
+        Ok(CanSignal {
+            input,
+            can_id: entry.id,
+            offset: entry.offset,
+            length: entry.length,
+            byte_order: entry.byte_order,
+            scale,
+            value_offset: entry.value_offset,
+            label: entry.label.unwrap_or(entry.sensor),
+            unit: entry.unit,
+            min: entry.min,
+            max: entry.max.unwrap_or(raw_max * scale + entry.value_offset),
+            digital: entry.digital,
+        })
+    }).collect::<Result<Vec<_>, String>>()?;
+    Ok(CanConfig { interface: file.interface, signals })
+}
+
+/// A cloneable, thread-safe handle to the latest decoded values, kept current by the
+/// CanDataProvider thread. Hardware providers hold this, like ADCFrame for the ADC.
+#[derive(Clone)]
+pub struct CanValues {
+    values: Arc<Mutex<HashMap<HWInput, (f32, Instant)>>>,
+}
+
+impl CanValues {
+    pub(crate) fn new() -> Self {
+        CanValues { values: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    pub(crate) fn set(&self, input: HWInput, value: f32) {
+        self.values.lock().unwrap().insert(input, (value, Instant::now()));
+    }
+
+    /// Latest value for `input`; an error if it was never received or has gone stale
+    pub fn get(&self, input: HWInput) -> Result<f32, String> {
+        match self.values.lock().unwrap().get(&input) {
+            Some(&(value, received)) if received.elapsed() <= CAN_SIGNAL_MAX_AGE => Ok(value),
+            Some(_) => Err(format!("CAN value for {:?} is stale", input)),
+            None => Err(format!("No CAN value for {:?} yet", input)),
+        }
+    }
+}
+
+/// Raw SocketCAN socket bound to one interface (classic frames only)
+struct CanSocket {
+    fd: libc::c_int,
+}
+
+impl CanSocket {
+    fn open(interface: &str) -> Result<Self, String> {
+        let name = CString::new(interface).map_err(|_| format!("Invalid CAN interface name {:?}", interface))?;
+        let ifindex = unsafe { libc::if_nametoindex(name.as_ptr()) };
+        if ifindex == 0 {
+            return Err(format!("CAN interface {} not found", interface));
+        }
+
+        let fd = unsafe { libc::socket(libc::PF_CAN, libc::SOCK_RAW, libc::CAN_RAW) };
+        if fd < 0 {
+            return Err(format!("Failed to open CAN socket: {}", io::Error::last_os_error()));
+        }
+        // Closes the fd on the error returns below
+        let socket = CanSocket { fd };
+
+        let timeout = libc::timeval { tv_sec: 0, tv_usec: READ_TIMEOUT.as_micros() as libc::suseconds_t };
+        let result = unsafe {
+            libc::setsockopt(fd, libc::SOL_SOCKET, libc::SO_RCVTIMEO, &timeout as *const _ as *const libc::c_void,
+                             std::mem::size_of::<libc::timeval>() as libc::socklen_t)
+        };
+        if result < 0 {
+            return Err(format!("Failed to set CAN read timeout: {}", io::Error::last_os_error()));
+        }
+
+        let mut addr: libc::sockaddr_can = unsafe { std::mem::zeroed() };
+        addr.can_family = libc::AF_CAN as libc::sa_family_t;
+        addr.can_ifindex = ifindex as libc::c_int;
+        let result = unsafe {
+            libc::bind(fd, &addr as *const _ as *const libc::sockaddr,
+                       std::mem::size_of::<libc::sockaddr_can>() as libc::socklen_t)
+        };
+        if result < 0 {
+            return Err(format!("Failed to bind CAN socket to {}: {}", interface, io::Error::last_os_error()));
+        }
+        Ok(socket)
+    }
+
+    /// Next data frame as (id, payload); None on timeout and for error/remote frames
+    fn read_frame(&self) -> Result<Option<(u32, Vec<u8>)>, String> {
+        let mut frame: libc::can_frame = unsafe { std::mem::zeroed() };
+        let size = std::mem::size_of::<libc::can_frame>();
+        let read = unsafe { libc::read(self.fd, &mut frame as *mut _ as *mut libc::c_void, size) };
+        if read < 0 {
+            let err = io::Error::last_os_error();
+            return match err.kind() {
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut | io::ErrorKind::Interrupted => Ok(None),
+                _ => Err(format!("CAN read failed: {}", err)),
+            };
+        }
+        if (read as usize) < size || frame.can_id & (libc::CAN_ERR_FLAG | libc::CAN_RTR_FLAG) != 0 {
+            return Ok(None);
+        }
+        let can_id = if frame.can_id & libc::CAN_EFF_FLAG != 0 {
+            frame.can_id & libc::CAN_EFF_MASK
+        } else {
+            frame.can_id & libc::CAN_SFF_MASK
+        };
+        let len = (frame.can_dlc as usize).min(frame.data.len());
+        Ok(Some((can_id, frame.data[..len].to_vec())))
+    }
+}
+
+impl Drop for CanSocket {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.fd) };
+    }
+}
+
+/// Reads a SocketCAN interface on a background thread, decoding the configured signals
+/// into a shared latest-value map. Like ADCDataProvider, starting succeeds even if the
+/// interface isn't up yet; the thread keeps retrying and the values stay missing meanwhile.
+pub struct CanDataProvider {
+    config: CanConfig,
+    should_stop: Arc<AtomicBool>,
+    values: CanValues,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl CanDataProvider {
+    pub fn new(config: CanConfig) -> Self {
+        CanDataProvider {
+            config,
+            should_stop: Arc::new(AtomicBool::new(false)),
+            values: CanValues::new(),
+            thread: None,
+        }
+    }
+
+    pub fn run(&mut self) -> Result<(), String> {
+        if self.thread.is_some() {
+            return Err("CAN data provider already started".to_string());
+        }
+
+        let interface = self.config.interface.clone();
+        let signals = self.config.signals.clone();
+        let should_stop = Arc::clone(&self.should_stop);
+        let values = self.values.clone();
+        let handle = thread::Builder::new()
+            .name("can-data-provider".into())
+            .spawn(move || Self::run_loop(&interface, &signals, &should_stop, &values))
+            .map_err(|e| format!("Failed to spawn thread: {}", e))?;
+        self.thread = Some(handle);
+        Ok(())
+    }
+
+    fn run_loop(interface: &str, signals: &[CanSignal], should_stop: &AtomicBool, values: &CanValues) {
+        let mut socket: Option<CanSocket> = None;
+        // Logged once per outage, not every RECONNECT_INTERVAL
+        let mut outage_logged = false;
+
+        while !should_stop.load(Ordering::Relaxed) {
+            let Some(open_socket) = &socket else {
+                match CanSocket::open(interface) {
+                    Ok(opened) => {
+                        log::info!("Reading CAN interface {}", interface);
+                        socket = Some(opened);
+                        outage_logged = false;
+                    }
+                    Err(e) => {
+                        if !outage_logged {
+                            log::warn!("{}, retrying", e);
+                            outage_logged = true;
+                        }
+                        thread::sleep(RECONNECT_INTERVAL);
+                    }
+                }
+                continue;
+            };
+
+            match open_socket.read_frame() {
+                Ok(Some((can_id, data))) => {
+                    for (input, value) in decode_frame(signals, can_id, &data) {
+                        values.set(input, value);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    log::warn!("{} on {}, reopening", e, interface);
+                    socket = None;
+                }
+            }
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.should_stop.store(true, Ordering::SeqCst);
+    }
+
+    pub fn signals(&self) -> &[CanSignal] {
+        &self.config.signals
+    }
+
+    /// Returns a cloneable handle to the decoded values for use by hardware providers
+    pub fn values(&self) -> CanValues {
+        self.values.clone()
+    }
+}
+
+impl Drop for CanDataProvider {
+    fn drop(&mut self) {
+        self.stop();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_frame_per_config() {
+        let config = parse_can_config(r#"{ "interface": "can0", "signals": [
+            { "sensor": "HwTacho", "id": 640, "offset": 2, "length": 2, "scale": 0.25, "max": 8000 },
+            { "sensor": "HwEngineCoolantTemp", "id": 640, "offset": 1, "length": 1, "value_offset": -48 },
+            { "sensor": "HwSpeed", "id": 640, "offset": 4, "length": 2, "scale": 0.01, "byte_order": "big_endian" },
+            { "sensor": "HwCheckEngine", "id": 1312, "offset": 0, "length": 1, "digital": true }
+        ] }"#).unwrap();
+        assert_eq!(config.interface, "can0");
+        assert_eq!(config.signals[0].label, "HwTacho");
+        // Without an explicit max the gauge spans the field's full range
+        assert_eq!(config.signals[1].max, 255.0 - 48.0);
+
+        // 0x0C80 / 4 = 800 rpm, 0x8A - 48 = 90 °C, 0x1964 / 100 = 65.0 km/h
+        let frame = [0x00, 0x8A, 0x80, 0x0C, 0x19, 0x64, 0xFF, 0xFF];
+        let values = decode_frame(&config.signals, 640, &frame);
+        assert_eq!(values, vec![(HWInput::HwTacho, 800.0), (HWInput::HwEngineCoolantTemp, 90.0),
+                                (HWInput::HwSpeed, 65.0)]);
+
+        // Other ids aren't ours; a short frame only yields the signals it covers
+        assert!(decode_frame(&config.signals, 641, &frame).is_empty());
+        assert_eq!(decode_frame(&config.signals, 640, &frame[..3]), vec![(HWInput::HwEngineCoolantTemp, 90.0)]);
+
+        // Config mistakes are rejected up front
+        assert!(parse_can_config(r#"{ "interface": "can0", "signals": [
+            { "sensor": "HwRpm", "id": 640, "offset": 0, "length": 2 } ] }"#).is_err());
+        assert!(parse_can_config(r#"{ "interface": "can0", "signals": [
+            { "sensor": "HwTacho", "id": 640, "offset": 7, "length": 2 } ] }"#).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+#![allow(dead_code)]
+use std::collections::VecDeque;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Frame times averaged for the reported FPS, about a second at 60 fps
+const FRAME_TIME_SAMPLES: usize = 60;
+
+/// Paces a render loop to a target frame rate and measures the rate actually achieved.
+/// Call `wait` once per frame after the work: it sleeps only what is left of the frame
+/// period. Deadlines advance from the previous deadline, not from when the sleep ended,
+/// so sleep overshoot doesn't accumulate into drift.
+#[derive(Debug)]
+pub struct FrameLimiter {
+    // Zero when unlimited
+    period: Duration,
+    next_frame: Instant,
+    last_frame: Instant,
+    frame_times: VecDeque<Duration>,
+    frame_count: u64,
+}
+
+impl FrameLimiter {
+    pub fn new(target_fps: f32) -> Self {
+        let period = if target_fps > 0.0 {
+            Duration::from_secs_f32(1.0 / target_fps)
+        } else {
+            Duration::ZERO
+        };
+        let now = Instant::now();
+        FrameLimiter {
+            period,
+            next_frame: now + period,
+            last_frame: now,
+            frame_times: VecDeque::with_capacity(FRAME_TIME_SAMPLES),
+            frame_count: 0,
+        }
+    }
+
+    /// Measures without sleeping, for loops already paced by something else
+    pub fn unlimited() -> Self {
+        Self::new(0.0)
+    }
+
+    /// End the current frame: sleep until its deadline, then record how long it took
+    pub fn wait(&mut self) {
+        let now = Instant::now();
+        if self.next_frame > now {
+            thread::sleep(self.next_frame - now);
+        }
+
+        let now = Instant::now();
+        // More than a frame behind (a slow frame, or a stall): start over from now rather
+        // than rushing through the missed deadlines
+        self.next_frame = if now > self.next_frame + self.period {
+            now + self.period
+        } else {
+            self.next_frame + self.period
+        };
+
+        if self.frame_times.len() == FRAME_TIME_SAMPLES {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(now - self.last_frame);
+        self.last_frame = now;
+        self.frame_count += 1;
+    }
+
+    pub fn target_fps(&self) -> Option<f32> {
+        (!self.period.is_zero()).then(|| 1.0 / self.period.as_secs_f32())
+    }
+
+    /// Average frame time over the recent frames
+    pub fn frame_time(&self) -> Duration {
+        if self.frame_times.is_empty() {
+            return Duration::ZERO;
+        }
+        self.frame_times.iter().sum::<Duration>() / self.frame_times.len() as u32
+    }
+
+    /// Measured frame rate, 0 until the first frame
+    pub fn fps(&self) -> f32 {
+        let frame_time = self.frame_time();
+        if frame_time.is_zero() {
+            0.0
+        } else {
+            1.0 / frame_time.as_secs_f32()
+        }
+    }
+
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_average_period_approaches_target() {
+        const FRAMES: u32 = 30;
+        let mut limiter = FrameLimiter::new(100.0);
+        assert_eq!(limiter.fps(), 0.0);
+
+        let start = Instant::now();
+        for frame in 0..FRAMES {
+            // Uneven work, always under the 10 ms period
+            thread::sleep(Duration::from_millis(1 + (frame % 3) as u64 * 2));
+            limiter.wait();
+        }
+        let average = start.elapsed() / FRAMES;
+
+        // Sleeps only the remainder, so the period holds despite the work, and the
+        // deadline schedule keeps sleep overshoot from adding up
+        assert!(average >= Duration::from_micros(9_900), "average period {:?}", average);
+        assert!(average < Duration::from_millis(11), "average period {:?}", average);
+        assert_eq!(limiter.frame_count(), FRAMES as u64);
+        assert!((limiter.fps() - 100.0).abs() < 10.0, "measured {} fps", limiter.fps());
+        assert_eq!(limiter.target_fps().map(f32::round), Some(100.0));
+    }
+}
@@ -1,6 +1,10 @@
 pub mod adc_serial_reader;
 pub mod adc_data_provider;
+pub mod can_data_provider;
 pub mod diagnostics;
 pub mod logging;
 pub mod shutdown;
-pub mod ups_monitor;
\ No newline at end of file
+pub mod ups_monitor;
+pub mod totals_file;
+pub mod rtc_clock;
+pub mod frame_limiter;
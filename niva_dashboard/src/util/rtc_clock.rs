@@ -0,0 +1,121 @@
+#![allow(dead_code)]
+use rppal::i2c::I2c;
+
+/// DS3231 and DS1307 share the address and the timekeeping register layout
+const RTC_I2C_BUS: u8 = 1;
+const RTC_ADDR: u16 = 0x68;
+const REG_SECONDS: u8 = 0x00;
+
+/// Wall clock time, local
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl ClockTime {
+    /// "HH:MM:SS"
+    pub fn time_text(&self) -> String {
+        format!("{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+
+    /// "DD.MM.YYYY"
+    pub fn date_text(&self) -> String {
+        format!("{:02}.{:02}.{:04}", self.day, self.month, self.year)
+    }
+}
+
+fn bcd(byte: u8) -> Result<u8, String> {
+    if byte >> 4 > 9 || byte & 0x0F > 9 {
+        return Err(format!("Invalid BCD byte {:#04x}", byte));
+    }
+    Ok((byte >> 4) * 10 + (byte & 0x0F))
+}
+
+/// Decode the seven timekeeping registers (0x00 seconds .. 0x06 year).
+/// Masks the DS1307 clock-halt bit and the DS3231 century bit, and handles 12-hour mode.
+pub fn decode_rtc_registers(regs: &[u8; 7]) -> Result<ClockTime, String> {
+    let second = bcd(regs[0] & 0x7F)?;
+    let minute = bcd(regs[1] & 0x7F)?;
+    let hour = if regs[2] & 0x40 != 0 {
+        // 12-hour mode: bit 5 is PM, 12 AM is midnight
+        let hour12 = bcd(regs[2] & 0x1F)?;
+        let pm = regs[2] & 0x20 != 0;
+        (hour12 % 12) + if pm { 12 } else { 0 }
+    } else {
+        bcd(regs[2] & 0x3F)?
+    };
+    let day = bcd(regs[4] & 0x3F)?;
+    let month = bcd(regs[5] & 0x1F)?;
+    let year = 2000 + bcd(regs[6])? as u16;
+
+    if second > 59 || minute > 59 || hour > 23 || !(1..=31).contains(&day) || !(1..=12).contains(&month) {
+        return Err(format!("RTC time out of range: {:02x?}", regs));
+    }
+    Ok(ClockTime { year, month, day, hour, minute, second })
+}
+
+/// Local time from the system clock
+pub fn system_time() -> ClockTime {
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        libc::localtime_r(&now, &mut tm);
+    }
+    ClockTime {
+        year: (tm.tm_year + 1900) as u16,
+        month: (tm.tm_mon + 1) as u8,
+        day: tm.tm_mday as u8,
+        hour: tm.tm_hour as u8,
+        minute: tm.tm_min as u8,
+        second: tm.tm_sec as u8,
+    }
+}
+
+/// Battery backed DS3231/DS1307 on the I2C bus. The Pi has no RTC of its own, so without
+/// network time the system clock starts wherever it stopped at the last shutdown.
+pub struct RtcClock {
+    i2c: I2c,
+}
+
+impl RtcClock {
+    pub fn open() -> Result<Self, String> {
+        let mut i2c = I2c::with_bus(RTC_I2C_BUS).map_err(|e| format!("Failed to open I2C bus {}: {}", RTC_I2C_BUS, e))?;
+        i2c.set_slave_address(RTC_ADDR).map_err(|e| format!("Failed to address RTC: {}", e))?;
+        Ok(RtcClock { i2c })
+    }
+
+    pub fn read(&mut self) -> Result<ClockTime, String> {
+        let mut regs = [0u8; 7];
+        self.i2c.write_read(&[REG_SECONDS], &mut regs).map_err(|e| format!("RTC read failed: {}", e))?;
+        decode_rtc_registers(&regs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_rtc_registers() {
+        // 2026-10-15 23:59:58, 24-hour mode, weekday 4
+        let time = decode_rtc_registers(&[0x58, 0x59, 0x23, 0x04, 0x15, 0x10, 0x26]).unwrap();
+        assert_eq!(time, ClockTime { year: 2026, month: 10, day: 15, hour: 23, minute: 59, second: 58 });
+        assert_eq!(time.time_text(), "23:59:58");
+        assert_eq!(time.date_text(), "15.10.2026");
+
+        // 12-hour mode: 12:05 AM is midnight, 07 PM is 19; DS1307 halt and DS3231 century bits ignored
+        let midnight = decode_rtc_registers(&[0x80, 0x05, 0x52, 0x01, 0x01, 0x81, 0x00]).unwrap();
+        assert_eq!((midnight.hour, midnight.minute, midnight.second, midnight.month), (0, 5, 0, 1));
+        let evening = decode_rtc_registers(&[0x00, 0x30, 0x67, 0x01, 0x01, 0x01, 0x00]).unwrap();
+        assert_eq!(evening.hour, 19);
+
+        // Non-BCD nibble, and an impossible date (unset chip)
+        assert!(decode_rtc_registers(&[0x5A, 0x00, 0x00, 0x01, 0x01, 0x01, 0x00]).is_err());
+        assert!(decode_rtc_registers(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+//! Small `key=value` text files for counters that must survive restarts (odometer,
+//! engine hours). Written via a temp file + rename, since the Pi loses power whenever
+//! the ignition is switched off and a half-written file would reset the totals.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Reads all totals from `path`. A missing file is not an error - it yields no totals.
+pub fn load_totals(path: &Path) -> Result<HashMap<String, f64>, String> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+
+    let mut totals = HashMap::new();
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once('=') else { continue };
+        let value: f64 = value.trim().parse()
+            .map_err(|_| format!("Bad value for '{}' in {}", key.trim(), path.display()))?;
+        totals.insert(key.trim().to_string(), value);
+    }
+    Ok(totals)
+}
+
+pub fn save_totals(path: &Path, totals: &[(&str, f64)]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let contents: String = totals.iter()
+        .map(|(key, value)| format!("{}={:.3}\n", key, value))
+        .collect();
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, contents)
+        .and_then(|_| std::fs::rename(&tmp_path, path))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_totals_round_trip() {
+        let dir = std::env::temp_dir().join(format!("niva_totals_{}", std::process::id()));
+        let path = dir.join("nested").join("totals.txt");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(load_totals(&path).unwrap().is_empty());
+        save_totals(&path, &[("a", 1.5), ("b", 1234.25)]).unwrap();
+        let totals = load_totals(&path).unwrap();
+        assert_eq!(totals["a"], 1.5);
+        assert_eq!(totals["b"], 1234.25);
+
+        std::fs::write(&path, "a=oops\n").unwrap();
+        assert!(load_totals(&path).is_err());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}